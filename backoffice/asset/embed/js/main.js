@@ -14,6 +14,24 @@ export function start() {
             }
             element.innerHTML = date.toLocaleString();
         },
+        /**
+         * Shows the UTC value of the hidden input named by data-target in local time,
+         * and writes the picked local time back as UTC.
+         * @param {HTMLInputElement} element
+         */
+        bindLocalDateTime(element) {
+            let target = document.getElementById(element.dataset.target);
+            let date = new Date(target.value);
+            if (target.value !== "" && !isNaN(date.getTime())) {
+                let pad = (value) => String(value).padStart(2, "0");
+                element.value = date.getFullYear() + "-" + pad(date.getMonth() + 1) + "-" + pad(date.getDate())
+                    + "T" + pad(date.getHours()) + ":" + pad(date.getMinutes());
+            }
+            element.addEventListener("change", () => {
+                let local = new Date(element.value);
+                target.value = element.value === "" || isNaN(local.getTime()) ? "" : local.toISOString();
+            });
+        },
         /**
          * @param {HTMLElement} from
          * @param {string} toHtml
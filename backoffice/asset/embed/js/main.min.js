@@ -1 +1 @@
-import htmx from"./lib/htmx/htmx.esm.js";import Alpine from"./lib/alpine/alpine.esm.js";import morph from"./lib/alpine/plugin/morph.esm.js";export function start(){Alpine.store("util",{formatToLocalTime(e){let t=new Date(e.innerHTML);if(isNaN(t.getTime())||t.toString()==="Invalid Date"||t.getTime()===0)return;e.innerHTML=t.toLocaleString()},async morph(e,t){let n={updating(e,t,n,s){if(e.dataset&&e.dataset.morphChildrenOnly==="true")return n();if(e.dataset&&e.dataset.morphIgnore==="true")return s()}};await Alpine.morph(e,t,n)},async morphFooterSplit(e,t){let n=t.split("<!-- split -->");t=n[0];let s=n[1];await this.morph(e,t),htmx.swap("#footer",s,{swapStyle:"beforeend"})}}),Alpine.store("nav",{async clearActive(){let e=document.getElementsByClassName("nav-item");for(let t of e)t.classList.remove("nav-item-active")},async updateActive(e){if(await this.clearActive(),e==="")return;let t=document.getElementById(e);t!==null&&t.classList.add("nav-item-active")},async updateActiveByElement(e){e.dataset.tag&&await this.updateActive(e.dataset.tag),e.remove()}}),Alpine.store("csrf",{token:"",updateToken(e){this.token!==e&&(this.token=e)},updateTokenByElement(e,t=!0){e.dataset.csrf&&this.updateToken(e.dataset.csrf),t&&e.remove()},fetch(e,t={}){return fetch(e,{...t,headers:{...t.headers,"X-Csrf-Token":this.token}})}}),Alpine.store("loader",{async easymde(e={}){let t=await import("./loaders/easymde.js");return await t.default.start(e)}}),htmx.on("htmx:responseError",function(e){if(e.detail.xhr.status===422)return;let t=document.createElement("pre");t.classList.add("pre"),t.innerText=e.detail.xhr.responseText;let n=document.createElement("div");n.innerHTML="<h1>Error "+e.detail.xhr.status+" "+e.detail.xhr.statusText+"</h1><br>",n.appendChild(t),htmx.swap("#main-content",n.outerHTML,{swapStyle:"innerHTML",swapDelay:0,settleDelay:0,transition:!1,ignoreTitle:!0,head:"<title>"+e.detail.xhr.status+" "+e.detail.xhr.statusText+"</title>",scroll:"top",show:"#main-content",focusScroll:!0})}),document.body.addEventListener("htmx:configRequest",function(e){e.detail.verb!=="get"&&e.detail.verb!=="head"&&(e.detail.headers["X-Csrf-Token"]=Alpine.store("csrf").token)}),window.Alpine=Alpine,window.htmx=htmx,Alpine.plugin(morph),Alpine.start()}
\ No newline at end of file
+import htmx from"./lib/htmx/htmx.esm.js";import Alpine from"./lib/alpine/alpine.esm.js";import morph from"./lib/alpine/plugin/morph.esm.js";export function start(){Alpine.store("util",{formatToLocalTime(e){let t=new Date(e.innerHTML);if(isNaN(t.getTime())||t.toString()==="Invalid Date"||t.getTime()===0)return;e.innerHTML=t.toLocaleString()},bindLocalDateTime(e){let t=document.getElementById(e.dataset.target),n=new Date(t.value);if(t.value!==""&&!isNaN(n.getTime())){let s=e=>String(e).padStart(2,"0");e.value=n.getFullYear()+"-"+s(n.getMonth()+1)+"-"+s(n.getDate())+"T"+s(n.getHours())+":"+s(n.getMinutes())}e.addEventListener("change",()=>{let n=new Date(e.value);t.value=e.value===""||isNaN(n.getTime())?"":n.toISOString()})},async morph(e,t){let n={updating(e,t,n,s){if(e.dataset&&e.dataset.morphChildrenOnly==="true")return n();if(e.dataset&&e.dataset.morphIgnore==="true")return s()}};await Alpine.morph(e,t,n)},async morphFooterSplit(e,t){let n=t.split("<!-- split -->");t=n[0];let s=n[1];await this.morph(e,t),htmx.swap("#footer",s,{swapStyle:"beforeend"})}}),Alpine.store("nav",{async clearActive(){let e=document.getElementsByClassName("nav-item");for(let t of e)t.classList.remove("nav-item-active")},async updateActive(e){if(await this.clearActive(),e==="")return;let t=document.getElementById(e);t!==null&&t.classList.add("nav-item-active")},async updateActiveByElement(e){e.dataset.tag&&await this.updateActive(e.dataset.tag),e.remove()}}),Alpine.store("csrf",{token:"",updateToken(e){this.token!==e&&(this.token=e)},updateTokenByElement(e,t=!0){e.dataset.csrf&&this.updateToken(e.dataset.csrf),t&&e.remove()},fetch(e,t={}){return fetch(e,{...t,headers:{...t.headers,"X-Csrf-Token":this.token}})}}),Alpine.store("loader",{async easymde(e={}){let t=await import("./loaders/easymde.js");return await t.default.start(e)}}),htmx.on("htmx:responseError",function(e){if(e.detail.xhr.status===422)return;let t=document.createElement("pre");t.classList.add("pre"),t.innerText=e.detail.xhr.responseText;let n=document.createElement("div");n.innerHTML="<h1>Error "+e.detail.xhr.status+" "+e.detail.xhr.statusText+"</h1><br>",n.appendChild(t),htmx.swap("#main-content",n.outerHTML,{swapStyle:"innerHTML",swapDelay:0,settleDelay:0,transition:!1,ignoreTitle:!0,head:"<title>"+e.detail.xhr.status+" "+e.detail.xhr.statusText+"</title>",scroll:"top",show:"#main-content",focusScroll:!0})}),document.body.addEventListener("htmx:configRequest",function(e){e.detail.verb!=="get"&&e.detail.verb!=="head"&&(e.detail.headers["X-Csrf-Token"]=Alpine.store("csrf").token)}),window.Alpine=Alpine,window.htmx=htmx,Alpine.plugin(morph),Alpine.start()}
\ No newline at end of file
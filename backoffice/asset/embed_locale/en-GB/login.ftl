@@ -7,6 +7,11 @@ login-confirm-button = Login
 # Login Post Locale
 login-post-flash-success = Login success
 login-post-flash-failed = Login failed
+login-post-flash-throttled = Too many failed attempts, please try again later
 
 # Logout Locale
-login-logout-post-success = Logout success
\ No newline at end of file
+login-logout-post-success = Logout success
+
+# Change Password Locale
+login-change-password-flash-required = You must change your password before continuing
+login-change-password-flash-success = Password changed
\ No newline at end of file
@@ -0,0 +1,14 @@
+# Active Sessions
+session-title = Active Sessions
+session-intro = Every device signed in to your account, revoke any you do not recognise.
+session-head-device = Device
+session-head-ip = IP Address
+session-head-created = Signed In
+session-head-last-seen = Last Seen
+session-head-action = Action
+session-unknown-device = Unknown device
+session-current = This device
+session-revoke = Revoke Session
+session-revoke-confirm = Sign this device out of your account?
+session-flash-revoked = Session revoked
+session-flash-not-found = That session has already ended
@@ -0,0 +1,7 @@
+# Setup Locale
+setup-title = First Run Setup
+setup-paragraph = Create the root account, this page goes away once it exists.
+setup-submit = Create Root Account
+
+# Setup Post Locale
+setup-post-flash-success = Root account created, please login
\ No newline at end of file
@@ -5,9 +5,11 @@ stack-list-error-stack-head-name = Name
 stack-list-error-stack-head-summary = Summary
 stack-list-error-stack-head-reported = Reported At
 stack-list-error-stack-head-action = Action
+stack-list-error-stack-filter-name = Filter by name
 
 stack-list-error-stack-action-details = View Error Details
 stack-list-error-stack-action-clear = Clear Older than 30 days
+stack-list-error-stack-action-files = Orphaned Files
 
 stack-list-error-stack-fetch-title = Error Stack: { $name }
 
@@ -15,4 +17,18 @@ stack-list-error-stack-fetch-head-reported = Reported At
 stack-list-error-stack-fetch-head-summary = Summary
 stack-list-error-stack-fetch-head-stack = Stack
 
-stack-route-logout-confirm-message = Are you sure you want to clear all error stacks older than 30 days?
\ No newline at end of file
+stack-route-logout-confirm-message = Are you sure you want to clear all error stacks older than 30 days?
+
+stack-file-gc-title = Orphaned Files
+
+stack-file-gc-head-orphan-files = Files without an attachment
+stack-file-gc-head-missing-files = Attachments without a file
+stack-file-gc-head-id = ID
+stack-file-gc-head-component = Component
+stack-file-gc-head-name = File Name
+stack-file-gc-head-path = Path
+stack-file-gc-head-size = Size
+stack-file-gc-empty = Nothing found
+
+stack-file-gc-action-clean = Remove all listed
+stack-file-gc-confirm-message = Are you sure you want to remove the listed files and attachments?
\ No newline at end of file
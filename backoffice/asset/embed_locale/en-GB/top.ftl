@@ -1,6 +1,9 @@
 # Top Build
 top-hello = Hello, { $username }
 top-hello-logout = Click here to logout
+top-hello-password = Change your password
+top-hello-two-factor = Two-factor authentication
+top-hello-sessions = Active sessions
 top-visitor = You're a visitor, click here to login
 
 # Top Navigation
@@ -9,4 +12,9 @@ top-navigation-user = User
 top-navigation-url = URL Redirect
 top-navigation-stack = Stack
 
+# List Navigation
+top-list-previous = Previous
+top-list-next = Next
+top-list-filter = Filter
+
 top-date-time = { DATETIME($date) }
\ No newline at end of file
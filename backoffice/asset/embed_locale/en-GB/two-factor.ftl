@@ -0,0 +1,32 @@
+# Two-Factor Enrolment
+two-factor-title = Two-Factor Authentication
+two-factor-pending-intro = Scan the QR code with an authenticator app, then enter the six digit code it shows.
+two-factor-pending-secret = Or enter this key by hand:
+two-factor-code = Code
+two-factor-code-placeholder = Six digit code
+two-factor-confirm-button = Enable two-factor authentication
+two-factor-enabled-intro = Two-factor authentication is enabled for your account.
+two-factor-enabled-remaining = Unused recovery codes: { $remaining }
+two-factor-regenerate-button = Generate new recovery codes
+two-factor-disable-button = Disable two-factor authentication
+two-factor-recovery-title = Recovery Codes
+two-factor-recovery-intro = Keep these codes somewhere safe, each one signs you in once without your authenticator app. They will not be shown again.
+two-factor-recovery-continue = Continue
+two-factor-flash-required = You must enable two-factor authentication before continuing
+two-factor-flash-enabled = Two-factor authentication enabled
+two-factor-flash-disabled = Two-factor authentication disabled
+two-factor-flash-invalid-code = The code was not accepted
+
+# Two-Factor Login
+two-factor-verify-title = Two-Factor Verification
+two-factor-verify-intro = Enter the code from your authenticator app, or one of your recovery codes.
+two-factor-verify-button = Verify
+
+# Two-Factor Status
+two-factor-status-title = Two-Factor Authentication
+two-factor-status-enabled = Enabled
+two-factor-status-disabled = Not enabled
+two-factor-status-reset = Reset two-factor authentication
+two-factor-status-reset-confirm = Are you sure you want to reset two-factor authentication for '{ $username }'?
+two-factor-status-flash-reset-success = Two-factor authentication reset for user id: { $user_id }
+two-factor-status-flash-reset-error = Failed to reset two-factor authentication for user id: { $user_id }
@@ -1,6 +1,7 @@
 user-form-title-add = Add User
 user-form-title-edit = Edit User
 user-form-title-edit-password = Edit User Password
+user-form-title-change-password = Change Your Password
 
 
 user-form-username = Username:
@@ -12,6 +13,7 @@ user-form-password-confirm-placeholder = Password Confirm
 user-form-password-current = Current Password:
 user-form-password-current-placeholder = Current
 user-form-role = Role:
+user-form-must-change-password = Must change password on next login
 
 user-route-submit-add = Add
 user-route-submit-edit = Edit
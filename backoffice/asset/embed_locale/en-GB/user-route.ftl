@@ -2,10 +2,12 @@ user-route-list-user-title = List of Users
 user-route-list-head-id = Id
 user-route-list-head-username = Username
 user-route-list-head-role = Role
+user-route-list-filter-any-role = Any role
 
 user-route-list-action-edit = Edit User
 user-route-list-action-password = Edit Password
 user-route-list-action-sign-out = Sign Out User
+user-route-list-action-unlock = Clear Login Lockout
 user-route-list-action-add-user = Add User
 
 user-route-flash-edit-success = Successfully edited user id: { $user_id }
@@ -13,5 +15,7 @@ user-route-flash-password-success = Successfully edited password for user id: {
 user-route-flash-add-success = Successfully created user: { $username }
 user-route-flash-sign-out-error = Failed to sign out user id: { $user_id }
 user-route-flash-sign-out-success = Successfully signed out user id: { $user_id }
+user-route-flash-unlock-error = Failed to clear the login lockout for user id: { $user_id }
+user-route-flash-unlock-success = Login lockout cleared for user id: { $user_id }
 
 user-route-logout-confirm-message = Are you sure you want to log out '{ $username }' ?
\ No newline at end of file
@@ -19,6 +19,7 @@ validate-must-have-lowercase = Must contain at least one lowercase letter
 validate-must-have-digit = Must contain at least one digit
 
 validate-password-does-not-match = Does not match
+validate-password-current = Does not match the current password
 validate-username-taken = Already taken
 
 validate-password-entropy = Password entropy score must be over { $min }, try using a password manager?
@@ -40,4 +41,15 @@ validate-username-reserved = Username is reserved
 
 validate-must-be-kebab-case = Must be kebab case
 
+validate-slug-reserved = Slug is reserved
+validate-slug-taken = Already taken
+validate-page-parent-invalid = Cannot be the page itself or one of its children
+validate-term-name-no-slug = Must contain a letter or digit
+validate-schedule-invalid = Must be a valid date and time
+validate-schedule-order = Must be after the publish time
+validate-image-invalid = Must be a JPEG, PNG, WebP or GIF image
+validate-upload-file-too-large = { $name } is larger than { $max } MB
+validate-upload-request-too-large = Uploads are limited to { $max } MB per request
+validate-upload-type-not-allowed = { $name } is not an allowed file type
+
 validate-flash = Please check the form above for errors.
\ No newline at end of file
@@ -1,7 +1,12 @@
+use crate::cms::enums::RevisionDiffKind;
 use crate::cms::query_model::UpdateFetchQuery;
+use crate::user::pointer::user_pointer::UserPointer;
+use crate::user::role::Role;
 use chrono::{DateTime, Utc};
 use shared::cms::markers::ComponentDataMarker;
 use shared::cms::status::CmsPageStatus;
+use shared::cms::taxonomy::TermKind;
+use std::sync::Arc;
 
 #[derive(Debug)]
 pub struct ReturningIdModel(pub i64);
@@ -12,26 +17,53 @@ pub struct UserIdModel(pub i64);
 #[derive(Debug)]
 pub struct FilePath(pub String);
 
+#[derive(Debug)]
+pub struct PagePath(pub String);
+
+#[derive(Debug)]
+pub struct PageIdModel(pub i64);
+
+#[derive(Debug, Clone)]
 pub struct AddFileAttachmentModel {
     pub component_id: i64,
     pub file_name: String,
     pub file_path: String,
     pub file_type: String,
+    pub file_hash: String,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct AddPageModel {
     pub user_id: i64,
+    pub slug: String,
+    pub path: String,
     pub title: String,
     pub status: CmsPageStatus,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
+pub struct AddPageTermModel {
+    pub page_id: i64,
+    pub kind: TermKind,
+    pub slug: String,
+    pub name: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct AddReviewModel {
+    pub page_id: i64,
+    pub reviewer_id: i64,
+    pub status: CmsPageStatus,
+    pub comment: String,
+}
+
+#[derive(Debug, Clone)]
 pub struct CreateComponentModel<T: ComponentDataMarker = Vec<u8>> {
     pub page_id: i64,
     pub kind_uuid: String,
     pub raw_data: T,
     pub label: String,
+    pub revision_author: RevisionAuthorModel,
 }
 
 #[derive(Debug)]
@@ -44,9 +76,15 @@ pub struct FetchComponentModel<T: ComponentDataMarker = Vec<u8>> {
 pub struct FetchPageModel {
     #[allow(dead_code)]
     pub id: i64,
+    pub parent_id: Option<i64>,
+    pub slug: String,
+    pub path: String,
     pub title: String,
     pub summary: String,
     pub status: CmsPageStatus,
+    pub publish_at: Option<DateTime<Utc>>,
+    pub unpublish_at: Option<DateTime<Utc>>,
+    pub terms: Arc<[PageTermModel]>,
 }
 
 #[derive(Debug)]
@@ -72,11 +110,19 @@ pub struct ListFileAttachmentModel {
     pub uploaded: DateTime<Utc>,
 }
 
+pub struct FetchRevisionModel {
+    pub id: i64,
+    pub page_id: i64,
+    pub title: String,
+    pub summary: String,
+}
+
 #[derive(Debug)]
 pub struct ListPageModel {
     pub id: i64,
     pub author: String,
     pub user_id: i64,
+    pub path: String,
     pub title: String,
     pub added: DateTime<Utc>,
     pub updated: Option<DateTime<Utc>>,
@@ -84,23 +130,108 @@ pub struct ListPageModel {
 }
 
 #[derive(Debug)]
+pub struct ListPendingReviewModel {
+    pub id: i64,
+    pub title: String,
+    pub author: String,
+    pub status: CmsPageStatus,
+    pub reviewer: Option<String>,
+    pub comment: Option<String>,
+    pub reviewed: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug)]
+pub struct ListReviewModel {
+    pub reviewer: String,
+    pub status: CmsPageStatus,
+    pub comment: String,
+    pub added: DateTime<Utc>,
+}
+
+#[derive(Debug)]
+pub struct ListRevisionModel {
+    pub id: i64,
+    pub author: String,
+    pub title: String,
+    pub added: DateTime<Utc>,
+}
+
+#[derive(Debug)]
+pub struct PageAuthorModel {
+    pub id: i64,
+    pub username: String,
+}
+
+#[derive(Debug)]
+pub struct PageParentOptionModel {
+    pub id: i64,
+    pub title: String,
+    pub path: String,
+}
+
+#[derive(Debug)]
+pub struct PageTermModel {
+    pub id: i64,
+    pub kind: TermKind,
+    pub slug: String,
+    pub name: String,
+}
+
+/// Who a save is recorded against, a save by someone without publishing rights sends a
+/// published page back to review.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RevisionAuthorModel {
+    pub user_id: i64,
+    pub can_publish: bool,
+}
+
+impl From<&UserPointer> for RevisionAuthorModel {
+    fn from(user_pointer: &UserPointer) -> Self {
+        Self {
+            user_id: user_pointer.id,
+            can_publish: user_pointer.role >= Role::Editor,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct RevisionComponentModel {
+    pub kind_uuid: String,
+    pub label: String,
+    pub raw_data: Vec<u8>,
+}
+
+#[derive(Debug)]
+pub struct RevisionDiffLine {
+    pub kind: RevisionDiffKind,
+    pub line: String,
+}
+
+#[derive(Debug, Clone)]
 pub struct UpdateComponentModel<T: ComponentDataMarker = Vec<u8>> {
     pub id: i64,
     pub label: String,
     pub raw_data: T,
+    pub revision_author: RevisionAuthorModel,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct UpdateComponentPositionModel {
     pub id: i64,
     pub position: i64,
-    pub page_id: i64,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct UpdatePageModel {
     pub id: i64,
+    pub parent_id: Option<i64>,
+    pub slug: String,
+    pub old_path: String,
+    pub path: String,
     pub title: String,
     pub summary: String,
     pub status: CmsPageStatus,
+    pub publish_at: Option<DateTime<Utc>>,
+    pub unpublish_at: Option<DateTime<Utc>>,
+    pub revision_author: RevisionAuthorModel,
 }
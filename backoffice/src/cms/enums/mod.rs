@@ -1,5 +1,22 @@
+use serde::Deserialize;
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum ComponentRequestKind {
     Create,
     UpdateFetch,
 }
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RevisionDiffKind {
+    Equal,
+    Insert,
+    Delete,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReviewDecision {
+    #[default]
+    Approve,
+    Reject,
+}
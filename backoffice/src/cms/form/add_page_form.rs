@@ -1,33 +1,46 @@
+use crate::cms::rules::slug_rule::{IsSlugTakenAsync, SlugRuleExt};
 use crate::cms::rules::title_rule::TitleRuleExt;
 use crate::common::html::context_html::ContextHtmlBuilder;
 use crate::common::html::validate::ValidateErrorMessageExt;
 use cjtoolkit_structured_validator::common::flag_error::FlagCounter;
-use cjtoolkit_structured_validator::types::name::name_alias::{Title, TitleError};
+use cjtoolkit_structured_validator::types::name::name_alias::{
+    Field, FieldError, Title, TitleError,
+};
 use maud::{Markup, html};
 use poem::i18n::Locale;
 use serde::{Deserialize, Serialize};
+use shared::cms::slug::slugify;
 use shared::utils::locale::LocaleExtForResult;
 use std::sync::Arc;
 
 #[derive(Deserialize, Default)]
 pub struct AddPageForm {
     pub title: String,
+    #[serde(default)]
+    pub slug: String,
 }
 
 impl AddPageForm {
-    pub async fn as_validated(&self) -> AddPageFormResult {
+    pub async fn as_validated<T: IsSlugTakenAsync>(&self, service: &T) -> AddPageFormResult {
         AddPageFormResult(
             async {
                 let mut flag = FlagCounter::new();
 
                 let title = flag.check(Title::parse_title(Some(self.title.trim())));
+                let slug = match self.slug.trim() {
+                    "" => slugify(self.title.trim()),
+                    slug => slug.to_string(),
+                };
+                let slug =
+                    flag.check(Field::parse_slug(Some(slug.as_str()), service, None, None).await);
 
                 if flag.is_flagged() {
-                    return Err(AddPageFormError { title });
+                    return Err(AddPageFormError { title, slug });
                 }
 
                 Ok(AddPageFormValidated {
                     title: title.expect("title is not empty"),
+                    slug: slug.expect("slug is not empty"),
                 })
             }
             .await,
@@ -53,6 +66,12 @@ impl AddPageForm {
                         placeholder="Title" {}
                     (errors.title.into_error_html())
                 }
+                div .form-group {
+                    label .label for="add-slug" { "Slug" }
+                    input .form-item .w-full type="text" #add-slug name="slug" value=(self.slug)
+                        placeholder="Leave blank to generate from title" {}
+                    (errors.slug.into_error_html())
+                }
                 div .form-group {
                     button .btn .btn-sky-blue type="submit" { "Add Page" }
                 }
@@ -63,16 +82,19 @@ impl AddPageForm {
 
 pub struct AddPageFormValidated {
     pub title: Title,
+    pub slug: Field,
 }
 
 pub struct AddPageFormError {
     pub title: Result<Title, TitleError>,
+    pub slug: Result<Field, FieldError>,
 }
 
 impl AddPageFormError {
     pub fn as_message(&self, locale: &Locale) -> AddPageFormMessage {
         AddPageFormMessage {
             title: self.title.as_translated_message(locale),
+            slug: self.slug.as_translated_message(locale),
         }
     }
 }
@@ -82,4 +104,5 @@ pub struct AddPageFormResult(pub Result<AddPageFormValidated, AddPageFormError>)
 #[derive(Debug, Clone, Serialize, Default)]
 pub struct AddPageFormMessage {
     pub title: Arc<[String]>,
+    pub slug: Arc<[String]>,
 }
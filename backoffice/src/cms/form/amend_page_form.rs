@@ -1,11 +1,18 @@
+use crate::cms::data_model::cms_data::PageParentOptionModel;
+use crate::cms::rules::parent_rule::{IsParentValidAsync, parse_parent};
+use crate::cms::rules::schedule_rule::{parse_schedule, parse_unpublish_schedule};
+use crate::cms::rules::slug_rule::{IsSlugTakenAsync, SlugRuleExt};
 use crate::cms::rules::summary_rule::SummaryRuleExt;
 use crate::cms::rules::title_rule::TitleRuleExt;
 use crate::common::html::validate::ValidateErrorMessageExt;
+use chrono::{DateTime, Utc};
 use cjtoolkit_structured_validator::common::flag_error::FlagCounter;
 use cjtoolkit_structured_validator::types::description::description_alias::{
     Summary, SummaryError,
 };
-use cjtoolkit_structured_validator::types::name::name_alias::{Title, TitleError};
+use cjtoolkit_structured_validator::types::name::name_alias::{
+    Field, FieldError, Title, TitleError,
+};
 use maud::{Markup, html};
 use poem::i18n::Locale;
 use serde::{Deserialize, Serialize};
@@ -18,32 +25,75 @@ pub struct AmendPageForm {
     pub title: String,
     pub summary: String,
     pub status: CmsPageStatus,
+    pub slug: String,
+    #[serde(default)]
+    pub parent_id: i64,
+    #[serde(default)]
+    pub publish_at: String,
+    #[serde(default)]
+    pub unpublish_at: String,
 }
 
 impl AmendPageForm {
-    pub async fn as_validated(&self) -> AmendPageFormResult {
+    pub async fn as_validated<T: IsSlugTakenAsync + IsParentValidAsync>(
+        &self,
+        service: &T,
+        page_id: i64,
+    ) -> AmendPageFormResult {
         AmendPageFormResult(
             async {
                 let mut flag = FlagCounter::new();
 
                 let title = flag.check(Title::parse_title(Some(self.title.trim())));
                 let summary = flag.check(Summary::parse_summary(Some(self.summary.trim())));
+                let parent_id = flag.check(parse_parent(self.parent_id, service, page_id).await);
+                let slug = flag.check(
+                    Field::parse_slug(
+                        Some(self.slug.trim()),
+                        service,
+                        parent_id.clone().ok().flatten(),
+                        Some(page_id),
+                    )
+                    .await,
+                );
+                let publish_at = flag.check(parse_schedule(self.publish_at.trim()));
+                let unpublish_at = flag.check(parse_unpublish_schedule(
+                    self.unpublish_at.trim(),
+                    publish_at.clone().ok().flatten(),
+                ));
 
                 if flag.is_flagged() {
-                    return Err(AmendPageFormError { title, summary });
+                    return Err(AmendPageFormError {
+                        title,
+                        summary,
+                        slug,
+                        parent_id,
+                        publish_at,
+                        unpublish_at,
+                    });
                 }
 
                 Ok(AmendPageFormValidated {
                     title: title.expect("title is not empty"),
                     summary: summary.expect("summary is not empty"),
                     status: self.status,
+                    slug: slug.expect("slug is not empty"),
+                    parent_id: parent_id.expect("parent is valid"),
+                    publish_at: publish_at.expect("publish_at is valid"),
+                    unpublish_at: unpublish_at.expect("unpublish_at is valid"),
                 })
             }
             .await,
         )
     }
 
-    pub async fn as_form_html(&self, errors: Option<AmendPageFormMessage>) -> Markup {
+    pub async fn as_form_html(
+        &self,
+        errors: Option<AmendPageFormMessage>,
+        parent_options: Arc<[PageParentOptionModel]>,
+        status_options: &[CmsPageStatus],
+        can_publish: bool,
+    ) -> Markup {
         let errors = errors.unwrap_or_default();
 
         html! {
@@ -61,10 +111,44 @@ impl AmendPageForm {
                     }
                     (errors.summary.into_error_html())
                 }
+                div .form-group {
+                    label .label for="amend-slug" { "Slug" }
+                    input .form-item .w-full type="text" #amend-slug name="slug" value=(self.slug) required
+                        placeholder="Slug" {}
+                    (errors.slug.into_error_html())
+                }
+                div .form-group {
+                    label .label for="amend-parent" { "Parent Page" }
+                    select .form-item .w-full #amend-parent name="parent_id" {
+                        option value="0" { "None" }
+                        @for parent in parent_options.iter() {
+                            option value=(parent.id) selected[parent.id == self.parent_id] {
+                                (parent.title) " (/" (parent.path) ")"
+                            }
+                        }
+                    }
+                    (errors.parent_id.into_error_html())
+                }
                 div .form-group {
                     label .label for="amend-status" { "Status" }
                     select .form-item .w-full #amend-status name="status" required {
-                        (self.status.html_option())
+                        (self.status.html_option(status_options))
+                    }
+                }
+                @if can_publish {
+                    div .form-group {
+                        label .label for="amend-publish-at-local" { "Publish At" }
+                        input type="hidden" #amend-publish-at name="publish_at" value=(self.publish_at) {}
+                        input .form-item .w-full type="datetime-local" #amend-publish-at-local
+                            data-target="amend-publish-at" x-init="$store.util.bindLocalDateTime($el)" {}
+                        (errors.publish_at.into_error_html())
+                    }
+                    div .form-group {
+                        label .label for="amend-unpublish-at-local" { "Unpublish At" }
+                        input type="hidden" #amend-unpublish-at name="unpublish_at" value=(self.unpublish_at) {}
+                        input .form-item .w-full type="datetime-local" #amend-unpublish-at-local
+                            data-target="amend-unpublish-at" x-init="$store.util.bindLocalDateTime($el)" {}
+                        (errors.unpublish_at.into_error_html())
                     }
                 }
                 div .form-group {
@@ -79,11 +163,19 @@ pub struct AmendPageFormValidated {
     pub title: Title,
     pub summary: Summary,
     pub status: CmsPageStatus,
+    pub slug: Field,
+    pub parent_id: Option<i64>,
+    pub publish_at: Option<DateTime<Utc>>,
+    pub unpublish_at: Option<DateTime<Utc>>,
 }
 
 pub struct AmendPageFormError {
     pub title: Result<Title, TitleError>,
     pub summary: Result<Summary, SummaryError>,
+    pub slug: Result<Field, FieldError>,
+    pub parent_id: Result<Option<i64>, FieldError>,
+    pub publish_at: Result<Option<DateTime<Utc>>, FieldError>,
+    pub unpublish_at: Result<Option<DateTime<Utc>>, FieldError>,
 }
 
 impl AmendPageFormError {
@@ -91,6 +183,10 @@ impl AmendPageFormError {
         AmendPageFormMessage {
             title: self.title.as_translated_message(locale),
             summary: self.summary.as_translated_message(locale),
+            slug: self.slug.as_translated_message(locale),
+            parent_id: self.parent_id.as_translated_message(locale),
+            publish_at: self.publish_at.as_translated_message(locale),
+            unpublish_at: self.unpublish_at.as_translated_message(locale),
         }
     }
 }
@@ -101,4 +197,8 @@ pub struct AmendPageFormResult(pub Result<AmendPageFormValidated, AmendPageFormE
 pub struct AmendPageFormMessage {
     pub title: Arc<[String]>,
     pub summary: Arc<[String]>,
+    pub slug: Arc<[String]>,
+    pub parent_id: Arc<[String]>,
+    pub publish_at: Arc<[String]>,
+    pub unpublish_at: Arc<[String]>,
 }
@@ -0,0 +1,36 @@
+({
+    uri: '',
+    values: {},
+    init() {
+        this.uri = this.$el.dataset.uri;
+    },
+    fieldHandle(element) {
+        this.values[element.name] = element.dataset.value;
+        element.addEventListener('blur', () => {
+            if (this.values[element.name] !== element.value) {
+                this.values[element.name] = element.value;
+                this.save();
+            }
+        })
+        element.addEventListener('keydown', (event) => {
+            if (event.key === 'Enter' && element.tagName === 'INPUT') {
+                event.preventDefault();
+                if (this.values[element.name] !== element.value) {
+                    this.values[element.name] = element.value;
+                    this.save();
+                }
+            }
+        })
+    },
+    async save() {
+        let response = await this.$store.csrf.fetch(this.uri, {
+            method: 'PATCH',
+            headers: {
+                'Content-Type': 'application/x-www-form-urlencoded'
+            },
+            body: new URLSearchParams(this.values)
+        });
+        let toHtml = await response.text();
+        await this.$store.util.morphFooterSplit(this.$root, toHtml);
+    }
+})
\ No newline at end of file
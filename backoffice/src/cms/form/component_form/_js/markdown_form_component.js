@@ -26,6 +26,10 @@
     async markdownHandle(element) {
         let editor = await this.$store.loader.easymde({
             element: element,
+            previewRender: (plainText, preview) => {
+                this.preview(plainText, preview);
+                return preview.innerHTML;
+            },
         });
         this.markdown = element.dataset.value;
         editor.value(element.dataset.value);
@@ -36,6 +40,20 @@
             }
         });
     },
+    async preview(markdown, preview) {
+        let response = await this.$store.csrf.fetch(this.uri, {
+            method: 'POST',
+            headers: {
+                'Content-Type': 'application/x-www-form-urlencoded'
+            },
+            body: new URLSearchParams({
+                markdown: markdown,
+            })
+        });
+        if (response.ok) {
+            preview.innerHTML = await response.text();
+        }
+    },
     async save() {
         let response = await this.$store.csrf.fetch(this.uri, {
             method: 'PATCH',
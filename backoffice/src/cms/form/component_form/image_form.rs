@@ -0,0 +1,121 @@
+use crate::cms::query_model::UpdateFetchQuery;
+use crate::cms::rules::component_rules::image_rules::ImageRulesExt;
+use crate::cms::rules::title_rule::TitleRuleExt;
+use crate::common::html::validate::ValidateErrorMessageExt;
+use cjtoolkit_structured_validator::common::flag_error::FlagCounter;
+use cjtoolkit_structured_validator::types::description::description_alias::{Text, TextError};
+use cjtoolkit_structured_validator::types::name::name_alias::{Field, FieldError};
+use maud::{Markup, html};
+use poem::i18n::Locale;
+use serde::{Deserialize, Serialize};
+use shared::utils::locale::LocaleExtForResult;
+use std::sync::Arc;
+
+#[derive(Deserialize, Default)]
+pub struct ImageForm {
+    pub label: String,
+    pub alt: String,
+    #[serde(default)]
+    pub caption: String,
+}
+
+impl ImageForm {
+    pub async fn as_validated(&self) -> ImageFormResult {
+        ImageFormResult(
+            async {
+                let mut flag = FlagCounter::new();
+
+                let label = flag.check(Field::parse_title(Some(self.label.trim())));
+                let alt = flag.check(Text::parse_image_alt(Some(self.alt.trim())));
+                let caption = flag.check(Text::parse_image_caption(Some(self.caption.trim())));
+
+                if flag.is_flagged() {
+                    return Err(ImageFormError {
+                        label,
+                        alt,
+                        caption,
+                    });
+                }
+
+                Ok(ImageFormValidated {
+                    label: label.expect("label is not empty"),
+                    alt: alt.expect("alt is not empty"),
+                    caption: caption.expect("caption is valid"),
+                })
+            }
+            .await,
+        )
+    }
+
+    pub fn as_form_html(
+        &self,
+        query: &UpdateFetchQuery,
+        errors: Option<ImageFormMessage>,
+    ) -> Markup {
+        let errors = errors.unwrap_or_default();
+
+        html! {
+            form .mb-3 .form x-data=(include_str!("_js/image_form_component.js")) data-uri=(query.as_uri()) {
+                div .form-group {
+                    label .label for=(format!("label-group-label-{}", query.id)) { "Label" }
+                    input id=(format!("label-group-label-{}", query.id)) .form-item .w-full type="text" name="label"
+                        value=(self.label) data-value=(self.label) x-init="fieldHandle($el)"
+                        placeholder="Label" {}
+                    span {
+                        (errors.label.into_error_html())
+                    }
+                }
+                div .form-group {
+                    label .label for=(format!("image-alt-{}", query.id)) { "Alt Text" }
+                    input id=(format!("image-alt-{}", query.id)) .form-item .w-full type="text" name="alt"
+                        value=(self.alt) data-value=(self.alt) x-init="fieldHandle($el)"
+                        placeholder="Describe the image for screen readers" {}
+                    span {
+                        (errors.alt.into_error_html())
+                    }
+                }
+                div .form-group {
+                    label .label for=(format!("image-caption-{}", query.id)) { "Caption" }
+                    textarea id=(format!("image-caption-{}", query.id)) .form-item .w-full name="caption"
+                        data-value=(self.caption) x-init="fieldHandle($el)" {
+                        (self.caption)
+                    }
+                    span {
+                        (errors.caption.into_error_html())
+                    }
+                }
+            }
+        }
+    }
+}
+
+pub struct ImageFormValidated {
+    pub label: Field,
+    pub alt: Text,
+    pub caption: Text,
+}
+
+pub struct ImageFormError {
+    pub label: Result<Field, FieldError>,
+    pub alt: Result<Text, TextError>,
+    pub caption: Result<Text, TextError>,
+}
+
+impl ImageFormError {
+    pub fn as_message(&self, locale: &Locale) -> ImageFormMessage {
+        ImageFormMessage {
+            label: self.label.as_translated_message(locale),
+            alt: self.alt.as_translated_message(locale),
+            caption: self.caption.as_translated_message(locale),
+        }
+    }
+}
+
+pub struct ImageFormResult(pub Result<ImageFormValidated, ImageFormError>);
+
+#[derive(Debug, Clone, Serialize, Default)]
+pub struct ImageFormMessage {
+    pub label: Arc<[String]>,
+    pub alt: Arc<[String]>,
+    pub caption: Arc<[String]>,
+}
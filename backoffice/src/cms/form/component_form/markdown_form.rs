@@ -74,6 +74,11 @@ impl MarkdownForm {
     }
 }
 
+#[derive(Deserialize, Default)]
+pub struct MarkdownPreviewForm {
+    pub markdown: String,
+}
+
 pub struct MarkdownFormValidated {
     pub label: Field,
     pub markdown: Text,
@@ -1,2 +1,3 @@
 pub mod common_label_form;
+pub mod image_form;
 pub mod markdown_form;
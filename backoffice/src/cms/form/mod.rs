@@ -2,3 +2,5 @@ pub mod add_page_form;
 pub mod amend_page_form;
 pub mod component_form;
 pub mod component_position_form;
+pub mod page_term_form;
+pub mod review_page_form;
@@ -0,0 +1,91 @@
+use crate::cms::route::CMS_ROUTE;
+use crate::cms::rules::term_rule::TermNameRuleExt;
+use crate::common::html::validate::ValidateErrorMessageExt;
+use cjtoolkit_structured_validator::common::flag_error::FlagCounter;
+use cjtoolkit_structured_validator::types::name::name_alias::{Field, FieldError};
+use maud::{Markup, html};
+use poem::i18n::Locale;
+use serde::{Deserialize, Serialize};
+use shared::cms::taxonomy::TermKind;
+use shared::utils::locale::LocaleExtForResult;
+use std::sync::Arc;
+
+#[derive(Deserialize, Default)]
+pub struct PageTermForm {
+    #[serde(default)]
+    pub kind: TermKind,
+    #[serde(default)]
+    pub name: String,
+}
+
+impl PageTermForm {
+    pub async fn as_validated(&self) -> PageTermFormResult {
+        PageTermFormResult(
+            async {
+                let mut flag = FlagCounter::new();
+
+                let name = flag.check(Field::parse_term_name(Some(self.name.trim())));
+
+                if flag.is_flagged() {
+                    return Err(PageTermFormError { name });
+                }
+
+                Ok(PageTermFormValidated {
+                    kind: self.kind,
+                    name: name.expect("name is valid"),
+                })
+            }
+            .await,
+        )
+    }
+
+    pub fn as_form_html(&self, page_id: u64, errors: Option<PageTermFormMessage>) -> Markup {
+        let errors = errors.unwrap_or_default();
+
+        html! {
+            form .form hx-post=(format!("{}/page-term/{}", CMS_ROUTE, page_id)) hx-target="this" hx-swap="outerHTML" {
+                div .form-group {
+                    label .label for="page-term-name" { "Add Term" }
+                    select .form-item #page-term-kind name="kind" {
+                        @for kind in TermKind::all_kinds() {
+                            option value=(kind.as_stringed()) selected[kind == self.kind] { (kind.label()) }
+                        }
+                    }
+                    " "
+                    input .form-item type="text" #page-term-name name="name" value=(self.name) required
+                        placeholder="Name" list="term-suggestion" autocomplete="off"
+                        hx-get=(format!("{}/term-suggestion", CMS_ROUTE)) hx-include="#page-term-kind"
+                        hx-trigger="input changed delay:300ms" hx-target="#term-suggestion" hx-swap="innerHTML" {}
+                    datalist #term-suggestion {}
+                    " "
+                    button .btn .btn-sky-blue .cursor-pointer type="submit" { "Add" }
+                    (errors.name.into_error_html())
+                }
+            }
+        }
+    }
+}
+
+pub struct PageTermFormValidated {
+    pub kind: TermKind,
+    pub name: Field,
+}
+
+pub struct PageTermFormError {
+    pub name: Result<Field, FieldError>,
+}
+
+impl PageTermFormError {
+    pub fn as_message(&self, locale: &Locale) -> PageTermFormMessage {
+        PageTermFormMessage {
+            name: self.name.as_translated_message(locale),
+        }
+    }
+}
+
+pub struct PageTermFormResult(pub Result<PageTermFormValidated, PageTermFormError>);
+
+#[derive(Debug, Clone, Serialize, Default)]
+pub struct PageTermFormMessage {
+    pub name: Arc<[String]>,
+}
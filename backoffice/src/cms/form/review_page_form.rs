@@ -0,0 +1,89 @@
+use crate::cms::enums::ReviewDecision;
+use crate::cms::route::CMS_ROUTE;
+use crate::cms::rules::review_rule::ReviewCommentRulesExt;
+use crate::common::html::validate::ValidateErrorMessageExt;
+use cjtoolkit_structured_validator::common::flag_error::FlagCounter;
+use cjtoolkit_structured_validator::types::description::description_alias::{Text, TextError};
+use maud::{Markup, html};
+use poem::i18n::Locale;
+use serde::{Deserialize, Serialize};
+use shared::utils::locale::LocaleExtForResult;
+use std::sync::Arc;
+
+#[derive(Deserialize, Default)]
+pub struct ReviewPageForm {
+    #[serde(default)]
+    pub decision: ReviewDecision,
+    #[serde(default)]
+    pub comment: String,
+}
+
+impl ReviewPageForm {
+    pub async fn as_validated(&self) -> ReviewPageFormResult {
+        ReviewPageFormResult(
+            async {
+                let mut flag = FlagCounter::new();
+
+                // a rejection has to tell the author what to change.
+                let comment = flag.check(Text::parse_review_comment(
+                    Some(self.comment.trim()),
+                    self.decision == ReviewDecision::Reject,
+                ));
+
+                if flag.is_flagged() {
+                    return Err(ReviewPageFormError { comment });
+                }
+
+                Ok(ReviewPageFormValidated {
+                    decision: self.decision,
+                    comment: comment.expect("comment is valid"),
+                })
+            }
+            .await,
+        )
+    }
+
+    pub fn as_form_html(&self, page_id: u64, errors: Option<ReviewPageFormMessage>) -> Markup {
+        let errors = errors.unwrap_or_default();
+
+        html! {
+            form .form hx-post=(format!("{}/review-page/{}", CMS_ROUTE, page_id)) hx-target="this" hx-swap="outerHTML" {
+                div .form-group {
+                    label .label for="review-comment" { "Comment" }
+                    textarea .form-item .w-full #review-comment name="comment" placeholder="Required when requesting changes" {
+                        (self.comment)
+                    }
+                    (errors.comment.into_error_html())
+                }
+                div .form-group {
+                    button .btn .btn-sky-blue .cursor-pointer .mr-2 type="submit" name="decision" value="approve" { "Approve and Publish" }
+                    button .btn .btn-sky-blue .cursor-pointer type="submit" name="decision" value="reject" { "Request Changes" }
+                }
+            }
+        }
+    }
+}
+
+pub struct ReviewPageFormValidated {
+    pub decision: ReviewDecision,
+    pub comment: Text,
+}
+
+pub struct ReviewPageFormError {
+    pub comment: Result<Text, TextError>,
+}
+
+impl ReviewPageFormError {
+    pub fn as_message(&self, locale: &Locale) -> ReviewPageFormMessage {
+        ReviewPageFormMessage {
+            comment: self.comment.as_translated_message(locale),
+        }
+    }
+}
+
+pub struct ReviewPageFormResult(pub Result<ReviewPageFormValidated, ReviewPageFormError>);
+
+#[derive(Debug, Clone, Serialize, Default)]
+pub struct ReviewPageFormMessage {
+    pub comment: Arc<[String]>,
+}
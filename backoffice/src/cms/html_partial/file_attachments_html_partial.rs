@@ -1,6 +1,7 @@
 use crate::FILES_ROUTE;
 use crate::cms::data_model::cms_data::ListFileAttachmentModel;
 use crate::cms::query_model::UpdateFetchQuery;
+use crate::common::html::validate::ValidateErrorMessageExt;
 use crate::common::icon::{arrow_path_icon, link_icon, minus_icon, plus_icon, trash_icon};
 use maud::{Markup, html};
 use serde_json::json;
@@ -10,6 +11,7 @@ pub fn file_attachments_list_partial(
     swap_oob: Option<&str>,
     update_fetch_query: &UpdateFetchQuery,
     file_list: Arc<[ListFileAttachmentModel]>,
+    errors: Arc<[String]>,
 ) -> Markup {
     let trash_icon = trash_icon();
     let link_icon = link_icon();
@@ -40,12 +42,19 @@ pub fn file_attachments_list_partial(
                     }
                 }
             }
-            span hx-get=(update_fetch_query.as_uri()) hx-headers=(json!({"X-Route": "form"})) hx-trigger="load" hx-swap="outerHTML" {}
+            @if errors.is_empty() {
+                span hx-get=(update_fetch_query.as_uri()) hx-headers=(json!({"X-Route": "form"})) hx-trigger="load" hx-swap="outerHTML" {}
+            } @else {
+                (file_attachments_form_partial(update_fetch_query, errors))
+            }
         }
     }
 }
 
-pub fn file_attachments_form_partial(update_fetch_query: &UpdateFetchQuery) -> Markup {
+pub fn file_attachments_form_partial(
+    update_fetch_query: &UpdateFetchQuery,
+    errors: Arc<[String]>,
+) -> Markup {
     let js_data = include_str!("_js/file_attachment_form_data.js");
     html! {
         div x-cloak x-data=(js_data) {
@@ -64,6 +73,7 @@ pub fn file_attachments_form_partial(update_fetch_query: &UpdateFetchQuery) -> M
                         (file_attachments_form_field_partial())
                     }
                 }
+                (errors.into_error_html())
 
                 button .btn .btn-sky-blue .cursor-pointer .mt-3 type="submit" { "Upload Files" }
             }
@@ -0,0 +1,50 @@
+use crate::FILES_ROUTE;
+use crate::cms::query_model::UpdateFetchQuery;
+use crate::common::html::validate::ValidateErrorMessageExt;
+use maud::{Markup, html};
+use shared::cms::components::image::ImageComponent;
+use std::sync::Arc;
+
+pub fn image_upload_partial(
+    swap_oob: Option<&str>,
+    update_fetch_query: &UpdateFetchQuery,
+    image: &ImageComponent,
+    errors: Arc<[String]>,
+) -> Markup {
+    // the narrowest variant is enough for a preview in the editor.
+    let preview = image
+        .variants
+        .iter()
+        .filter(|variant| variant.file_type == "image/jpeg")
+        .min_by_key(|variant| variant.width);
+    html! {
+        div id=(format!("image-upload-{}", update_fetch_query.id)) hx-swap-oob=[swap_oob] {
+            @if let Some(preview) = preview {
+                figure .mb-3 {
+                    img src=(format!("{}{}", FILES_ROUTE, preview.file_path)) alt=(image.alt);
+                    figcaption {
+                        (format!("{} x {}, ", image.width, image.height))
+                        @for (index, variant) in image.variants.iter().enumerate() {
+                            @if index > 0 { ", " }
+                            a href=(format!("{}{}", FILES_ROUTE, variant.file_path)) target="_blank" {
+                                (format!("{}w {}", variant.width, variant.file_type))
+                            }
+                        }
+                    }
+                }
+            } @else {
+                p { "No image uploaded" }
+            }
+            form .form hx-post=(update_fetch_query.as_uri()) hx-target=(format!("#image-upload-{}", update_fetch_query.id))
+                hx-swap="outerHTML" hx-encoding="multipart/form-data" {
+                div .form-group {
+                    label .label for=(format!("image-file-{}", update_fetch_query.id)) { "Upload Image" }
+                    input id=(format!("image-file-{}", update_fetch_query.id)) .upload-form-file type="file" name="file"
+                        accept="image/jpeg,image/png,image/webp,image/gif" {}
+                    (errors.into_error_html())
+                }
+                button .btn .btn-sky-blue .cursor-pointer type="submit" { "Upload Image" }
+            }
+        }
+    }
+}
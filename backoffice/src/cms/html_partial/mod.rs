@@ -1,4 +1,8 @@
 pub mod file_attachments_html_partial;
+pub mod image_html_partial;
+pub mod review_html_partial;
+pub mod revision_html_partial;
+pub mod term_html_partial;
 
 use crate::cms::data_model::cms_data::ListComponentModel;
 use crate::cms::route::CMS_ROUTE;
@@ -0,0 +1,78 @@
+use crate::cms::data_model::cms_data::{ListPendingReviewModel, ListReviewModel};
+use crate::cms::route::CMS_ROUTE;
+use maud::{Markup, html};
+use std::sync::Arc;
+
+pub fn review_list_partial(reviews: Arc<[ListReviewModel]>) -> Markup {
+    html! {
+        div #reviews {
+            @if reviews.is_empty() {
+                p { "No review yet" }
+            } @else {
+                table .table-full {
+                    thead {
+                        tr {
+                            th { "Reviewer" }
+                            th { "Decision" }
+                            th { "Comment" }
+                            th { "Added" }
+                        }
+                    }
+                    tbody {
+                        @for review in reviews.iter() {
+                            tr {
+                                td { (review.reviewer) }
+                                td { (review.status.as_stringed()) }
+                                td { (review.comment) }
+                                td x-init="$store.util.formatToLocalTime($el)" { (review.added.to_rfc3339()) }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+pub fn pending_review_partial(pending_reviews: Arc<[ListPendingReviewModel]>) -> Markup {
+    html! {
+        @if !pending_reviews.is_empty() {
+            h3 { "Pending Review" }
+            table .table-full .mb-5 {
+                thead {
+                    tr {
+                        th { "ID" }
+                        th { "Title" }
+                        th { "Author" }
+                        th { "Status" }
+                        th { "Last Review" }
+                    }
+                }
+                tbody {
+                    @for page in pending_reviews.iter() {
+                        tr {
+                            td { (page.id) }
+                            td {
+                                a href=(format!("{}/amend-page/{}", CMS_ROUTE, page.id))
+                                    hx-boost="true" hx-target="#main-content" hx-push-url="true" { (page.title) }
+                            }
+                            td { (page.author) }
+                            td { (page.status.as_stringed()) }
+                            @if let (Some(reviewer), Some(reviewed)) = (page.reviewer.as_ref(), page.reviewed.as_ref()) {
+                                td {
+                                    (reviewer) ", "
+                                    span x-init="$store.util.formatToLocalTime($el)" { (reviewed.to_rfc3339()) }
+                                    @if let Some(comment) = page.comment.as_ref().filter(|comment| !comment.is_empty()) {
+                                        ": " (comment)
+                                    }
+                                }
+                            } @else {
+                                td { "N/A" }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,101 @@
+use crate::cms::data_model::cms_data::{ListRevisionModel, RevisionDiffLine};
+use crate::cms::enums::RevisionDiffKind;
+use crate::cms::route::CMS_ROUTE;
+use crate::common::icon::arrow_path_icon;
+use maud::{Markup, html};
+use std::sync::Arc;
+
+fn revision_select_partial(
+    name: &str,
+    revisions: &[ListRevisionModel],
+    selected_index: usize,
+) -> Markup {
+    html! {
+        select .form-item name=(name) {
+            @for (index, revision) in revisions.iter().enumerate() {
+                option value=(revision.id) selected[index == selected_index] {
+                    "#" (revision.id) " " (revision.title)
+                }
+            }
+        }
+    }
+}
+
+pub fn revision_list_partial(
+    swap_oob: Option<&str>,
+    revisions: Arc<[ListRevisionModel]>,
+    page_id: u64,
+) -> Markup {
+    let restore_icon = arrow_path_icon();
+    html! {
+        div #revisions hx-swap-oob=[swap_oob] {
+            @if revisions.is_empty() {
+                p { "No revision recorded" }
+            } @else {
+                form .form hx-get=(format!("{}/revision-diff/{}", CMS_ROUTE, page_id))
+                    hx-target="#revision-diff" hx-swap="innerHTML" {
+                    div .form-group {
+                        label .label { "Compare" }
+                        (revision_select_partial("from", &revisions, 1.min(revisions.len() - 1)))
+                        " to "
+                        (revision_select_partial("to", &revisions, 0))
+                        " "
+                        button .btn .btn-sky-blue .cursor-pointer type="submit" { "Show Diff" }
+                    }
+                }
+                div #revision-diff {}
+                table .table-full .mt-3 {
+                    thead {
+                        tr {
+                            th { "ID" }
+                            th { "Author" }
+                            th { "Title" }
+                            th { "Added" }
+                            th .action { "Action" }
+                        }
+                    }
+                    tbody {
+                        @for (index, revision) in revisions.iter().enumerate() {
+                            tr {
+                                td { (revision.id) }
+                                td { (revision.author) }
+                                td { (revision.title) }
+                                td x-init="$store.util.formatToLocalTime($el)" { (revision.added.to_rfc3339()) }
+                                td .action {
+                                    @if index == 0 {
+                                        "Current"
+                                    } @else {
+                                        a .icon .cursor-pointer
+                                            hx-patch=(format!("{}/restore-revision/{}/{}", CMS_ROUTE, page_id, revision.id))
+                                            hx-confirm=(format!("Restore revision {}?", revision.id)) title="Restore" {
+                                            (restore_icon)
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+pub fn revision_diff_partial(diff: &[RevisionDiffLine]) -> Markup {
+    html! {
+        @if diff.iter().all(|line| line.kind == RevisionDiffKind::Equal) {
+            p { "No difference" }
+        } @else {
+            pre .pre {
+                @for line in diff.iter() {
+                    @match line.kind {
+                        RevisionDiffKind::Equal => span { "  " (line.line) },
+                        RevisionDiffKind::Insert => ins { "+ " (line.line) },
+                        RevisionDiffKind::Delete => del { "- " (line.line) },
+                    }
+                    "\n"
+                }
+            }
+        }
+    }
+}
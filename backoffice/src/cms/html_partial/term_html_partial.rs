@@ -0,0 +1,53 @@
+use crate::cms::data_model::cms_data::PageTermModel;
+use crate::cms::route::CMS_ROUTE;
+use crate::common::icon::trash_icon;
+use maud::{Markup, html};
+use std::sync::Arc;
+
+pub fn term_list_partial(
+    swap_oob: Option<&str>,
+    terms: Arc<[PageTermModel]>,
+    page_id: u64,
+) -> Markup {
+    let trash_icon = trash_icon();
+    html! {
+        div #terms hx-swap-oob=[swap_oob] {
+            @if terms.is_empty() {
+                p { "No category or tag" }
+            } @else {
+                table .table-full {
+                    thead {
+                        tr {
+                            th { "Kind" }
+                            th { "Name" }
+                            th { "Slug" }
+                            th .action { "Action" }
+                        }
+                    }
+                    tbody {
+                        @for term in terms.iter() {
+                            tr {
+                                td { (term.kind.label()) }
+                                td { (term.name) }
+                                td { (term.slug) }
+                                td .action {
+                                    a .icon .cursor-pointer
+                                        hx-delete=(format!("{}/delete-page-term/{}/{}", CMS_ROUTE, page_id, term.id))
+                                        title="Remove" { (trash_icon) }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+pub fn term_suggestion_partial(suggestions: Arc<[PageTermModel]>) -> Markup {
+    html! {
+        @for suggestion in suggestions.iter() {
+            option value=(suggestion.name) {}
+        }
+    }
+}
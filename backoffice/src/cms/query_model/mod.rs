@@ -3,6 +3,9 @@ use poem::http::StatusCode;
 use poem::{FromRequest, Request, RequestBody};
 use serde::{Deserialize, Serialize};
 use shared::cms::CmsComponentInfo;
+use shared::cms::status::CmsPageStatus;
+use shared::cms::taxonomy::TermKind;
+use shared::utils::query_string::list_query::{ListQuery, SortKey, empty_as_none};
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct CreateQuery {
@@ -71,3 +74,59 @@ impl<'a> FromRequest<'a> for UpdateFetchQuery {
         Ok(query.clone())
     }
 }
+
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct RevisionDiffQuery {
+    pub from: u64,
+    pub to: u64,
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq, Default)]
+#[serde(default)]
+pub struct TermSuggestionQuery {
+    pub kind: TermKind,
+    pub name: String,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Default)]
+#[serde(default)]
+pub struct CmsPageFilter {
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub q: String,
+    #[serde(
+        deserialize_with = "empty_as_none",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub status: Option<CmsPageStatus>,
+    #[serde(
+        deserialize_with = "empty_as_none",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub author: Option<i64>,
+    #[serde(
+        deserialize_with = "empty_as_none",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub term: Option<i64>,
+}
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum CmsPageSort {
+    Id,
+    Title,
+    #[default]
+    Updated,
+}
+
+impl SortKey for CmsPageSort {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::Id => "id",
+            Self::Title => "title",
+            Self::Updated => "updated",
+        }
+    }
+}
+
+pub type ListPageQuery = ListQuery<CmsPageFilter, CmsPageSort>;
@@ -0,0 +1,4 @@
+insert into cms_component_revision (revision_id, component_id, kind_uuid, position, label, raw_data)
+select :revision_id, id, kind_uuid, position, label, raw_data
+from cms_component
+where page_id = :page_id
\ No newline at end of file
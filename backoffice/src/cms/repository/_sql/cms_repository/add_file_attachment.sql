@@ -1,2 +1,2 @@
-insert into cms_file_attachment (component_id, file_name, file_path, file_type)
-values (:component_id, :file_name, :file_path, :file_type)
\ No newline at end of file
+insert into cms_file_attachment (component_id, file_name, file_path, file_type, file_hash)
+values (:component_id, :file_name, :file_path, :file_type, :file_hash)
\ No newline at end of file
@@ -0,0 +1,6 @@
+insert into cms_file_attachment_revision (revision_id, attachment_id, component_id, file_name, file_path, file_type,
+                                          file_hash, uploaded)
+select :revision_id, cfa.id, cfa.component_id, cfa.file_name, cfa.file_path, cfa.file_type, cfa.file_hash, cfa.uploaded
+from cms_file_attachment as cfa
+         inner join cms_component cc on cc.id = cfa.component_id
+where cc.page_id = :page_id
\ No newline at end of file
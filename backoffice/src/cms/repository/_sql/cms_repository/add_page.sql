@@ -1,3 +1,3 @@
-insert into cms_page (user_id, title, summary, added, status)
-values (:user_id, :title, '', datetime('now'), :status)
-returning id;
+insert into cms_page (user_id, slug, path, title, summary, added, status)
+values (:user_id, :slug, :path, :title, '', datetime('now'), :status)
+returning id;
\ No newline at end of file
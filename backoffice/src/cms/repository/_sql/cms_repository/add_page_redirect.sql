@@ -0,0 +1,5 @@
+insert or replace into cms_page_redirect (path, page_id)
+select path, id
+from cms_page
+where path = :old_path
+   or path like :old_path || '/%';
\ No newline at end of file
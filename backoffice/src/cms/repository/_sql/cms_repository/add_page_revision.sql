@@ -0,0 +1,5 @@
+insert into cms_page_revision (page_id, user_id, title, summary, added)
+select id, :user_id, title, summary, datetime('now')
+from cms_page
+where id = :page_id
+returning id;
\ No newline at end of file
@@ -0,0 +1,5 @@
+insert or ignore into cms_page_term (page_id, term_id)
+select :page_id, id
+from cms_term
+where kind = :kind
+  and slug = :slug
\ No newline at end of file
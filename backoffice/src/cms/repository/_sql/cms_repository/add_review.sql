@@ -0,0 +1,2 @@
+insert into cms_page_review (page_id, reviewer_id, status, comment)
+values (:page_id, :reviewer_id, :status, :comment)
\ No newline at end of file
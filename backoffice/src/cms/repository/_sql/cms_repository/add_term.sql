@@ -0,0 +1,3 @@
+insert into cms_term (kind, slug, name)
+values (:kind, :slug, :name)
+on conflict (kind, slug) do nothing
\ No newline at end of file
@@ -0,0 +1,3 @@
+delete
+from cms_page_redirect
+where path in (select path from cms_page);
\ No newline at end of file
@@ -0,0 +1,3 @@
+select count(*) as child_count
+from cms_page
+where parent_id = :id
\ No newline at end of file
@@ -0,0 +1,2 @@
+select (select count(*) from cms_file_attachment where file_path = :file_path)
+           + (select count(*) from cms_file_attachment_revision where file_path = :file_path) as file_count
\ No newline at end of file
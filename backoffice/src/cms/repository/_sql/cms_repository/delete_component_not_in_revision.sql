@@ -0,0 +1,6 @@
+delete
+from cms_component
+where page_id = :page_id
+  and id not in (select component_id
+                 from cms_component_revision
+                 where revision_id = :revision_id)
\ No newline at end of file
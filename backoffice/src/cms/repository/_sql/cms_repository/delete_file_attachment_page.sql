@@ -0,0 +1,3 @@
+delete
+from cms_file_attachment
+where component_id in (select id from cms_component where page_id = :page_id);
\ No newline at end of file
@@ -0,0 +1,3 @@
+delete
+from cms_page
+where id = :id
\ No newline at end of file
@@ -0,0 +1,4 @@
+delete
+from cms_page_term
+where page_id = :page_id
+  and term_id = :term_id
\ No newline at end of file
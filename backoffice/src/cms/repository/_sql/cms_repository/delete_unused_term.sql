@@ -0,0 +1,4 @@
+delete
+from cms_term
+where id = :term_id
+  and not exists (select 1 from cms_page_term where term_id = :term_id)
\ No newline at end of file
@@ -1,3 +1,3 @@
-select id, title, summary, status
+select id, parent_id, slug, path, title, summary, status, publish_at, unpublish_at
 from cms_page
 where id = :id
\ No newline at end of file
@@ -0,0 +1,3 @@
+select path
+from cms_page
+where id = :id
\ No newline at end of file
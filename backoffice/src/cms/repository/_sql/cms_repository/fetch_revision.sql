@@ -0,0 +1,3 @@
+select id, page_id, title, summary
+from cms_page_revision
+where id = :id
\ No newline at end of file
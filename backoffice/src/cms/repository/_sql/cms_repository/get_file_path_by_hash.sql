@@ -0,0 +1,5 @@
+select file_path
+from cms_file_attachment
+where file_hash = :file_hash
+order by id
+limit 1
\ No newline at end of file
@@ -0,0 +1,3 @@
+select page_id
+from cms_component
+where id = :id
\ No newline at end of file
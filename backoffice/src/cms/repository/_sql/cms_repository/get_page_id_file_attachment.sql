@@ -0,0 +1,4 @@
+select c.page_id
+from cms_file_attachment a
+         join cms_component c on c.id = a.component_id
+where a.id = :id
\ No newline at end of file
@@ -0,0 +1,4 @@
+update cms_page
+set status=:in_review_status
+where id = :page_id
+  and (status = :published_status or (status = :draft_status and publish_at is not null))
\ No newline at end of file
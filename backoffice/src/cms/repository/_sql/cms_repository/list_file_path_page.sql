@@ -0,0 +1,9 @@
+select cfa.file_path
+from cms_file_attachment as cfa
+         inner join cms_component cc on cc.id = cfa.component_id
+where cc.page_id = :page_id
+union
+select cfar.file_path
+from cms_file_attachment_revision as cfar
+         inner join cms_page_revision cpr on cpr.id = cfar.revision_id
+where cpr.page_id = :page_id
\ No newline at end of file
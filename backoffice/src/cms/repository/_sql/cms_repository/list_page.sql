@@ -1,9 +1,28 @@
 select cp.id,
        bu.username as author,
        cp.user_id,
+       cp.path,
        cp.title,
        cp.added,
        cp.updated,
-       cp.status
+       cp.status,
+       case :sort
+           when 'updated' then coalesce(cp.updated, cp.added)
+           when 'title' then cp.title
+           else ''
+           end as sort_key
 from cms_page as cp
-         inner join backoffice_users bu on bu.id = cp.user_id;
\ No newline at end of file
+         inner join backoffice_users bu on bu.id = cp.user_id
+where cp.status != :trash_status
+  and (:status is null or cp.status = :status)
+  and (:author is null or cp.user_id = :author)
+  and (:term is null or cp.id in (select page_id from cms_page_term where term_id = :term))
+  and (:query is null or cp.id in (select rowid from cms_page_search where cms_page_search match :query))
+  and (:cursor_id is null
+    or (:scan_desc and (sort_key, cp.id) < (:cursor_key, :cursor_id))
+    or (not :scan_desc and (sort_key, cp.id) > (:cursor_key, :cursor_id)))
+order by case when :scan_desc then sort_key end desc,
+         case when :scan_desc then cp.id end desc,
+         sort_key,
+         cp.id
+limit :limit;
\ No newline at end of file
@@ -0,0 +1,5 @@
+select distinct bu.id, bu.username
+from cms_page as cp
+         inner join backoffice_users bu on bu.id = cp.user_id
+where cp.status != :trash_status
+order by bu.username;
\ No newline at end of file
@@ -0,0 +1,7 @@
+select id, title, path
+from cms_page
+where id != :id
+  and path != :path
+  and path not like :path || '/%'
+  and status != :trash_status
+order by path;
\ No newline at end of file
@@ -0,0 +1,5 @@
+select ct.id, ct.kind, ct.slug, ct.name
+from cms_page_term as cpt
+         inner join cms_term ct on ct.id = cpt.term_id
+where cpt.page_id = :page_id
+order by ct.kind, ct.name
\ No newline at end of file
@@ -0,0 +1,15 @@
+select cp.id,
+       cp.title,
+       bu.username as author,
+       cp.status,
+       rv.username as reviewer,
+       cpr.comment,
+       cpr.added   as reviewed
+from cms_page as cp
+         inner join backoffice_users bu on bu.id = cp.user_id
+         left join cms_page_review cpr
+                   on cpr.id = (select max(id) from cms_page_review where page_id = cp.id)
+         left join backoffice_users rv on rv.id = cpr.reviewer_id
+where cp.status in (:in_review_status, :changes_requested_status)
+  and (:user_id is null or cp.user_id = :user_id)
+order by cp.id
\ No newline at end of file
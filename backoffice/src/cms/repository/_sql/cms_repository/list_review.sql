@@ -0,0 +1,8 @@
+select bu.username as reviewer,
+       cpr.status,
+       cpr.comment,
+       cpr.added
+from cms_page_review as cpr
+         inner join backoffice_users bu on bu.id = cpr.reviewer_id
+where cpr.page_id = :page_id
+order by cpr.id desc
\ No newline at end of file
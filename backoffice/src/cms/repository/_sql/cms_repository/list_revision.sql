@@ -0,0 +1,8 @@
+select cpr.id,
+       bu.username as author,
+       cpr.title,
+       cpr.added
+from cms_page_revision as cpr
+         inner join backoffice_users bu on bu.id = cpr.user_id
+where cpr.page_id = :page_id
+order by cpr.id desc
\ No newline at end of file
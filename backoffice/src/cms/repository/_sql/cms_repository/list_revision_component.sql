@@ -0,0 +1,4 @@
+select kind_uuid, label, raw_data
+from cms_component_revision
+where revision_id = :revision_id
+order by position
\ No newline at end of file
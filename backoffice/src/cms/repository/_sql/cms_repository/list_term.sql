@@ -0,0 +1,3 @@
+select id, kind, slug, name
+from cms_term
+order by kind, name
\ No newline at end of file
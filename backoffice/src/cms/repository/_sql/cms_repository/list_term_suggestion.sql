@@ -0,0 +1,6 @@
+select id, kind, slug, name
+from cms_term
+where kind = :kind
+  and instr(lower(name), lower(:query)) > 0
+order by name
+limit :limit
\ No newline at end of file
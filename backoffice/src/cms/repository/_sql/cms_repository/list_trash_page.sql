@@ -0,0 +1,11 @@
+select cp.id,
+       bu.username as author,
+       cp.user_id,
+       cp.path,
+       cp.title,
+       cp.added,
+       cp.updated,
+       cp.status
+from cms_page as cp
+         inner join backoffice_users bu on bu.id = cp.user_id
+where cp.status = :trash_status;
\ No newline at end of file
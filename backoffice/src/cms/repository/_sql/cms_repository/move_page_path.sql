@@ -0,0 +1,4 @@
+update cms_page
+set path = :new_path || substr(path, length(:old_path) + 1)
+where path = :old_path
+   or path like :old_path || '/%';
\ No newline at end of file
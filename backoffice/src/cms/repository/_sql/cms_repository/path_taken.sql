@@ -0,0 +1,4 @@
+select count(*) as taken
+from cms_page
+where path = :path
+  and id != :exclude_id
\ No newline at end of file
@@ -0,0 +1,7 @@
+update cms_component
+set label=ccr.label,
+    raw_data=ccr.raw_data,
+    position=ccr.position
+from cms_component_revision as ccr
+where ccr.revision_id = :revision_id
+  and cms_component.id = ccr.component_id
\ No newline at end of file
@@ -0,0 +1,5 @@
+insert into cms_component (id, page_id, kind_uuid, position, label, raw_data)
+select ccr.component_id, :page_id, ccr.kind_uuid, ccr.position, ccr.label, ccr.raw_data
+from cms_component_revision as ccr
+where ccr.revision_id = :revision_id
+  and not exists (select 1 from cms_component as cc where cc.id = ccr.component_id)
\ No newline at end of file
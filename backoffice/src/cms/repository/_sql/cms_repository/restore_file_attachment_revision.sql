@@ -0,0 +1,4 @@
+insert into cms_file_attachment (id, component_id, file_name, file_path, file_type, file_hash, uploaded)
+select attachment_id, component_id, file_name, file_path, file_type, file_hash, uploaded
+from cms_file_attachment_revision
+where revision_id = :revision_id
\ No newline at end of file
@@ -0,0 +1,7 @@
+update cms_page
+set title=cpr.title,
+    summary=cpr.summary,
+    updated=datetime('now')
+from cms_page_revision as cpr
+where cpr.id = :revision_id
+  and cms_page.id = cpr.page_id
\ No newline at end of file
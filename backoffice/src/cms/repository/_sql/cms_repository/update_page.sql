@@ -1,6 +1,10 @@
 update cms_page
 set title=:title,
     summary=:summary,
+    slug=:slug,
+    parent_id=:parent_id,
     updated=datetime('now'),
-    status=:status
+    status=:status,
+    publish_at=:publish_at,
+    unpublish_at=:unpublish_at
 where id = :id;
\ No newline at end of file
@@ -0,0 +1,4 @@
+update cms_page
+set status=:status,
+    updated=datetime('now')
+where id = :id;
\ No newline at end of file
@@ -1,17 +1,22 @@
 use crate::cms::data_model::cms_data::{
-    AddFileAttachmentModel, AddPageModel, CreateComponentModel, FetchComponentModel,
-    FetchPageModel, FilePath, ListComponentModel, ListFileAttachmentModel, ListPageModel,
-    ReturningIdModel, UpdateComponentModel, UpdateComponentPositionModel, UpdatePageModel,
-    UserIdModel,
+    AddFileAttachmentModel, AddPageModel, AddPageTermModel, AddReviewModel, CreateComponentModel,
+    FetchComponentModel, FetchPageModel, FetchRevisionModel, FilePath, ListComponentModel,
+    ListFileAttachmentModel, ListPageModel, ListPendingReviewModel, ListReviewModel,
+    ListRevisionModel, PageAuthorModel, PageIdModel, PageParentOptionModel, PagePath,
+    PageTermModel, ReturningIdModel, RevisionAuthorModel, RevisionComponentModel,
+    UpdateComponentModel, UpdateComponentPositionModel, UpdatePageModel, UserIdModel,
 };
+use crate::cms::query_model::ListPageQuery;
 use error_stack::{Report, ResultExt};
 use poem::http::StatusCode;
-use rusqlite::{Connection, OptionalExtension, named_params};
+use rusqlite::{Connection, OptionalExtension, Transaction, TransactionBehavior, named_params};
 use shared::cms::status::CmsPageStatus;
+use shared::cms::taxonomy::TermKind;
 use shared::utils::context::{Context, ContextError, FromContext};
-use shared::utils::db::{BorrowConnectionExt, SqliteClient};
+use shared::utils::db::{BorrowConnectionExt, SqliteClient, SqliteConn, run_blocking};
 use shared::utils::error::ExtraResultExt;
-use std::sync::{Arc, MutexGuard};
+use shared::utils::query_string::list_query::{Cursor, KeysetPage, SortKey};
+use std::sync::Arc;
 use thiserror::Error;
 
 #[derive(Debug, Error)]
@@ -24,6 +29,7 @@ pub enum CmsRepositoryError {
     BorrowConnError,
 }
 
+#[mry::mry]
 pub struct CmsRepository {
     sqlite_client: Option<SqliteClient>,
 }
@@ -32,103 +38,115 @@ impl CmsRepository {
     pub fn new(sqlite_client: SqliteClient) -> Self {
         Self {
             sqlite_client: Some(sqlite_client),
+            mry: Default::default(),
         }
     }
 
-    fn borrow_conn(&self) -> Result<MutexGuard<'_, Connection>, Report<CmsRepositoryError>> {
+    fn borrow_conn(&self) -> Result<SqliteConn, Report<CmsRepositoryError>> {
         self.sqlite_client
             .borrow_conn()
             .change_context(CmsRepositoryError::BorrowConnError)
     }
-}
-
-impl CmsRepository {
-    pub fn add_file_attachment(
-        &self,
-        add_file_attachment_model: AddFileAttachmentModel,
-    ) -> Result<(), Report<CmsRepositoryError>> {
-        let conn = self.borrow_conn()?;
 
-        conn.execute(
-            include_str!("_sql/cms_repository/add_file_attachment.sql"),
-            named_params! {
-                ":component_id": add_file_attachment_model.component_id,
-                ":file_name": add_file_attachment_model.file_name,
-                ":file_path": add_file_attachment_model.file_path,
-                ":file_type": add_file_attachment_model.file_type,
-            },
-        )
-        .change_context(CmsRepositoryError::QueryError)
-        .attach(StatusCode::INTERNAL_SERVER_ERROR)
-        .log_it()?;
-
-        Ok(())
-    }
-
-    pub fn add_page(
-        &self,
-        page: AddPageModel,
+    /// Copies the page, its components and their attachments as they are now, inside the
+    /// caller's transaction.
+    fn insert_revision(
+        tx: &Transaction,
+        page_id: i64,
+        user_id: i64,
     ) -> Result<ReturningIdModel, Report<CmsRepositoryError>> {
-        let conn = self.borrow_conn()?;
-
-        let mut stmt = conn
-            .prepare(include_str!("_sql/cms_repository/add_page.sql"))
-            .change_context(CmsRepositoryError::QueryError)
-            .attach(StatusCode::INTERNAL_SERVER_ERROR)
-            .log_it()?;
-
-        let row: ReturningIdModel = stmt
+        let revision_id: ReturningIdModel = tx
             .query_one(
+                include_str!("_sql/cms_repository/add_page_revision.sql"),
                 named_params! {
-                    ":user_id": page.user_id,
-                    ":title": page.title,
-                    ":status": page.status.as_stringed(),
+                    ":page_id": page_id,
+                    ":user_id": user_id,
                 },
                 |row| Ok(ReturningIdModel(row.get("id")?)),
             )
-            .change_context(CmsRepositoryError::RowValueError)
+            .change_context(CmsRepositoryError::QueryError)
             .attach(StatusCode::INTERNAL_SERVER_ERROR)
             .log_it()?;
 
-        Ok(row)
-    }
+        tx.execute(
+            include_str!("_sql/cms_repository/add_component_revision.sql"),
+            named_params! {
+                ":revision_id": revision_id.0,
+                ":page_id": page_id,
+            },
+        )
+        .change_context(CmsRepositoryError::QueryError)
+        .attach(StatusCode::INTERNAL_SERVER_ERROR)
+        .log_it()?;
 
-    pub fn create_component(
-        &self,
-        create_component_model: CreateComponentModel,
-    ) -> Result<ReturningIdModel, Report<CmsRepositoryError>> {
-        let conn = self.borrow_conn()?;
+        tx.execute(
+            include_str!("_sql/cms_repository/add_file_attachment_revision.sql"),
+            named_params! {
+                ":revision_id": revision_id.0,
+                ":page_id": page_id,
+            },
+        )
+        .change_context(CmsRepositoryError::QueryError)
+        .attach(StatusCode::INTERNAL_SERVER_ERROR)
+        .log_it()?;
 
-        let mut stmt = conn
-            .prepare(include_str!("_sql/cms_repository/create_component.sql"))
-            .change_context(CmsRepositoryError::QueryError)
-            .attach(StatusCode::INTERNAL_SERVER_ERROR)
-            .log_it()?;
+        Ok(revision_id)
+    }
 
-        let row: ReturningIdModel = stmt
-            .query_one(
+    /// Records the save as a new revision. When the author has no publishing rights a page that is
+    /// live, or scheduled to go live, is sent back to review first, so the change only goes public
+    /// once an editor approves it.
+    fn record_revision(
+        tx: &Transaction,
+        page_id: i64,
+        revision_author: RevisionAuthorModel,
+    ) -> Result<ReturningIdModel, Report<CmsRepositoryError>> {
+        if !revision_author.can_publish {
+            tx.execute(
+                include_str!("_sql/cms_repository/hold_page_for_review.sql"),
                 named_params! {
-                    ":page_id": create_component_model.page_id,
-                    ":kind_uuid": create_component_model.kind_uuid,
-                    ":raw_data": create_component_model.raw_data,
-                    ":label": create_component_model.label,
+                    ":page_id": page_id,
+                    ":published_status": CmsPageStatus::Published.as_stringed(),
+                    ":draft_status": CmsPageStatus::Draft.as_stringed(),
+                    ":in_review_status": CmsPageStatus::InReview.as_stringed(),
                 },
-                |row| Ok(ReturningIdModel(row.get("id")?)),
             )
-            .change_context(CmsRepositoryError::RowValueError)
+            .change_context(CmsRepositoryError::QueryError)
             .attach(StatusCode::INTERNAL_SERVER_ERROR)
             .log_it()?;
+        }
 
-        Ok(row)
+        Self::insert_revision(tx, page_id, revision_author.user_id)
     }
 
-    pub fn delete_component(&self, id: i64) -> Result<(), Report<CmsRepositoryError>> {
-        let conn = self.borrow_conn()?;
+    fn page_id_component(
+        tx: &Transaction,
+        component_id: i64,
+    ) -> Result<PageIdModel, Report<CmsRepositoryError>> {
+        tx.query_one(
+            include_str!("_sql/cms_repository/get_page_id_component.sql"),
+            named_params! {
+                ":id": component_id,
+            },
+            |row| Ok(PageIdModel(row.get("page_id")?)),
+        )
+        .change_context(CmsRepositoryError::RowValueError)
+        .attach(StatusCode::NOT_FOUND)
+        .log_it()
+    }
 
-        conn.execute(
-            include_str!("_sql/cms_repository/delete_component.sql"),
+    fn insert_file_attachment(
+        tx: &Transaction,
+        add_file_attachment_model: &AddFileAttachmentModel,
+    ) -> Result<(), Report<CmsRepositoryError>> {
+        tx.execute(
+            include_str!("_sql/cms_repository/add_file_attachment.sql"),
             named_params! {
-                ":id": id,
+                ":component_id": add_file_attachment_model.component_id,
+                ":file_name": add_file_attachment_model.file_name,
+                ":file_path": add_file_attachment_model.file_path,
+                ":file_type": add_file_attachment_model.file_type,
+                ":file_hash": add_file_attachment_model.file_hash,
             },
         )
         .change_context(CmsRepositoryError::QueryError)
@@ -138,32 +156,92 @@ impl CmsRepository {
         Ok(())
     }
 
-    pub fn delete_file_attachment(&self, id: i64) -> Result<(), Report<CmsRepositoryError>> {
-        let conn = self.borrow_conn()?;
+    /// Saves the component inside the caller's transaction, returning the page it belongs to.
+    fn save_component(
+        tx: &Transaction,
+        update_component_model: &UpdateComponentModel,
+    ) -> Result<PageIdModel, Report<CmsRepositoryError>> {
+        let page_id = Self::page_id_component(tx, update_component_model.id)?;
 
-        conn.execute(
-            include_str!("_sql/cms_repository/delete_file_attachment.sql"),
+        tx.execute(
+            include_str!("_sql/cms_repository/update_component.sql"),
             named_params! {
-                ":id": id,
+                ":label": update_component_model.label,
+                ":raw_data": update_component_model.raw_data,
+                ":id": update_component_model.id,
             },
         )
         .change_context(CmsRepositoryError::QueryError)
         .attach(StatusCode::INTERNAL_SERVER_ERROR)
         .log_it()?;
 
-        Ok(())
+        Ok(page_id)
     }
 
-    pub fn delete_file_attachment_by_component_id(
-        &self,
-        component_id: i64,
+    /// Makes the page match the revision inside the caller's transaction: components added since
+    /// are dropped, deleted ones come back under their old id, every position is put back and
+    /// the components are linked to the attachments they had then.
+    fn apply_revision(
+        tx: &Transaction,
+        page_id: i64,
+        revision_id: i64,
     ) -> Result<(), Report<CmsRepositoryError>> {
-        let conn = self.borrow_conn()?;
+        tx.execute(
+            include_str!("_sql/cms_repository/restore_page_revision.sql"),
+            named_params! {
+                ":revision_id": revision_id,
+            },
+        )
+        .change_context(CmsRepositoryError::QueryError)
+        .attach(StatusCode::INTERNAL_SERVER_ERROR)
+        .log_it()?;
 
-        conn.execute(
-            include_str!("_sql/cms_repository/delele_file_attachment_component.sql"),
+        tx.execute(
+            include_str!("_sql/cms_repository/delete_component_not_in_revision.sql"),
+            named_params! {
+                ":page_id": page_id,
+                ":revision_id": revision_id,
+            },
+        )
+        .change_context(CmsRepositoryError::QueryError)
+        .attach(StatusCode::INTERNAL_SERVER_ERROR)
+        .log_it()?;
+
+        tx.execute(
+            include_str!("_sql/cms_repository/restore_component_revision.sql"),
             named_params! {
-                ":component_id": component_id,
+                ":revision_id": revision_id,
+            },
+        )
+        .change_context(CmsRepositoryError::QueryError)
+        .attach(StatusCode::INTERNAL_SERVER_ERROR)
+        .log_it()?;
+
+        tx.execute(
+            include_str!("_sql/cms_repository/restore_deleted_component_revision.sql"),
+            named_params! {
+                ":page_id": page_id,
+                ":revision_id": revision_id,
+            },
+        )
+        .change_context(CmsRepositoryError::QueryError)
+        .attach(StatusCode::INTERNAL_SERVER_ERROR)
+        .log_it()?;
+
+        tx.execute(
+            include_str!("_sql/cms_repository/delete_file_attachment_page.sql"),
+            named_params! {
+                ":page_id": page_id,
+            },
+        )
+        .change_context(CmsRepositoryError::QueryError)
+        .attach(StatusCode::INTERNAL_SERVER_ERROR)
+        .log_it()?;
+
+        tx.execute(
+            include_str!("_sql/cms_repository/restore_file_attachment_revision.sql"),
+            named_params! {
+                ":revision_id": revision_id,
             },
         )
         .change_context(CmsRepositoryError::QueryError)
@@ -173,336 +251,1585 @@ impl CmsRepository {
         Ok(())
     }
 
-    pub fn fetch_component(
-        &self,
-        id: i64,
-    ) -> Result<Option<FetchComponentModel>, Report<CmsRepositoryError>> {
-        let conn = self.borrow_conn()?;
-
+    fn query_page_term(
+        conn: &Connection,
+        page_id: i64,
+    ) -> Result<Arc<[PageTermModel]>, Report<CmsRepositoryError>> {
         let mut stmt = conn
-            .prepare(include_str!("_sql/cms_repository/fetch_component.sql"))
+            .prepare(include_str!("_sql/cms_repository/list_page_term.sql"))
             .change_context(CmsRepositoryError::QueryError)
             .attach(StatusCode::INTERNAL_SERVER_ERROR)
             .log_it()?;
 
-        let row: Option<FetchComponentModel> = stmt
-            .query_one(
+        let rows = stmt
+            .query_map(
                 named_params! {
-                    ":id": id,
+                    ":page_id": page_id,
                 },
                 |row| {
-                    Ok(FetchComponentModel {
-                        label: row.get("label")?,
-                        position: row.get("position")?,
-                        raw_data: row.get("raw_data")?,
+                    Ok(PageTermModel {
+                        id: row.get("id")?,
+                        kind: TermKind::try_from(row.get::<_, String>("kind")?).unwrap_or_default(),
+                        slug: row.get("slug")?,
+                        name: row.get("name")?,
                     })
                 },
             )
-            .optional()
             .change_context(CmsRepositoryError::RowValueError)
             .attach(StatusCode::INTERNAL_SERVER_ERROR)
             .log_it()?;
 
-        Ok(row)
+        let rows = rows
+            .collect::<Result<Vec<_>, _>>()
+            .change_context(CmsRepositoryError::RowValueError)
+            .attach(StatusCode::INTERNAL_SERVER_ERROR)
+            .log_it()?;
+
+        Ok(rows.into())
     }
+}
 
-    pub fn fetch_page(
+#[mry::mry]
+impl CmsRepository {
+    /// Links the file to the component and records its page as a new revision, in one transaction.
+    pub fn add_file_attachment(
         &self,
-        id: i64,
-    ) -> Result<Option<FetchPageModel>, Report<CmsRepositoryError>> {
-        let conn = self.borrow_conn()?;
+        add_file_attachment_model: AddFileAttachmentModel,
+        revision_author: RevisionAuthorModel,
+    ) -> Result<(), Report<CmsRepositoryError>> {
+        run_blocking(|| {
+            let mut conn = self.borrow_conn()?;
+
+            let tx = conn
+                .transaction_with_behavior(TransactionBehavior::Immediate)
+                .change_context(CmsRepositoryError::QueryError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)
+                .log_it()?;
+
+            let page_id = Self::page_id_component(&tx, add_file_attachment_model.component_id)?;
+            Self::insert_file_attachment(&tx, &add_file_attachment_model)?;
+            Self::record_revision(&tx, page_id.0, revision_author)?;
+
+            tx.commit()
+                .change_context(CmsRepositoryError::QueryError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)
+                .log_it()?;
+
+            Ok(())
+        })
+    }
 
-        let mut stmt = conn
-            .prepare(include_str!("_sql/cms_repository/fetch_page.sql"))
+    /// Inserts the page and records it as the first revision, in one transaction.
+    pub fn add_page(
+        &self,
+        page: AddPageModel,
+    ) -> Result<ReturningIdModel, Report<CmsRepositoryError>> {
+        run_blocking(|| {
+            let mut conn = self.borrow_conn()?;
+
+            let tx = conn
+                .transaction_with_behavior(TransactionBehavior::Immediate)
+                .change_context(CmsRepositoryError::QueryError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)
+                .log_it()?;
+
+            let row: ReturningIdModel = tx
+                .query_one(
+                    include_str!("_sql/cms_repository/add_page.sql"),
+                    named_params! {
+                        ":user_id": page.user_id,
+                        ":slug": page.slug,
+                        ":path": page.path,
+                        ":title": page.title,
+                        ":status": page.status.as_stringed(),
+                    },
+                    |row| Ok(ReturningIdModel(row.get("id")?)),
+                )
+                .change_context(CmsRepositoryError::RowValueError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)
+                .log_it()?;
+
+            Self::insert_revision(&tx, row.0, page.user_id)?;
+
+            tx.commit()
+                .change_context(CmsRepositoryError::QueryError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)
+                .log_it()?;
+
+            Ok(row)
+        })
+    }
+
+    /// Creates the term on first use, so picking a suggestion and typing a new name are the same call.
+    pub fn add_page_term(
+        &self,
+        add_page_term_model: AddPageTermModel,
+    ) -> Result<(), Report<CmsRepositoryError>> {
+        run_blocking(|| {
+            let mut conn = self.borrow_conn()?;
+
+            let tx = conn
+                .transaction_with_behavior(TransactionBehavior::Immediate)
+                .change_context(CmsRepositoryError::QueryError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)
+                .log_it()?;
+
+            tx.execute(
+                include_str!("_sql/cms_repository/add_term.sql"),
+                named_params! {
+                    ":kind": add_page_term_model.kind.as_stringed(),
+                    ":slug": add_page_term_model.slug,
+                    ":name": add_page_term_model.name,
+                },
+            )
             .change_context(CmsRepositoryError::QueryError)
             .attach(StatusCode::INTERNAL_SERVER_ERROR)
             .log_it()?;
 
-        let row: Option<FetchPageModel> = stmt
-            .query_one(
+            tx.execute(
+                include_str!("_sql/cms_repository/add_page_term.sql"),
                 named_params! {
-                    ":id": id,
-                },
-                |row| {
-                    Ok(FetchPageModel {
-                        id: row.get("id")?,
-                        title: row.get("title")?,
-                        summary: row.get("summary")?,
-                        status: CmsPageStatus::try_from(row.get::<_, String>("status")?)
-                            .unwrap_or_default(),
-                    })
+                    ":page_id": add_page_term_model.page_id,
+                    ":kind": add_page_term_model.kind.as_stringed(),
+                    ":slug": add_page_term_model.slug,
                 },
             )
-            .optional()
-            .change_context(CmsRepositoryError::RowValueError)
+            .change_context(CmsRepositoryError::QueryError)
             .attach(StatusCode::INTERNAL_SERVER_ERROR)
             .log_it()?;
 
-        Ok(row)
+            tx.commit()
+                .change_context(CmsRepositoryError::QueryError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)
+                .log_it()
+        })
     }
 
-    pub fn get_author_id_component(
+    /// Records the review and moves the page to the status it decided, in one transaction.
+    pub fn add_review(
         &self,
-        id: i64,
-    ) -> Result<Option<UserIdModel>, Report<CmsRepositoryError>> {
-        let conn = self.borrow_conn()?;
+        add_review_model: AddReviewModel,
+    ) -> Result<(), Report<CmsRepositoryError>> {
+        run_blocking(|| {
+            let mut conn = self.borrow_conn()?;
 
-        let mut stmt = conn
-            .prepare(include_str!(
-                "_sql/cms_repository/get_author_id_component.sql"
-            ))
+            let tx = conn
+                .transaction_with_behavior(TransactionBehavior::Immediate)
+                .change_context(CmsRepositoryError::QueryError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)
+                .log_it()?;
+
+            tx.execute(
+                include_str!("_sql/cms_repository/add_review.sql"),
+                named_params! {
+                    ":page_id": add_review_model.page_id,
+                    ":reviewer_id": add_review_model.reviewer_id,
+                    ":status": add_review_model.status.as_stringed(),
+                    ":comment": add_review_model.comment,
+                },
+            )
             .change_context(CmsRepositoryError::QueryError)
             .attach(StatusCode::INTERNAL_SERVER_ERROR)
             .log_it()?;
 
-        let row: Option<UserIdModel> = stmt
-            .query_one(
+            tx.execute(
+                include_str!("_sql/cms_repository/update_page_status.sql"),
                 named_params! {
-                    ":id": id,
+                    ":status": add_review_model.status.as_stringed(),
+                    ":id": add_review_model.page_id,
                 },
-                |row| Ok(UserIdModel(row.get("user_id")?)),
             )
-            .optional()
-            .change_context(CmsRepositoryError::RowValueError)
+            .change_context(CmsRepositoryError::QueryError)
             .attach(StatusCode::INTERNAL_SERVER_ERROR)
             .log_it()?;
 
-        Ok(row)
+            tx.commit()
+                .change_context(CmsRepositoryError::QueryError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)
+                .log_it()
+        })
     }
 
-    pub fn get_author_id_page(
+    pub fn count_child_page(&self, id: i64) -> Result<i64, Report<CmsRepositoryError>> {
+        run_blocking(|| {
+            let conn = self.borrow_conn()?;
+
+            let mut stmt = conn
+                .prepare(include_str!("_sql/cms_repository/count_child_page.sql"))
+                .change_context(CmsRepositoryError::QueryError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)
+                .log_it()?;
+
+            let child_count: i64 = stmt
+                .query_one(
+                    named_params! {
+                        ":id": id,
+                    },
+                    |row| row.get("child_count"),
+                )
+                .change_context(CmsRepositoryError::RowValueError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)
+                .log_it()?;
+
+            Ok(child_count)
+        })
+    }
+
+    /// How many attachments still point at a stored blob, duplicate uploads share one.
+    pub fn count_file_path(&self, file_path: &str) -> Result<i64, Report<CmsRepositoryError>> {
+        run_blocking(|| {
+            let conn = self.borrow_conn()?;
+
+            let mut stmt = conn
+                .prepare(include_str!("_sql/cms_repository/count_file_path.sql"))
+                .change_context(CmsRepositoryError::QueryError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)
+                .log_it()?;
+
+            let file_count: i64 = stmt
+                .query_one(
+                    named_params! {
+                        ":file_path": file_path,
+                    },
+                    |row| row.get("file_count"),
+                )
+                .change_context(CmsRepositoryError::RowValueError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)
+                .log_it()?;
+
+            Ok(file_count)
+        })
+    }
+
+    /// Appends the component to the page and records the page as a new revision, in one transaction.
+    pub fn create_component(
+        &self,
+        create_component_model: CreateComponentModel,
+    ) -> Result<ReturningIdModel, Report<CmsRepositoryError>> {
+        run_blocking(|| {
+            let mut conn = self.borrow_conn()?;
+
+            let tx = conn
+                .transaction_with_behavior(TransactionBehavior::Immediate)
+                .change_context(CmsRepositoryError::QueryError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)
+                .log_it()?;
+
+            let row: ReturningIdModel = tx
+                .query_one(
+                    include_str!("_sql/cms_repository/create_component.sql"),
+                    named_params! {
+                        ":page_id": create_component_model.page_id,
+                        ":kind_uuid": create_component_model.kind_uuid,
+                        ":raw_data": create_component_model.raw_data,
+                        ":label": create_component_model.label,
+                    },
+                    |row| Ok(ReturningIdModel(row.get("id")?)),
+                )
+                .change_context(CmsRepositoryError::RowValueError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)
+                .log_it()?;
+
+            Self::record_revision(
+                &tx,
+                create_component_model.page_id,
+                create_component_model.revision_author,
+            )?;
+
+            tx.commit()
+                .change_context(CmsRepositoryError::QueryError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)
+                .log_it()?;
+
+            Ok(row)
+        })
+    }
+
+    /// Deletes the component with its attachments and records its page as a new revision, in one
+    /// transaction.
+    pub fn delete_component(
         &self,
         id: i64,
-    ) -> Result<Option<UserIdModel>, Report<CmsRepositoryError>> {
-        let conn = self.borrow_conn()?;
+        revision_author: RevisionAuthorModel,
+    ) -> Result<(), Report<CmsRepositoryError>> {
+        run_blocking(|| {
+            let mut conn = self.borrow_conn()?;
 
-        let mut stmt = conn
-            .prepare(include_str!("_sql/cms_repository/get_author_id_page.sql"))
-            .change_context(CmsRepositoryError::QueryError)
-            .attach(StatusCode::INTERNAL_SERVER_ERROR)
-            .log_it()?;
+            let tx = conn
+                .transaction_with_behavior(TransactionBehavior::Immediate)
+                .change_context(CmsRepositoryError::QueryError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)
+                .log_it()?;
 
-        let row: Option<UserIdModel> = stmt
-            .query_one(
+            let page_id = Self::page_id_component(&tx, id)?;
+
+            tx.execute(
+                include_str!("_sql/cms_repository/delete_component.sql"),
                 named_params! {
                     ":id": id,
                 },
-                |row| Ok(UserIdModel(row.get("user_id")?)),
             )
-            .optional()
-            .change_context(CmsRepositoryError::RowValueError)
+            .change_context(CmsRepositoryError::QueryError)
             .attach(StatusCode::INTERNAL_SERVER_ERROR)
             .log_it()?;
 
-        Ok(row)
+            Self::record_revision(&tx, page_id.0, revision_author)?;
+
+            tx.commit()
+                .change_context(CmsRepositoryError::QueryError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)
+                .log_it()?;
+
+            Ok(())
+        })
     }
 
-    pub fn get_file_path(
+    /// Unlinks the file and records its page as a new revision, in one transaction.
+    pub fn delete_file_attachment(
         &self,
         id: i64,
-        component_id: i64,
-    ) -> Result<Option<FilePath>, Report<CmsRepositoryError>> {
-        let conn = self.borrow_conn()?;
-
-        let mut stmt = conn
-            .prepare(include_str!("_sql/cms_repository/get_file_path.sql"))
+        revision_author: RevisionAuthorModel,
+    ) -> Result<(), Report<CmsRepositoryError>> {
+        run_blocking(|| {
+            let mut conn = self.borrow_conn()?;
+
+            let tx = conn
+                .transaction_with_behavior(TransactionBehavior::Immediate)
+                .change_context(CmsRepositoryError::QueryError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)
+                .log_it()?;
+
+            let page_id: PageIdModel = tx
+                .query_one(
+                    include_str!("_sql/cms_repository/get_page_id_file_attachment.sql"),
+                    named_params! {
+                        ":id": id,
+                    },
+                    |row| Ok(PageIdModel(row.get("page_id")?)),
+                )
+                .change_context(CmsRepositoryError::RowValueError)
+                .attach(StatusCode::NOT_FOUND)
+                .log_it()?;
+
+            tx.execute(
+                include_str!("_sql/cms_repository/delete_file_attachment.sql"),
+                named_params! {
+                    ":id": id,
+                },
+            )
             .change_context(CmsRepositoryError::QueryError)
             .attach(StatusCode::INTERNAL_SERVER_ERROR)
             .log_it()?;
 
-        let row: Option<FilePath> = stmt
-            .query_one(
+            Self::record_revision(&tx, page_id.0, revision_author)?;
+
+            tx.commit()
+                .change_context(CmsRepositoryError::QueryError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)
+                .log_it()?;
+
+            Ok(())
+        })
+    }
+
+    pub fn delete_page(&self, id: i64) -> Result<(), Report<CmsRepositoryError>> {
+        run_blocking(|| {
+            let conn = self.borrow_conn()?;
+
+            conn.execute(
+                include_str!("_sql/cms_repository/delete_page.sql"),
                 named_params! {
                     ":id": id,
-                    ":component_id": component_id,
                 },
-                |row| Ok(FilePath(row.get("file_path")?)),
             )
-            .optional()
-            .change_context(CmsRepositoryError::RowValueError)
+            .change_context(CmsRepositoryError::QueryError)
             .attach(StatusCode::INTERNAL_SERVER_ERROR)
             .log_it()?;
 
-        Ok(row)
+            Ok(())
+        })
     }
 
-    pub fn list_component(
+    /// Drops the term as well once no page uses it, so suggestions only offer terms in use.
+    pub fn delete_page_term(
         &self,
         page_id: i64,
-    ) -> Result<Arc<[ListComponentModel]>, Report<CmsRepositoryError>> {
-        let conn = self.borrow_conn()?;
+        term_id: i64,
+    ) -> Result<(), Report<CmsRepositoryError>> {
+        run_blocking(|| {
+            let mut conn = self.borrow_conn()?;
 
-        let mut stmt = conn
-            .prepare(include_str!("_sql/cms_repository/list_component.sql"))
-            .change_context(CmsRepositoryError::QueryError)
-            .attach(StatusCode::INTERNAL_SERVER_ERROR)
-            .log_it()?;
+            let tx = conn
+                .transaction_with_behavior(TransactionBehavior::Immediate)
+                .change_context(CmsRepositoryError::QueryError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)
+                .log_it()?;
 
-        let rows = stmt
-            .query_map(
+            tx.execute(
+                include_str!("_sql/cms_repository/delete_page_term.sql"),
                 named_params! {
                     ":page_id": page_id,
-                },
-                |row| {
-                    Ok(ListComponentModel {
-                        id: row.get("id")?,
-                        kind_uuid: row.get("kind_uuid")?,
-                        position: row.get("position")?,
-                        label: row.get("label")?,
-                    })
+                    ":term_id": term_id,
                 },
             )
-            .change_context(CmsRepositoryError::RowValueError)
+            .change_context(CmsRepositoryError::QueryError)
             .attach(StatusCode::INTERNAL_SERVER_ERROR)
             .log_it()?;
 
-        let rows = rows
-            .collect::<Result<Vec<_>, _>>()
-            .change_context(CmsRepositoryError::RowValueError)
+            tx.execute(
+                include_str!("_sql/cms_repository/delete_unused_term.sql"),
+                named_params! {
+                    ":term_id": term_id,
+                },
+            )
+            .change_context(CmsRepositoryError::QueryError)
             .attach(StatusCode::INTERNAL_SERVER_ERROR)
             .log_it()?;
 
-        Ok(rows.into())
+            tx.commit()
+                .change_context(CmsRepositoryError::QueryError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)
+                .log_it()
+        })
+    }
+
+    pub fn fetch_component(
+        &self,
+        id: i64,
+    ) -> Result<Option<FetchComponentModel>, Report<CmsRepositoryError>> {
+        run_blocking(|| {
+            let conn = self.borrow_conn()?;
+
+            let mut stmt = conn
+                .prepare(include_str!("_sql/cms_repository/fetch_component.sql"))
+                .change_context(CmsRepositoryError::QueryError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)
+                .log_it()?;
+
+            let row: Option<FetchComponentModel> = stmt
+                .query_one(
+                    named_params! {
+                        ":id": id,
+                    },
+                    |row| {
+                        Ok(FetchComponentModel {
+                            label: row.get("label")?,
+                            position: row.get("position")?,
+                            raw_data: row.get("raw_data")?,
+                        })
+                    },
+                )
+                .optional()
+                .change_context(CmsRepositoryError::RowValueError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)
+                .log_it()?;
+
+            Ok(row)
+        })
+    }
+
+    pub fn fetch_page(
+        &self,
+        id: i64,
+    ) -> Result<Option<FetchPageModel>, Report<CmsRepositoryError>> {
+        run_blocking(|| {
+            let conn = self.borrow_conn()?;
+
+            // a missing page simply has no terms, the row decides whether it exists.
+            let terms = Self::query_page_term(&conn, id)?;
+
+            let mut stmt = conn
+                .prepare(include_str!("_sql/cms_repository/fetch_page.sql"))
+                .change_context(CmsRepositoryError::QueryError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)
+                .log_it()?;
+
+            let row: Option<FetchPageModel> = stmt
+                .query_one(
+                    named_params! {
+                        ":id": id,
+                    },
+                    |row| {
+                        Ok(FetchPageModel {
+                            id: row.get("id")?,
+                            parent_id: row.get("parent_id")?,
+                            slug: row.get("slug")?,
+                            path: row.get("path")?,
+                            title: row.get("title")?,
+                            summary: row.get("summary")?,
+                            status: CmsPageStatus::try_from(row.get::<_, String>("status")?)
+                                .unwrap_or_default(),
+                            publish_at: row.get("publish_at")?,
+                            unpublish_at: row.get("unpublish_at")?,
+                            terms,
+                        })
+                    },
+                )
+                .optional()
+                .change_context(CmsRepositoryError::RowValueError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)
+                .log_it()?;
+
+            Ok(row)
+        })
+    }
+
+    pub fn fetch_page_path(&self, id: i64) -> Result<Option<PagePath>, Report<CmsRepositoryError>> {
+        run_blocking(|| {
+            let conn = self.borrow_conn()?;
+
+            let mut stmt = conn
+                .prepare(include_str!("_sql/cms_repository/fetch_page_path.sql"))
+                .change_context(CmsRepositoryError::QueryError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)
+                .log_it()?;
+
+            let row: Option<PagePath> = stmt
+                .query_one(
+                    named_params! {
+                        ":id": id,
+                    },
+                    |row| Ok(PagePath(row.get("path")?)),
+                )
+                .optional()
+                .change_context(CmsRepositoryError::RowValueError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)
+                .log_it()?;
+
+            Ok(row)
+        })
+    }
+
+    pub fn fetch_revision(
+        &self,
+        id: i64,
+    ) -> Result<Option<FetchRevisionModel>, Report<CmsRepositoryError>> {
+        run_blocking(|| {
+            let conn = self.borrow_conn()?;
+
+            let mut stmt = conn
+                .prepare(include_str!("_sql/cms_repository/fetch_revision.sql"))
+                .change_context(CmsRepositoryError::QueryError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)
+                .log_it()?;
+
+            let row: Option<FetchRevisionModel> = stmt
+                .query_one(
+                    named_params! {
+                        ":id": id,
+                    },
+                    |row| {
+                        Ok(FetchRevisionModel {
+                            id: row.get("id")?,
+                            page_id: row.get("page_id")?,
+                            title: row.get("title")?,
+                            summary: row.get("summary")?,
+                        })
+                    },
+                )
+                .optional()
+                .change_context(CmsRepositoryError::RowValueError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)
+                .log_it()?;
+
+            Ok(row)
+        })
+    }
+
+    pub fn get_author_id_component(
+        &self,
+        id: i64,
+    ) -> Result<Option<UserIdModel>, Report<CmsRepositoryError>> {
+        run_blocking(|| {
+            let conn = self.borrow_conn()?;
+
+            let mut stmt = conn
+                .prepare(include_str!(
+                    "_sql/cms_repository/get_author_id_component.sql"
+                ))
+                .change_context(CmsRepositoryError::QueryError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)
+                .log_it()?;
+
+            let row: Option<UserIdModel> = stmt
+                .query_one(
+                    named_params! {
+                        ":id": id,
+                    },
+                    |row| Ok(UserIdModel(row.get("user_id")?)),
+                )
+                .optional()
+                .change_context(CmsRepositoryError::RowValueError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)
+                .log_it()?;
+
+            Ok(row)
+        })
+    }
+
+    pub fn get_author_id_page(
+        &self,
+        id: i64,
+    ) -> Result<Option<UserIdModel>, Report<CmsRepositoryError>> {
+        run_blocking(|| {
+            let conn = self.borrow_conn()?;
+
+            let mut stmt = conn
+                .prepare(include_str!("_sql/cms_repository/get_author_id_page.sql"))
+                .change_context(CmsRepositoryError::QueryError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)
+                .log_it()?;
+
+            let row: Option<UserIdModel> = stmt
+                .query_one(
+                    named_params! {
+                        ":id": id,
+                    },
+                    |row| Ok(UserIdModel(row.get("user_id")?)),
+                )
+                .optional()
+                .change_context(CmsRepositoryError::RowValueError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)
+                .log_it()?;
+
+            Ok(row)
+        })
+    }
+
+    pub fn get_file_path(
+        &self,
+        id: i64,
+        component_id: i64,
+    ) -> Result<Option<FilePath>, Report<CmsRepositoryError>> {
+        run_blocking(|| {
+            let conn = self.borrow_conn()?;
+
+            let mut stmt = conn
+                .prepare(include_str!("_sql/cms_repository/get_file_path.sql"))
+                .change_context(CmsRepositoryError::QueryError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)
+                .log_it()?;
+
+            let row: Option<FilePath> = stmt
+                .query_one(
+                    named_params! {
+                        ":id": id,
+                        ":component_id": component_id,
+                    },
+                    |row| Ok(FilePath(row.get("file_path")?)),
+                )
+                .optional()
+                .change_context(CmsRepositoryError::RowValueError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)
+                .log_it()?;
+
+            Ok(row)
+        })
+    }
+
+    pub fn get_file_path_by_hash(
+        &self,
+        file_hash: &str,
+    ) -> Result<Option<FilePath>, Report<CmsRepositoryError>> {
+        run_blocking(|| {
+            let conn = self.borrow_conn()?;
+
+            let mut stmt = conn
+                .prepare(include_str!(
+                    "_sql/cms_repository/get_file_path_by_hash.sql"
+                ))
+                .change_context(CmsRepositoryError::QueryError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)
+                .log_it()?;
+
+            let row: Option<FilePath> = stmt
+                .query_one(
+                    named_params! {
+                        ":file_hash": file_hash,
+                    },
+                    |row| Ok(FilePath(row.get("file_path")?)),
+                )
+                .optional()
+                .change_context(CmsRepositoryError::RowValueError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)
+                .log_it()?;
+
+            Ok(row)
+        })
+    }
+
+    pub fn get_page_id_component(
+        &self,
+        id: i64,
+    ) -> Result<Option<PageIdModel>, Report<CmsRepositoryError>> {
+        run_blocking(|| {
+            let conn = self.borrow_conn()?;
+
+            let mut stmt = conn
+                .prepare(include_str!(
+                    "_sql/cms_repository/get_page_id_component.sql"
+                ))
+                .change_context(CmsRepositoryError::QueryError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)
+                .log_it()?;
+
+            let row: Option<PageIdModel> = stmt
+                .query_one(
+                    named_params! {
+                        ":id": id,
+                    },
+                    |row| Ok(PageIdModel(row.get("page_id")?)),
+                )
+                .optional()
+                .change_context(CmsRepositoryError::RowValueError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)
+                .log_it()?;
+
+            Ok(row)
+        })
+    }
+
+    pub fn list_component(
+        &self,
+        page_id: i64,
+    ) -> Result<Arc<[ListComponentModel]>, Report<CmsRepositoryError>> {
+        run_blocking(|| {
+            let conn = self.borrow_conn()?;
+
+            let mut stmt = conn
+                .prepare(include_str!("_sql/cms_repository/list_component.sql"))
+                .change_context(CmsRepositoryError::QueryError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)
+                .log_it()?;
+
+            let rows = stmt
+                .query_map(
+                    named_params! {
+                        ":page_id": page_id,
+                    },
+                    |row| {
+                        Ok(ListComponentModel {
+                            id: row.get("id")?,
+                            kind_uuid: row.get("kind_uuid")?,
+                            position: row.get("position")?,
+                            label: row.get("label")?,
+                        })
+                    },
+                )
+                .change_context(CmsRepositoryError::RowValueError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)
+                .log_it()?;
+
+            let rows = rows
+                .collect::<Result<Vec<_>, _>>()
+                .change_context(CmsRepositoryError::RowValueError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)
+                .log_it()?;
+
+            Ok(rows.into())
+        })
     }
 
     pub fn list_file_attachment(
         &self,
         component_id: i64,
     ) -> Result<Arc<[ListFileAttachmentModel]>, Report<CmsRepositoryError>> {
-        let conn = self.borrow_conn()?;
+        run_blocking(|| {
+            let conn = self.borrow_conn()?;
+
+            let mut stmt = conn
+                .prepare(include_str!(
+                    "_sql/cms_repository/list_file_attachment_component.sql"
+                ))
+                .change_context(CmsRepositoryError::QueryError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)
+                .log_it()?;
+
+            let rows = stmt
+                .query_map(
+                    named_params! {
+                        ":component_id": component_id,
+                    },
+                    |row| {
+                        Ok(ListFileAttachmentModel {
+                            id: row.get("id")?,
+                            file_name: row.get("file_name")?,
+                            file_path: row.get("file_path")?,
+                            file_type: row.get("file_type")?,
+                            uploaded: row.get("uploaded")?,
+                        })
+                    },
+                )
+                .change_context(CmsRepositoryError::RowValueError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)
+                .log_it()?;
+
+            let rows = rows
+                .collect::<Result<Vec<_>, _>>()
+                .change_context(CmsRepositoryError::RowValueError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)
+                .log_it()?;
+
+            Ok(rows.into())
+        })
+    }
 
-        let mut stmt = conn
-            .prepare(include_str!(
-                "_sql/cms_repository/list_file_attachment_component.sql"
-            ))
-            .change_context(CmsRepositoryError::QueryError)
-            .attach(StatusCode::INTERNAL_SERVER_ERROR)
-            .log_it()?;
+    /// Every file the page points at, now or in any of its revisions.
+    pub fn list_file_path_by_page_id(
+        &self,
+        page_id: i64,
+    ) -> Result<Arc<[FilePath]>, Report<CmsRepositoryError>> {
+        run_blocking(|| {
+            let conn = self.borrow_conn()?;
+
+            let mut stmt = conn
+                .prepare(include_str!("_sql/cms_repository/list_file_path_page.sql"))
+                .change_context(CmsRepositoryError::QueryError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)
+                .log_it()?;
+
+            let rows = stmt
+                .query_map(
+                    named_params! {
+                        ":page_id": page_id,
+                    },
+                    |row| Ok(FilePath(row.get("file_path")?)),
+                )
+                .change_context(CmsRepositoryError::RowValueError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)
+                .log_it()?;
+
+            let rows = rows
+                .collect::<Result<Vec<_>, _>>()
+                .change_context(CmsRepositoryError::RowValueError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)
+                .log_it()?;
+
+            Ok(rows.into())
+        })
+    }
 
-        let rows = stmt
-            .query_map(
-                named_params! {
-                    ":component_id": component_id,
-                },
-                |row| {
-                    Ok(ListFileAttachmentModel {
+    pub fn list_page(
+        &self,
+        list_query: &ListPageQuery,
+        query: Option<String>,
+    ) -> Result<KeysetPage<ListPageModel>, Report<CmsRepositoryError>> {
+        run_blocking(|| {
+            let conn = self.borrow_conn()?;
+
+            let mut stmt = conn
+                .prepare(include_str!("_sql/cms_repository/list_page.sql"))
+                .change_context(CmsRepositoryError::QueryError)
+                .log_it()?;
+
+            let cursor = list_query.cursor();
+            let rows = stmt
+                .query_map(
+                    named_params! {
+                        ":trash_status": CmsPageStatus::Trash.as_stringed(),
+                        ":status": list_query.filter.status.map(|status| status.as_stringed()),
+                        ":author": list_query.filter.author,
+                        ":term": list_query.filter.term,
+                        ":query": query,
+                        ":sort": list_query.sort.as_str(),
+                        ":scan_desc": list_query.scan_desc(),
+                        ":cursor_key": cursor.map(|cursor| cursor.key.as_str()),
+                        ":cursor_id": cursor.map(|cursor| cursor.id),
+                        ":limit": list_query.fetch_limit(),
+                    },
+                    |row| {
+                        Ok((
+                            ListPageModel {
+                                id: row.get("id")?,
+                                author: row.get("author")?,
+                                user_id: row.get("user_id")?,
+                                path: row.get("path")?,
+                                title: row.get("title")?,
+                                added: row.get("added")?,
+                                updated: row.get("updated")?,
+                                status: CmsPageStatus::try_from(row.get::<_, String>("status")?)
+                                    .unwrap_or_default(),
+                            },
+                            Cursor {
+                                key: row.get("sort_key")?,
+                                id: row.get("id")?,
+                            },
+                        ))
+                    },
+                )
+                .change_context(CmsRepositoryError::RowValueError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)
+                .log_it()?;
+
+            let rows = rows
+                .collect::<Result<Vec<_>, _>>()
+                .change_context(CmsRepositoryError::RowValueError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)
+                .log_it()?;
+
+            Ok(KeysetPage::from_rows(list_query, rows))
+        })
+    }
+
+    pub fn list_page_author(&self) -> Result<Arc<[PageAuthorModel]>, Report<CmsRepositoryError>> {
+        run_blocking(|| {
+            let conn = self.borrow_conn()?;
+
+            let mut stmt = conn
+                .prepare(include_str!("_sql/cms_repository/list_page_author.sql"))
+                .change_context(CmsRepositoryError::QueryError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)
+                .log_it()?;
+
+            let rows = stmt
+                .query_map(
+                    named_params! {
+                        ":trash_status": CmsPageStatus::Trash.as_stringed(),
+                    },
+                    |row| {
+                        Ok(PageAuthorModel {
+                            id: row.get("id")?,
+                            username: row.get("username")?,
+                        })
+                    },
+                )
+                .change_context(CmsRepositoryError::RowValueError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)
+                .log_it()?;
+
+            let rows = rows
+                .collect::<Result<Vec<_>, _>>()
+                .change_context(CmsRepositoryError::RowValueError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)
+                .log_it()?;
+
+            Ok(rows.into())
+        })
+    }
+
+    pub fn list_page_term(
+        &self,
+        page_id: i64,
+    ) -> Result<Arc<[PageTermModel]>, Report<CmsRepositoryError>> {
+        run_blocking(|| {
+            let conn = self.borrow_conn()?;
+
+            Self::query_page_term(&conn, page_id)
+        })
+    }
+
+    pub fn list_page_parent_option(
+        &self,
+        id: i64,
+        path: String,
+    ) -> Result<Arc<[PageParentOptionModel]>, Report<CmsRepositoryError>> {
+        run_blocking(|| {
+            let conn = self.borrow_conn()?;
+
+            let mut stmt = conn
+                .prepare(include_str!(
+                    "_sql/cms_repository/list_page_parent_option.sql"
+                ))
+                .change_context(CmsRepositoryError::QueryError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)
+                .log_it()?;
+
+            let rows = stmt
+                .query_map(
+                    named_params! {
+                        ":id": id,
+                        ":path": path,
+                        ":trash_status": CmsPageStatus::Trash.as_stringed(),
+                    },
+                    |row| {
+                        Ok(PageParentOptionModel {
+                            id: row.get("id")?,
+                            title: row.get("title")?,
+                            path: row.get("path")?,
+                        })
+                    },
+                )
+                .change_context(CmsRepositoryError::RowValueError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)
+                .log_it()?;
+
+            let rows = rows
+                .collect::<Result<Vec<_>, _>>()
+                .change_context(CmsRepositoryError::RowValueError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)
+                .log_it()?;
+
+            Ok(rows.into())
+        })
+    }
+
+    /// Pages waiting on a review or on their author, `user_id` narrows the list to one author.
+    pub fn list_pending_review(
+        &self,
+        user_id: Option<i64>,
+    ) -> Result<Arc<[ListPendingReviewModel]>, Report<CmsRepositoryError>> {
+        run_blocking(|| {
+            let conn = self.borrow_conn()?;
+
+            let mut stmt = conn
+                .prepare(include_str!("_sql/cms_repository/list_pending_review.sql"))
+                .change_context(CmsRepositoryError::QueryError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)
+                .log_it()?;
+
+            let rows = stmt
+                .query_map(
+                    named_params! {
+                        ":in_review_status": CmsPageStatus::InReview.as_stringed(),
+                        ":changes_requested_status": CmsPageStatus::ChangesRequested.as_stringed(),
+                        ":user_id": user_id,
+                    },
+                    |row| {
+                        Ok(ListPendingReviewModel {
+                            id: row.get("id")?,
+                            title: row.get("title")?,
+                            author: row.get("author")?,
+                            status: CmsPageStatus::try_from(row.get::<_, String>("status")?)
+                                .unwrap_or_default(),
+                            reviewer: row.get("reviewer")?,
+                            comment: row.get("comment")?,
+                            reviewed: row.get("reviewed")?,
+                        })
+                    },
+                )
+                .change_context(CmsRepositoryError::RowValueError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)
+                .log_it()?;
+
+            let rows = rows
+                .collect::<Result<Vec<_>, _>>()
+                .change_context(CmsRepositoryError::RowValueError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)
+                .log_it()?;
+
+            Ok(rows.into())
+        })
+    }
+
+    pub fn list_review(
+        &self,
+        page_id: i64,
+    ) -> Result<Arc<[ListReviewModel]>, Report<CmsRepositoryError>> {
+        run_blocking(|| {
+            let conn = self.borrow_conn()?;
+
+            let mut stmt = conn
+                .prepare(include_str!("_sql/cms_repository/list_review.sql"))
+                .change_context(CmsRepositoryError::QueryError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)
+                .log_it()?;
+
+            let rows = stmt
+                .query_map(
+                    named_params! {
+                        ":page_id": page_id,
+                    },
+                    |row| {
+                        Ok(ListReviewModel {
+                            reviewer: row.get("reviewer")?,
+                            status: CmsPageStatus::try_from(row.get::<_, String>("status")?)
+                                .unwrap_or_default(),
+                            comment: row.get("comment")?,
+                            added: row.get("added")?,
+                        })
+                    },
+                )
+                .change_context(CmsRepositoryError::RowValueError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)
+                .log_it()?;
+
+            let rows = rows
+                .collect::<Result<Vec<_>, _>>()
+                .change_context(CmsRepositoryError::RowValueError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)
+                .log_it()?;
+
+            Ok(rows.into())
+        })
+    }
+
+    pub fn list_revision(
+        &self,
+        page_id: i64,
+    ) -> Result<Arc<[ListRevisionModel]>, Report<CmsRepositoryError>> {
+        run_blocking(|| {
+            let conn = self.borrow_conn()?;
+
+            let mut stmt = conn
+                .prepare(include_str!("_sql/cms_repository/list_revision.sql"))
+                .change_context(CmsRepositoryError::QueryError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)
+                .log_it()?;
+
+            let rows = stmt
+                .query_map(
+                    named_params! {
+                        ":page_id": page_id,
+                    },
+                    |row| {
+                        Ok(ListRevisionModel {
+                            id: row.get("id")?,
+                            author: row.get("author")?,
+                            title: row.get("title")?,
+                            added: row.get("added")?,
+                        })
+                    },
+                )
+                .change_context(CmsRepositoryError::RowValueError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)
+                .log_it()?;
+
+            let rows = rows
+                .collect::<Result<Vec<_>, _>>()
+                .change_context(CmsRepositoryError::RowValueError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)
+                .log_it()?;
+
+            Ok(rows.into())
+        })
+    }
+
+    pub fn list_revision_component(
+        &self,
+        revision_id: i64,
+    ) -> Result<Arc<[RevisionComponentModel]>, Report<CmsRepositoryError>> {
+        run_blocking(|| {
+            let conn = self.borrow_conn()?;
+
+            let mut stmt = conn
+                .prepare(include_str!(
+                    "_sql/cms_repository/list_revision_component.sql"
+                ))
+                .change_context(CmsRepositoryError::QueryError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)
+                .log_it()?;
+
+            let rows = stmt
+                .query_map(
+                    named_params! {
+                        ":revision_id": revision_id,
+                    },
+                    |row| {
+                        Ok(RevisionComponentModel {
+                            kind_uuid: row.get("kind_uuid")?,
+                            label: row.get("label")?,
+                            raw_data: row.get("raw_data")?,
+                        })
+                    },
+                )
+                .change_context(CmsRepositoryError::RowValueError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)
+                .log_it()?;
+
+            let rows = rows
+                .collect::<Result<Vec<_>, _>>()
+                .change_context(CmsRepositoryError::RowValueError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)
+                .log_it()?;
+
+            Ok(rows.into())
+        })
+    }
+
+    pub fn list_term(&self) -> Result<Arc<[PageTermModel]>, Report<CmsRepositoryError>> {
+        run_blocking(|| {
+            let conn = self.borrow_conn()?;
+
+            let mut stmt = conn
+                .prepare(include_str!("_sql/cms_repository/list_term.sql"))
+                .change_context(CmsRepositoryError::QueryError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)
+                .log_it()?;
+
+            let rows = stmt
+                .query_map(named_params! {}, |row| {
+                    Ok(PageTermModel {
                         id: row.get("id")?,
-                        file_name: row.get("file_name")?,
-                        file_path: row.get("file_path")?,
-                        file_type: row.get("file_type")?,
-                        uploaded: row.get("uploaded")?,
+                        kind: TermKind::try_from(row.get::<_, String>("kind")?).unwrap_or_default(),
+                        slug: row.get("slug")?,
+                        name: row.get("name")?,
                     })
-                },
-            )
-            .change_context(CmsRepositoryError::RowValueError)
-            .attach(StatusCode::INTERNAL_SERVER_ERROR)
-            .log_it()?;
+                })
+                .change_context(CmsRepositoryError::RowValueError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)
+                .log_it()?;
+
+            let rows = rows
+                .collect::<Result<Vec<_>, _>>()
+                .change_context(CmsRepositoryError::RowValueError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)
+                .log_it()?;
+
+            Ok(rows.into())
+        })
+    }
 
-        let rows = rows
-            .collect::<Result<Vec<_>, _>>()
-            .change_context(CmsRepositoryError::RowValueError)
-            .attach(StatusCode::INTERNAL_SERVER_ERROR)
-            .log_it()?;
+    pub fn list_term_suggestion(
+        &self,
+        kind: TermKind,
+        query: &str,
+        limit: i64,
+    ) -> Result<Arc<[PageTermModel]>, Report<CmsRepositoryError>> {
+        run_blocking(|| {
+            let conn = self.borrow_conn()?;
+
+            let mut stmt = conn
+                .prepare(include_str!("_sql/cms_repository/list_term_suggestion.sql"))
+                .change_context(CmsRepositoryError::QueryError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)
+                .log_it()?;
+
+            let rows = stmt
+                .query_map(
+                    named_params! {
+                        ":kind": kind.as_stringed(),
+                        ":query": query,
+                        ":limit": limit,
+                    },
+                    |row| {
+                        Ok(PageTermModel {
+                            id: row.get("id")?,
+                            kind: TermKind::try_from(row.get::<_, String>("kind")?)
+                                .unwrap_or_default(),
+                            slug: row.get("slug")?,
+                            name: row.get("name")?,
+                        })
+                    },
+                )
+                .change_context(CmsRepositoryError::RowValueError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)
+                .log_it()?;
+
+            let rows = rows
+                .collect::<Result<Vec<_>, _>>()
+                .change_context(CmsRepositoryError::RowValueError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)
+                .log_it()?;
+
+            Ok(rows.into())
+        })
+    }
 
-        Ok(rows.into())
+    pub fn list_trash_page(&self) -> Result<Arc<[ListPageModel]>, Report<CmsRepositoryError>> {
+        run_blocking(|| {
+            let conn = self.borrow_conn()?;
+
+            let mut stmt = conn
+                .prepare(include_str!("_sql/cms_repository/list_trash_page.sql"))
+                .change_context(CmsRepositoryError::QueryError)
+                .log_it()?;
+
+            let rows = stmt
+                .query_map(
+                    named_params! {
+                        ":trash_status": CmsPageStatus::Trash.as_stringed(),
+                    },
+                    |row| {
+                        Ok(ListPageModel {
+                            id: row.get("id")?,
+                            author: row.get("author")?,
+                            user_id: row.get("user_id")?,
+                            path: row.get("path")?,
+                            title: row.get("title")?,
+                            added: row.get("added")?,
+                            updated: row.get("updated")?,
+                            status: CmsPageStatus::try_from(row.get::<_, String>("status")?)
+                                .unwrap_or_default(),
+                        })
+                    },
+                )
+                .change_context(CmsRepositoryError::RowValueError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)
+                .log_it()?;
+
+            let rows = rows
+                .collect::<Result<Vec<_>, _>>()
+                .change_context(CmsRepositoryError::RowValueError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)
+                .log_it()?;
+
+            Ok(rows.into())
+        })
     }
 
-    pub fn list_page(&self) -> Result<Arc<[ListPageModel]>, Report<CmsRepositoryError>> {
-        let conn = self.borrow_conn()?;
+    pub fn path_taken(
+        &self,
+        path: String,
+        exclude_id: i64,
+    ) -> Result<bool, Report<CmsRepositoryError>> {
+        run_blocking(|| {
+            let conn = self.borrow_conn()?;
+
+            let mut stmt = conn
+                .prepare(include_str!("_sql/cms_repository/path_taken.sql"))
+                .change_context(CmsRepositoryError::QueryError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)
+                .log_it()?;
+
+            let taken: i64 = stmt
+                .query_one(
+                    named_params! {
+                        ":path": path,
+                        ":exclude_id": exclude_id,
+                    },
+                    |row| row.get("taken"),
+                )
+                .change_context(CmsRepositoryError::RowValueError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)
+                .log_it()?;
+
+            Ok(taken > 0)
+        })
+    }
 
-        let mut stmt = conn
-            .prepare(include_str!("_sql/cms_repository/list_page.sql"))
-            .change_context(CmsRepositoryError::QueryError)
-            .log_it()?;
+    /// Puts the page title, summary and components back to the revision, then records the
+    /// result as a new revision so the history is never rewritten.
+    pub fn restore_revision(
+        &self,
+        page_id: i64,
+        revision_id: i64,
+        revision_author: RevisionAuthorModel,
+    ) -> Result<ReturningIdModel, Report<CmsRepositoryError>> {
+        run_blocking(|| {
+            let mut conn = self.borrow_conn()?;
 
-        let rows = stmt
-            .query_map(named_params! {}, |row| {
-                Ok(ListPageModel {
-                    id: row.get("id")?,
-                    author: row.get("author")?,
-                    user_id: row.get("user_id")?,
-                    title: row.get("title")?,
-                    added: row.get("added")?,
-                    updated: row.get("updated")?,
-                    status: CmsPageStatus::try_from(row.get::<_, String>("status")?)
-                        .unwrap_or_default(),
-                })
-            })
-            .change_context(CmsRepositoryError::RowValueError)
-            .attach(StatusCode::INTERNAL_SERVER_ERROR)
-            .log_it()?;
+            let tx = conn
+                .transaction_with_behavior(TransactionBehavior::Immediate)
+                .change_context(CmsRepositoryError::QueryError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)
+                .log_it()?;
 
-        let rows = rows
-            .collect::<Result<Vec<_>, _>>()
-            .change_context(CmsRepositoryError::RowValueError)
-            .attach(StatusCode::INTERNAL_SERVER_ERROR)
-            .log_it()?;
+            Self::apply_revision(&tx, page_id, revision_id)?;
+            let new_revision_id = Self::record_revision(&tx, page_id, revision_author)?;
 
-        Ok(rows.into())
+            tx.commit()
+                .change_context(CmsRepositoryError::QueryError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)
+                .log_it()?;
+
+            Ok(new_revision_id)
+        })
     }
 
+    /// Saves the component and records its page as a new revision, in one transaction.
     pub fn update_component(
         &self,
         update_component_model: UpdateComponentModel,
     ) -> Result<(), Report<CmsRepositoryError>> {
-        let conn = self.borrow_conn()?;
+        run_blocking(|| {
+            let mut conn = self.borrow_conn()?;
 
-        conn.execute(
-            include_str!("_sql/cms_repository/update_component.sql"),
-            named_params! {
-                ":label": update_component_model.label,
-                ":raw_data": update_component_model.raw_data,
-                ":id": update_component_model.id,
-            },
-        )
-        .change_context(CmsRepositoryError::QueryError)
-        .attach(StatusCode::INTERNAL_SERVER_ERROR)
-        .log_it()?;
+            let tx = conn
+                .transaction_with_behavior(TransactionBehavior::Immediate)
+                .change_context(CmsRepositoryError::QueryError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)
+                .log_it()?;
 
-        Ok(())
+            let page_id = Self::save_component(&tx, &update_component_model)?;
+            Self::record_revision(&tx, page_id.0, update_component_model.revision_author)?;
+
+            tx.commit()
+                .change_context(CmsRepositoryError::QueryError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)
+                .log_it()?;
+
+            Ok(())
+        })
     }
 
-    pub fn update_component_position(
+    /// Swaps the component's attachments for the given ones, whose files are already written,
+    /// saves the component and records its page as a new revision, in one transaction.
+    pub fn update_component_file(
         &self,
-        update_component_position_model: UpdateComponentPositionModel,
+        update_component_model: UpdateComponentModel,
+        add_file_attachment_models: Vec<AddFileAttachmentModel>,
     ) -> Result<(), Report<CmsRepositoryError>> {
-        let conn = self.borrow_conn()?;
+        run_blocking(|| {
+            let mut conn = self.borrow_conn()?;
 
-        conn.execute(
-            include_str!("_sql/cms_repository/update_component_position.sql"),
-            named_params! {
-                ":id": update_component_position_model.id,
-                ":position": update_component_position_model.position,
-                ":page_id": update_component_position_model.page_id,
-            },
-        )
-        .change_context(CmsRepositoryError::QueryError)
-        .attach(StatusCode::INTERNAL_SERVER_ERROR)
-        .log_it()?;
+            let tx = conn
+                .transaction_with_behavior(TransactionBehavior::Immediate)
+                .change_context(CmsRepositoryError::QueryError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)
+                .log_it()?;
 
-        Ok(())
+            tx.execute(
+                include_str!("_sql/cms_repository/delele_file_attachment_component.sql"),
+                named_params! {
+                    ":component_id": update_component_model.id,
+                },
+            )
+            .change_context(CmsRepositoryError::QueryError)
+            .attach(StatusCode::INTERNAL_SERVER_ERROR)
+            .log_it()?;
+
+            for add_file_attachment_model in add_file_attachment_models.iter() {
+                Self::insert_file_attachment(&tx, add_file_attachment_model)?;
+            }
+
+            let page_id = Self::save_component(&tx, &update_component_model)?;
+            Self::record_revision(&tx, page_id.0, update_component_model.revision_author)?;
+
+            tx.commit()
+                .change_context(CmsRepositoryError::QueryError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)
+                .log_it()?;
+
+            Ok(())
+        })
     }
 
+    /// Moves every component of the page to its new position and records the page as one new
+    /// revision, in one transaction.
+    pub fn update_component_position(
+        &self,
+        page_id: i64,
+        update_component_position_models: Vec<UpdateComponentPositionModel>,
+        revision_author: RevisionAuthorModel,
+    ) -> Result<(), Report<CmsRepositoryError>> {
+        run_blocking(|| {
+            let mut conn = self.borrow_conn()?;
+
+            let tx = conn
+                .transaction_with_behavior(TransactionBehavior::Immediate)
+                .change_context(CmsRepositoryError::QueryError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)
+                .log_it()?;
+
+            for update_component_position_model in update_component_position_models.iter() {
+                tx.execute(
+                    include_str!("_sql/cms_repository/update_component_position.sql"),
+                    named_params! {
+                        ":id": update_component_position_model.id,
+                        ":position": update_component_position_model.position,
+                        ":page_id": page_id,
+                    },
+                )
+                .change_context(CmsRepositoryError::QueryError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)
+                .log_it()?;
+            }
+
+            Self::record_revision(&tx, page_id, revision_author)?;
+
+            tx.commit()
+                .change_context(CmsRepositoryError::QueryError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)
+                .log_it()?;
+
+            Ok(())
+        })
+    }
+
+    /// Saves the page, moving its path and redirects along, and records it as a new revision,
+    /// in one transaction.
     pub fn update_page(
         &self,
         update_page_model: UpdatePageModel,
     ) -> Result<(), Report<CmsRepositoryError>> {
-        let conn = self.borrow_conn()?;
+        run_blocking(|| {
+            let mut conn = self.borrow_conn()?;
 
-        conn.execute(
-            include_str!("_sql/cms_repository/update_page.sql"),
-            named_params! {
-                ":title": update_page_model.title,
-                ":summary": update_page_model.summary,
-                ":status": update_page_model.status.as_stringed(),
-                ":id": update_page_model.id,
-            },
-        )
-        .change_context(CmsRepositoryError::QueryError)
-        .attach(StatusCode::INTERNAL_SERVER_ERROR)
-        .log_it()?;
+            let tx = conn
+                .transaction_with_behavior(TransactionBehavior::Immediate)
+                .change_context(CmsRepositoryError::QueryError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)
+                .log_it()?;
 
-        Ok(())
+            tx.execute(
+                include_str!("_sql/cms_repository/update_page.sql"),
+                named_params! {
+                    ":title": update_page_model.title,
+                    ":summary": update_page_model.summary,
+                    ":slug": update_page_model.slug,
+                    ":parent_id": update_page_model.parent_id,
+                    ":status": update_page_model.status.as_stringed(),
+                    ":publish_at": update_page_model.publish_at,
+                    ":unpublish_at": update_page_model.unpublish_at,
+                    ":id": update_page_model.id,
+                },
+            )
+            .change_context(CmsRepositoryError::QueryError)
+            .attach(StatusCode::INTERNAL_SERVER_ERROR)
+            .log_it()?;
+
+            if update_page_model.old_path != update_page_model.path {
+                tx.execute(
+                    include_str!("_sql/cms_repository/add_page_redirect.sql"),
+                    named_params! {
+                        ":old_path": update_page_model.old_path,
+                    },
+                )
+                .change_context(CmsRepositoryError::QueryError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)
+                .log_it()?;
+
+                tx.execute(
+                    include_str!("_sql/cms_repository/move_page_path.sql"),
+                    named_params! {
+                        ":old_path": update_page_model.old_path,
+                        ":new_path": update_page_model.path,
+                    },
+                )
+                .change_context(CmsRepositoryError::QueryError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)
+                .log_it()?;
+
+                tx.execute(
+                    include_str!("_sql/cms_repository/clear_page_redirect.sql"),
+                    named_params! {},
+                )
+                .change_context(CmsRepositoryError::QueryError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)
+                .log_it()?;
+            }
+
+            Self::record_revision(&tx, update_page_model.id, update_page_model.revision_author)?;
+
+            tx.commit()
+                .change_context(CmsRepositoryError::QueryError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)
+                .log_it()?;
+
+            Ok(())
+        })
+    }
+
+    pub fn update_page_status(
+        &self,
+        id: i64,
+        status: CmsPageStatus,
+    ) -> Result<(), Report<CmsRepositoryError>> {
+        run_blocking(|| {
+            let conn = self.borrow_conn()?;
+
+            conn.execute(
+                include_str!("_sql/cms_repository/update_page_status.sql"),
+                named_params! {
+                    ":status": status.as_stringed(),
+                    ":id": id,
+                },
+            )
+            .change_context(CmsRepositoryError::QueryError)
+            .attach(StatusCode::INTERNAL_SERVER_ERROR)
+            .log_it()?;
+
+            Ok(())
+        })
+    }
+}
+
+#[cfg(test)]
+impl CmsRepository {
+    pub fn new_mock() -> Self {
+        mry::new!(Self {
+            sqlite_client: None
+        })
     }
 }
 
@@ -511,3 +1838,274 @@ impl FromContext for CmsRepository {
         Ok(Self::new(ctx.inject().await?))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use shared::utils::config::sqlite::SqliteConfig;
+    use shared::utils::db::migration::migrate;
+    use uuid::Uuid;
+
+    fn setup() -> Connection {
+        let mut conn = Connection::open_in_memory().unwrap();
+        migrate(&mut conn).unwrap();
+        conn.execute_batch(
+            "insert into backoffice_users (id, username, password, role) values (1, 'a', x'00', 'root');
+             insert into cms_page (id, user_id, title, summary, status, slug, path)
+                values (1, 1, 'Old title', 's', 'draft', 'page', 'page');
+             insert into cms_component (id, page_id, kind_uuid, position, label, raw_data)
+                values (1, 1, 'markdown', 0, 'First', x'01'),
+                       (2, 1, 'markdown', 1, 'Second', x'02');",
+        )
+        .unwrap();
+        conn
+    }
+
+    fn record_revision(conn: &mut Connection) -> i64 {
+        let tx = conn.transaction().unwrap();
+        let revision_id = CmsRepository::insert_revision(&tx, 1, 1).unwrap();
+        tx.commit().unwrap();
+        revision_id.0
+    }
+
+    fn components(conn: &Connection) -> Vec<(i64, i64, String)> {
+        let mut stmt = conn
+            .prepare("select id, position, label from cms_component where page_id = 1 order by id")
+            .unwrap();
+        stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_apply_revision_restores_components_as_they_were() {
+        let mut conn = setup();
+        let revision_id = record_revision(&mut conn);
+        conn.execute_batch(
+            "update cms_page set title = 'New title' where id = 1;
+             delete from cms_component where id = 1;
+             update cms_component set position = 0, label = 'Second edited' where id = 2;
+             insert into cms_component (id, page_id, kind_uuid, position, label, raw_data)
+                values (3, 1, 'markdown', 1, 'Third', x'03');",
+        )
+        .unwrap();
+
+        let tx = conn.transaction().unwrap();
+        CmsRepository::apply_revision(&tx, 1, revision_id).unwrap();
+        tx.commit().unwrap();
+
+        assert_eq!(
+            components(&conn),
+            vec![(1, 0, "First".to_string()), (2, 1, "Second".to_string())]
+        );
+        let title: String = conn
+            .query_row("select title from cms_page where id = 1", [], |row| {
+                row.get(0)
+            })
+            .unwrap();
+        assert_eq!(title, "Old title");
+    }
+
+    fn page_status(conn: &Connection) -> String {
+        conn.query_row("select status from cms_page where id = 1", [], |row| {
+            row.get(0)
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_record_revision_holds_published_page_for_review() {
+        let mut conn = setup();
+        conn.execute("update cms_page set status = 'published' where id = 1", [])
+            .unwrap();
+
+        let tx = conn.transaction().unwrap();
+        let editor = RevisionAuthorModel {
+            user_id: 1,
+            can_publish: true,
+        };
+        CmsRepository::record_revision(&tx, 1, editor).unwrap();
+        tx.commit().unwrap();
+        assert_eq!(page_status(&conn), "published");
+
+        let tx = conn.transaction().unwrap();
+        let author = RevisionAuthorModel {
+            user_id: 1,
+            can_publish: false,
+        };
+        CmsRepository::record_revision(&tx, 1, author).unwrap();
+        tx.commit().unwrap();
+        assert_eq!(page_status(&conn), "in_review");
+    }
+
+    #[test]
+    fn test_record_revision_holds_scheduled_draft_for_review() {
+        let mut conn = setup();
+        let author = RevisionAuthorModel {
+            user_id: 1,
+            can_publish: false,
+        };
+
+        let tx = conn.transaction().unwrap();
+        CmsRepository::record_revision(&tx, 1, author).unwrap();
+        tx.commit().unwrap();
+        assert_eq!(page_status(&conn), "draft");
+
+        conn.execute(
+            "update cms_page set publish_at = '2099-01-01T00:00:00Z' where id = 1",
+            [],
+        )
+        .unwrap();
+        let tx = conn.transaction().unwrap();
+        CmsRepository::record_revision(&tx, 1, author).unwrap();
+        tx.commit().unwrap();
+        assert_eq!(page_status(&conn), "in_review");
+    }
+
+    #[test]
+    fn test_author_changes_to_files_and_components_hold_published_page() {
+        let root = std::env::temp_dir().join(format!("rusty-cms-{}", Uuid::new_v4()));
+        std::fs::create_dir_all(&root).unwrap();
+        let sqlite_client: SqliteClient = SqliteClient::new(&SqliteConfig {
+            path: root.join("sqlite.db").to_string_lossy().to_string(),
+            pool_size: 1,
+            busy_timeout_ms: 5000,
+        })
+        .unwrap();
+        sqlite_client
+            .borrow_conn()
+            .unwrap()
+            .execute_batch(
+                "insert into backoffice_users (id, username, password, role) values (1, 'a', x'00', 'root');
+                 insert into cms_page (id, user_id, title, summary, status, slug, path)
+                    values (1, 1, 't', 's', 'published', 'page', 'page');
+                 insert into cms_component (id, page_id, kind_uuid, position, label, raw_data)
+                    values (1, 1, 'markdown', 0, 'First', x'01'),
+                           (2, 1, 'markdown', 1, 'Second', x'02');",
+            )
+            .unwrap();
+        let cms_repository = CmsRepository::new(sqlite_client.clone());
+        let author = RevisionAuthorModel {
+            user_id: 1,
+            can_publish: false,
+        };
+        let publish = || {
+            sqlite_client
+                .borrow_conn()
+                .unwrap()
+                .execute("update cms_page set status = 'published' where id = 1", [])
+                .unwrap();
+        };
+        let held = || {
+            let conn = sqlite_client.borrow_conn().unwrap();
+            let revision_count: i64 = conn
+                .query_row("select count(*) from cms_page_revision", [], |row| {
+                    row.get(0)
+                })
+                .unwrap();
+            (page_status(&conn), revision_count)
+        };
+
+        cms_repository
+            .add_file_attachment(
+                AddFileAttachmentModel {
+                    component_id: 1,
+                    file_name: "a.txt".to_string(),
+                    file_path: "/cms/files/ab/ab.txt".to_string(),
+                    file_type: "text/plain".to_string(),
+                    file_hash: "ab".to_string(),
+                },
+                author,
+            )
+            .unwrap();
+        assert_eq!(held(), ("in_review".to_string(), 1));
+
+        publish();
+        let attachment_id = cms_repository.list_file_attachment(1).unwrap()[0].id;
+        cms_repository
+            .delete_file_attachment(attachment_id, author)
+            .unwrap();
+        assert_eq!(held(), ("in_review".to_string(), 2));
+
+        publish();
+        cms_repository
+            .update_component_position(
+                1,
+                vec![
+                    UpdateComponentPositionModel { id: 1, position: 1 },
+                    UpdateComponentPositionModel { id: 2, position: 0 },
+                ],
+                author,
+            )
+            .unwrap();
+        assert_eq!(held(), ("in_review".to_string(), 3));
+
+        publish();
+        cms_repository.delete_component(1, author).unwrap();
+        assert_eq!(held(), ("in_review".to_string(), 4));
+
+        std::fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn test_restore_latest_revision_keeps_uploaded_file() {
+        let root = std::env::temp_dir().join(format!("rusty-cms-{}", Uuid::new_v4()));
+        std::fs::create_dir_all(&root).unwrap();
+        let sqlite_client: SqliteClient = SqliteClient::new(&SqliteConfig {
+            path: root.join("sqlite.db").to_string_lossy().to_string(),
+            pool_size: 1,
+            busy_timeout_ms: 5000,
+        })
+        .unwrap();
+        sqlite_client
+            .borrow_conn()
+            .unwrap()
+            .execute_batch(
+                "insert into backoffice_users (id, username, password, role) values (1, 'a', x'00', 'root');
+                 insert into cms_page (id, user_id, title, summary, status, slug, path)
+                    values (1, 1, 't', 's', 'draft', 'page', 'page');
+                 insert into cms_component (id, page_id, kind_uuid, position, label, raw_data)
+                    values (1, 1, 'markdown', 0, 'First', x'01');",
+            )
+            .unwrap();
+        let cms_repository = CmsRepository::new(sqlite_client.clone());
+        let author = RevisionAuthorModel {
+            user_id: 1,
+            can_publish: true,
+        };
+
+        cms_repository
+            .add_file_attachment(
+                AddFileAttachmentModel {
+                    component_id: 1,
+                    file_name: "a.txt".to_string(),
+                    file_path: "/cms/files/ab/ab.txt".to_string(),
+                    file_type: "text/plain".to_string(),
+                    file_hash: "ab".to_string(),
+                },
+                author,
+            )
+            .unwrap();
+        let latest_revision_id: i64 = sqlite_client
+            .borrow_conn()
+            .unwrap()
+            .query_row("select max(id) from cms_page_revision", [], |row| {
+                row.get(0)
+            })
+            .unwrap();
+        cms_repository
+            .restore_revision(1, latest_revision_id, author)
+            .unwrap();
+
+        let file_names: Vec<String> = cms_repository
+            .list_file_attachment(1)
+            .unwrap()
+            .iter()
+            .map(|attachment| attachment.file_name.clone())
+            .collect();
+        assert_eq!(file_names, vec!["a.txt".to_string()]);
+
+        std::fs::remove_dir_all(root).unwrap();
+    }
+}
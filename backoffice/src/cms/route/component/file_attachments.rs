@@ -5,6 +5,7 @@ use crate::cms::html_partial::file_attachments_html_partial::{
 use crate::cms::html_partial::positions_partial;
 use crate::cms::methods::ComponentMethods;
 use crate::cms::query_model::{CreateQuery, UpdateFetchQuery};
+use crate::cms::rules::upload_rule::check_upload_rejections;
 use crate::cms::service::cms_attachment_service::CmsAttachmentService;
 use crate::cms::service::cms_page_service::CmsPageService;
 use crate::cms::service::component_service::common_label_service::CommonLabelService;
@@ -21,6 +22,7 @@ use shared::utils::context::Dep;
 use shared::utils::csrf::{CsrfTokenHtml, csrf_header_check_strict};
 use shared::utils::error::FromErrorStack;
 use shared::utils::flash::Flash;
+use shared::utils::locale::LocaleExtForResult;
 use shared::utils::query_string::form::FormQs;
 use shared::utils::route_header::route_header;
 use std::sync::Arc;
@@ -45,7 +47,7 @@ async fn file_attachments_component_create(
     Ok(html! {
         h4 .mt-3 { "File Attachments" }
         (form.as_form_html(&update_fetch_query, None))
-        (file_attachments_list_partial(None, &update_fetch_query, vec![].into()))
+        (file_attachments_list_partial(None, &update_fetch_query, vec![].into(), vec![].into()))
         (positions_partial(Some("true".to_string()), Arc::clone(&list_component_model), query.page_id))
     })
 }
@@ -70,14 +72,14 @@ async fn file_attachments_component_fetch(
     Ok(html! {
         h4 .mt-3 { "File Attachments" }
         (form.as_form_html(&query, None))
-        (file_attachments_list_partial(None, &query, file_list))
+        (file_attachments_list_partial(None, &query, file_list, vec![].into()))
     })
 }
 
 #[handler]
 async fn file_attachments_component_fetch_form(query: UpdateFetchQuery) -> Markup {
     html! {
-        (file_attachments_form_partial(&query))
+        (file_attachments_form_partial(&query, vec![].into()))
     }
 }
 
@@ -123,14 +125,34 @@ async fn file_attachments_component_upload(
     query: UpdateFetchQuery,
     Dep(cms_attachment_service): Dep<CmsAttachmentService>,
     mut multipart: Multipart,
+    locale: Locale,
     csrf_token: &CsrfToken,
-) -> poem::Result<Markup> {
+) -> poem::Result<poem::Response> {
+    let mut rejections = Vec::new();
     while let Some(file) = multipart.next_field().await? {
-        if file.name().unwrap_or_default() == "file" {
-            cms_attachment_service
-                .add_file(query.id as i64, file)
-                .await
-                .map_err(poem::Error::from_error_stack)?;
+        // an empty file input is still sent, without a file name.
+        if file.name().unwrap_or_default() != "file"
+            || file.file_name().unwrap_or_default().is_empty()
+        {
+            continue;
+        }
+        match cms_attachment_service
+            .receive_file(file, FileAttachmentsComponent::ALLOWED_FILE_TYPES)
+            .await
+            .map_err(poem::Error::from_error_stack)?
+        {
+            Ok(received_file) => {
+                cms_attachment_service
+                    .add_received_file(query.id as i64, received_file)
+                    .map_err(poem::Error::from_error_stack)?;
+            }
+            Err(rejection) => {
+                let stops_upload = rejection.stops_upload();
+                rejections.push(rejection);
+                if stops_upload {
+                    break;
+                }
+            }
         }
     }
 
@@ -138,13 +160,23 @@ async fn file_attachments_component_upload(
         .list_file_by_component_id(query.id as i64)
         .map_err(poem::Error::from_error_stack)?;
 
-    Ok(html! {
-        (file_attachments_list_partial(None, &query, file_list))
-        (flash_partial(Flash::Success {
-            msg: "File uploaded successfully".to_string(),
-        }))
-        (command_list_partial(vec![csrf_token.as_html_command()]))
-    })
+    let upload_checked = check_upload_rejections(&rejections);
+    match &upload_checked {
+        Ok(_) => Ok(html! {
+            (file_attachments_list_partial(None, &query, file_list, vec![].into()))
+            (flash_partial(Flash::Success {
+                msg: "File uploaded successfully".to_string(),
+            }))
+            (command_list_partial(vec![csrf_token.as_html_command()]))
+        }
+        .into_response()),
+        Err(_) => Ok(html! {
+            (file_attachments_list_partial(None, &query, file_list, upload_checked.as_translated_message(&locale)))
+            (command_list_partial(vec![csrf_token.as_html_command()]))
+        }
+        .with_status(poem::http::StatusCode::UNPROCESSABLE_ENTITY)
+        .into_response()),
+    }
 }
 
 #[handler]
@@ -163,7 +195,7 @@ async fn file_attachments_component_delete(
         .map_err(poem::Error::from_error_stack)?;
 
     Ok(html! {
-        (file_attachments_list_partial(None, &query, file_list))
+        (file_attachments_list_partial(None, &query, file_list, vec![].into()))
         (flash_partial(Flash::Success {
             msg: "File deleted successfully".to_string(),
         }))
@@ -0,0 +1,192 @@
+use crate::cms::form::component_form::image_form::ImageForm;
+use crate::cms::html_partial::image_html_partial::image_upload_partial;
+use crate::cms::html_partial::positions_partial;
+use crate::cms::methods::ComponentMethods;
+use crate::cms::query_model::{CreateQuery, UpdateFetchQuery};
+use crate::cms::rules::component_rules::image_rules::parse_image;
+use crate::cms::rules::upload_rule::check_upload_rejections;
+use crate::cms::service::cms_page_service::CmsPageService;
+use crate::cms::service::component_service::image_component_service::ImageComponentService;
+use crate::common::html::consts::SPLIT;
+use crate::common::html::partial::{command_list_partial, flash_partial};
+use maud::{Markup, html};
+use poem::http::StatusCode;
+use poem::i18n::Locale;
+use poem::web::{CsrfToken, Multipart};
+use poem::{IntoResponse, get, handler};
+use shared::cms::components::image::ImageComponent;
+use shared::cms::markers::ComponentInfoMarker;
+use shared::utils::context::Dep;
+use shared::utils::csrf::{CsrfTokenHtml, csrf_header_check_strict};
+use shared::utils::db::run_blocking;
+use shared::utils::error::FromErrorStack;
+use shared::utils::flash::Flash;
+use shared::utils::locale::LocaleExtForResult;
+use shared::utils::query_string::form::FormQs;
+use std::sync::Arc;
+
+#[handler]
+async fn image_component_create(
+    query: CreateQuery,
+    Dep(image_component_service): Dep<ImageComponentService>,
+    Dep(cms_page_service): Dep<CmsPageService>,
+) -> poem::Result<Markup> {
+    let form = ImageForm {
+        label: "Image".to_string(),
+        ..Default::default()
+    };
+
+    let update_fetch_query = image_component_service
+        .create_component(&query, form.label.clone())
+        .map_err(poem::Error::from_error_stack)?;
+
+    let list_component_model = cms_page_service
+        .list_component(query.page_id as i64)
+        .map_err(poem::Error::from_error_stack)?;
+
+    Ok(html! {
+        h4 .mt-3 { "Image" }
+        (form.as_form_html(&update_fetch_query, None))
+        (image_upload_partial(None, &update_fetch_query, &ImageComponent::default(), vec![].into()))
+        (positions_partial(Some("true".to_string()), Arc::clone(&list_component_model), query.page_id))
+    })
+}
+
+#[handler]
+async fn image_component_fetch(
+    query: UpdateFetchQuery,
+    Dep(image_component_service): Dep<ImageComponentService>,
+) -> poem::Result<Markup> {
+    let image_component_model = image_component_service
+        .fetch_component(&query)
+        .map_err(poem::Error::from_error_stack)?;
+
+    let form = ImageForm {
+        label: image_component_model.label,
+        alt: image_component_model.raw_data.alt.clone(),
+        caption: image_component_model.raw_data.caption.clone(),
+    };
+
+    Ok(html! {
+        h4 .mt-3 { "Image" }
+        (form.as_form_html(&query, None))
+        (image_upload_partial(None, &query, &image_component_model.raw_data, vec![].into()))
+    })
+}
+
+#[handler]
+async fn image_component_update(
+    query: UpdateFetchQuery,
+    Dep(image_component_service): Dep<ImageComponentService>,
+    FormQs(form): FormQs<ImageForm>,
+    locale: Locale,
+    csrf_token: &CsrfToken,
+) -> poem::Result<poem::Response> {
+    let form_validated = form.as_validated().await.0;
+
+    match form_validated {
+        Ok(validated) => {
+            image_component_service
+                .update_component(&query, &validated)
+                .map_err(poem::Error::from_error_stack)?;
+            Ok(html! {
+                (form.as_form_html(&query, None))
+                (SPLIT)
+                span id=(format!{"component-position-label-{}", query.id}) hx-swap-oob="true"
+                    { (validated.label.as_str()) }
+                (command_list_partial(vec![csrf_token.as_html_command()]))
+            }
+            .into_response())
+        }
+        Err(verror) => {
+            let message = verror.as_message(&locale);
+            Ok(html! {
+                (form.as_form_html(&query, Some(message)))
+                (SPLIT)
+                (command_list_partial(vec![csrf_token.as_html_command()]))
+            }
+            .with_status(StatusCode::UNPROCESSABLE_ENTITY)
+            .into_response())
+        }
+    }
+}
+
+#[handler]
+async fn image_component_upload(
+    query: UpdateFetchQuery,
+    Dep(image_component_service): Dep<ImageComponentService>,
+    mut multipart: Multipart,
+    locale: Locale,
+    csrf_token: &CsrfToken,
+) -> poem::Result<poem::Response> {
+    let mut upload = None;
+    let mut rejections = Vec::new();
+    while let Some(field) = multipart.next_field().await? {
+        if field.name().unwrap_or_default() == "file" {
+            match image_component_service
+                .receive_image(field)
+                .await
+                .map_err(poem::Error::from_error_stack)?
+            {
+                Ok(received_file) => upload = Some(received_file),
+                Err(rejection) => {
+                    let stops_upload = rejection.stops_upload();
+                    rejections.push(rejection);
+                    if stops_upload {
+                        break;
+                    }
+                }
+            }
+        }
+    }
+    let (file_name, content) = match &upload {
+        Some(received_file) => (
+            received_file.file_name.as_str(),
+            received_file
+                .content()
+                .map_err(poem::Error::from_error_stack)?,
+        ),
+        None => ("", vec![]),
+    };
+
+    let image =
+        run_blocking(|| check_upload_rejections(&rejections).and_then(|_| parse_image(&content)));
+    match &image {
+        Ok(image) => {
+            let image_component =
+                run_blocking(|| image_component_service.upload_image(&query, file_name, image))
+                    .map_err(poem::Error::from_error_stack)?;
+            Ok(html! {
+                (image_upload_partial(None, &query, &image_component, vec![].into()))
+                (flash_partial(Flash::Success {
+                    msg: "Image uploaded successfully".to_string(),
+                }))
+                (command_list_partial(vec![csrf_token.as_html_command()]))
+            }
+            .into_response())
+        }
+        Err(_) => {
+            let image_component = image_component_service
+                .fetch_component(&query)
+                .map_err(poem::Error::from_error_stack)?
+                .raw_data;
+            let message = image.as_translated_message(&locale);
+            Ok(html! {
+                (image_upload_partial(None, &query, &image_component, message))
+                (command_list_partial(vec![csrf_token.as_html_command()]))
+            }
+            .with_status(StatusCode::UNPROCESSABLE_ENTITY)
+            .into_response())
+        }
+    }
+}
+
+pub fn image_registry_item() -> ComponentMethods {
+    ComponentMethods {
+        info: ImageComponent::component_info(),
+        create: get(image_component_create),
+        update_fetch: get(image_component_fetch)
+            .patch(csrf_header_check_strict(image_component_update))
+            .post(csrf_header_check_strict(image_component_upload)),
+    }
+}
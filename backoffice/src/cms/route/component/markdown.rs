@@ -1,4 +1,4 @@
-use crate::cms::form::component_form::markdown_form::MarkdownForm;
+use crate::cms::form::component_form::markdown_form::{MarkdownForm, MarkdownPreviewForm};
 use crate::cms::html_partial::positions_partial;
 use crate::cms::methods::ComponentMethods;
 use crate::cms::query_model::{CreateQuery, UpdateFetchQuery};
@@ -6,7 +6,7 @@ use crate::cms::service::cms_page_service::CmsPageService;
 use crate::cms::service::component_service::markdown_component_service::MarkdownComponentService;
 use crate::common::html::consts::SPLIT;
 use crate::common::html::partial::command_list_partial;
-use maud::{Markup, html};
+use maud::{Markup, PreEscaped, html};
 use poem::http::StatusCode;
 use poem::i18n::Locale;
 use poem::web::CsrfToken;
@@ -100,11 +100,20 @@ async fn markdown_component_update(
     }
 }
 
+#[handler]
+async fn markdown_component_preview(
+    Dep(markdown_component_service): Dep<MarkdownComponentService>,
+    FormQs(form): FormQs<MarkdownPreviewForm>,
+) -> Markup {
+    PreEscaped(markdown_component_service.preview(&form))
+}
+
 pub fn markdown_registry_item() -> ComponentMethods {
     ComponentMethods {
         info: MarkdownComponent::component_info(),
         create: get(markdown_component_create),
         update_fetch: get(markdown_component_fetch)
-            .patch(csrf_header_check_strict(markdown_component_update)),
+            .patch(csrf_header_check_strict(markdown_component_update))
+            .post(csrf_header_check_strict(markdown_component_preview)),
     }
 }
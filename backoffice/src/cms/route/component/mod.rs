@@ -1,2 +1,3 @@
 pub mod file_attachments;
+pub mod image;
 pub mod markdown;
@@ -1,30 +1,50 @@
+use crate::cms::data_model::cms_data::ListPageModel;
 use crate::cms::form::add_page_form::AddPageForm;
 use crate::cms::form::amend_page_form::AmendPageForm;
 use crate::cms::form::component_position_form::ComponentPositionForm;
+use crate::cms::form::page_term_form::PageTermForm;
+use crate::cms::form::review_page_form::ReviewPageForm;
+use crate::cms::html_partial::review_html_partial::{pending_review_partial, review_list_partial};
+use crate::cms::html_partial::revision_html_partial::{
+    revision_diff_partial, revision_list_partial,
+};
+use crate::cms::html_partial::term_html_partial::{term_list_partial, term_suggestion_partial};
 use crate::cms::html_partial::{component_partial, positions_partial};
-use crate::cms::query_model::CreateQueryExt;
+use crate::cms::query_model::{
+    CmsPageSort, CreateQueryExt, ListPageQuery, RevisionDiffQuery, TermSuggestionQuery,
+};
 use crate::cms::registry::{registry_ep_create, registry_ep_update_fetch, registry_item};
 use crate::cms::service::cms_attachment_service::CmsAttachmentService;
-use crate::cms::service::cms_page_service::CmsPageService;
+use crate::cms::service::cms_page_service::{CmsPageService, CmsPageServiceError};
 use crate::cms::service::cms_permission_check_service::CmsPermissionCheckService;
+use crate::cms::service::cms_review_service::CmsReviewService;
+use crate::cms::service::cms_revision_service::CmsRevisionService;
+use crate::cms::service::cms_term_service::CmsTermService;
 use crate::common::html::context_html::ContextHtmlBuilder;
-use crate::common::html::partial::{command_list_partial, flash_partial};
-use crate::common::icon::{pencil_square_icon, plus_icon};
+use crate::common::html::partial::{
+    command_list_partial, flash_partial, pagination_partial, sort_heading_partial,
+};
+use crate::common::icon::{arrow_path_icon, pencil_square_icon, plus_icon, trash_icon};
 use crate::user::pointer::user_pointer::UserPointer;
 use crate::user::role::Role;
+use crate::user::role::user_role_check::{must_be_editor, must_be_root};
 use maud::{Markup, html};
 use poem::http::StatusCode;
 use poem::i18n::Locale;
 use poem::session::Session;
 use poem::web::{CsrfToken, Path, Redirect};
-use poem::{IntoResponse, Route, delete, get, handler, patch};
+use poem::{IntoResponse, Response, Route, delete, get, handler, patch, post};
 use serde_qs::Config;
+use shared::cms::status::CmsPageStatus;
+use shared::cms::taxonomy::TermKind;
 use shared::utils::context::Dep;
 use shared::utils::csrf::{CsrfFormQs, CsrfTokenHtml, csrf_header_check, csrf_header_check_strict};
 use shared::utils::error::{ExtraResultExt, FromErrorStack};
 use shared::utils::flash::{Flash, FlashMessage};
 use shared::utils::htmx::HtmxHeader;
 use shared::utils::query_string::form::FormQs;
+use shared::utils::query_string::list_query::SortKey;
+use shared::utils::query_string::query::QueryQs;
 use shared::utils::query_string::serde_qs_config::with_serde_qs_config;
 use std::sync::Arc;
 
@@ -34,66 +54,179 @@ pub const CMS_ROUTE: &str = "/cms";
 
 #[handler]
 async fn cms_list_page(
+    QueryQs(query): QueryQs<ListPageQuery>,
     Dep(cms_page_service): Dep<CmsPageService>,
+    Dep(cms_review_service): Dep<CmsReviewService>,
+    Dep(cms_term_service): Dep<CmsTermService>,
     Dep(context_html_builder): Dep<ContextHtmlBuilder>,
     Dep(user_pointer): Dep<UserPointer>,
+    csrf_token: &CsrfToken,
 ) -> poem::Result<Markup> {
-    let list_page_model = cms_page_service
-        .list_page()
+    let list_page = cms_page_service
+        .list_page(&query)
+        .map_err(poem::Error::from_error_stack)?;
+    let list_page_author = cms_page_service
+        .list_page_author()
+        .map_err(poem::Error::from_error_stack)?;
+    let list_term = cms_term_service
+        .list_term()
+        .map_err(poem::Error::from_error_stack)?;
+    let list_pending_review_model = cms_review_service
+        .list_pending_review()
         .map_err(poem::Error::from_error_stack)?;
     let edit_icon = pencil_square_icon();
+    let trash_icon = trash_icon();
     let add_icon = plus_icon();
+    let list_path = format!("{}/list-page", CMS_ROUTE);
 
     Ok(context_html_builder
         .attach_title("CMS Page List")
         .set_current_tag("id-tag-cms")
         .attach_content(html! {
             h1 { "CMS Page List" }
-            table .table-full {
-                thead {
-                    tr {
-                        th { "ID" }
-                        th { "Author" }
-                        th { "Title" }
-                        th { "Added" }
-                        th { "Updated" }
-                        th { "Status" }
-                        th .action { "Action" }
+            (pending_review_partial(list_pending_review_model))
+            form .form hx-boost="true" hx-target="#main-content" hx-push-url="true" method="get"
+                action=(list_path) {
+                input type="hidden" name="sort" value=(query.sort.as_str());
+                input type="hidden" name="order" value=(query.order.as_str());
+                div .form-group {
+                    input .form-item type="search" name="filter[q]" value=(query.filter.q) placeholder="Search pages";
+                    " "
+                    select .form-item name="filter[status]" {
+                        option value="" { "Any status" }
+                        @for status in CmsPageStatus::all_statuses().iter().filter(|status| **status != CmsPageStatus::Trash) {
+                            option value=(status.as_stringed()) selected[query.filter.status == Some(*status)] {
+                                (status.as_stringed()) }
+                        }
                     }
-                }
-                tbody {
-                    @for page in list_page_model.iter() {
-                        tr {
-                            td { (page.id) }
-                            td { (page.author) }
-                            td { (page.title) }
-                            td x-init="$store.util.formatToLocalTime($el)" { (page.added.to_rfc3339()) }
-                            @if let Some(updated) = page.updated.as_ref() {
-                                td x-init="$store.util.formatToLocalTime($el)" { (updated.to_rfc3339()) }
-                            } @else {
-                                td { "N/A" }
-                            }
-                            td { (page.status.as_stringed()) }
-                            td .action {
-                                @if user_pointer.role == Role::Root || user_pointer.id == page.user_id {
-                                    a .icon href=(format!("{}/amend-page/{}", CMS_ROUTE, page.id)) title="Edit"
-                                        hx-boost="true" hx-target="#main-content" hx-push-url="true" {
-                                        (edit_icon) }
+                    " "
+                    select .form-item name="filter[author]" {
+                        option value="" { "Any author" }
+                        @for author in list_page_author.iter() {
+                            option value=(author.id) selected[query.filter.author == Some(author.id)] {
+                                (author.username) }
+                        }
+                    }
+                    " "
+                    select .form-item name="filter[term]" {
+                        option value="" { "Any category or tag" }
+                        @for kind in TermKind::all_kinds() {
+                            optgroup label=(kind.label()) {
+                                @for term in list_term.iter().filter(|term| term.kind == kind) {
+                                    option value=(term.id) selected[query.filter.term == Some(term.id)] {
+                                        (term.name) }
                                 }
                             }
                         }
                     }
+                    " "
+                    button .btn .btn-sky-blue .cursor-pointer type="submit" { "Filter" }
                 }
             }
+            (page_table_partial(Arc::clone(&list_page.items), |sort, label| {
+                sort_heading_partial(&list_path, &query, sort, label)
+            }, |page| html! {
+                @if user_pointer.role >= Role::Editor || user_pointer.id == page.user_id {
+                    a .icon href=(format!("{}/amend-page/{}", CMS_ROUTE, page.id)) title="Edit"
+                        hx-boost="true" hx-target="#main-content" hx-push-url="true" {
+                        (edit_icon) }
+                    " "
+                    a .icon .cursor-pointer hx-delete=(format!("{}/delete-page/{}", CMS_ROUTE, page.id))
+                        hx-confirm="Move this page to the trash?" title="Delete" {
+                        (trash_icon) }
+                }
+            }))
+            (pagination_partial(&context_html_builder.locale, &list_path, &query, &list_page))
             div .text-right .mt-3 {
+                @if user_pointer.role == Role::Root {
+                    a .inline-block .mr-2 href=(format!("{}/trash-page", CMS_ROUTE)) title="Trash"
+                        hx-boost="true" hx-target="#main-content" hx-push-url="true" {
+                        (trash_icon) }
+                }
                 a .inline-block href=(format!("{}/create-page", CMS_ROUTE)) title="Create"
                     hx-boost="true" hx-target="#main-content" hx-push-url="true" {
                     (add_icon) }
             }
         })
+        .attach_footer(html! {
+            (csrf_token.as_html_command())
+        })
+        .build())
+}
+
+#[handler]
+async fn cms_trash_page(
+    Dep(cms_page_service): Dep<CmsPageService>,
+    Dep(context_html_builder): Dep<ContextHtmlBuilder>,
+    csrf_token: &CsrfToken,
+) -> poem::Result<Markup> {
+    let list_page_model = cms_page_service
+        .list_trash_page()
+        .map_err(poem::Error::from_error_stack)?;
+    let restore_icon = arrow_path_icon();
+    let purge_icon = trash_icon();
+
+    Ok(context_html_builder
+        .attach_title("CMS Trash")
+        .set_current_tag("id-tag-cms")
+        .attach_content(html! {
+            h1 { "CMS Trash" }
+            (page_table_partial(list_page_model, |_, label| html! { (label) }, |page| html! {
+                a .icon .cursor-pointer hx-patch=(format!("{}/restore-page/{}", CMS_ROUTE, page.id))
+                    title="Restore" { (restore_icon) }
+                " "
+                a .icon .cursor-pointer hx-delete=(format!("{}/purge-page/{}", CMS_ROUTE, page.id))
+                    hx-confirm="Permanently delete this page and its files?" title="Purge" {
+                    (purge_icon) }
+            }))
+        })
+        .attach_footer(html! {
+            (csrf_token.as_html_command())
+        })
         .build())
 }
 
+fn page_table_partial(
+    list_page_model: Arc<[ListPageModel]>,
+    sort_heading: impl Fn(CmsPageSort, &str) -> Markup,
+    action: impl Fn(&ListPageModel) -> Markup,
+) -> Markup {
+    html! {
+        table .table-full {
+            thead {
+                tr {
+                    th { (sort_heading(CmsPageSort::Id, "ID")) }
+                    th { "Author" }
+                    th { (sort_heading(CmsPageSort::Title, "Title")) }
+                    th { "Path" }
+                    th { "Added" }
+                    th { (sort_heading(CmsPageSort::Updated, "Updated")) }
+                    th { "Status" }
+                    th .action { "Action" }
+                }
+            }
+            tbody {
+                @for page in list_page_model.iter() {
+                    tr {
+                        td { (page.id) }
+                        td { (page.author) }
+                        td { (page.title) }
+                        td { "/" (page.path) }
+                        td x-init="$store.util.formatToLocalTime($el)" { (page.added.to_rfc3339()) }
+                        @if let Some(updated) = page.updated.as_ref() {
+                            td x-init="$store.util.formatToLocalTime($el)" { (updated.to_rfc3339()) }
+                        } @else {
+                            td { "N/A" }
+                        }
+                        td { (page.status.as_stringed()) }
+                        td .action { (action(page)) }
+                    }
+                }
+            }
+        }
+    }
+}
+
 #[handler]
 async fn cms_create_page_get(
     Dep(context_html_builder): Dep<ContextHtmlBuilder>,
@@ -118,7 +251,7 @@ async fn cms_create_page_post(
     htmx_header: HtmxHeader,
     csrf_token: &CsrfToken,
 ) -> poem::Result<poem::Response> {
-    let validated_form = add_page_form.as_validated().await.0;
+    let validated_form = add_page_form.as_validated(&cms_page_service).await.0;
     let l = &context_html_builder.locale;
     match validated_form {
         Ok(validated) => {
@@ -156,6 +289,8 @@ async fn cms_amend_page_get(
     Dep(context_html_builder): Dep<ContextHtmlBuilder>,
     Dep(cms_page_service): Dep<CmsPageService>,
     Dep(cms_permission_check_service): Dep<CmsPermissionCheckService>,
+    Dep(cms_revision_service): Dep<CmsRevisionService>,
+    Dep(cms_review_service): Dep<CmsReviewService>,
     csrf_token: &CsrfToken,
 ) -> poem::Result<Markup> {
     cms_permission_check_service
@@ -170,6 +305,20 @@ async fn cms_amend_page_get(
     amend_page_form.title = page_model.title.clone();
     amend_page_form.summary = page_model.summary;
     amend_page_form.status = page_model.status;
+    amend_page_form.slug = page_model.slug;
+    amend_page_form.parent_id = page_model.parent_id.unwrap_or_default();
+    amend_page_form.publish_at = page_model
+        .publish_at
+        .map(|publish_at| publish_at.to_rfc3339())
+        .unwrap_or_default();
+    amend_page_form.unpublish_at = page_model
+        .unpublish_at
+        .map(|unpublish_at| unpublish_at.to_rfc3339())
+        .unwrap_or_default();
+
+    let parent_options = cms_page_service
+        .list_page_parent_option(page_id as i64, &page_model.path)
+        .map_err(poem::Error::from_error_stack)?;
 
     let title = format!("CMS Page - {}", page_model.title);
 
@@ -177,11 +326,34 @@ async fn cms_amend_page_get(
         .list_component(page_id as i64)
         .map_err(poem::Error::from_error_stack)?;
 
+    let list_revision_model = cms_revision_service
+        .list_revision(page_id as i64)
+        .map_err(poem::Error::from_error_stack)?;
+
+    let list_review_model = cms_review_service
+        .list_review(page_id as i64)
+        .map_err(poem::Error::from_error_stack)?;
+    let can_review =
+        cms_review_service.can_review() && page_model.status == CmsPageStatus::InReview;
+
     Ok(context_html_builder
         .attach_title(&title)
         .attach_content(html! {
             h1 { (title) }
-            (amend_page_form.as_form_html(None).await)
+            (amend_page_form.as_form_html(
+                None,
+                parent_options,
+                &cms_page_service.status_options(),
+                cms_page_service.can_publish(),
+            ).await)
+            h3 .mt-5 { "Review" }
+            @if can_review {
+                (ReviewPageForm::default().as_form_html(page_id, None))
+            }
+            (review_list_partial(list_review_model))
+            h3 .mt-5 { "Categories and Tags" }
+            (PageTermForm::default().as_form_html(page_id, None))
+            (term_list_partial(None, page_model.terms, page_id))
             div .flex .flex-row .mt-10 {
                 div class="basis-4/5 pr-6" {
                     h3 { "Components" }
@@ -189,6 +361,12 @@ async fn cms_amend_page_get(
                     (component_partial(None, Arc::clone(&list_component_model)))
                     h3 .mt-5 { "Positions" }
                     (positions_partial(None, Arc::clone(&list_component_model), page_id))
+                    h3 .mt-5 {
+                        "Revisions "
+                        a .icon .cursor-pointer hx-get=(format!("{}/revision/{}", CMS_ROUTE, page_id))
+                            hx-target="#revisions" hx-swap="outerHTML" title="Refresh" { (arrow_path_icon()) }
+                    }
+                    (revision_list_partial(None, list_revision_model, page_id))
                 }
                 div class="basis-1/5" {
                     h3 { "Add Component" }
@@ -210,6 +388,7 @@ async fn cms_amend_page_post(
     Path(page_id): Path<u64>,
     Dep(cms_page_service): Dep<CmsPageService>,
     Dep(cms_permission_check_service): Dep<CmsPermissionCheckService>,
+    Dep(cms_revision_service): Dep<CmsRevisionService>,
     FormQs(amend_page_form): FormQs<AmendPageForm>,
     locale: Locale,
 ) -> poem::Result<poem::Response> {
@@ -217,14 +396,32 @@ async fn cms_amend_page_post(
         .check_permission_by_page_id(page_id as i64)
         .map_err(poem::Error::from_error_stack)?;
 
-    let validated_form = amend_page_form.as_validated().await.0;
+    let validated_form = amend_page_form
+        .as_validated(&cms_page_service, page_id as i64)
+        .await
+        .0;
     match validated_form {
         Ok(validated) => {
             cms_page_service
                 .update_page(page_id as i64, &validated)
                 .map_err(poem::Error::from_error_stack)?;
+            let list_revision_model = cms_revision_service
+                .list_revision(page_id as i64)
+                .map_err(poem::Error::from_error_stack)?;
+            let page_model = cms_page_service
+                .fetch_page(page_id as i64)
+                .map_err(poem::Error::from_error_stack)?;
+            let parent_options = cms_page_service
+                .list_page_parent_option(page_id as i64, &page_model.path)
+                .map_err(poem::Error::from_error_stack)?;
             Ok(html! {
-                (amend_page_form.as_form_html(None).await)
+                (amend_page_form.as_form_html(
+                    None,
+                    parent_options,
+                    &cms_page_service.status_options(),
+                    cms_page_service.can_publish(),
+                ).await)
+                (revision_list_partial(Some("true"), list_revision_model, page_id))
                 (flash_partial(Flash::Success {
                     msg: "Updated Info and Status".to_string()
                 }))
@@ -233,8 +430,19 @@ async fn cms_amend_page_post(
         }
         Err(error) => {
             let error_message = error.as_message(&locale);
+            let page_model = cms_page_service
+                .fetch_page(page_id as i64)
+                .map_err(poem::Error::from_error_stack)?;
+            let parent_options = cms_page_service
+                .list_page_parent_option(page_id as i64, &page_model.path)
+                .map_err(poem::Error::from_error_stack)?;
             Ok(html! {
-                (amend_page_form.as_form_html(Some(error_message)).await)
+                (amend_page_form.as_form_html(
+                    Some(error_message),
+                    parent_options,
+                    &cms_page_service.status_options(),
+                    cms_page_service.can_publish(),
+                ).await)
                 (flash_partial(Flash::Error {
                     msg: "Failed to update info and status".to_string()
                 }))
@@ -245,6 +453,184 @@ async fn cms_amend_page_post(
     }
 }
 
+#[handler]
+async fn cms_add_page_term(
+    Path(page_id): Path<u64>,
+    Dep(cms_permission_check_service): Dep<CmsPermissionCheckService>,
+    Dep(cms_term_service): Dep<CmsTermService>,
+    FormQs(page_term_form): FormQs<PageTermForm>,
+    locale: Locale,
+    csrf_token: &CsrfToken,
+) -> poem::Result<Response> {
+    cms_permission_check_service
+        .check_permission_by_page_id(page_id as i64)
+        .map_err(poem::Error::from_error_stack)?;
+
+    match page_term_form.as_validated().await.0 {
+        Ok(validated) => {
+            cms_term_service
+                .add_page_term(page_id as i64, &validated)
+                .map_err(poem::Error::from_error_stack)?;
+            let list_page_term = cms_term_service
+                .list_page_term(page_id as i64)
+                .map_err(poem::Error::from_error_stack)?;
+            Ok(html! {
+                (PageTermForm {
+                    kind: page_term_form.kind,
+                    ..Default::default()
+                }.as_form_html(page_id, None))
+                (term_list_partial(Some("true"), list_page_term, page_id))
+                (flash_partial(Flash::Success {
+                    msg: format!("Added {} {}", validated.kind.label(), validated.name.as_str())
+                }))
+                (command_list_partial(vec![csrf_token.as_html_command()]))
+            }
+            .into_response())
+        }
+        Err(error) => {
+            let error_message = error.as_message(&locale);
+            Ok(html! {
+                (page_term_form.as_form_html(page_id, Some(error_message)))
+            }
+            .with_status(StatusCode::UNPROCESSABLE_ENTITY)
+            .into_response())
+        }
+    }
+}
+
+#[handler]
+async fn cms_delete_page_term(
+    Path((page_id, term_id)): Path<(u64, u64)>,
+    Dep(cms_permission_check_service): Dep<CmsPermissionCheckService>,
+    Dep(cms_term_service): Dep<CmsTermService>,
+    csrf_token: &CsrfToken,
+) -> poem::Result<Markup> {
+    cms_permission_check_service
+        .check_permission_by_page_id(page_id as i64)
+        .map_err(poem::Error::from_error_stack)?;
+
+    cms_term_service
+        .delete_page_term(page_id as i64, term_id as i64)
+        .map_err(poem::Error::from_error_stack)?;
+
+    let list_page_term = cms_term_service
+        .list_page_term(page_id as i64)
+        .map_err(poem::Error::from_error_stack)?;
+
+    Ok(html! {
+        (term_list_partial(Some("true"), list_page_term, page_id))
+        (flash_partial(Flash::Success {
+            msg: "Removed category or tag".to_string()
+        }))
+        (command_list_partial(vec![csrf_token.as_html_command()]))
+    })
+}
+
+#[handler]
+async fn cms_term_suggestion(
+    QueryQs(query): QueryQs<TermSuggestionQuery>,
+    Dep(cms_term_service): Dep<CmsTermService>,
+) -> poem::Result<Markup> {
+    let suggestions = cms_term_service
+        .suggest_term(query.kind, &query.name)
+        .map_err(poem::Error::from_error_stack)?;
+
+    Ok(term_suggestion_partial(suggestions))
+}
+
+#[handler]
+async fn cms_review_page(
+    Path(page_id): Path<u64>,
+    Dep(cms_review_service): Dep<CmsReviewService>,
+    FormQs(review_page_form): FormQs<ReviewPageForm>,
+    locale: Locale,
+    session: &Session,
+    htmx_header: HtmxHeader,
+) -> poem::Result<Response> {
+    match review_page_form.as_validated().await.0 {
+        Ok(validated) => {
+            let status = cms_review_service
+                .review_page(page_id as i64, &validated)
+                .map_err(poem::Error::from_error_stack)?;
+            session.flash(Flash::Success {
+                msg: format!("Page {} is now {}", page_id, status.as_stringed()),
+            });
+            Ok(htmx_header.do_location(
+                Redirect::see_other(format!("{}/amend-page/{}", CMS_ROUTE, page_id)),
+                "#main-content",
+            ))
+        }
+        Err(error) => {
+            let error_message = error.as_message(&locale);
+            Ok(html! {
+                (review_page_form.as_form_html(page_id, Some(error_message)))
+            }
+            .with_status(StatusCode::UNPROCESSABLE_ENTITY)
+            .into_response())
+        }
+    }
+}
+
+#[handler]
+async fn cms_revision_list(
+    Path(page_id): Path<u64>,
+    Dep(cms_permission_check_service): Dep<CmsPermissionCheckService>,
+    Dep(cms_revision_service): Dep<CmsRevisionService>,
+) -> poem::Result<Markup> {
+    cms_permission_check_service
+        .check_permission_by_page_id(page_id as i64)
+        .map_err(poem::Error::from_error_stack)?;
+
+    let list_revision_model = cms_revision_service
+        .list_revision(page_id as i64)
+        .map_err(poem::Error::from_error_stack)?;
+
+    Ok(revision_list_partial(None, list_revision_model, page_id))
+}
+
+#[handler]
+async fn cms_revision_diff(
+    Path(page_id): Path<u64>,
+    QueryQs(query): QueryQs<RevisionDiffQuery>,
+    Dep(cms_permission_check_service): Dep<CmsPermissionCheckService>,
+    Dep(cms_revision_service): Dep<CmsRevisionService>,
+) -> poem::Result<Markup> {
+    cms_permission_check_service
+        .check_permission_by_page_id(page_id as i64)
+        .map_err(poem::Error::from_error_stack)?;
+
+    let diff = cms_revision_service
+        .diff_revision(page_id as i64, query.from as i64, query.to as i64)
+        .map_err(poem::Error::from_error_stack)?;
+
+    Ok(revision_diff_partial(&diff))
+}
+
+#[handler]
+async fn cms_restore_revision(
+    Path((page_id, revision_id)): Path<(u64, u64)>,
+    Dep(cms_permission_check_service): Dep<CmsPermissionCheckService>,
+    Dep(cms_revision_service): Dep<CmsRevisionService>,
+    session: &Session,
+    htmx_header: HtmxHeader,
+) -> poem::Result<Response> {
+    cms_permission_check_service
+        .check_permission_by_page_id(page_id as i64)
+        .map_err(poem::Error::from_error_stack)?;
+
+    cms_revision_service
+        .restore_revision(page_id as i64, revision_id as i64)
+        .map_err(poem::Error::from_error_stack)?;
+
+    session.flash(Flash::Success {
+        msg: format!("Restored revision {}", revision_id),
+    });
+    Ok(htmx_header.do_location(
+        Redirect::see_other(format!("{}/amend-page/{}", CMS_ROUTE, page_id)),
+        "#main-content",
+    ))
+}
+
 #[handler]
 async fn cms_update_position(
     Path(page_id): Path<u64>,
@@ -287,14 +673,18 @@ async fn cms_delete_component(
         .check_permission_by_component_id(component_id as i64)
         .map_err(poem::Error::from_error_stack)?;
 
-    cms_attachment_service
-        .delete_file_by_component_id(component_id as i64)
+    let file_paths = cms_attachment_service
+        .list_file_path_by_component_id(component_id as i64)
         .map_err(poem::Error::from_error_stack)?;
 
     cms_page_service
         .delete_component(component_id as i64)
         .map_err(poem::Error::from_error_stack)?;
 
+    cms_attachment_service
+        .remove_unreferenced_files(&file_paths)
+        .map_err(poem::Error::from_error_stack)?;
+
     let list_component_model = cms_page_service
         .list_component(page_id as i64)
         .map_err(poem::Error::from_error_stack)?;
@@ -309,9 +699,102 @@ async fn cms_delete_component(
     })
 }
 
+#[handler]
+async fn cms_delete_page(
+    Path(page_id): Path<u64>,
+    Dep(cms_page_service): Dep<CmsPageService>,
+    Dep(cms_permission_check_service): Dep<CmsPermissionCheckService>,
+    session: &Session,
+    htmx_header: HtmxHeader,
+) -> poem::Result<Response> {
+    cms_permission_check_service
+        .check_permission_by_page_id(page_id as i64)
+        .map_err(poem::Error::from_error_stack)?;
+
+    cms_page_service
+        .trash_page(page_id as i64)
+        .map_err(poem::Error::from_error_stack)?;
+
+    session.flash(Flash::Success {
+        msg: format!("Moved page {} to the trash", page_id),
+    });
+    Ok(htmx_header.do_location(
+        Redirect::see_other(format!("{}/list-page", CMS_ROUTE)),
+        "#main-content",
+    ))
+}
+
+#[handler]
+async fn cms_restore_page(
+    Path(page_id): Path<u64>,
+    Dep(cms_page_service): Dep<CmsPageService>,
+    session: &Session,
+    htmx_header: HtmxHeader,
+) -> poem::Result<Response> {
+    cms_page_service
+        .restore_page(page_id as i64)
+        .map_err(poem::Error::from_error_stack)?;
+
+    session.flash(Flash::Success {
+        msg: format!("Restored page {} as draft", page_id),
+    });
+    Ok(htmx_header.do_location(
+        Redirect::see_other(format!("{}/trash-page", CMS_ROUTE)),
+        "#main-content",
+    ))
+}
+
+#[handler]
+async fn cms_purge_page(
+    Path(page_id): Path<u64>,
+    Dep(cms_page_service): Dep<CmsPageService>,
+    Dep(cms_attachment_service): Dep<CmsAttachmentService>,
+    session: &Session,
+    htmx_header: HtmxHeader,
+) -> poem::Result<Response> {
+    let redirect = Redirect::see_other(format!("{}/trash-page", CMS_ROUTE));
+
+    if let Err(report) = cms_page_service.ensure_purgeable(page_id as i64) {
+        if !matches!(report.current_context(), CmsPageServiceError::HasChildPage) {
+            return Err(poem::Error::from_error_stack(report));
+        }
+        session.flash(Flash::Error {
+            msg: format!(
+                "Page {} still has child pages, move or purge them first",
+                page_id
+            ),
+        });
+        return Ok(htmx_header.do_location(redirect, "#main-content"));
+    }
+
+    let file_paths = cms_attachment_service
+        .list_file_path_by_page_id(page_id as i64)
+        .map_err(poem::Error::from_error_stack)?;
+
+    cms_page_service
+        .purge_page(page_id as i64)
+        .map_err(poem::Error::from_error_stack)?;
+
+    cms_attachment_service
+        .remove_unreferenced_files(&file_paths)
+        .map_err(poem::Error::from_error_stack)?;
+
+    session.flash(Flash::Success {
+        msg: format!("Purged page {}", page_id),
+    });
+    Ok(htmx_header.do_location(redirect, "#main-content"))
+}
+
 pub fn cms_route() -> Route {
     Route::new()
-        .at("/list-page", get(cms_list_page))
+        .at(
+            "/list-page",
+            get(with_serde_qs_config(
+                Config::default().use_form_encoding(true),
+                cms_list_page,
+            )),
+        )
+        .at("/trash-page", must_be_root(get(cms_trash_page)))
         .at(
             "/create-page",
             get(cms_create_page_get).post(csrf_header_check(cms_create_page_post)),
@@ -327,8 +810,39 @@ pub fn cms_route() -> Route {
                 csrf_header_check_strict(cms_update_position),
             )),
         )
+        .at(
+            "/review-page/:page_id",
+            must_be_editor(post(csrf_header_check_strict(cms_review_page))),
+        )
+        .at(
+            "/page-term/:page_id",
+            post(csrf_header_check_strict(cms_add_page_term)),
+        )
+        .at(
+            "/delete-page-term/:page_id/:term_id",
+            delete(csrf_header_check_strict(cms_delete_page_term)),
+        )
+        .at("/term-suggestion", get(cms_term_suggestion))
+        .at("/revision/:page_id", get(cms_revision_list))
+        .at("/revision-diff/:page_id", get(cms_revision_diff))
+        .at(
+            "/restore-revision/:page_id/:revision_id",
+            patch(csrf_header_check_strict(cms_restore_revision)),
+        )
         .at("/create-component", registry_ep_create())
         .at("/component", registry_ep_update_fetch())
+        .at(
+            "/delete-page/:page_id",
+            delete(csrf_header_check_strict(cms_delete_page)),
+        )
+        .at(
+            "/restore-page/:page_id",
+            must_be_root(patch(csrf_header_check_strict(cms_restore_page))),
+        )
+        .at(
+            "/purge-page/:page_id",
+            must_be_root(delete(csrf_header_check_strict(cms_purge_page))),
+        )
         .at(
             "/delete-component/:component_id/:page_id",
             delete(csrf_header_check_strict(cms_delete_component)),
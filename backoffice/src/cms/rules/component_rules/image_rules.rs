@@ -0,0 +1,91 @@
+use cjtoolkit_structured_validator::common::locale::{LocaleData, LocaleMessage};
+use cjtoolkit_structured_validator::common::validation_check::ValidationCheck;
+use cjtoolkit_structured_validator::common::validation_collector::ValidateErrorCollector;
+use cjtoolkit_structured_validator::types::description::description_alias::{
+    Text, TextError, TextRules,
+};
+use cjtoolkit_structured_validator::types::name::name_alias::FieldError;
+use image::{DynamicImage, ImageReader};
+use std::io::Cursor;
+use std::sync::Arc;
+
+fn image_alt_rules() -> TextRules {
+    TextRules {
+        is_mandatory: true,
+        min_length: None,
+        max_length: Some(250),
+    }
+}
+
+fn image_caption_rules() -> TextRules {
+    TextRules {
+        is_mandatory: false,
+        min_length: None,
+        max_length: Some(500),
+    }
+}
+
+pub trait ImageRulesExt {
+    fn parse_image_alt(s: Option<&str>) -> Result<Text, TextError>;
+    fn parse_image_caption(s: Option<&str>) -> Result<Text, TextError>;
+}
+
+impl ImageRulesExt for Text {
+    fn parse_image_alt(s: Option<&str>) -> Result<Text, TextError> {
+        Self::parse_custom(s, image_alt_rules())
+    }
+
+    fn parse_image_caption(s: Option<&str>) -> Result<Text, TextError> {
+        Self::parse_custom(s, image_caption_rules())
+    }
+}
+
+struct ImageInvalidLocale;
+
+impl LocaleMessage for ImageInvalidLocale {
+    fn get_locale_data(&self) -> Arc<LocaleData> {
+        LocaleData::new("validate-image-invalid")
+    }
+}
+
+/// The format is sniffed from the content, the browser's file name and content type are not trusted.
+pub fn parse_image(content: &[u8]) -> Result<DynamicImage, FieldError> {
+    let image = ImageReader::new(Cursor::new(content))
+        .with_guessed_format()
+        .ok()
+        .and_then(|reader| reader.decode().ok());
+    match image {
+        Some(image) => Ok(image),
+        None => {
+            let mut messages = ValidateErrorCollector::new();
+            messages.push((
+                "Must be a JPEG, PNG, WebP or GIF image".to_string(),
+                Box::new(ImageInvalidLocale),
+            ));
+            FieldError::validate_check(messages)?;
+            Ok(DynamicImage::default())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{ImageFormat, RgbImage};
+
+    #[test]
+    fn test_parse_image_accepts_png() {
+        let mut content = Cursor::new(Vec::new());
+        DynamicImage::ImageRgb8(RgbImage::new(4, 2))
+            .write_to(&mut content, ImageFormat::Png)
+            .unwrap();
+
+        let image = parse_image(content.get_ref()).unwrap();
+        assert_eq!((image.width(), image.height()), (4, 2));
+    }
+
+    #[test]
+    fn test_parse_image_rejects_non_image() {
+        assert!(parse_image(b"%PDF-1.7 not an image").is_err());
+    }
+}
@@ -1 +1,2 @@
+pub mod image_rules;
 pub mod markdown_rules;
@@ -1,3 +1,9 @@
 pub mod component_rules;
+pub mod parent_rule;
+pub mod review_rule;
+pub mod schedule_rule;
+pub mod slug_rule;
 pub mod summary_rule;
+pub mod term_rule;
 pub mod title_rule;
+pub mod upload_rule;
@@ -0,0 +1,36 @@
+use cjtoolkit_structured_validator::common::locale::{LocaleData, LocaleMessage};
+use cjtoolkit_structured_validator::common::validation_check::ValidationCheck;
+use cjtoolkit_structured_validator::common::validation_collector::ValidateErrorCollector;
+use cjtoolkit_structured_validator::types::name::name_alias::FieldError;
+use std::sync::Arc;
+
+struct ParentInvalidLocale;
+
+impl LocaleMessage for ParentInvalidLocale {
+    fn get_locale_data(&self) -> Arc<LocaleData> {
+        LocaleData::new("validate-page-parent-invalid")
+    }
+}
+
+pub trait IsParentValidAsync {
+    fn is_parent_valid_async(&self, parent_id: i64, page_id: i64) -> impl Future<Output = bool>;
+}
+
+pub async fn parse_parent<T: IsParentValidAsync>(
+    parent_id: i64,
+    service: &T,
+    page_id: i64,
+) -> Result<Option<i64>, FieldError> {
+    if parent_id == 0 {
+        return Ok(None);
+    }
+    if parent_id == page_id || !service.is_parent_valid_async(parent_id, page_id).await {
+        let mut messages = ValidateErrorCollector::new();
+        messages.push((
+            "Cannot be the page itself or one of its children".to_string(),
+            Box::new(ParentInvalidLocale),
+        ));
+        FieldError::validate_check(messages)?;
+    }
+    Ok(Some(parent_id))
+}
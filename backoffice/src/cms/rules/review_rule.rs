@@ -0,0 +1,22 @@
+use cjtoolkit_structured_validator::types::description::description_alias::{
+    Text, TextError, TextRules,
+};
+
+#[inline]
+fn review_comment_rules(is_mandatory: bool) -> TextRules {
+    TextRules {
+        is_mandatory,
+        min_length: None,
+        max_length: Some(2000),
+    }
+}
+
+pub trait ReviewCommentRulesExt {
+    fn parse_review_comment(s: Option<&str>, is_mandatory: bool) -> Result<Text, TextError>;
+}
+
+impl ReviewCommentRulesExt for Text {
+    fn parse_review_comment(s: Option<&str>, is_mandatory: bool) -> Result<Text, TextError> {
+        Self::parse_custom(s, review_comment_rules(is_mandatory))
+    }
+}
@@ -0,0 +1,59 @@
+use chrono::{DateTime, Utc};
+use cjtoolkit_structured_validator::common::locale::{LocaleData, LocaleMessage};
+use cjtoolkit_structured_validator::common::validation_check::ValidationCheck;
+use cjtoolkit_structured_validator::common::validation_collector::ValidateErrorCollector;
+use cjtoolkit_structured_validator::types::name::name_alias::FieldError;
+use std::sync::Arc;
+
+struct ScheduleInvalidLocale;
+
+impl LocaleMessage for ScheduleInvalidLocale {
+    fn get_locale_data(&self) -> Arc<LocaleData> {
+        LocaleData::new("validate-schedule-invalid")
+    }
+}
+
+struct ScheduleOrderLocale;
+
+impl LocaleMessage for ScheduleOrderLocale {
+    fn get_locale_data(&self) -> Arc<LocaleData> {
+        LocaleData::new("validate-schedule-order")
+    }
+}
+
+/// The amend form posts the browser's local time already converted to RFC 3339 UTC, empty means unscheduled.
+pub fn parse_schedule(value: &str) -> Result<Option<DateTime<Utc>>, FieldError> {
+    if value.is_empty() {
+        return Ok(None);
+    }
+    match DateTime::parse_from_rfc3339(value) {
+        Ok(date_time) => Ok(Some(date_time.with_timezone(&Utc))),
+        Err(_) => {
+            let mut messages = ValidateErrorCollector::new();
+            messages.push((
+                "Must be a valid date and time".to_string(),
+                Box::new(ScheduleInvalidLocale),
+            ));
+            FieldError::validate_check(messages)?;
+            Ok(None)
+        }
+    }
+}
+
+pub fn parse_unpublish_schedule(
+    value: &str,
+    publish_at: Option<DateTime<Utc>>,
+) -> Result<Option<DateTime<Utc>>, FieldError> {
+    let unpublish_at = parse_schedule(value)?;
+    if let (Some(publish_at), Some(unpublish_at)) = (publish_at, unpublish_at)
+        && unpublish_at <= publish_at
+    {
+        let mut messages = ValidateErrorCollector::new();
+        messages.push((
+            "Must be after the publish time".to_string(),
+            Box::new(ScheduleOrderLocale),
+        ));
+        FieldError::validate_check(messages)?;
+    }
+    Ok(unpublish_at)
+}
@@ -0,0 +1,91 @@
+use cjtoolkit_structured_validator::common::locale::{LocaleData, LocaleMessage};
+use cjtoolkit_structured_validator::common::validation_check::ValidationCheck;
+use cjtoolkit_structured_validator::common::validation_collector::ValidateErrorCollector;
+use cjtoolkit_structured_validator::types::name::name_alias::{Field, FieldError, FieldRules};
+use shared::cms::slug::{is_reserved_root_slug, is_valid_slug};
+use std::sync::Arc;
+
+#[inline]
+fn slug_rule() -> FieldRules {
+    FieldRules {
+        is_mandatory: true,
+        min_length: Some(1),
+        max_length: Some(60),
+    }
+}
+
+struct SlugKebabCaseLocale;
+
+impl LocaleMessage for SlugKebabCaseLocale {
+    fn get_locale_data(&self) -> Arc<LocaleData> {
+        LocaleData::new("validate-must-be-kebab-case")
+    }
+}
+
+struct SlugReservedLocale;
+
+impl LocaleMessage for SlugReservedLocale {
+    fn get_locale_data(&self) -> Arc<LocaleData> {
+        LocaleData::new("validate-slug-reserved")
+    }
+}
+
+struct SlugTakenLocale;
+
+impl LocaleMessage for SlugTakenLocale {
+    fn get_locale_data(&self) -> Arc<LocaleData> {
+        LocaleData::new("validate-slug-taken")
+    }
+}
+
+pub trait IsSlugTakenAsync {
+    fn is_slug_taken_async(
+        &self,
+        slug: &str,
+        parent_id: Option<i64>,
+        page_id: Option<i64>,
+    ) -> impl Future<Output = bool>;
+}
+
+fn check_slug(slug: &str, parent_id: Option<i64>) -> Result<(), FieldError> {
+    let mut messages = ValidateErrorCollector::new();
+    if !is_valid_slug(slug) {
+        messages.push((
+            "Must be kebab case".to_string(),
+            Box::new(SlugKebabCaseLocale),
+        ));
+    } else if parent_id.is_none() && is_reserved_root_slug(slug) {
+        messages.push(("Slug is reserved".to_string(), Box::new(SlugReservedLocale)));
+    }
+    FieldError::validate_check(messages)
+}
+
+pub trait SlugRuleExt {
+    fn parse_slug<T: IsSlugTakenAsync>(
+        s: Option<&str>,
+        service: &T,
+        parent_id: Option<i64>,
+        page_id: Option<i64>,
+    ) -> impl Future<Output = Result<Field, FieldError>>;
+}
+
+impl SlugRuleExt for Field {
+    async fn parse_slug<T: IsSlugTakenAsync>(
+        s: Option<&str>,
+        service: &T,
+        parent_id: Option<i64>,
+        page_id: Option<i64>,
+    ) -> Result<Field, FieldError> {
+        let slug = Self::parse_custom(s, slug_rule())?;
+        check_slug(slug.as_str(), parent_id)?;
+        if service
+            .is_slug_taken_async(slug.as_str(), parent_id, page_id)
+            .await
+        {
+            let mut messages = ValidateErrorCollector::new();
+            messages.push(("Already taken".to_string(), Box::new(SlugTakenLocale)));
+            FieldError::validate_check(messages)?;
+        }
+        Ok(slug)
+    }
+}
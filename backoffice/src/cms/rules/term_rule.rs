@@ -0,0 +1,55 @@
+use cjtoolkit_structured_validator::common::locale::{LocaleData, LocaleMessage};
+use cjtoolkit_structured_validator::common::validation_check::ValidationCheck;
+use cjtoolkit_structured_validator::common::validation_collector::ValidateErrorCollector;
+use cjtoolkit_structured_validator::types::name::name_alias::{Field, FieldError, FieldRules};
+use shared::cms::slug::slugify;
+use std::sync::Arc;
+
+#[inline]
+fn term_name_rule() -> FieldRules {
+    FieldRules {
+        is_mandatory: true,
+        min_length: Some(1),
+        max_length: Some(40),
+    }
+}
+
+struct TermNameNoSlugLocale;
+
+impl LocaleMessage for TermNameNoSlugLocale {
+    fn get_locale_data(&self) -> Arc<LocaleData> {
+        LocaleData::new("validate-term-name-no-slug")
+    }
+}
+
+pub trait TermNameRuleExt {
+    fn parse_term_name(s: Option<&str>) -> Result<Field, FieldError>;
+}
+
+impl TermNameRuleExt for Field {
+    /// The slug is derived from the name, so the name needs at least one letter or digit.
+    fn parse_term_name(s: Option<&str>) -> Result<Field, FieldError> {
+        let name = Self::parse_custom(s, term_name_rule())?;
+        if slugify(name.as_str()).is_empty() {
+            let mut messages = ValidateErrorCollector::new();
+            messages.push((
+                "Must contain a letter or digit".to_string(),
+                Box::new(TermNameNoSlugLocale),
+            ));
+            FieldError::validate_check(messages)?;
+        }
+        Ok(name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_term_name() {
+        assert!(Field::parse_term_name(Some("Rust Web")).is_ok());
+        assert!(Field::parse_term_name(Some("")).is_err());
+        assert!(Field::parse_term_name(Some("!?")).is_err());
+    }
+}
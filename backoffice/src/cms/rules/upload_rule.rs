@@ -0,0 +1,80 @@
+use cjtoolkit_structured_validator::common::locale::{LocaleData, LocaleMessage, LocaleValue};
+use cjtoolkit_structured_validator::common::validation_check::ValidationCheck;
+use cjtoolkit_structured_validator::common::validation_collector::ValidateErrorCollector;
+use cjtoolkit_structured_validator::types::name::name_alias::FieldError;
+use std::sync::Arc;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum UploadRejection {
+    FileTooLarge { file_name: String, max_mb: u64 },
+    RequestTooLarge { max_mb: u64 },
+    FileTypeNotAllowed { file_name: String },
+}
+
+impl UploadRejection {
+    /// The rest of the request body is not read once the request budget is spent.
+    pub fn stops_upload(&self) -> bool {
+        matches!(self, Self::RequestTooLarge { .. })
+    }
+
+    fn message(&self) -> String {
+        match self {
+            Self::FileTooLarge { file_name, max_mb } => {
+                format!("{} is larger than {} MB", file_name, max_mb)
+            }
+            Self::RequestTooLarge { max_mb } => {
+                format!("Uploads are limited to {} MB per request", max_mb)
+            }
+            Self::FileTypeNotAllowed { file_name } => {
+                format!("{} is not an allowed file type", file_name)
+            }
+        }
+    }
+}
+
+impl LocaleMessage for UploadRejection {
+    fn get_locale_data(&self) -> Arc<LocaleData> {
+        match self {
+            Self::FileTooLarge { file_name, max_mb } => LocaleData::new_with_vec(
+                "validate-upload-file-too-large",
+                vec![
+                    ("name".to_string(), LocaleValue::from(file_name.as_str())),
+                    ("max".to_string(), LocaleValue::from(*max_mb as usize)),
+                ],
+            ),
+            Self::RequestTooLarge { max_mb } => LocaleData::new_with_vec(
+                "validate-upload-request-too-large",
+                vec![("max".to_string(), LocaleValue::from(*max_mb as usize))],
+            ),
+            Self::FileTypeNotAllowed { file_name } => LocaleData::new_with_vec(
+                "validate-upload-type-not-allowed",
+                vec![("name".to_string(), LocaleValue::from(file_name.as_str()))],
+            ),
+        }
+    }
+}
+
+pub fn check_upload_rejections(rejections: &[UploadRejection]) -> Result<(), FieldError> {
+    let mut messages = ValidateErrorCollector::new();
+    for rejection in rejections {
+        messages.push((rejection.message(), Box::new(rejection.clone())));
+    }
+    FieldError::validate_check(messages)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_upload_rejections() {
+        assert!(check_upload_rejections(&[]).is_ok());
+        assert!(
+            check_upload_rejections(&[UploadRejection::FileTypeNotAllowed {
+                file_name: "page.html".to_string(),
+            }])
+            .is_err()
+        );
+        assert!(UploadRejection::RequestTooLarge { max_mb: 1 }.stops_upload());
+    }
+}
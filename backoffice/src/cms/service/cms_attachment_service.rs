@@ -1,14 +1,24 @@
-use crate::cms::data_model::cms_data::{AddFileAttachmentModel, ListFileAttachmentModel};
+use crate::cms::data_model::cms_data::{
+    AddFileAttachmentModel, FilePath, ListFileAttachmentModel, RevisionAuthorModel,
+};
 use crate::cms::repository::cms_repository::CmsRepository;
+use crate::cms::rules::upload_rule::UploadRejection;
 use crate::user::pointer::user_pointer::UserPointer;
-use chrono::{DateTime, Utc};
 use error_stack::{Report, ResultExt};
 use poem::web::Field;
+use sha2::{Digest, Sha256};
+use shared::cms::file_path::disk_path;
 use shared::utils::config::ConfigPointer;
 use shared::utils::context::{Context, ContextError, FromContext};
-use std::path::Path;
+use std::collections::HashSet;
+use std::fs::File;
+use std::io::Write;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
 use thiserror::Error;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use uuid::Uuid;
 
 #[derive(Debug, Error)]
 pub enum CmsAttachmentServiceError {
@@ -20,105 +30,396 @@ pub enum CmsAttachmentServiceError {
     NotFoundError,
 }
 
+const SAVE_PATH: &str = "/cms/files";
+const TEMP_PATH: &str = "/cms/tmp";
+const MAX_FILE_NAME_CHARS: usize = 255;
+const READ_CHUNK_BYTES: usize = 64 * 1024;
+const SNIFF_BYTES: usize = 8 * 1024;
+const BYTES_PER_MB: u64 = 1024 * 1024;
+
+/// Keeps the last segment of a client supplied name, without control characters or leading dots.
+fn normalize_file_name(file_name: &str) -> String {
+    let base_name = file_name.rsplit(['/', '\\']).next().unwrap_or_default();
+    let base_name: String = base_name.chars().filter(|c| !c.is_control()).collect();
+    let base_name: String = base_name
+        .trim()
+        .trim_start_matches('.')
+        .trim_start()
+        .chars()
+        .take(MAX_FILE_NAME_CHARS)
+        .collect();
+    match base_name.is_empty() {
+        true => "file".to_string(),
+        false => base_name,
+    }
+}
+
+fn file_extension(file_name: &str) -> Option<String> {
+    let (_, extension) = file_name.rsplit_once('.')?;
+    (!extension.is_empty()
+        && extension.len() <= 10
+        && extension.chars().all(|c| c.is_ascii_alphanumeric()))
+    .then(|| extension.to_ascii_lowercase())
+}
+
+/// Blobs are addressed by content, the extension only helps the backoffice guess a content type.
+fn blob_path(file_hash: &str, extension: Option<&str>) -> String {
+    match extension {
+        Some(extension) => format!(
+            "{}/{}/{}.{}",
+            SAVE_PATH,
+            &file_hash[..2],
+            file_hash,
+            extension
+        ),
+        None => format!("{}/{}/{}", SAVE_PATH, &file_hash[..2], file_hash),
+    }
+}
+
+/// The type is taken from the magic bytes, text without a signature is only accepted as plain text.
+fn sniff_file_type(head: &[u8]) -> String {
+    if let Some(kind) = infer::get(head) {
+        return kind.mime_type().to_string();
+    }
+    let is_text = match std::str::from_utf8(head) {
+        Ok(_) => true,
+        // the head may end in the middle of a character.
+        Err(error) => error.error_len().is_none(),
+    };
+    match is_text && !head.contains(&0) {
+        true => "text/plain".to_string(),
+        false => "application/octet-stream".to_string(),
+    }
+}
+
+/// Removed on drop, unless it has been moved into place.
+struct TempFile(PathBuf);
+
+impl TempFile {
+    fn persist(self, save_file_path: &Path) -> std::io::Result<()> {
+        let parent = save_file_path
+            .parent()
+            .ok_or_else(|| std::io::Error::from(std::io::ErrorKind::InvalidInput))?;
+        std::fs::create_dir_all(parent)?;
+        std::fs::rename(&self.0, save_file_path)
+    }
+}
+
+impl Drop for TempFile {
+    fn drop(&mut self) {
+        _ = std::fs::remove_file(&self.0);
+    }
+}
+
+/// An upload that passed the size and type checks, held in a temporary file until it is stored.
+pub struct ReceivedFile {
+    pub file_name: String,
+    pub file_type: String,
+    file_hash: String,
+    temp_file: TempFile,
+}
+
+impl ReceivedFile {
+    pub fn content(&self) -> Result<Vec<u8>, Report<CmsAttachmentServiceError>> {
+        std::fs::read(&self.temp_file.0)
+            .change_context(CmsAttachmentServiceError::SaveError)
+            .attach(poem::http::StatusCode::INTERNAL_SERVER_ERROR)
+    }
+}
+
+fn write_atomic(save_file_path: &Path, file_content: &[u8]) -> std::io::Result<()> {
+    let parent = save_file_path
+        .parent()
+        .ok_or_else(|| std::io::Error::from(std::io::ErrorKind::InvalidInput))?;
+    std::fs::create_dir_all(parent)?;
+
+    let temp_file_path = parent.join(format!(".{}.tmp", Uuid::new_v4()));
+    let result = File::create(&temp_file_path)
+        .and_then(|mut file| {
+            file.write_all(file_content)?;
+            file.sync_all()
+        })
+        .and_then(|_| std::fs::rename(&temp_file_path, save_file_path));
+    if result.is_err() {
+        _ = std::fs::remove_file(&temp_file_path);
+    }
+    result
+}
+
 pub struct CmsAttachmentService {
     file_upload_path: String,
+    max_file_size_mb: u64,
+    max_request_size_mb: u64,
+    received_bytes: AtomicU64,
     cms_repository: CmsRepository,
     user_pointer: UserPointer,
-    stamp: DateTime<Utc>,
 }
 
 impl CmsAttachmentService {
-    const SAVE_PATH: &'static str = "/cms/files";
-
     pub fn new(
         file_upload_path: String,
+        max_file_size_mb: u64,
+        max_request_size_mb: u64,
         cms_repository: CmsRepository,
         user_pointer: UserPointer,
     ) -> Self {
         Self {
             file_upload_path,
+            max_file_size_mb,
+            max_request_size_mb,
+            received_bytes: AtomicU64::new(0),
             cms_repository,
             user_pointer,
-            stamp: Utc::now(),
         }
     }
 
-    pub async fn add_file(
+    fn save_file_path(&self, file_path: &str) -> PathBuf {
+        disk_path(&self.file_upload_path, file_path)
+    }
+
+    fn existing_file_path(
         &self,
-        component_id: i64,
-        field: Field,
+        file_hash: &str,
+    ) -> Result<Option<String>, Report<CmsAttachmentServiceError>> {
+        Ok(self
+            .cms_repository
+            .get_file_path_by_hash(file_hash)
+            .change_context(CmsAttachmentServiceError::DbError)
+            .attach(poem::http::StatusCode::INTERNAL_SERVER_ERROR)?
+            .map(|file_path| file_path.0)
+            .filter(|file_path| self.save_file_path(file_path).is_file()))
+    }
+
+    fn add_file_attachment(
+        &self,
+        add_file_attachment_model: AddFileAttachmentModel,
     ) -> Result<(), Report<CmsAttachmentServiceError>> {
-        let file_name = field.file_name().unwrap_or_default().to_string();
-        let file_type = field.content_type().unwrap_or_default().to_string();
-        let file_path = format!(
-            "{}/{}-{}/{}",
-            Self::SAVE_PATH,
-            self.stamp.format("%Y-%m-%d-%s"),
-            self.user_pointer.id,
-            &file_name
-        );
-        let save_file_path = format!("{}/{}", &self.file_upload_path, &file_path);
+        self.cms_repository
+            .add_file_attachment(
+                add_file_attachment_model,
+                RevisionAuthorModel::from(&self.user_pointer),
+            )
+            .change_context(CmsAttachmentServiceError::DbError)
+            .attach(poem::http::StatusCode::INTERNAL_SERVER_ERROR)
+    }
 
-        let file_content = field
-            .bytes()
+    /// Streams the field into a temporary file, counting it against the per file and per request limits.
+    /// The request budget is shared by every field received through this service.
+    pub async fn receive_file(
+        &self,
+        field: Field,
+        allowed_file_types: &[&str],
+    ) -> Result<Result<ReceivedFile, UploadRejection>, Report<CmsAttachmentServiceError>> {
+        let file_name = normalize_file_name(field.file_name().unwrap_or_default());
+        let max_file_bytes = self.max_file_size_mb.saturating_mul(BYTES_PER_MB);
+        let max_request_bytes = self.max_request_size_mb.saturating_mul(BYTES_PER_MB);
+
+        let temp_dir = self.save_file_path(TEMP_PATH);
+        tokio::fs::create_dir_all(&temp_dir)
             .await
             .change_context(CmsAttachmentServiceError::SaveError)
-            .attach(poem::http::StatusCode::BAD_REQUEST)?;
-
-        // save file path dirname
-        let save_file_path = Path::new(&save_file_path);
+            .attach(poem::http::StatusCode::INTERNAL_SERVER_ERROR)?;
+        let temp_file = TempFile(temp_dir.join(format!(".{}.tmp", Uuid::new_v4())));
+        let mut file = tokio::fs::File::create(&temp_file.0)
+            .await
+            .change_context(CmsAttachmentServiceError::SaveError)
+            .attach(poem::http::StatusCode::INTERNAL_SERVER_ERROR)?;
 
-        _ = std::fs::create_dir_all(save_file_path.parent().ok_or_else(|| {
-            Report::new(CmsAttachmentServiceError::SaveError)
-                .attach(poem::http::StatusCode::BAD_REQUEST)
-        })?);
+        let mut reader = Box::pin(field.into_async_read());
+        let mut buffer = vec![0u8; READ_CHUNK_BYTES];
+        let mut head = Vec::with_capacity(SNIFF_BYTES);
+        let mut hasher = Sha256::new();
+        let mut file_bytes = 0u64;
+        loop {
+            let read = reader
+                .read(&mut buffer)
+                .await
+                .change_context(CmsAttachmentServiceError::SaveError)
+                .attach(poem::http::StatusCode::BAD_REQUEST)?;
+            if read == 0 {
+                break;
+            }
+            let request_bytes = self
+                .received_bytes
+                .fetch_add(read as u64, Ordering::Relaxed)
+                + read as u64;
+            if request_bytes > max_request_bytes {
+                return Ok(Err(UploadRejection::RequestTooLarge {
+                    max_mb: self.max_request_size_mb,
+                }));
+            }
+            file_bytes += read as u64;
+            if file_bytes > max_file_bytes {
+                // keep reading so the next field can be parsed, the request limit still applies.
+                continue;
+            }
 
-        std::fs::write(save_file_path, file_content)
-            .change_context(CmsAttachmentServiceError::SaveError)
-            .attach(poem::http::StatusCode::BAD_REQUEST)?;
+            let chunk = &buffer[..read];
+            let head_missing = SNIFF_BYTES.saturating_sub(head.len()).min(read);
+            head.extend_from_slice(&chunk[..head_missing]);
+            hasher.update(chunk);
+            file.write_all(chunk)
+                .await
+                .change_context(CmsAttachmentServiceError::SaveError)
+                .attach(poem::http::StatusCode::INTERNAL_SERVER_ERROR)?;
+        }
 
-        self.cms_repository
-            .add_file_attachment(AddFileAttachmentModel {
-                component_id,
+        if file_bytes > max_file_bytes {
+            return Ok(Err(UploadRejection::FileTooLarge {
                 file_name,
-                file_path,
-                file_type,
-            })
-            .change_context(CmsAttachmentServiceError::DbError)
+                max_mb: self.max_file_size_mb,
+            }));
+        }
+
+        let file_type = sniff_file_type(&head);
+        if !allowed_file_types.contains(&file_type.as_str()) {
+            return Ok(Err(UploadRejection::FileTypeNotAllowed { file_name }));
+        }
+
+        file.sync_all()
+            .await
+            .change_context(CmsAttachmentServiceError::SaveError)
             .attach(poem::http::StatusCode::INTERNAL_SERVER_ERROR)?;
 
-        Ok(())
+        Ok(Ok(ReceivedFile {
+            file_name,
+            file_type,
+            file_hash: format!("{:x}", hasher.finalize()),
+            temp_file,
+        }))
     }
 
-    pub fn delete_file_by_component_id(
+    /// Moves a received file into its content addressed place and records it against the component.
+    pub fn add_received_file(
         &self,
         component_id: i64,
-    ) -> Result<(), Report<CmsAttachmentServiceError>> {
-        let list_file = self
-            .cms_repository
-            .list_file_attachment(component_id)
-            .change_context(CmsAttachmentServiceError::DbError)
-            .attach(poem::http::StatusCode::INTERNAL_SERVER_ERROR)?;
+        received_file: ReceivedFile,
+    ) -> Result<String, Report<CmsAttachmentServiceError>> {
+        let ReceivedFile {
+            file_name,
+            file_type,
+            file_hash,
+            temp_file,
+        } = received_file;
 
-        self.cms_repository
-            .delete_file_attachment_by_component_id(component_id)
-            .change_context(CmsAttachmentServiceError::DbError)
-            .attach(poem::http::StatusCode::INTERNAL_SERVER_ERROR)?;
+        let file_path = match self.existing_file_path(&file_hash)? {
+            Some(file_path) => file_path,
+            None => {
+                let file_path = blob_path(&file_hash, file_extension(&file_name).as_deref());
+                temp_file
+                    .persist(&self.save_file_path(&file_path))
+                    .change_context(CmsAttachmentServiceError::SaveError)
+                    .attach(poem::http::StatusCode::INTERNAL_SERVER_ERROR)?;
+                file_path
+            }
+        };
 
-        for file in list_file.iter() {
-            let file_path = format!("{}/{}", &self.file_upload_path, &file.file_path);
-            _ = std::fs::remove_file(file_path);
+        self.add_file_attachment(AddFileAttachmentModel {
+            component_id,
+            file_name,
+            file_path: file_path.clone(),
+            file_type,
+            file_hash,
+        })?;
+
+        Ok(file_path)
+    }
+
+    /// Writes `file_content` once per distinct content, returning the attachment for the caller to
+    /// record against the component together with the rest of its change.
+    pub fn store_file_content(
+        &self,
+        component_id: i64,
+        file_name: String,
+        file_type: String,
+        file_content: &[u8],
+    ) -> Result<AddFileAttachmentModel, Report<CmsAttachmentServiceError>> {
+        let file_name = normalize_file_name(&file_name);
+        let file_hash = format!("{:x}", Sha256::digest(file_content));
+
+        let file_path = match self.existing_file_path(&file_hash)? {
+            Some(file_path) => file_path,
+            None => {
+                let file_path = blob_path(&file_hash, file_extension(&file_name).as_deref());
+                let save_file_path = self.save_file_path(&file_path);
+                if !save_file_path.is_file() {
+                    write_atomic(&save_file_path, file_content)
+                        .change_context(CmsAttachmentServiceError::SaveError)
+                        .attach(poem::http::StatusCode::INTERNAL_SERVER_ERROR)?;
+                }
+                file_path
+            }
+        };
+
+        Ok(AddFileAttachmentModel {
+            component_id,
+            file_name,
+            file_path,
+            file_type,
+            file_hash,
+        })
+    }
+
+    /// Removes the blobs no attachment or revision points at anymore, call after the rows are gone.
+    fn remove_unreferenced<'a>(
+        &self,
+        file_paths: impl Iterator<Item = &'a str>,
+    ) -> Result<(), Report<CmsAttachmentServiceError>> {
+        let file_paths: HashSet<&str> = file_paths.collect();
+        for file_path in file_paths {
+            let file_count = self
+                .cms_repository
+                .count_file_path(file_path)
+                .change_context(CmsAttachmentServiceError::DbError)
+                .attach(poem::http::StatusCode::INTERNAL_SERVER_ERROR)?;
+            if file_count == 0
+                && let Err(err) = std::fs::remove_file(self.save_file_path(file_path))
+                && err.kind() != std::io::ErrorKind::NotFound
+            {
+                // the rows are gone already, the file gc picks up what is left behind.
+                log::error!("Failed to remove {}: {}", file_path, err);
+            }
         }
 
         Ok(())
     }
 
+    /// Every file the component points at, for `remove_unreferenced_files` once it lets go of them.
+    pub fn list_file_path_by_component_id(
+        &self,
+        component_id: i64,
+    ) -> Result<Arc<[FilePath]>, Report<CmsAttachmentServiceError>> {
+        Ok(self
+            .list_file_by_component_id(component_id)?
+            .iter()
+            .map(|file| FilePath(file.file_path.clone()))
+            .collect())
+    }
+
+    /// Every file the page or its revisions point at, for `remove_unreferenced_files` once the page is purged.
+    pub fn list_file_path_by_page_id(
+        &self,
+        page_id: i64,
+    ) -> Result<Arc<[FilePath]>, Report<CmsAttachmentServiceError>> {
+        self.cms_repository
+            .list_file_path_by_page_id(page_id)
+            .change_context(CmsAttachmentServiceError::DbError)
+            .attach(poem::http::StatusCode::INTERNAL_SERVER_ERROR)
+    }
+
+    pub fn remove_unreferenced_files(
+        &self,
+        file_paths: &[FilePath],
+    ) -> Result<(), Report<CmsAttachmentServiceError>> {
+        self.remove_unreferenced(file_paths.iter().map(|file_path| file_path.0.as_str()))
+    }
+
     pub fn delete_file_by_id(
         &self,
         id: i64,
         component_id: i64,
     ) -> Result<(), Report<CmsAttachmentServiceError>> {
-        let file = self
+        let FilePath(file_path) = self
             .cms_repository
             .get_file_path(id, component_id)
             .change_context(CmsAttachmentServiceError::DbError)
@@ -129,14 +430,11 @@ impl CmsAttachmentService {
             })?;
 
         self.cms_repository
-            .delete_file_attachment(id)
+            .delete_file_attachment(id, RevisionAuthorModel::from(&self.user_pointer))
             .change_context(CmsAttachmentServiceError::DbError)
             .attach(poem::http::StatusCode::INTERNAL_SERVER_ERROR)?;
 
-        let file_path = format!("{}/{}", &self.file_upload_path, &file.0);
-        _ = std::fs::remove_file(file_path);
-
-        Ok(())
+        self.remove_unreferenced(std::iter::once(file_path.as_str()))
     }
 
     pub fn list_file_by_component_id(
@@ -155,8 +453,66 @@ impl FromContext for CmsAttachmentService {
         let config: ConfigPointer = ctx.inject().await?;
         Ok(Self::new(
             config.file_path.clone(),
+            config.max_file_size_mb,
+            config.max_request_size_mb,
             ctx.inject().await?,
             ctx.inject().await?,
         ))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_file_name_strips_directories() {
+        assert_eq!(normalize_file_name("../../etc/passwd"), "passwd");
+        assert_eq!(normalize_file_name("C:\\Users\\a\\photo.png"), "photo.png");
+        assert_eq!(normalize_file_name("..."), "file");
+        assert_eq!(normalize_file_name(" .hidden\u{0}name "), "hiddenname");
+        assert_eq!(normalize_file_name(""), "file");
+    }
+
+    #[test]
+    fn test_file_extension() {
+        assert_eq!(file_extension("photo.PNG"), Some("png".to_string()));
+        assert_eq!(file_extension("archive.tar.gz"), Some("gz".to_string()));
+        assert_eq!(file_extension("noext"), None);
+        assert_eq!(file_extension("weird.e x"), None);
+    }
+
+    #[test]
+    fn test_blob_path_is_content_addressed() {
+        let file_hash = format!("{:x}", Sha256::digest(b"hello"));
+        assert_eq!(
+            blob_path(&file_hash, Some("txt")),
+            format!("/cms/files/2c/{}.txt", file_hash)
+        );
+    }
+
+    #[test]
+    fn test_sniff_file_type_ignores_the_extension() {
+        assert_eq!(sniff_file_type(b"%PDF-1.7\n"), "application/pdf");
+        assert_eq!(sniff_file_type(b"plain notes\n"), "text/plain");
+        assert_eq!(sniff_file_type("caf\u{e9}".as_bytes()), "text/plain");
+        assert_eq!(sniff_file_type(&"caf\u{e9}".as_bytes()[..4]), "text/plain");
+        assert_eq!(sniff_file_type(b"\0\x01binary"), "application/octet-stream");
+        assert_eq!(
+            sniff_file_type(b"<!DOCTYPE html><html><body></body></html>"),
+            "text/html"
+        );
+    }
+
+    #[test]
+    fn test_write_atomic_leaves_no_temp_file() {
+        let dir = std::env::temp_dir().join(format!("rusty-cms-{}", Uuid::new_v4()));
+        let save_file_path = dir.join("ab").join("blob");
+
+        write_atomic(&save_file_path, b"content").unwrap();
+
+        assert_eq!(std::fs::read(&save_file_path).unwrap(), b"content");
+        assert_eq!(std::fs::read_dir(dir.join("ab")).unwrap().count(), 1);
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -1,15 +1,25 @@
 use crate::cms::data_model::cms_data::{
-    AddPageModel, FetchPageModel, ListComponentModel, ListPageModel, ReturningIdModel,
-    UpdateComponentPositionModel, UpdatePageModel,
+    AddPageModel, FetchPageModel, ListComponentModel, ListPageModel, PageAuthorModel,
+    PageParentOptionModel, ReturningIdModel, RevisionAuthorModel, UpdateComponentPositionModel,
+    UpdatePageModel,
 };
 use crate::cms::form::add_page_form::AddPageFormValidated;
 use crate::cms::form::amend_page_form::AmendPageFormValidated;
 use crate::cms::form::component_position_form::ComponentPositionForm;
+use crate::cms::query_model::ListPageQuery;
 use crate::cms::repository::cms_repository::CmsRepository;
+use crate::cms::rules::parent_rule::IsParentValidAsync;
+use crate::cms::rules::slug_rule::IsSlugTakenAsync;
+use crate::cms::service::cms_search_service::CmsSearchService;
 use crate::user::pointer::user_pointer::UserPointer;
+use crate::user::role::Role;
 use error_stack::{Report, ResultExt};
 use poem::http::StatusCode;
+use shared::cms::search::match_query;
+use shared::cms::slug::join_path;
+use shared::cms::status::CmsPageStatus;
 use shared::utils::context::{Context, ContextError, FromContext};
+use shared::utils::query_string::list_query::KeysetPage;
 use std::sync::Arc;
 use thiserror::Error;
 
@@ -19,17 +29,27 @@ pub enum CmsPageServiceError {
     DbError,
     #[error("Not found error")]
     NotFoundError,
+    #[error("Invalid status error")]
+    InvalidStatus,
+    #[error("Has child page error")]
+    HasChildPage,
 }
 
 pub struct CmsPageService {
     cms_repository: CmsRepository,
+    cms_search_service: CmsSearchService,
     user_pointer: UserPointer,
 }
 
 impl CmsPageService {
-    pub fn new(cms_repository: CmsRepository, user_pointer: UserPointer) -> Self {
+    pub fn new(
+        cms_repository: CmsRepository,
+        cms_search_service: CmsSearchService,
+        user_pointer: UserPointer,
+    ) -> Self {
         Self {
             cms_repository,
+            cms_search_service,
             user_pointer,
         }
     }
@@ -41,13 +61,18 @@ impl CmsPageService {
         self.cms_repository
             .add_page(AddPageModel {
                 user_id: self.user_pointer.id,
+                slug: add_page_form.slug.as_str().to_string(),
+                path: join_path(None, add_page_form.slug.as_str()),
                 title: add_page_form.title.as_str().to_string(),
                 status: Default::default(),
             })
             .change_context(CmsPageServiceError::DbError)
     }
 
-    pub fn fetch_page(&self, page_id: i64) -> Result<FetchPageModel, Report<CmsPageServiceError>> {
+    fn fetch_page_any_status(
+        &self,
+        page_id: i64,
+    ) -> Result<FetchPageModel, Report<CmsPageServiceError>> {
         self.cms_repository
             .fetch_page(page_id)
             .change_context(CmsPageServiceError::DbError)?
@@ -56,48 +81,217 @@ impl CmsPageService {
             })
     }
 
-    pub fn list_page(&self) -> Result<Arc<[ListPageModel]>, Report<CmsPageServiceError>> {
+    pub fn fetch_page(&self, page_id: i64) -> Result<FetchPageModel, Report<CmsPageServiceError>> {
+        let page = self.fetch_page_any_status(page_id)?;
+        if page.status == CmsPageStatus::Trash {
+            return Err(
+                Report::new(CmsPageServiceError::NotFoundError).attach(StatusCode::NOT_FOUND)
+            );
+        }
+        Ok(page)
+    }
+
+    fn fetch_trash_page(
+        &self,
+        page_id: i64,
+    ) -> Result<FetchPageModel, Report<CmsPageServiceError>> {
+        let page = self.fetch_page_any_status(page_id)?;
+        if page.status != CmsPageStatus::Trash {
+            return Err(
+                Report::new(CmsPageServiceError::NotFoundError).attach(StatusCode::NOT_FOUND)
+            );
+        }
+        Ok(page)
+    }
+
+    /// Editors and root publish directly and schedule, authors go through review.
+    pub fn can_publish(&self) -> bool {
+        self.user_pointer.role >= Role::Editor
+    }
+
+    pub fn status_options(&self) -> Vec<CmsPageStatus> {
+        match self.can_publish() {
+            true => CmsPageStatus::editable_statuses(),
+            false => CmsPageStatus::author_statuses(),
+        }
+    }
+
+    /// Filters by the search index when the filter's text has any searchable term.
+    pub fn list_page(
+        &self,
+        list_query: &ListPageQuery,
+    ) -> Result<KeysetPage<ListPageModel>, Report<CmsPageServiceError>> {
+        self.cms_repository
+            .list_page(list_query, match_query(&list_query.filter.q))
+            .change_context(CmsPageServiceError::DbError)
+    }
+
+    pub fn list_page_author(&self) -> Result<Arc<[PageAuthorModel]>, Report<CmsPageServiceError>> {
         self.cms_repository
-            .list_page()
+            .list_page_author()
             .change_context(CmsPageServiceError::DbError)
     }
 
+    pub fn list_trash_page(&self) -> Result<Arc<[ListPageModel]>, Report<CmsPageServiceError>> {
+        self.cms_repository
+            .list_trash_page()
+            .change_context(CmsPageServiceError::DbError)
+    }
+
+    pub fn list_page_parent_option(
+        &self,
+        page_id: i64,
+        path: &str,
+    ) -> Result<Arc<[PageParentOptionModel]>, Report<CmsPageServiceError>> {
+        self.cms_repository
+            .list_page_parent_option(page_id, path.to_string())
+            .change_context(CmsPageServiceError::DbError)
+    }
+
+    fn page_path(&self, page_id: i64) -> Result<String, Report<CmsPageServiceError>> {
+        self.cms_repository
+            .fetch_page_path(page_id)
+            .change_context(CmsPageServiceError::DbError)?
+            .map(|page_path| page_path.0)
+            .ok_or_else(|| {
+                Report::new(CmsPageServiceError::NotFoundError).attach(StatusCode::NOT_FOUND)
+            })
+    }
+
+    fn resolve_path(
+        &self,
+        parent_id: Option<i64>,
+        slug: &str,
+    ) -> Result<String, Report<CmsPageServiceError>> {
+        let parent_path = match parent_id {
+            Some(parent_id) => Some(self.page_path(parent_id)?),
+            None => None,
+        };
+        Ok(join_path(parent_path.as_deref(), slug))
+    }
+
     pub fn update_page(
         &self,
         page_id: i64,
         amend_page_form: &AmendPageFormValidated,
     ) -> Result<(), Report<CmsPageServiceError>> {
+        if amend_page_form.status == CmsPageStatus::Trash {
+            return Err(
+                Report::new(CmsPageServiceError::InvalidStatus).attach(StatusCode::BAD_REQUEST)
+            );
+        }
+
+        let page_model = self.fetch_page(page_id)?;
+        if amend_page_form.status != page_model.status
+            && !self.status_options().contains(&amend_page_form.status)
+        {
+            return Err(
+                Report::new(CmsPageServiceError::InvalidStatus).attach(StatusCode::FORBIDDEN)
+            );
+        }
+        // an author's edit of a published page only goes live once an editor approves it again.
+        let status = match !self.can_publish() && amend_page_form.status == CmsPageStatus::Published
+        {
+            true => CmsPageStatus::InReview,
+            false => amend_page_form.status,
+        };
+        let (publish_at, unpublish_at) = match self.can_publish() {
+            true => (amend_page_form.publish_at, amend_page_form.unpublish_at),
+            false => (page_model.publish_at, page_model.unpublish_at),
+        };
+
+        let old_path = page_model.path;
+        let path = self.resolve_path(amend_page_form.parent_id, amend_page_form.slug.as_str())?;
+
         self.cms_repository
             .update_page(UpdatePageModel {
                 id: page_id,
+                parent_id: amend_page_form.parent_id,
+                slug: amend_page_form.slug.as_str().to_string(),
+                old_path,
+                path,
                 title: amend_page_form.title.as_str().to_string(),
                 summary: amend_page_form.summary.as_str().to_string(),
-                status: amend_page_form.status,
+                status,
+                publish_at,
+                unpublish_at,
+                revision_author: RevisionAuthorModel::from(&self.user_pointer),
             })
             .change_context(CmsPageServiceError::DbError)
     }
 
+    pub fn trash_page(&self, page_id: i64) -> Result<(), Report<CmsPageServiceError>> {
+        self.fetch_page(page_id)?;
+        self.cms_repository
+            .update_page_status(page_id, CmsPageStatus::Trash)
+            .change_context(CmsPageServiceError::DbError)
+    }
+
+    pub fn restore_page(&self, page_id: i64) -> Result<(), Report<CmsPageServiceError>> {
+        self.fetch_trash_page(page_id)?;
+        self.cms_repository
+            .update_page_status(page_id, CmsPageStatus::Draft)
+            .change_context(CmsPageServiceError::DbError)
+    }
+
+    /// Children would be left with a path under a parent that no longer exists,
+    /// so they have to be moved or purged first.
+    pub fn ensure_purgeable(&self, page_id: i64) -> Result<(), Report<CmsPageServiceError>> {
+        self.fetch_trash_page(page_id)?;
+        let child_count = self
+            .cms_repository
+            .count_child_page(page_id)
+            .change_context(CmsPageServiceError::DbError)?;
+        if child_count > 0 {
+            return Err(Report::new(CmsPageServiceError::HasChildPage).attach(StatusCode::CONFLICT));
+        }
+        Ok(())
+    }
+
+    pub fn purge_page(&self, page_id: i64) -> Result<(), Report<CmsPageServiceError>> {
+        self.ensure_purgeable(page_id)?;
+        self.cms_repository
+            .delete_page(page_id)
+            .change_context(CmsPageServiceError::DbError)
+    }
+
     pub fn update_component_position(
         &self,
         component_position_form: &ComponentPositionForm,
         page_id: i64,
     ) -> Result<(), Report<CmsPageServiceError>> {
-        for (component_id, position) in component_position_form.mapping.iter() {
-            self.cms_repository
-                .update_component_position(UpdateComponentPositionModel {
-                    id: *component_id as i64,
-                    position: *position as i64,
-                    page_id,
-                })
-                .change_context(CmsPageServiceError::DbError)?;
-        }
-        Ok(())
+        let update_component_position_models = component_position_form
+            .mapping
+            .iter()
+            .map(|(component_id, position)| UpdateComponentPositionModel {
+                id: *component_id as i64,
+                position: *position as i64,
+            })
+            .collect();
+        self.cms_repository
+            .update_component_position(
+                page_id,
+                update_component_position_models,
+                RevisionAuthorModel::from(&self.user_pointer),
+            )
+            .change_context(CmsPageServiceError::DbError)
     }
 
     pub fn delete_component(&self, component_id: i64) -> Result<(), Report<CmsPageServiceError>> {
+        let page_id = self
+            .cms_repository
+            .get_page_id_component(component_id)
+            .change_context(CmsPageServiceError::DbError)?;
         self.cms_repository
-            .delete_component(component_id)
-            .change_context(CmsPageServiceError::DbError)
+            .delete_component(component_id, RevisionAuthorModel::from(&self.user_pointer))
+            .change_context(CmsPageServiceError::DbError)?;
+        match page_id {
+            Some(page_id) => self
+                .cms_search_service
+                .index_page(page_id.0)
+                .change_context(CmsPageServiceError::DbError),
+            None => Ok(()),
+        }
     }
 
     pub fn list_component(
@@ -110,8 +304,140 @@ impl CmsPageService {
     }
 }
 
+impl IsSlugTakenAsync for CmsPageService {
+    async fn is_slug_taken_async(
+        &self,
+        slug: &str,
+        parent_id: Option<i64>,
+        page_id: Option<i64>,
+    ) -> bool {
+        let Ok(path) = self.resolve_path(parent_id, slug) else {
+            return false;
+        };
+        self.cms_repository
+            .path_taken(path, page_id.unwrap_or_default())
+            .ok()
+            .unwrap_or_default()
+    }
+}
+
+impl IsParentValidAsync for CmsPageService {
+    async fn is_parent_valid_async(&self, parent_id: i64, page_id: i64) -> bool {
+        let Ok(path) = self.page_path(page_id) else {
+            return false;
+        };
+        self.list_page_parent_option(page_id, &path)
+            .map(|parent_options| parent_options.iter().any(|parent| parent.id == parent_id))
+            .unwrap_or_default()
+    }
+}
+
 impl FromContext for CmsPageService {
     async fn from_context(ctx: &'_ Context<'_>) -> Result<Self, Report<ContextError>> {
-        Ok(Self::new(ctx.inject().await?, ctx.inject().await?))
+        Ok(Self::new(
+            ctx.inject().await?,
+            ctx.inject().await?,
+            ctx.inject().await?,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cms::rules::summary_rule::SummaryRuleExt;
+    use crate::cms::rules::title_rule::TitleRuleExt;
+    use cjtoolkit_structured_validator::types::description::description_alias::Summary;
+    use cjtoolkit_structured_validator::types::name::name_alias::{Field, FieldRules, Title};
+    use mry::Any;
+    use shared::cms::repository::search_cms_repository::SearchCmsRepository;
+    use shared::utils::config::sqlite::SqliteConfig;
+    use shared::utils::db::SqliteClient;
+
+    fn published_page() -> FetchPageModel {
+        FetchPageModel {
+            id: 1,
+            parent_id: None,
+            slug: "about".to_string(),
+            path: "about".to_string(),
+            title: "About".to_string(),
+            summary: "".to_string(),
+            status: CmsPageStatus::Published,
+            publish_at: None,
+            unpublish_at: None,
+            terms: Arc::new([]),
+        }
+    }
+
+    fn amend_page_form(status: CmsPageStatus) -> AmendPageFormValidated {
+        AmendPageFormValidated {
+            title: Title::parse_title(Some("About us")).unwrap(),
+            summary: Summary::parse_summary(Some("Who we are")).unwrap(),
+            status,
+            slug: Field::parse_custom(
+                Some("about"),
+                FieldRules {
+                    is_mandatory: true,
+                    min_length: Some(1),
+                    max_length: Some(60),
+                },
+            )
+            .unwrap(),
+            parent_id: None,
+            publish_at: None,
+            unpublish_at: None,
+        }
+    }
+
+    fn update_published_page_as(role: Role) -> CmsPageStatus {
+        let mut cms_repository = CmsRepository::new_mock();
+        cms_repository
+            .mock_fetch_page(Any)
+            .returns_once(Ok(Some(published_page())));
+        let saved_status = Arc::new(std::sync::Mutex::new(None));
+        let saved_status_mock = saved_status.clone();
+        cms_repository.mock_update_page(Any).returns_with(
+            move |update_page_model: UpdatePageModel| {
+                *saved_status_mock.lock().unwrap() = Some(update_page_model.status);
+                Ok(())
+            },
+        );
+        let sqlite_client: SqliteClient = SqliteClient::new(&SqliteConfig {
+            path: ":memory:".to_string(),
+            pool_size: 1,
+            busy_timeout_ms: 5000,
+        })
+        .unwrap();
+        let cms_search_service = CmsSearchService::new(
+            CmsRepository::new_mock(),
+            SearchCmsRepository::new(sqlite_client),
+        );
+
+        let service = CmsPageService::new(
+            cms_repository,
+            cms_search_service,
+            UserPointer::new_mock(2, role),
+        );
+        service
+            .update_page(1, &amend_page_form(CmsPageStatus::Published))
+            .unwrap();
+
+        saved_status.lock().unwrap().unwrap()
+    }
+
+    #[test]
+    fn test_update_page_by_author_sends_published_page_back_to_review() {
+        assert_eq!(
+            update_published_page_as(Role::User),
+            CmsPageStatus::InReview
+        );
+    }
+
+    #[test]
+    fn test_update_page_by_editor_keeps_page_published() {
+        assert_eq!(
+            update_published_page_as(Role::Editor),
+            CmsPageStatus::Published
+        );
     }
 }
@@ -41,7 +41,7 @@ impl CmsPermissionCheckService {
                 Report::new(CmsPermissionCheckServiceError::NotFound).attach(StatusCode::NOT_FOUND)
             })?;
 
-        if self.user_pointer.role == Role::Root {
+        if self.user_pointer.role >= Role::Editor {
             return Ok(());
         }
 
@@ -65,7 +65,7 @@ impl CmsPermissionCheckService {
                 Report::new(CmsPermissionCheckServiceError::NotFound).attach(StatusCode::NOT_FOUND)
             })?;
 
-        if self.user_pointer.role == Role::Root {
+        if self.user_pointer.role >= Role::Editor {
             return Ok(());
         }
 
@@ -0,0 +1,242 @@
+use crate::cms::data_model::cms_data::{AddReviewModel, ListPendingReviewModel, ListReviewModel};
+use crate::cms::enums::ReviewDecision;
+use crate::cms::form::review_page_form::ReviewPageFormValidated;
+use crate::cms::repository::cms_repository::CmsRepository;
+use crate::user::pointer::user_pointer::UserPointer;
+use crate::user::role::Role;
+use chrono::{DateTime, Utc};
+use error_stack::{Report, ResultExt};
+use poem::http::StatusCode;
+use shared::cms::status::CmsPageStatus;
+use shared::utils::context::{Context, ContextError, FromContext};
+use std::sync::Arc;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum CmsReviewServiceError {
+    #[error("Db error")]
+    DbError,
+    #[error("Not found error")]
+    NotFound,
+    #[error("No permission")]
+    NoPermission,
+    #[error("Page is not in review")]
+    NotInReview,
+}
+
+pub struct CmsReviewService {
+    cms_repository: CmsRepository,
+    user_pointer: UserPointer,
+}
+
+impl CmsReviewService {
+    pub fn new(cms_repository: CmsRepository, user_pointer: UserPointer) -> Self {
+        Self {
+            cms_repository,
+            user_pointer,
+        }
+    }
+
+    pub fn can_review(&self) -> bool {
+        self.user_pointer.role >= Role::Editor
+    }
+
+    pub fn list_review(
+        &self,
+        page_id: i64,
+    ) -> Result<Arc<[ListReviewModel]>, Report<CmsReviewServiceError>> {
+        self.cms_repository
+            .list_review(page_id)
+            .change_context(CmsReviewServiceError::DbError)
+    }
+
+    /// Reviewers see every page awaiting a decision, authors only their own.
+    pub fn list_pending_review(
+        &self,
+    ) -> Result<Arc<[ListPendingReviewModel]>, Report<CmsReviewServiceError>> {
+        let user_id = match self.can_review() {
+            true => None,
+            false => Some(self.user_pointer.id),
+        };
+        self.cms_repository
+            .list_pending_review(user_id)
+            .change_context(CmsReviewServiceError::DbError)
+    }
+
+    pub fn review_page(
+        &self,
+        page_id: i64,
+        review_page_form: &ReviewPageFormValidated,
+    ) -> Result<CmsPageStatus, Report<CmsReviewServiceError>> {
+        if !self.can_review() {
+            return Err(
+                Report::new(CmsReviewServiceError::NoPermission).attach(StatusCode::FORBIDDEN)
+            );
+        }
+
+        let page_model = self
+            .cms_repository
+            .fetch_page(page_id)
+            .change_context(CmsReviewServiceError::DbError)?
+            .ok_or_else(|| {
+                Report::new(CmsReviewServiceError::NotFound).attach(StatusCode::NOT_FOUND)
+            })?;
+        if page_model.status != CmsPageStatus::InReview {
+            return Err(
+                Report::new(CmsReviewServiceError::NotInReview).attach(StatusCode::CONFLICT)
+            );
+        }
+
+        let status = approved_status(review_page_form.decision, page_model.publish_at, Utc::now());
+        self.cms_repository
+            .add_review(AddReviewModel {
+                page_id,
+                reviewer_id: self.user_pointer.id,
+                status,
+                comment: review_page_form.comment.as_str().to_string(),
+            })
+            .change_context(CmsReviewServiceError::DbError)?;
+
+        Ok(status)
+    }
+}
+
+/// An approved page waiting on its publish date stays a draft, the schedule publishes it when due.
+fn approved_status(
+    decision: ReviewDecision,
+    publish_at: Option<DateTime<Utc>>,
+    now: DateTime<Utc>,
+) -> CmsPageStatus {
+    match decision {
+        ReviewDecision::Approve if publish_at.is_some_and(|publish_at| publish_at > now) => {
+            CmsPageStatus::Draft
+        }
+        ReviewDecision::Approve => CmsPageStatus::Published,
+        ReviewDecision::Reject => CmsPageStatus::ChangesRequested,
+    }
+}
+
+impl FromContext for CmsReviewService {
+    async fn from_context(ctx: &'_ Context<'_>) -> Result<Self, Report<ContextError>> {
+        Ok(Self::new(ctx.inject().await?, ctx.inject().await?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cms::data_model::cms_data::FetchPageModel;
+    use crate::cms::rules::review_rule::ReviewCommentRulesExt;
+    use chrono::Duration;
+    use cjtoolkit_structured_validator::types::description::description_alias::Text;
+    use mry::Any;
+
+    fn page_model(status: CmsPageStatus, publish_at: Option<DateTime<Utc>>) -> FetchPageModel {
+        FetchPageModel {
+            id: 1,
+            parent_id: None,
+            slug: "about".to_string(),
+            path: "about".to_string(),
+            title: "About".to_string(),
+            summary: "".to_string(),
+            status,
+            publish_at,
+            unpublish_at: None,
+            terms: Arc::new([]),
+        }
+    }
+
+    fn review_form(decision: ReviewDecision) -> ReviewPageFormValidated {
+        ReviewPageFormValidated {
+            decision,
+            comment: Text::parse_review_comment(Some("Looks good"), false).unwrap(),
+        }
+    }
+
+    fn review_page_with(
+        page_model: FetchPageModel,
+        decision: ReviewDecision,
+    ) -> Result<CmsPageStatus, Report<CmsReviewServiceError>> {
+        let mut cms_repository = CmsRepository::new_mock();
+        cms_repository
+            .mock_fetch_page(Any)
+            .returns_once(Ok(Some(page_model)));
+        cms_repository
+            .mock_add_review(Any)
+            .returns_with(|add_review_model: AddReviewModel| {
+                assert_eq!(add_review_model.page_id, 1);
+                assert_eq!(add_review_model.reviewer_id, 3);
+                Ok(())
+            });
+
+        let service = CmsReviewService::new(cms_repository, UserPointer::new_mock(3, Role::Editor));
+        service.review_page(1, &review_form(decision))
+    }
+
+    #[test]
+    fn test_review_page_approve_publishes() {
+        let status = review_page_with(
+            page_model(CmsPageStatus::InReview, None),
+            ReviewDecision::Approve,
+        )
+        .unwrap();
+        assert_eq!(status, CmsPageStatus::Published);
+    }
+
+    #[test]
+    fn test_review_page_approve_keeps_scheduled_page_as_draft() {
+        let publish_at = Utc::now() + Duration::days(1);
+        let status = review_page_with(
+            page_model(CmsPageStatus::InReview, Some(publish_at)),
+            ReviewDecision::Approve,
+        )
+        .unwrap();
+        assert_eq!(status, CmsPageStatus::Draft);
+    }
+
+    #[test]
+    fn test_review_page_reject_requests_changes() {
+        let status = review_page_with(
+            page_model(CmsPageStatus::InReview, None),
+            ReviewDecision::Reject,
+        )
+        .unwrap();
+        assert_eq!(status, CmsPageStatus::ChangesRequested);
+    }
+
+    #[test]
+    fn test_review_page_without_permission() {
+        let service = CmsReviewService::new(
+            CmsRepository::new_mock(),
+            UserPointer::new_mock(2, Role::User),
+        );
+
+        let result = service.review_page(1, &review_form(ReviewDecision::Approve));
+        let report = result.unwrap_err();
+        assert!(matches!(
+            report.current_context(),
+            CmsReviewServiceError::NoPermission
+        ));
+        assert_eq!(
+            *report.downcast_ref::<StatusCode>().unwrap(),
+            StatusCode::FORBIDDEN
+        );
+    }
+
+    #[test]
+    fn test_review_page_not_in_review() {
+        let result = review_page_with(
+            page_model(CmsPageStatus::Draft, None),
+            ReviewDecision::Approve,
+        );
+        let report = result.unwrap_err();
+        assert!(matches!(
+            report.current_context(),
+            CmsReviewServiceError::NotInReview
+        ));
+        assert_eq!(
+            *report.downcast_ref::<StatusCode>().unwrap(),
+            StatusCode::CONFLICT
+        );
+    }
+}
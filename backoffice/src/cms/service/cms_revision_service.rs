@@ -0,0 +1,205 @@
+use crate::cms::data_model::cms_data::{
+    FetchRevisionModel, ListRevisionModel, RevisionAuthorModel, RevisionComponentModel,
+    RevisionDiffLine,
+};
+use crate::cms::enums::RevisionDiffKind;
+use crate::cms::repository::cms_repository::CmsRepository;
+use crate::cms::service::cms_search_service::CmsSearchService;
+use crate::user::pointer::user_pointer::UserPointer;
+use error_stack::{Report, ResultExt};
+use poem::http::StatusCode;
+use shared::cms::components::markdown::MarkdownComponent;
+use shared::cms::markers::ComponentInfoMarker;
+use shared::utils::context::{Context, ContextError, FromContext};
+use similar::{ChangeTag, TextDiff};
+use std::sync::Arc;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum CmsRevisionServiceError {
+    #[error("Db error")]
+    DbError,
+    #[error("Not found error")]
+    NotFoundError,
+}
+
+pub struct CmsRevisionService {
+    cms_repository: CmsRepository,
+    cms_search_service: CmsSearchService,
+    user_pointer: UserPointer,
+}
+
+impl CmsRevisionService {
+    pub fn new(
+        cms_repository: CmsRepository,
+        cms_search_service: CmsSearchService,
+        user_pointer: UserPointer,
+    ) -> Self {
+        Self {
+            cms_repository,
+            cms_search_service,
+            user_pointer,
+        }
+    }
+
+    pub fn list_revision(
+        &self,
+        page_id: i64,
+    ) -> Result<Arc<[ListRevisionModel]>, Report<CmsRevisionServiceError>> {
+        self.cms_repository
+            .list_revision(page_id)
+            .change_context(CmsRevisionServiceError::DbError)
+    }
+
+    fn fetch_revision(
+        &self,
+        page_id: i64,
+        revision_id: i64,
+    ) -> Result<FetchRevisionModel, Report<CmsRevisionServiceError>> {
+        self.cms_repository
+            .fetch_revision(revision_id)
+            .change_context(CmsRevisionServiceError::DbError)?
+            .filter(|revision| revision.page_id == page_id)
+            .ok_or_else(|| {
+                Report::new(CmsRevisionServiceError::NotFoundError).attach(StatusCode::NOT_FOUND)
+            })
+    }
+
+    fn revision_markdown(
+        &self,
+        page_id: i64,
+        revision_id: i64,
+    ) -> Result<String, Report<CmsRevisionServiceError>> {
+        let revision = self.fetch_revision(page_id, revision_id)?;
+        let component_list = self
+            .cms_repository
+            .list_revision_component(revision.id)
+            .change_context(CmsRevisionServiceError::DbError)?;
+        Ok(revision_as_markdown(&revision, &component_list))
+    }
+
+    pub fn diff_revision(
+        &self,
+        page_id: i64,
+        from_revision_id: i64,
+        to_revision_id: i64,
+    ) -> Result<Vec<RevisionDiffLine>, Report<CmsRevisionServiceError>> {
+        let from = self.revision_markdown(page_id, from_revision_id)?;
+        let to = self.revision_markdown(page_id, to_revision_id)?;
+        Ok(diff_lines(&from, &to))
+    }
+
+    pub fn restore_revision(
+        &self,
+        page_id: i64,
+        revision_id: i64,
+    ) -> Result<(), Report<CmsRevisionServiceError>> {
+        self.fetch_revision(page_id, revision_id)?;
+        self.cms_repository
+            .restore_revision(
+                page_id,
+                revision_id,
+                RevisionAuthorModel::from(&self.user_pointer),
+            )
+            .change_context(CmsRevisionServiceError::DbError)?;
+        self.cms_search_service
+            .index_page(page_id)
+            .change_context(CmsRevisionServiceError::DbError)
+    }
+}
+
+/// Flattens a revision into one markdown document, only markdown components carry text worth diffing.
+fn revision_as_markdown(
+    revision: &FetchRevisionModel,
+    component_list: &[RevisionComponentModel],
+) -> String {
+    let markdown_kind_uuid = MarkdownComponent::component_info().kind_uuid;
+    let mut document = format!("# {}\n\n{}\n", revision.title, revision.summary);
+    for component in component_list
+        .iter()
+        .filter(|component| component.kind_uuid == markdown_kind_uuid)
+    {
+        let markdown = MarkdownComponent::from(component.raw_data.clone());
+        document.push_str(&format!(
+            "\n## {}\n\n{}\n",
+            component.label, markdown.content
+        ));
+    }
+    document
+}
+
+fn diff_lines(from: &str, to: &str) -> Vec<RevisionDiffLine> {
+    TextDiff::from_lines(from, to)
+        .iter_all_changes()
+        .map(|change| RevisionDiffLine {
+            kind: match change.tag() {
+                ChangeTag::Equal => RevisionDiffKind::Equal,
+                ChangeTag::Insert => RevisionDiffKind::Insert,
+                ChangeTag::Delete => RevisionDiffKind::Delete,
+            },
+            line: change.value().trim_end_matches('\n').to_string(),
+        })
+        .collect()
+}
+
+impl FromContext for CmsRevisionService {
+    async fn from_context(ctx: &'_ Context<'_>) -> Result<Self, Report<ContextError>> {
+        Ok(Self::new(
+            ctx.inject().await?,
+            ctx.inject().await?,
+            ctx.inject().await?,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use shared::cms::markers::ComponentDataMarker;
+
+    fn revision(title: &str) -> FetchRevisionModel {
+        FetchRevisionModel {
+            id: 1,
+            page_id: 1,
+            title: title.to_string(),
+            summary: "summary".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_revision_as_markdown_skips_other_components() {
+        let component_list = [
+            RevisionComponentModel {
+                kind_uuid: MarkdownComponent::component_info().kind_uuid,
+                label: "Intro".to_string(),
+                raw_data: MarkdownComponent::new("Hello".to_string()).into_data(),
+            },
+            RevisionComponentModel {
+                kind_uuid: "file-attachments".to_string(),
+                label: "Files".to_string(),
+                raw_data: vec![],
+            },
+        ];
+
+        let document = revision_as_markdown(&revision("Title"), &component_list);
+
+        assert_eq!(document, "# Title\n\nsummary\n\n## Intro\n\nHello\n");
+    }
+
+    #[test]
+    fn test_diff_lines() {
+        let diff = diff_lines("a\nb\n", "a\nc\n");
+
+        let kinds: Vec<_> = diff.iter().map(|line| line.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                RevisionDiffKind::Equal,
+                RevisionDiffKind::Delete,
+                RevisionDiffKind::Insert
+            ]
+        );
+        assert_eq!(diff[1].line, "b");
+        assert_eq!(diff[2].line, "c");
+    }
+}
@@ -0,0 +1,55 @@
+use crate::cms::repository::cms_repository::CmsRepository;
+use error_stack::{Report, ResultExt};
+use poem::http::StatusCode;
+use shared::cms::repository::search_cms_repository::SearchCmsRepository;
+use shared::cms::search::index_page_body;
+use shared::utils::context::{Context, ContextError, FromContext};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum CmsSearchServiceError {
+    #[error("Db error")]
+    DbError,
+    #[error("Not found error")]
+    NotFoundError,
+}
+
+/// Title and summary are kept in the index by triggers, this keeps the text of the components.
+pub struct CmsSearchService {
+    cms_repository: CmsRepository,
+    search_cms_repository: SearchCmsRepository,
+}
+
+impl CmsSearchService {
+    pub fn new(cms_repository: CmsRepository, search_cms_repository: SearchCmsRepository) -> Self {
+        Self {
+            cms_repository,
+            search_cms_repository,
+        }
+    }
+
+    pub fn index_page(&self, page_id: i64) -> Result<(), Report<CmsSearchServiceError>> {
+        index_page_body(&self.search_cms_repository, page_id)
+            .change_context(CmsSearchServiceError::DbError)
+    }
+
+    pub fn index_component_page(
+        &self,
+        component_id: i64,
+    ) -> Result<(), Report<CmsSearchServiceError>> {
+        let page_id = self
+            .cms_repository
+            .get_page_id_component(component_id)
+            .change_context(CmsSearchServiceError::DbError)?
+            .ok_or_else(|| {
+                Report::new(CmsSearchServiceError::NotFoundError).attach(StatusCode::NOT_FOUND)
+            })?;
+        self.index_page(page_id.0)
+    }
+}
+
+impl FromContext for CmsSearchService {
+    async fn from_context(ctx: &'_ Context<'_>) -> Result<Self, Report<ContextError>> {
+        Ok(Self::new(ctx.inject().await?, ctx.inject().await?))
+    }
+}
@@ -0,0 +1,88 @@
+use crate::cms::data_model::cms_data::{AddPageTermModel, PageTermModel};
+use crate::cms::form::page_term_form::PageTermFormValidated;
+use crate::cms::repository::cms_repository::CmsRepository;
+use error_stack::{Report, ResultExt};
+use shared::cms::slug::slugify;
+use shared::cms::taxonomy::TermKind;
+use shared::utils::context::{Context, ContextError, FromContext};
+use std::sync::Arc;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum CmsTermServiceError {
+    #[error("Db error")]
+    DbError,
+}
+
+const SUGGESTION_LIMIT: i64 = 10;
+
+pub struct CmsTermService {
+    cms_repository: CmsRepository,
+}
+
+impl CmsTermService {
+    pub fn new(cms_repository: CmsRepository) -> Self {
+        Self { cms_repository }
+    }
+
+    /// A name that slugs to an existing term of the same kind joins that term.
+    pub fn add_page_term(
+        &self,
+        page_id: i64,
+        page_term_form: &PageTermFormValidated,
+    ) -> Result<(), Report<CmsTermServiceError>> {
+        self.cms_repository
+            .add_page_term(AddPageTermModel {
+                page_id,
+                kind: page_term_form.kind,
+                slug: slugify(page_term_form.name.as_str()),
+                name: page_term_form.name.as_str().to_string(),
+            })
+            .change_context(CmsTermServiceError::DbError)
+    }
+
+    pub fn delete_page_term(
+        &self,
+        page_id: i64,
+        term_id: i64,
+    ) -> Result<(), Report<CmsTermServiceError>> {
+        self.cms_repository
+            .delete_page_term(page_id, term_id)
+            .change_context(CmsTermServiceError::DbError)
+    }
+
+    pub fn list_page_term(
+        &self,
+        page_id: i64,
+    ) -> Result<Arc<[PageTermModel]>, Report<CmsTermServiceError>> {
+        self.cms_repository
+            .list_page_term(page_id)
+            .change_context(CmsTermServiceError::DbError)
+    }
+
+    pub fn list_term(&self) -> Result<Arc<[PageTermModel]>, Report<CmsTermServiceError>> {
+        self.cms_repository
+            .list_term()
+            .change_context(CmsTermServiceError::DbError)
+    }
+
+    pub fn suggest_term(
+        &self,
+        kind: TermKind,
+        name: &str,
+    ) -> Result<Arc<[PageTermModel]>, Report<CmsTermServiceError>> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Ok(Arc::new([]));
+        }
+        self.cms_repository
+            .list_term_suggestion(kind, name, SUGGESTION_LIMIT)
+            .change_context(CmsTermServiceError::DbError)
+    }
+}
+
+impl FromContext for CmsTermService {
+    async fn from_context(ctx: &'_ Context<'_>) -> Result<Self, Report<ContextError>> {
+        Ok(Self::new(ctx.inject().await?))
+    }
+}
@@ -1,9 +1,10 @@
 use crate::cms::data_model::cms_data::{
-    CreateComponentModel, FetchComponentModel, UpdateComponentModel,
+    CreateComponentModel, FetchComponentModel, RevisionAuthorModel, UpdateComponentModel,
 };
 use crate::cms::form::component_form::common_label_form::{CommonFormValidated, CommonLabelForm};
 use crate::cms::query_model::{CreateQuery, UpdateFetchQuery};
 use crate::cms::repository::cms_repository::CmsRepository;
+use crate::user::pointer::user_pointer::UserPointer;
 use error_stack::{Report, ResultExt};
 use shared::cms::CmsComponentInfo;
 use shared::utils::context::{Context, ContextError, FromContext};
@@ -20,13 +21,19 @@ pub enum CommonLabelServiceError {
 pub struct CommonLabelService {
     cms_repository: CmsRepository,
     pub cms_component_info: CmsComponentInfo,
+    user_pointer: UserPointer,
 }
 
 impl CommonLabelService {
-    pub fn new(cms_repository: CmsRepository, cms_component_info: CmsComponentInfo) -> Self {
+    pub fn new(
+        cms_repository: CmsRepository,
+        cms_component_info: CmsComponentInfo,
+        user_pointer: UserPointer,
+    ) -> Self {
         Self {
             cms_repository,
             cms_component_info,
+            user_pointer,
         }
     }
 
@@ -40,6 +47,7 @@ impl CommonLabelService {
             kind_uuid: self.cms_component_info.kind_uuid.clone(),
             raw_data: vec![],
             label: common_label_form.label.clone(),
+            revision_author: RevisionAuthorModel::from(&self.user_pointer),
         };
 
         let returning_id = self
@@ -76,6 +84,7 @@ impl CommonLabelService {
                 id: query.id as i64,
                 label: form.label.as_str().to_string(),
                 raw_data: vec![],
+                revision_author: RevisionAuthorModel::from(&self.user_pointer),
             })
             .change_context(CommonLabelServiceError::DbError)?;
 
@@ -85,6 +94,10 @@ impl CommonLabelService {
 
 impl FromContext for CommonLabelService {
     async fn from_context(ctx: &'_ Context<'_>) -> Result<Self, Report<ContextError>> {
-        Ok(Self::new(ctx.inject().await?, ctx.inject().await?))
+        Ok(Self::new(
+            ctx.inject().await?,
+            ctx.inject().await?,
+            ctx.inject().await?,
+        ))
     }
 }
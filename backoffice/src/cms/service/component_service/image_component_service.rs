@@ -0,0 +1,467 @@
+use crate::cms::data_model::cms_data::{
+    CreateComponentModel, FetchComponentModel, RevisionAuthorModel, UpdateComponentModel,
+};
+use crate::cms::form::component_form::image_form::ImageFormValidated;
+use crate::cms::query_model::{CreateQuery, UpdateFetchQuery};
+use crate::cms::repository::cms_repository::CmsRepository;
+use crate::cms::rules::upload_rule::UploadRejection;
+use crate::cms::service::cms_attachment_service::{CmsAttachmentService, ReceivedFile};
+use crate::user::pointer::user_pointer::UserPointer;
+use error_stack::{Report, ResultExt};
+use image::codecs::jpeg::JpegEncoder;
+use image::imageops::FilterType;
+use image::{DynamicImage, ImageResult};
+use poem::web::Field;
+use shared::cms::CmsComponentInfo;
+use shared::cms::components::image::{ImageComponent, ImageVariant};
+use shared::cms::markers::ComponentDataMarker;
+use shared::utils::context::{Context, ContextError, FromContext};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum ImageComponentServiceError {
+    #[error("db error")]
+    DbError,
+    #[error("not found error")]
+    NotFound,
+    #[error("encode error")]
+    EncodeError,
+    #[error("save error")]
+    SaveError,
+}
+
+const VARIANT_WIDTHS: [u32; 3] = [480, 960, 1600];
+const JPEG_QUALITY: u8 = 82;
+
+struct EncodedVariant {
+    width: u32,
+    height: u32,
+    file_type: &'static str,
+    extension: &'static str,
+    content: Vec<u8>,
+}
+
+/// Every preset narrower than the source, plus the source itself capped at the widest preset, never upscaled.
+fn variant_widths(width: u32) -> Vec<u32> {
+    let max_width = width.min(VARIANT_WIDTHS[VARIANT_WIDTHS.len() - 1]);
+    let mut widths: Vec<u32> = VARIANT_WIDTHS
+        .iter()
+        .copied()
+        .filter(|variant_width| *variant_width < max_width)
+        .collect();
+    widths.push(max_width);
+    widths
+}
+
+/// JPEG only until a lossy webp encoder is at hand, the lossless one comes out heavier than the JPEG.
+fn encode_variants(image: &DynamicImage) -> ImageResult<Vec<EncodedVariant>> {
+    let mut variants = Vec::new();
+    for width in variant_widths(image.width()) {
+        let resized = match width == image.width() {
+            true => image.clone(),
+            false => image.resize(width, u32::MAX, FilterType::Lanczos3),
+        };
+
+        let mut jpeg = Vec::new();
+        DynamicImage::ImageRgb8(resized.to_rgb8())
+            .write_with_encoder(JpegEncoder::new_with_quality(&mut jpeg, JPEG_QUALITY))?;
+        variants.push(EncodedVariant {
+            width: resized.width(),
+            height: resized.height(),
+            file_type: "image/jpeg",
+            extension: "jpg",
+            content: jpeg,
+        });
+    }
+    Ok(variants)
+}
+
+fn file_stem(file_name: &str) -> &str {
+    let stem = file_name
+        .rsplit_once('.')
+        .map(|(stem, _)| stem)
+        .unwrap_or(file_name);
+    if stem.is_empty() { "image" } else { stem }
+}
+
+pub struct ImageComponentService {
+    cms_repository: CmsRepository,
+    cms_attachment_service: CmsAttachmentService,
+    pub cms_component_info: CmsComponentInfo,
+    user_pointer: UserPointer,
+}
+
+impl ImageComponentService {
+    pub fn new(
+        cms_repository: CmsRepository,
+        cms_attachment_service: CmsAttachmentService,
+        cms_component_info: CmsComponentInfo,
+        user_pointer: UserPointer,
+    ) -> Self {
+        Self {
+            cms_repository,
+            cms_attachment_service,
+            cms_component_info,
+            user_pointer,
+        }
+    }
+
+    pub fn create_component(
+        &self,
+        query: &CreateQuery,
+        label: String,
+    ) -> Result<UpdateFetchQuery, Report<ImageComponentServiceError>> {
+        let create_component_model = CreateComponentModel {
+            page_id: query.page_id as i64,
+            kind_uuid: self.cms_component_info.kind_uuid.clone(),
+            raw_data: ImageComponent::default().into_data(),
+            label,
+            revision_author: RevisionAuthorModel::from(&self.user_pointer),
+        };
+
+        let returning_id = self
+            .cms_repository
+            .create_component(create_component_model)
+            .change_context(ImageComponentServiceError::DbError)?;
+
+        Ok(UpdateFetchQuery::new(
+            self.cms_component_info.kind_uuid.clone(),
+            returning_id.0 as u64,
+        ))
+    }
+
+    pub fn fetch_component(
+        &self,
+        query: &UpdateFetchQuery,
+    ) -> Result<FetchComponentModel<ImageComponent>, Report<ImageComponentServiceError>> {
+        let component_data = self
+            .cms_repository
+            .fetch_component(query.id as i64)
+            .change_context(ImageComponentServiceError::DbError)?
+            .ok_or_else(|| {
+                Report::new(ImageComponentServiceError::NotFound)
+                    .attach(poem::http::StatusCode::NOT_FOUND)
+            })?;
+
+        Ok(FetchComponentModel {
+            label: component_data.label,
+            position: component_data.position,
+            raw_data: component_data.raw_data.into(),
+        })
+    }
+
+    pub fn update_component(
+        &self,
+        query: &UpdateFetchQuery,
+        form: &ImageFormValidated,
+    ) -> Result<(), Report<ImageComponentServiceError>> {
+        let mut image_component = self.fetch_component(query)?.raw_data;
+        image_component.alt = form.alt.as_str().to_string();
+        image_component.caption = form.caption.as_str().to_string();
+
+        self.cms_repository
+            .update_component(UpdateComponentModel {
+                id: query.id as i64,
+                label: form.label.as_str().to_string(),
+                raw_data: image_component.into_data(),
+                revision_author: RevisionAuthorModel::from(&self.user_pointer),
+            })
+            .change_context(ImageComponentServiceError::DbError)?;
+
+        Ok(())
+    }
+
+    pub async fn receive_image(
+        &self,
+        field: Field,
+    ) -> Result<Result<ReceivedFile, UploadRejection>, Report<ImageComponentServiceError>> {
+        self.cms_attachment_service
+            .receive_file(field, ImageComponent::ALLOWED_FILE_TYPES)
+            .await
+            .change_context(ImageComponentServiceError::SaveError)
+    }
+
+    /// Replaces the component's variants with freshly encoded ones. The files are written first,
+    /// then the attachments and the component are swapped in one transaction, the previous files
+    /// stay on disk for as long as a revision points at them.
+    pub fn upload_image(
+        &self,
+        query: &UpdateFetchQuery,
+        file_name: &str,
+        image: &DynamicImage,
+    ) -> Result<ImageComponent, Report<ImageComponentServiceError>> {
+        let component_model = self.fetch_component(query)?;
+        let encoded_variants = encode_variants(image)
+            .change_context(ImageComponentServiceError::EncodeError)
+            .attach(poem::http::StatusCode::UNPROCESSABLE_ENTITY)?;
+
+        let old_file_paths = self
+            .cms_attachment_service
+            .list_file_path_by_component_id(query.id as i64)
+            .change_context(ImageComponentServiceError::SaveError)?;
+
+        let stem = file_stem(file_name);
+        let mut variants = Vec::with_capacity(encoded_variants.len());
+        let mut add_file_attachment_models = Vec::with_capacity(encoded_variants.len());
+        for variant in encoded_variants {
+            let add_file_attachment_model = self
+                .cms_attachment_service
+                .store_file_content(
+                    query.id as i64,
+                    format!("{}-{}w.{}", stem, variant.width, variant.extension),
+                    variant.file_type.to_string(),
+                    &variant.content,
+                )
+                .change_context(ImageComponentServiceError::SaveError)?;
+            variants.push(ImageVariant {
+                width: variant.width,
+                height: variant.height,
+                file_type: variant.file_type.to_string(),
+                file_path: add_file_attachment_model.file_path.clone(),
+            });
+            add_file_attachment_models.push(add_file_attachment_model);
+        }
+
+        let image_component = ImageComponent {
+            width: image.width(),
+            height: image.height(),
+            variants,
+            ..component_model.raw_data
+        };
+
+        self.cms_repository
+            .update_component_file(
+                UpdateComponentModel {
+                    id: query.id as i64,
+                    label: component_model.label,
+                    raw_data: image_component.clone().into_data(),
+                    revision_author: RevisionAuthorModel::from(&self.user_pointer),
+                },
+                add_file_attachment_models,
+            )
+            .change_context(ImageComponentServiceError::DbError)?;
+
+        self.cms_attachment_service
+            .remove_unreferenced_files(&old_file_paths)
+            .change_context(ImageComponentServiceError::SaveError)?;
+
+        Ok(image_component)
+    }
+}
+
+impl FromContext for ImageComponentService {
+    async fn from_context(ctx: &'_ Context<'_>) -> Result<Self, Report<ContextError>> {
+        Ok(Self::new(
+            ctx.inject().await?,
+            ctx.inject().await?,
+            ctx.inject().await?,
+            ctx.inject().await?,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::user::pointer::user_pointer::UserPointer;
+    use crate::user::role::Role;
+    use image::{Rgb, RgbImage};
+    use sha2::{Digest, Sha256};
+    use shared::cms::file_path::disk_path;
+    use shared::cms::markers::ComponentInfoMarker;
+    use shared::utils::config::sqlite::SqliteConfig;
+    use shared::utils::db::{BorrowConnectionExt, SqliteClient};
+    use std::path::Path;
+    use uuid::Uuid;
+
+    #[test]
+    fn test_variant_widths_never_upscale() {
+        assert_eq!(variant_widths(300), vec![300]);
+        assert_eq!(variant_widths(960), vec![480, 960]);
+        assert_eq!(variant_widths(1200), vec![480, 960, 1200]);
+        assert_eq!(variant_widths(4000), vec![480, 960, 1600]);
+    }
+
+    #[test]
+    fn test_encode_variants_keeps_aspect_ratio() {
+        let image = DynamicImage::ImageRgb8(RgbImage::new(1000, 500));
+
+        let variants = encode_variants(&image).unwrap();
+
+        let sizes: Vec<(u32, u32, &str)> = variants
+            .iter()
+            .map(|variant| (variant.width, variant.height, variant.file_type))
+            .collect();
+        assert_eq!(
+            sizes,
+            vec![
+                (480, 240, "image/jpeg"),
+                (960, 480, "image/jpeg"),
+                (1000, 500, "image/jpeg"),
+            ]
+        );
+        assert!(variants.iter().all(|variant| !variant.content.is_empty()));
+    }
+
+    #[test]
+    fn test_file_stem() {
+        assert_eq!(file_stem("photo.final.png"), "photo.final");
+        assert_eq!(file_stem("photo"), "photo");
+        assert_eq!(file_stem(".png"), "image");
+    }
+
+    fn service_with_page(root: &Path) -> (ImageComponentService, SqliteClient) {
+        std::fs::create_dir_all(root).unwrap();
+        let sqlite_client: SqliteClient = SqliteClient::new(&SqliteConfig {
+            path: root.join("sqlite.db").to_string_lossy().to_string(),
+            pool_size: 1,
+            busy_timeout_ms: 5000,
+        })
+        .unwrap();
+        sqlite_client
+            .borrow_conn()
+            .unwrap()
+            .execute_batch(
+                "insert into backoffice_users (id, username, password, role) values (1, 'a', x'00', 'root');
+                 insert into cms_page (id, user_id, title, summary, status, slug, path)
+                    values (1, 1, 't', 's', 'published', 'page', 'page');",
+            )
+            .unwrap();
+        let image_component_service = ImageComponentService::new(
+            CmsRepository::new(sqlite_client.clone()),
+            CmsAttachmentService::new(
+                root.to_string_lossy().to_string(),
+                10,
+                10,
+                CmsRepository::new(sqlite_client.clone()),
+                UserPointer::new_mock(1, Role::Root),
+            ),
+            ImageComponent::component_info(),
+            UserPointer::new_mock(1, Role::Root),
+        );
+        (image_component_service, sqlite_client)
+    }
+
+    fn create_image_component(image_component_service: &ImageComponentService) -> UpdateFetchQuery {
+        image_component_service
+            .create_component(
+                &CreateQuery {
+                    kind_uuid: ImageComponent::component_info().kind_uuid,
+                    page_id: 1,
+                },
+                "Image".to_string(),
+            )
+            .unwrap()
+    }
+
+    fn attachment_paths(sqlite_client: &SqliteClient, component_id: i64) -> Vec<String> {
+        CmsRepository::new(sqlite_client.clone())
+            .list_file_attachment(component_id)
+            .unwrap()
+            .iter()
+            .map(|attachment| attachment.file_path.clone())
+            .collect()
+    }
+
+    fn variant_paths(image_component: &ImageComponent) -> Vec<String> {
+        image_component
+            .variants
+            .iter()
+            .map(|variant| variant.file_path.clone())
+            .collect()
+    }
+
+    #[test]
+    fn test_restore_keeps_files_of_replaced_image() {
+        let root = std::env::temp_dir().join(format!("rusty-cms-{}", Uuid::new_v4()));
+        let (image_component_service, sqlite_client) = service_with_page(&root);
+        let cms_repository = CmsRepository::new(sqlite_client.clone());
+        let latest_revision_id = || -> i64 {
+            sqlite_client
+                .borrow_conn()
+                .unwrap()
+                .query_row("select max(id) from cms_page_revision", [], |row| {
+                    row.get(0)
+                })
+                .unwrap()
+        };
+
+        let query = create_image_component(&image_component_service);
+        let first = image_component_service
+            .upload_image(
+                &query,
+                "first.png",
+                &DynamicImage::ImageRgb8(RgbImage::new(300, 200)),
+            )
+            .unwrap();
+        let first_revision_id = latest_revision_id();
+        let second = image_component_service
+            .upload_image(
+                &query,
+                "second.png",
+                &DynamicImage::ImageRgb8(RgbImage::from_pixel(200, 200, Rgb([255, 255, 255]))),
+            )
+            .unwrap();
+        cms_repository
+            .restore_revision(
+                1,
+                first_revision_id,
+                RevisionAuthorModel {
+                    user_id: 1,
+                    can_publish: true,
+                },
+            )
+            .unwrap();
+
+        let restored = image_component_service.fetch_component(&query).unwrap();
+        assert_eq!(restored.raw_data.variants, first.variants);
+        assert_eq!(
+            attachment_paths(&sqlite_client, query.id as i64),
+            variant_paths(&first)
+        );
+        for variant in first.variants.iter().chain(second.variants.iter()) {
+            assert!(disk_path(&root.to_string_lossy(), &variant.file_path).is_file());
+        }
+        std::fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn test_failed_upload_keeps_current_image() {
+        let root = std::env::temp_dir().join(format!("rusty-cms-{}", Uuid::new_v4()));
+        let (image_component_service, sqlite_client) = service_with_page(&root);
+        let query = create_image_component(&image_component_service);
+        let first = image_component_service
+            .upload_image(
+                &query,
+                "first.png",
+                &DynamicImage::ImageRgb8(RgbImage::new(300, 200)),
+            )
+            .unwrap();
+
+        // a file where the last variant's folder has to go makes that write fail.
+        let second_image =
+            DynamicImage::ImageRgb8(RgbImage::from_pixel(600, 400, Rgb([255, 255, 255])));
+        let second_variants = encode_variants(&second_image).unwrap();
+        let last_hash = format!(
+            "{:x}",
+            Sha256::digest(&second_variants[second_variants.len() - 1].content)
+        );
+        let blocked_dir = root.join("cms/files").join(&last_hash[..2]);
+        assert!(!blocked_dir.exists());
+        std::fs::write(&blocked_dir, b"").unwrap();
+
+        let result = image_component_service.upload_image(&query, "second.png", &second_image);
+
+        assert!(result.is_err());
+        let current = image_component_service.fetch_component(&query).unwrap();
+        assert_eq!(current.raw_data.variants, first.variants);
+        assert_eq!(
+            attachment_paths(&sqlite_client, query.id as i64),
+            variant_paths(&first)
+        );
+        for variant in first.variants.iter() {
+            assert!(disk_path(&root.to_string_lossy(), &variant.file_path).is_file());
+        }
+        std::fs::remove_dir_all(root).unwrap();
+    }
+}
@@ -1,12 +1,16 @@
 use crate::cms::data_model::cms_data::{
-    CreateComponentModel, FetchComponentModel, UpdateComponentModel,
+    CreateComponentModel, FetchComponentModel, RevisionAuthorModel, UpdateComponentModel,
+};
+use crate::cms::form::component_form::markdown_form::{
+    MarkdownForm, MarkdownFormValidated, MarkdownPreviewForm,
 };
-use crate::cms::form::component_form::markdown_form::{MarkdownForm, MarkdownFormValidated};
 use crate::cms::query_model::{CreateQuery, UpdateFetchQuery};
 use crate::cms::repository::cms_repository::CmsRepository;
+use crate::cms::service::cms_search_service::CmsSearchService;
+use crate::user::pointer::user_pointer::UserPointer;
 use error_stack::{Report, ResultExt};
 use shared::cms::CmsComponentInfo;
-use shared::cms::components::markdown::MarkdownComponent;
+use shared::cms::components::markdown::{MarkdownComponent, render_markdown};
 use shared::cms::markers::ComponentDataMarker;
 use shared::utils::context::{Context, ContextError, FromContext};
 use thiserror::Error;
@@ -21,14 +25,23 @@ pub enum MarkdownComponentServiceError {
 
 pub struct MarkdownComponentService {
     cms_repository: CmsRepository,
+    cms_search_service: CmsSearchService,
     pub cms_component_info: CmsComponentInfo,
+    user_pointer: UserPointer,
 }
 
 impl MarkdownComponentService {
-    pub fn new(cms_repository: CmsRepository, cms_component_info: CmsComponentInfo) -> Self {
+    pub fn new(
+        cms_repository: CmsRepository,
+        cms_search_service: CmsSearchService,
+        cms_component_info: CmsComponentInfo,
+        user_pointer: UserPointer,
+    ) -> Self {
         Self {
             cms_repository,
+            cms_search_service,
             cms_component_info,
+            user_pointer,
         }
     }
 
@@ -40,18 +53,18 @@ impl MarkdownComponentService {
         let create_component_model = CreateComponentModel {
             page_id: query.page_id as i64,
             kind_uuid: self.cms_component_info.kind_uuid.clone(),
-            raw_data: MarkdownComponent {
-                content: markdown_form.markdown.clone(),
-                parse_html: markdown_form.markdown.clone(),
-            }
-            .into_data(),
+            raw_data: MarkdownComponent::new(markdown_form.markdown.clone()).into_data(),
             label: markdown_form.label.clone(),
+            revision_author: RevisionAuthorModel::from(&self.user_pointer),
         };
 
         let returning_id = self
             .cms_repository
             .create_component(create_component_model)
             .change_context(MarkdownComponentServiceError::DbError)?;
+        self.cms_search_service
+            .index_page(query.page_id as i64)
+            .change_context(MarkdownComponentServiceError::DbError)?;
 
         Ok(UpdateFetchQuery::new(
             self.cms_component_info.kind_uuid.clone(),
@@ -88,20 +101,29 @@ impl MarkdownComponentService {
             .update_component(UpdateComponentModel {
                 id: query.id as i64,
                 label: form.label.as_str().to_string(),
-                raw_data: MarkdownComponent {
-                    content: form.markdown.as_str().to_string(),
-                    parse_html: form.markdown.as_str().to_string(),
-                }
-                .into_data(),
+                raw_data: MarkdownComponent::new(form.markdown.as_str().to_string()).into_data(),
+                revision_author: RevisionAuthorModel::from(&self.user_pointer),
             })
             .change_context(MarkdownComponentServiceError::DbError)?;
+        self.cms_search_service
+            .index_component_page(query.id as i64)
+            .change_context(MarkdownComponentServiceError::DbError)?;
 
         Ok(())
     }
+
+    pub fn preview(&self, form: &MarkdownPreviewForm) -> String {
+        render_markdown(form.markdown.as_str())
+    }
 }
 
 impl FromContext for MarkdownComponentService {
     async fn from_context(ctx: &'_ Context<'_>) -> Result<Self, Report<ContextError>> {
-        Ok(Self::new(ctx.inject().await?, ctx.inject().await?))
+        Ok(Self::new(
+            ctx.inject().await?,
+            ctx.inject().await?,
+            ctx.inject().await?,
+            ctx.inject().await?,
+        ))
     }
 }
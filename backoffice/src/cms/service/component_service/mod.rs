@@ -1,2 +1,3 @@
 pub mod common_label_service;
+pub mod image_component_service;
 pub mod markdown_component_service;
@@ -1,4 +1,8 @@
 pub mod cms_attachment_service;
 pub mod cms_page_service;
 pub mod cms_permission_check_service;
+pub mod cms_review_service;
+pub mod cms_revision_service;
+pub mod cms_search_service;
+pub mod cms_term_service;
 pub mod component_service;
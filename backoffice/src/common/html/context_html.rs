@@ -1,11 +1,14 @@
 use crate::common::html::HtmlBuilder;
 use crate::common::html::locale::top::TopBuildLocale;
 use crate::common::icon::{
-    exclamation_circle_icon, home_icon, newspaper_icon, user_minus_icon, users_icon,
+    computer_desktop_icon, exclamation_circle_icon, home_icon, key_icon, newspaper_icon,
+    shield_check_icon, user_minus_icon, users_icon,
 };
 use crate::user::pointer::user_pointer::UserPointer;
 use crate::user::role::Role;
-use crate::user::route::login::LOGIN_ROUTE;
+use crate::user::route::login::{CHANGE_PASSWORD_PATH, LOGIN_ROUTE};
+use crate::user::route::session::SESSION_PATH;
+use crate::user::route::two_factor::TWO_FACTOR_PATH;
 use crate::user::route::user::USER_ROUTE;
 use error_stack::Report;
 use maud::{Markup, PreEscaped, html};
@@ -285,6 +288,18 @@ impl ContextHtmlBuilder {
                 @if user_context.role >= Role::User {
                     a href=(USER_ROUTE.to_owned() + "/")
                         hx-push-url="true" hx-target="#main-content" hx-boost="true" { (top_build_locale.hello) }
+                    a class="mt-1.5!" href=(LOGIN_ROUTE.to_owned() + CHANGE_PASSWORD_PATH)
+                        hx-push-url="true" hx-target="#main-content" hx-boost="true" {
+                        span .icon title=(top_build_locale.hello_password) { (key_icon()) }
+                    }
+                    a class="mt-1.5!" href=(LOGIN_ROUTE.to_owned() + TWO_FACTOR_PATH + "/")
+                        hx-push-url="true" hx-target="#main-content" hx-boost="true" {
+                        span .icon title=(top_build_locale.hello_two_factor) { (shield_check_icon()) }
+                    }
+                    a class="mt-1.5!" href=(LOGIN_ROUTE.to_owned() + SESSION_PATH + "/")
+                        hx-push-url="true" hx-target="#main-content" hx-boost="true" {
+                        span .icon title=(top_build_locale.hello_sessions) { (computer_desktop_icon()) }
+                    }
                     a class="mt-1.5!" href=(LOGIN_ROUTE.to_owned() + "/logout") {
                         span .icon title=(top_build_locale.hello_logout) { (user_minus_icon()) }
                     }
@@ -4,6 +4,9 @@ use shared::utils::locale::LocaleExt;
 pub struct TopBuildLocale {
     pub hello: String,
     pub hello_logout: String,
+    pub hello_password: String,
+    pub hello_two_factor: String,
+    pub hello_sessions: String,
     pub visitor: String,
 }
 
@@ -16,6 +19,10 @@ impl TopBuildLocale {
                 I18NArgs::from((("username", username),)),
             ),
             hello_logout: locale.text_with_default("top-hello-logout", "Click here to logout"),
+            hello_password: locale.text_with_default("top-hello-password", "Change your password"),
+            hello_two_factor: locale
+                .text_with_default("top-hello-two-factor", "Two-factor authentication"),
+            hello_sessions: locale.text_with_default("top-hello-sessions", "Active sessions"),
             visitor: locale
                 .text_with_default("top-visitor", "You're a visitor, click here to login"),
         }
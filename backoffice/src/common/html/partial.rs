@@ -1,5 +1,9 @@
 use maud::{Markup, html};
+use poem::i18n::Locale;
+use serde::Serialize;
 use shared::utils::flash::Flash;
+use shared::utils::locale::LocaleExt;
+use shared::utils::query_string::list_query::{KeysetPage, ListQuery, SortKey, SortOrder};
 
 pub fn flash_partial(flash: Flash) -> Markup {
     html! {
@@ -18,3 +22,46 @@ pub fn command_list_partial(commands: Vec<Markup>) -> Markup {
         }
     }
 }
+
+/// A column heading linking to the list sorted by `sort`, flipping the order when it already is.
+pub fn sort_heading_partial<F: Serialize, S: SortKey>(
+    path: &str,
+    list_query: &ListQuery<F, S>,
+    sort: S,
+    label: &str,
+) -> Markup {
+    html! {
+        a href=(list_query.sort_uri(path, sort)) hx-boost="true" hx-target="#main-content" hx-push-url="true" {
+            (label)
+            @if list_query.sort == sort {
+                @match list_query.order {
+                    SortOrder::Asc => " ▲",
+                    SortOrder::Desc => " ▼",
+                }
+            }
+        }
+    }
+}
+
+/// Previous and next links of a keyset page, the list state stays in the url.
+pub fn pagination_partial<F: Serialize, S: SortKey, T>(
+    locale: &Locale,
+    path: &str,
+    list_query: &ListQuery<F, S>,
+    page: &KeysetPage<T>,
+) -> Markup {
+    html! {
+        @if page.prev.is_some() || page.next.is_some() {
+            div .text-right .mt-3 {
+                @if let Some(prev) = page.prev.as_ref() {
+                    a .btn .btn-sky-blue .inline-block href=(list_query.page_uri(path, None, Some(prev)))
+                        hx-boost="true" hx-target="#main-content" hx-push-url="true" { (locale.text_with_default("top-list-previous", "Previous")) }
+                }
+                @if let Some(next) = page.next.as_ref() {
+                    a .btn .btn-sky-blue .inline-block .ml-2 href=(list_query.page_uri(path, Some(next), None))
+                        hx-boost="true" hx-target="#main-content" hx-push-url="true" { (locale.text_with_default("top-list-next", "Next")) }
+                }
+            }
+        }
+    }
+}
@@ -23,7 +23,6 @@ pub fn flag_icon() -> Markup {
     get_icon("icon/flag.svg")
 }
 
-#[allow(dead_code)]
 pub fn trash_icon() -> Markup {
     get_icon("icon/trash.svg")
 }
@@ -66,3 +65,15 @@ pub fn arrow_path_icon() -> Markup {
 pub fn minus_icon() -> Markup {
     get_icon("icon/minus.svg")
 }
+
+pub fn shield_check_icon() -> Markup {
+    get_icon("icon/shield_check.svg")
+}
+
+pub fn lock_open_icon() -> Markup {
+    get_icon("icon/lock_open.svg")
+}
+
+pub fn computer_desktop_icon() -> Markup {
+    get_icon("icon/computer_desktop.svg")
+}
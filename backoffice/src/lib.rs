@@ -12,6 +12,7 @@ use crate::stack::route::stack::{STACK_ROUTE, stack_route};
 use crate::user::role::user_role_check::{must_be_root, must_be_user};
 use crate::user::role::visitor_only::visitor_redirect;
 use crate::user::route::login::login_route;
+use crate::user::route::setup::{SETUP_ROUTE, setup_route};
 use crate::user::route::user::{USER_ROUTE, user_route};
 use error_stack::{Report, ResultExt};
 use poem::endpoint::StaticFilesEndpoint;
@@ -28,8 +29,15 @@ use shared::utils::request_cache::init_request_cache;
 use user::route::login::LOGIN_ROUTE;
 
 pub mod export {
+    pub use crate::user::session::{hash_legacy_login_token, run_session_purge};
+    pub use shared::cms::file_gc::run_file_gc;
+    pub use shared::cms::schedule::run_schedule;
+    pub use shared::cms::search::backfill_search_index;
+    pub use shared::utils::db::init_database;
+    pub use shared::utils::db::migration::print_migration_status;
     pub use shared::utils::error::boot_error::MainError;
     pub use shared::utils::log::init_log;
+    pub use shared::utils::password::print_password_benchmark;
 }
 
 pub const FILES_ROUTE: &str = "/files";
@@ -43,6 +51,7 @@ pub async fn boot() -> Result<(), Report<MainError>> {
 
     let route = route
         .nest(LOGIN_ROUTE, login_route())
+        .nest(SETUP_ROUTE, setup_route())
         .nest(USER_ROUTE, visitor_redirect(user_route()))
         .nest(CSRF_PATH, route_csrf())
         .nest(STACK_ROUTE, visitor_redirect(must_be_root(stack_route())))
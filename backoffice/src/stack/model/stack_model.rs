@@ -1,4 +1,6 @@
 use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use shared::utils::query_string::list_query::{ListQuery, SortKey};
 
 pub struct StackModel {
     #[allow(dead_code)]
@@ -15,3 +17,29 @@ pub struct ListStackModel {
     pub error_summary: String,
     pub reported_at: DateTime<Utc>,
 }
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Default)]
+#[serde(default)]
+pub struct StackFilter {
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub name: String,
+}
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum StackSort {
+    #[default]
+    Id,
+    Name,
+}
+
+impl SortKey for StackSort {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::Id => "id",
+            Self::Name => "name",
+        }
+    }
+}
+
+pub type ListStackQuery = ListQuery<StackFilter, StackSort>;
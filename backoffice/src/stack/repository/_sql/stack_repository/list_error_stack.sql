@@ -1,4 +1,16 @@
-select id, error_name, error_summary, reported_at
+select id,
+       error_name,
+       error_summary,
+       reported_at,
+       case :sort when 'name' then error_name else '' end as sort_key
 from error_stack
 where reported_at > datetime('now', '-30 day')
-order by id desc
\ No newline at end of file
+  and (:name = '' or instr(error_name, :name) > 0)
+  and (:cursor_id is null
+    or (:scan_desc and (sort_key, id) < (:cursor_key, :cursor_id))
+    or (not :scan_desc and (sort_key, id) > (:cursor_key, :cursor_id)))
+order by case when :scan_desc then sort_key end desc,
+         case when :scan_desc then id end desc,
+         sort_key,
+         id
+limit :limit
\ No newline at end of file
@@ -1,10 +1,10 @@
-use crate::stack::model::stack_model::{ListStackModel, StackModel};
+use crate::stack::model::stack_model::{ListStackModel, ListStackQuery, StackModel};
 use error_stack::{Report, ResultExt};
 use poem::http::StatusCode;
-use rusqlite::{Connection, OptionalExtension, named_params};
+use rusqlite::{OptionalExtension, named_params};
 use shared::utils::context::{Context, ContextError, FromContext};
-use shared::utils::db::{BorrowConnectionExt, SqliteClient};
-use std::sync::{Arc, MutexGuard};
+use shared::utils::db::{BorrowConnectionExt, SqliteClient, SqliteConn, run_blocking};
+use shared::utils::query_string::list_query::{Cursor, KeysetPage, SortKey};
 use thiserror::Error;
 
 #[derive(Debug, Error)]
@@ -30,7 +30,7 @@ impl StackRepository {
         }
     }
 
-    fn borrow_conn(&'_ self) -> Result<MutexGuard<'_, Connection>, Report<StackRepositoryError>> {
+    fn borrow_conn(&'_ self) -> Result<SqliteConn, Report<StackRepositoryError>> {
         self.sqlite_client
             .borrow_conn()
             .change_context(StackRepositoryError::BorrowConnError)
@@ -40,75 +40,101 @@ impl StackRepository {
 #[mry::mry]
 impl StackRepository {
     pub fn clear(&self) -> Result<(), Report<StackRepositoryError>> {
-        let conn = self.borrow_conn()?;
-        conn.execute(
-            include_str!("_sql/stack_repository/clear.sql"),
-            named_params! {},
-        )
-        .change_context(StackRepositoryError::QueryError)
-        .attach(StatusCode::INTERNAL_SERVER_ERROR)?;
-        Ok(())
+        run_blocking(|| {
+            let conn = self.borrow_conn()?;
+            conn.execute(
+                include_str!("_sql/stack_repository/clear.sql"),
+                named_params! {},
+            )
+            .change_context(StackRepositoryError::QueryError)
+            .attach(StatusCode::INTERNAL_SERVER_ERROR)?;
+            Ok(())
+        })
     }
 
     pub fn fetch_error_stack(
         &self,
         id: i64,
     ) -> Result<Option<StackModel>, Report<StackRepositoryError>> {
-        let conn = self.borrow_conn()?;
-
-        let mut stmt = conn
-            .prepare(include_str!("_sql/stack_repository/fetch_error_stack.sql"))
-            .change_context(StackRepositoryError::QueryError)
-            .attach(StatusCode::INTERNAL_SERVER_ERROR)?;
-
-        let row = stmt
-            .query_one(
-                named_params! {
-                    ":id": id
-                },
-                |row| {
-                    Ok(StackModel {
-                        id: row.get("id")?,
-                        error_name: row.get("error_name")?,
-                        error_summary: row.get("error_summary")?,
-                        error_stack: row.get("error_stack")?,
-                        reported_at: row.get("reported_at")?,
-                    })
-                },
-            )
-            .optional()
-            .change_context(StackRepositoryError::QueryError)
-            .attach(StatusCode::INTERNAL_SERVER_ERROR)?;
-
-        Ok(row)
+        run_blocking(|| {
+            let conn = self.borrow_conn()?;
+
+            let mut stmt = conn
+                .prepare(include_str!("_sql/stack_repository/fetch_error_stack.sql"))
+                .change_context(StackRepositoryError::QueryError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)?;
+
+            let row = stmt
+                .query_one(
+                    named_params! {
+                        ":id": id
+                    },
+                    |row| {
+                        Ok(StackModel {
+                            id: row.get("id")?,
+                            error_name: row.get("error_name")?,
+                            error_summary: row.get("error_summary")?,
+                            error_stack: row.get("error_stack")?,
+                            reported_at: row.get("reported_at")?,
+                        })
+                    },
+                )
+                .optional()
+                .change_context(StackRepositoryError::QueryError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)?;
+
+            Ok(row)
+        })
     }
 
-    pub fn list_error_stack(&self) -> Result<Arc<[ListStackModel]>, Report<StackRepositoryError>> {
-        let conn = self.borrow_conn()?;
-
-        let mut stmt = conn
-            .prepare(include_str!("_sql/stack_repository/list_error_stack.sql"))
-            .change_context(StackRepositoryError::QueryError)
-            .attach(StatusCode::INTERNAL_SERVER_ERROR)?;
-
-        let rows_iter = stmt
-            .query_map(named_params! {}, |row| {
-                Ok(ListStackModel {
-                    id: row.get("id")?,
-                    error_name: row.get("error_name")?,
-                    error_summary: row.get("error_summary")?,
-                    reported_at: row.get("reported_at")?,
-                })
-            })
-            .change_context(StackRepositoryError::QueryError)
-            .attach(StatusCode::INTERNAL_SERVER_ERROR)?;
-
-        let items = rows_iter
-            .collect::<Result<Vec<_>, _>>()
-            .change_context(StackRepositoryError::RowValueError)
-            .attach(StatusCode::INTERNAL_SERVER_ERROR)?;
-
-        Ok(items.into())
+    pub fn list_error_stack(
+        &self,
+        list_query: &ListStackQuery,
+    ) -> Result<KeysetPage<ListStackModel>, Report<StackRepositoryError>> {
+        run_blocking(|| {
+            let conn = self.borrow_conn()?;
+
+            let mut stmt = conn
+                .prepare(include_str!("_sql/stack_repository/list_error_stack.sql"))
+                .change_context(StackRepositoryError::QueryError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)?;
+
+            let cursor = list_query.cursor();
+            let rows_iter = stmt
+                .query_map(
+                    named_params! {
+                        ":name": list_query.filter.name.trim(),
+                        ":sort": list_query.sort.as_str(),
+                        ":scan_desc": list_query.scan_desc(),
+                        ":cursor_key": cursor.map(|cursor| cursor.key.as_str()),
+                        ":cursor_id": cursor.map(|cursor| cursor.id),
+                        ":limit": list_query.fetch_limit(),
+                    },
+                    |row| {
+                        Ok((
+                            ListStackModel {
+                                id: row.get("id")?,
+                                error_name: row.get("error_name")?,
+                                error_summary: row.get("error_summary")?,
+                                reported_at: row.get("reported_at")?,
+                            },
+                            Cursor {
+                                key: row.get("sort_key")?,
+                                id: row.get("id")?,
+                            },
+                        ))
+                    },
+                )
+                .change_context(StackRepositoryError::QueryError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)?;
+
+            let items = rows_iter
+                .collect::<Result<Vec<_>, _>>()
+                .change_context(StackRepositoryError::RowValueError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)?;
+
+            Ok(KeysetPage::from_rows(list_query, items))
+        })
     }
 }
 
@@ -0,0 +1,122 @@
+use crate::common::html::context_html::ContextHtmlBuilder;
+use crate::common::icon::trash_icon;
+use crate::stack::route::locale::stack_locale::{StackFileGcLocale, stack_file_gc_confirm_message};
+use crate::stack::route::stack::STACK_ROUTE;
+use crate::stack::service::file_gc_service::FileGcService;
+use maud::{Markup, html};
+use poem::session::Session;
+use poem::web::{CsrfToken, Redirect};
+use poem::{Response, Route, delete, get, handler};
+use shared::utils::context::Dep;
+use shared::utils::csrf::{CsrfTokenHtml, csrf_header_check_strict};
+use shared::utils::error::FromErrorStack;
+use shared::utils::flash::{Flash, FlashMessage};
+use shared::utils::htmx::HtmxHeader;
+
+pub const FILE_GC_ROUTE: &str = "/files";
+
+#[handler]
+fn list_orphan_files(
+    Dep(file_gc_service): Dep<FileGcService>,
+    Dep(context_html_builder): Dep<ContextHtmlBuilder>,
+    csrf_token: &CsrfToken,
+) -> poem::Result<Markup> {
+    let report = file_gc_service
+        .dry_run()
+        .map_err(poem::Error::from_error_stack)?;
+
+    let lc = StackFileGcLocale::new(&context_html_builder.locale);
+    let title = lc.title.as_str();
+    let clean_path = format!("{}{}/clean", STACK_ROUTE, FILE_GC_ROUTE);
+
+    Ok(context_html_builder
+        .attach_title(title)
+        .set_current_tag("id-tag-stack")
+        .attach_content(html! {
+            h1 { (title) }
+            h2 { (lc.head_orphan_files) }
+            @if report.orphan_files.is_empty() {
+                p { (lc.empty) }
+            } @else {
+                table .table-full {
+                    thead {
+                        th { (lc.head_path) }
+                        th { (lc.head_size) }
+                    }
+                    tbody {
+                        @for orphan_file in report.orphan_files.iter() {
+                            tr {
+                                td { (orphan_file.file_path) }
+                                td { (orphan_file.size) }
+                            }
+                        }
+                    }
+                }
+            }
+            h2 .mt-5 { (lc.head_missing_files) }
+            @if report.missing_files.is_empty() {
+                p { (lc.empty) }
+            } @else {
+                table .table-full {
+                    thead {
+                        th { (lc.head_id) }
+                        th { (lc.head_component) }
+                        th { (lc.head_name) }
+                        th { (lc.head_path) }
+                    }
+                    tbody {
+                        @for missing_file in report.missing_files.iter() {
+                            tr {
+                                td { (missing_file.id) }
+                                td { (missing_file.component_id) }
+                                td { (missing_file.file_name) }
+                                td { (missing_file.file_path) }
+                            }
+                        }
+                    }
+                }
+            }
+            @if !report.orphan_files.is_empty() || !report.missing_files.is_empty() {
+                div .text-right .mt-3 {
+                    a .inline-block .cursor-pointer hx-confirm=(stack_file_gc_confirm_message(&context_html_builder.locale))
+                    title=(lc.action_clean) hx-delete=(clean_path) { (trash_icon()) }
+                }
+            }
+        })
+        .attach_footer(html! {
+            (csrf_token.as_html_command())
+        })
+        .build())
+}
+
+#[handler]
+fn clean(
+    Dep(file_gc_service): Dep<FileGcService>,
+    session: &Session,
+    htmx_header: HtmxHeader,
+) -> poem::Result<Response> {
+    let report = file_gc_service
+        .clean()
+        .map_err(poem::Error::from_error_stack)?;
+
+    let msg = format!(
+        "Removed {} file(s) and {} attachment(s)",
+        report.removed_files, report.removed_rows
+    );
+    session.flash(match report.failures.is_empty() {
+        true => Flash::Success { msg },
+        false => Flash::Warning {
+            msg: format!("{}, could not remove {}", msg, report.failures.join(", ")),
+        },
+    });
+    Ok(htmx_header.do_location(
+        Redirect::see_other(format!("{}{}/", STACK_ROUTE, FILE_GC_ROUTE)),
+        "#main-content",
+    ))
+}
+
+pub fn file_gc_route() -> Route {
+    Route::new()
+        .at("/", get(list_orphan_files))
+        .at("/clean", delete(csrf_header_check_strict(clean)))
+}
@@ -8,8 +8,10 @@ pub struct StackLocale {
     pub head_summary: String,
     pub head_reported: String,
     pub head_action: String,
+    pub filter_name: String,
     pub action_details: String,
     pub action_clear: String,
+    pub action_files: String,
 }
 
 impl StackLocale {
@@ -22,6 +24,8 @@ impl StackLocale {
             head_reported: l
                 .text_with_default("stack-list-error-stack-head-reported", "Reported At"),
             head_action: l.text_with_default("stack-list-error-stack-head-action", "Action"),
+            filter_name: l
+                .text_with_default("stack-list-error-stack-filter-name", "Filter by name"),
             action_details: l.text_with_default(
                 "stack-list-error-stack-action-details",
                 "View Error Details",
@@ -30,6 +34,8 @@ impl StackLocale {
                 "stack-list-error-stack-action-clear",
                 "Clear Older than 30 days",
             ),
+            action_files: l
+                .text_with_default("stack-list-error-stack-action-files", "Orphaned Files"),
         }
     }
 }
@@ -64,3 +70,46 @@ pub fn stack_clear_confirm_message(l: &Locale) -> String {
         "Are you sure you want to clear all error stacks older than 30 days?",
     )
 }
+
+pub struct StackFileGcLocale {
+    pub title: String,
+    pub head_orphan_files: String,
+    pub head_missing_files: String,
+    pub head_id: String,
+    pub head_component: String,
+    pub head_name: String,
+    pub head_path: String,
+    pub head_size: String,
+    pub empty: String,
+    pub action_clean: String,
+}
+
+impl StackFileGcLocale {
+    pub fn new(l: &Locale) -> Self {
+        Self {
+            title: l.text_with_default("stack-file-gc-title", "Orphaned Files"),
+            head_orphan_files: l.text_with_default(
+                "stack-file-gc-head-orphan-files",
+                "Files without an attachment",
+            ),
+            head_missing_files: l.text_with_default(
+                "stack-file-gc-head-missing-files",
+                "Attachments without a file",
+            ),
+            head_id: l.text_with_default("stack-file-gc-head-id", "ID"),
+            head_component: l.text_with_default("stack-file-gc-head-component", "Component"),
+            head_name: l.text_with_default("stack-file-gc-head-name", "File Name"),
+            head_path: l.text_with_default("stack-file-gc-head-path", "Path"),
+            head_size: l.text_with_default("stack-file-gc-head-size", "Size"),
+            empty: l.text_with_default("stack-file-gc-empty", "Nothing found"),
+            action_clean: l.text_with_default("stack-file-gc-action-clean", "Remove all listed"),
+        }
+    }
+}
+
+pub fn stack_file_gc_confirm_message(l: &Locale) -> String {
+    l.text_with_default(
+        "stack-file-gc-confirm-message",
+        "Are you sure you want to remove the listed files and attachments?",
+    )
+}
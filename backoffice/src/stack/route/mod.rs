@@ -1,2 +1,3 @@
+pub mod file_gc;
 pub mod locale;
 pub mod stack;
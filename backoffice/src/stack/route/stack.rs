@@ -1,5 +1,8 @@
 use crate::common::html::context_html::ContextHtmlBuilder;
+use crate::common::html::partial::{pagination_partial, sort_heading_partial};
 use crate::common::icon::{document_magnifying_glass_icon, no_symbol_icon};
+use crate::stack::model::stack_model::{ListStackQuery, StackSort};
+use crate::stack::route::file_gc::{FILE_GC_ROUTE, file_gc_route};
 use crate::stack::route::locale::stack_locale::{
     StackFetchLocale, StackLocale, stack_clear_confirm_message,
 };
@@ -8,21 +11,28 @@ use maud::{Markup, html};
 use poem::session::Session;
 use poem::web::{CsrfToken, Path, Redirect};
 use poem::{Response, Route, get, handler};
+use serde_qs::Config;
 use shared::utils::context::Dep;
 use shared::utils::csrf::CsrfTokenHtml;
 use shared::utils::error::FromErrorStack;
 use shared::utils::flash::{Flash, FlashMessage};
 use shared::utils::htmx::HtmxHeader;
+use shared::utils::locale::LocaleExt;
+use shared::utils::query_string::list_query::SortKey;
+use shared::utils::query_string::query::QueryQs;
+use shared::utils::query_string::serde_qs_config::with_serde_qs_config;
 
 pub const STACK_ROUTE: &str = "/stack";
 
 #[handler]
 fn list_error_stack(
+    QueryQs(query): QueryQs<ListStackQuery>,
     Dep(stack_service): Dep<StackService>,
     Dep(context_html_builder): Dep<ContextHtmlBuilder>,
     csrf_token: &CsrfToken,
 ) -> Markup {
-    let error_stack_list = stack_service.list_error_stack();
+    let error_stack_list = stack_service.list_error_stack(&query);
+    let list_path = format!("{}/", STACK_ROUTE);
     let open_icon = document_magnifying_glass_icon();
     let clear_icon = no_symbol_icon();
 
@@ -34,16 +44,28 @@ fn list_error_stack(
         .set_current_tag("id-tag-stack")
         .attach_content(html! {
             h1 { (title) }
+            form .form hx-boost="true" hx-target="#main-content" hx-push-url="true" method="get"
+                action=(list_path) {
+                input type="hidden" name="sort" value=(query.sort.as_str());
+                input type="hidden" name="order" value=(query.order.as_str());
+                div .form-group {
+                    input .form-item type="search" name="filter[name]" value=(query.filter.name)
+                        placeholder=(lc.filter_name);
+                    " "
+                    button .btn .btn-sky-blue .cursor-pointer type="submit" {
+                        (context_html_builder.locale.text_with_default("top-list-filter", "Filter")) }
+                }
+            }
             table .table-full {
                 thead {
-                    th { (lc.head_id) }
-                    th { (lc.head_name) }
+                    th { (sort_heading_partial(&list_path, &query, StackSort::Id, &lc.head_id)) }
+                    th { (sort_heading_partial(&list_path, &query, StackSort::Name, &lc.head_name)) }
                     th { (lc.head_summary) }
                     th { (lc.head_reported) }
                     th .action { (lc.head_action) }
                 }
                 tbody {
-                    @for error_stack in error_stack_list.iter() {
+                    @for error_stack in error_stack_list.items.iter() {
                         tr {
                             td { (error_stack.id) }
                             td { (error_stack.error_name) }
@@ -58,8 +80,11 @@ fn list_error_stack(
                     }
                 }
             }
+            (pagination_partial(&context_html_builder.locale, &list_path, &query, &error_stack_list))
             div .text-right .mt-3 {
-                a .inline-block hx-confirm=(stack_clear_confirm_message(&context_html_builder.locale)) href=(format!("{}/clear", STACK_ROUTE))
+                a .inline-block href=(format!("{}{}/", STACK_ROUTE, FILE_GC_ROUTE)) title=(lc.action_files)
+                    hx-boost="true" hx-push-url="true" hx-target="#main-content" { (open_icon) }
+                a .inline-block .ml-2 hx-confirm=(stack_clear_confirm_message(&context_html_builder.locale)) href=(format!("{}/clear", STACK_ROUTE))
                 title=(lc.action_clear) hx-delete=(format!("{}/clear", STACK_ROUTE)) { (clear_icon) }
             }
         })
@@ -117,7 +142,14 @@ fn clear(
 
 pub fn stack_route() -> Route {
     Route::new()
-        .at("/", get(list_error_stack))
+        .at(
+            "/",
+            get(with_serde_qs_config(
+                Config::default().use_form_encoding(true),
+                list_error_stack,
+            )),
+        )
         .at("/view/:view_id", get(fetch_error_stack_detail))
         .at("/clear", get(clear).delete(clear))
+        .nest(FILE_GC_ROUTE, file_gc_route())
 }
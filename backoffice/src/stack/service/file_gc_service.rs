@@ -0,0 +1,50 @@
+use error_stack::{Report, ResultExt};
+use poem::http::StatusCode;
+use shared::cms::file_gc::{FileGcReport, reconcile_files};
+use shared::cms::repository::file_gc_cms_repository::FileGcCmsRepository;
+use shared::utils::config::ConfigPointer;
+use shared::utils::context::{Context, ContextError, FromContext};
+use shared::utils::error::ExtraResultExt;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum FileGcServiceError {
+    #[error("Reconcile error")]
+    ReconcileError,
+}
+
+pub struct FileGcService {
+    file_upload_path: String,
+    file_gc_cms_repository: FileGcCmsRepository,
+}
+
+impl FileGcService {
+    pub fn new(file_upload_path: String, file_gc_cms_repository: FileGcCmsRepository) -> Self {
+        Self {
+            file_upload_path,
+            file_gc_cms_repository,
+        }
+    }
+
+    pub fn clean(&self) -> Result<FileGcReport, Report<FileGcServiceError>> {
+        self.reconcile(true)
+    }
+
+    pub fn dry_run(&self) -> Result<FileGcReport, Report<FileGcServiceError>> {
+        self.reconcile(false)
+    }
+
+    fn reconcile(&self, delete: bool) -> Result<FileGcReport, Report<FileGcServiceError>> {
+        reconcile_files(&self.file_gc_cms_repository, &self.file_upload_path, delete)
+            .change_context(FileGcServiceError::ReconcileError)
+            .attach(StatusCode::INTERNAL_SERVER_ERROR)
+            .log_it()
+    }
+}
+
+impl FromContext for FileGcService {
+    async fn from_context(ctx: &'_ Context<'_>) -> Result<Self, Report<ContextError>> {
+        let config: ConfigPointer = ctx.inject().await?;
+        Ok(Self::new(config.file_path.clone(), ctx.inject().await?))
+    }
+}
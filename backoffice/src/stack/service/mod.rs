@@ -1 +1,2 @@
+pub mod file_gc_service;
 pub mod stack_service;
@@ -1,10 +1,10 @@
-use crate::stack::model::stack_model::{ListStackModel, StackModel};
+use crate::stack::model::stack_model::{ListStackModel, ListStackQuery, StackModel};
 use crate::stack::repository::stack_repository::StackRepository;
 use error_stack::{Report, ResultExt};
 use poem::http::StatusCode;
 use shared::utils::context::{Context, ContextError, FromContext};
 use shared::utils::error::ExtraResultExt;
-use std::sync::Arc;
+use shared::utils::query_string::list_query::KeysetPage;
 use thiserror::Error;
 
 #[derive(Debug, Error)]
@@ -39,8 +39,10 @@ impl StackService {
             .ok_or_else(|| Report::new(StackServiceError::NotFound).attach(StatusCode::NOT_FOUND))
     }
 
-    pub fn list_error_stack(&self) -> Arc<[ListStackModel]> {
-        self.stack_repository.list_error_stack().unwrap_or_default()
+    pub fn list_error_stack(&self, list_query: &ListStackQuery) -> KeysetPage<ListStackModel> {
+        self.stack_repository
+            .list_error_stack(list_query)
+            .unwrap_or_default()
     }
 }
 
@@ -20,6 +20,8 @@ pub struct AddUserForm {
     pub password: String,
     pub password_confirm: String,
     pub role: Role,
+    #[serde(default)]
+    pub must_change_password: bool,
 }
 
 impl AddUserForm {
@@ -52,6 +54,7 @@ impl AddUserForm {
                     password: password.expect("Password is not empty"),
                     password_confirm: password_confirm.expect("Password Confirm is not empty"),
                     role: self.role.clone(),
+                    must_change_password: self.must_change_password,
                 })
             }
             .await,
@@ -97,6 +100,13 @@ impl AddUserForm {
                             (self.role.html_option())
                         }
                     }
+                    div .form-group {
+                        label .label for="must-change-password" {
+                            input type="checkbox" name="must_change_password" #must-change-password value="true"
+                            checked[self.must_change_password] {}
+                            " " (user_form_locale.must_change_password)
+                        }
+                    }
                     div .form-group {
                         input .btn .btn-sky-blue type="submit" value=(user_form_locale.submit_add) {}
                     }
@@ -112,6 +122,7 @@ pub struct AddUserValidated {
     #[allow(dead_code)]
     pub password_confirm: Password,
     pub role: Role,
+    pub must_change_password: bool,
 }
 
 #[cfg(test)]
@@ -124,6 +135,7 @@ impl AddUserValidated {
             password_confirm: Password::parse(Some("aVHTsh_SEGW5[g_c`/uh>~0!YI0'~fJw"))
                 .expect("test password confirm"),
             role: Default::default(),
+            must_change_password: false,
         }
     }
 }
@@ -14,6 +14,8 @@ use std::sync::Arc;
 pub struct EditPasswordManagerForm {
     pub password: String,
     pub password_confirm: String,
+    #[serde(default)]
+    pub must_change_password: bool,
 }
 
 impl EditPasswordManagerForm {
@@ -39,6 +41,7 @@ impl EditPasswordManagerForm {
                 Ok(EditPasswordManagerValidated {
                     password: password.expect("Password is not empty"),
                     password_confirm: password_confirm.expect("Password Confirm is not empty"),
+                    must_change_password: self.must_change_password,
                 })
             }
             .await,
@@ -75,6 +78,13 @@ impl EditPasswordManagerForm {
                         placeholder=(user_form_locale.password_confirm_placeholder) {}
                         (errors.password_confirm.into_error_html())
                     }
+                    div .form-group {
+                        label .label for="must-change-password" {
+                            input type="checkbox" name="must_change_password" #must-change-password value="true"
+                            checked[self.must_change_password] {}
+                            " " (user_form_locale.must_change_password)
+                        }
+                    }
                     div .form-group {
                         input .btn .btn-sky-blue type="submit" value=(user_form_locale.submit_password) {}
                     }
@@ -88,6 +98,7 @@ pub struct EditPasswordManagerValidated {
     pub password: Password,
     #[allow(dead_code)]
     pub password_confirm: Password,
+    pub must_change_password: bool,
 }
 
 #[cfg(test)]
@@ -98,6 +109,7 @@ impl EditPasswordManagerValidated {
                 .expect("test password"),
             password_confirm: Password::parse(Some("aVHTsh_SEGW5[g_c`/uh>~0!YI0'~fJw"))
                 .expect("test password confirm"),
+            must_change_password: false,
         }
     }
 }
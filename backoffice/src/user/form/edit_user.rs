@@ -60,6 +60,7 @@ impl EditUserForm {
         errors: Option<EditUserMessage>,
         username: Option<String>,
         token: Option<Markup>,
+        two_factor_status: Markup,
     ) -> Markup {
         let errors = errors.unwrap_or_default();
         let user_form_locale = UserFormLocale::new(&context_html_builder.locale);
@@ -86,6 +87,7 @@ impl EditUserForm {
                     input .btn .btn-sky-blue type="submit" value=(user_form_locale.submit_edit) {}
                 }
             }
+            (two_factor_status)
         }).build()
     }
 }
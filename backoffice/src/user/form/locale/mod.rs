@@ -5,13 +5,17 @@ pub struct UserFormLocale {
     pub title_add: String,
     pub title_edit: String,
     pub title_edit_password: String,
+    pub title_change_password: String,
     pub username: String,
     pub username_placeholder: String,
     pub password: String,
     pub password_placeholder: String,
     pub password_confirm: String,
     pub password_confirm_placeholder: String,
+    pub password_current: String,
+    pub password_current_placeholder: String,
     pub role: String,
+    pub must_change_password: String,
     pub submit_add: String,
     pub submit_edit: String,
     pub submit_password: String,
@@ -24,6 +28,8 @@ impl UserFormLocale {
             title_edit: locale.text_with_default("user-form-title-edit", "Edit User"),
             title_edit_password: locale
                 .text_with_default("user-form-title-edit-password", "Edit User Password"),
+            title_change_password: locale
+                .text_with_default("user-form-title-change-password", "Change Your Password"),
             username: locale.text_with_default("user-form-username", "Username:"),
             username_placeholder: locale
                 .text_with_default("user-form-username-placeholder", "Username"),
@@ -34,7 +40,15 @@ impl UserFormLocale {
                 .text_with_default("user-form-password-confirm", "Password Confirm:"),
             password_confirm_placeholder: locale
                 .text_with_default("user-form-password-confirm-placeholder", "Password Confirm"),
+            password_current: locale
+                .text_with_default("user-form-password-current", "Current Password:"),
+            password_current_placeholder: locale
+                .text_with_default("user-form-password-current-placeholder", "Current"),
             role: locale.text_with_default("user-form-role", "Role:"),
+            must_change_password: locale.text_with_default(
+                "user-form-must-change-password",
+                "Must change password on next login",
+            ),
             submit_add: locale.text_with_default("user-form-submit-add", "Add"),
             submit_edit: locale.text_with_default("user-form-submit-edit", "Edit"),
             submit_password: locale.text_with_default("user-form-submit-password", "Submit"),
@@ -1,5 +1,8 @@
 pub mod add_user;
+pub mod change_password;
 pub mod edit_password_manager;
 pub mod edit_user;
 pub mod locale;
 pub mod login;
+pub mod setup;
+pub mod two_factor;
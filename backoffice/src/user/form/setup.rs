@@ -0,0 +1,145 @@
+use crate::common::html::context_html::ContextHtmlBuilder;
+use crate::common::html::validate::ValidateErrorMessageExt;
+use crate::user::form::locale::UserFormLocale;
+use crate::user::locale::setup::SetupLocale;
+use crate::user::rule::user_manager::{PasswordUserManagerRulesExt, UsernameUserManagerRulesExt};
+use cjtoolkit_structured_validator::common::flag_error::FlagCounter;
+use cjtoolkit_structured_validator::types::password::{Password, PasswordError};
+use cjtoolkit_structured_validator::types::username::{
+    IsUsernameTakenAsync, Username, UsernameError,
+};
+use maud::{Markup, html};
+use poem::i18n::Locale;
+use serde::{Deserialize, Serialize};
+use shared::utils::locale::LocaleExtForResult;
+use std::sync::Arc;
+
+#[derive(Deserialize, Default)]
+pub struct SetupForm {
+    pub username: String,
+    pub password: String,
+    pub password_confirm: String,
+}
+
+impl SetupForm {
+    pub async fn as_validated<T: IsUsernameTakenAsync>(&self, service: &T) -> SetupResult {
+        SetupResult(
+            async {
+                let mut flag = FlagCounter::new();
+
+                let username = flag.check(
+                    Username::parse_user_add(Some(self.username.trim()), service, None).await,
+                );
+                let (password, password_confirm) = Password::parse_password_add(
+                    Some(self.password.trim()),
+                    self.password_confirm.trim(),
+                );
+                let password = flag.check(password);
+                let password_confirm = flag.check(password_confirm);
+
+                if flag.is_flagged() {
+                    return Err(SetupError {
+                        username,
+                        password,
+                        password_confirm,
+                    });
+                }
+
+                Ok(SetupValidated {
+                    username: username.expect("Username is not empty"),
+                    password: password.expect("Password is not empty"),
+                    password_confirm: password_confirm.expect("Password Confirm is not empty"),
+                })
+            }
+            .await,
+        )
+    }
+
+    pub async fn as_form_html(
+        &self,
+        context_html_builder: &ContextHtmlBuilder,
+        errors: Option<SetupMessage>,
+        token: Option<Markup>,
+    ) -> Markup {
+        let errors = errors.unwrap_or_default();
+        let user_form_locale = UserFormLocale::new(&context_html_builder.locale);
+        let setup_locale = SetupLocale::new(&context_html_builder.locale);
+        let token = token.unwrap_or_default();
+        context_html_builder
+            .attach_title(&setup_locale.title)
+            .attach_content(html! {
+                h1 .mt-3 { (setup_locale.title) }
+                p { (setup_locale.paragraph) }
+                form .form method="post" {
+                    (token)
+                    div .form-group {
+                        label .label for="username" { (user_form_locale.username) }
+                        input .form-item .w-full type="text" name="username" #username value=(self.username)
+                        placeholder=(user_form_locale.username_placeholder) {}
+                        (errors.username.into_error_html())
+                    }
+                    div .form-group {
+                        label .label for="password" { (user_form_locale.password) }
+                        input .form-item .w-full type="password" name="password" #password
+                        placeholder=(user_form_locale.password_placeholder) {}
+                        (errors.password.into_error_html())
+                    }
+                    div .form-group {
+                        label .label for="password-confirm" { (user_form_locale.password_confirm) }
+                        input .form-item .w-full type="password" name="password_confirm" #password-confirm
+                        placeholder=(user_form_locale.password_confirm_placeholder) {}
+                        (errors.password_confirm.into_error_html())
+                    }
+                    div .form-group {
+                        input .btn .btn-sky-blue type="submit" value=(setup_locale.submit) {}
+                    }
+                }
+            })
+            .build()
+    }
+}
+
+pub struct SetupValidated {
+    pub username: Username,
+    pub password: Password,
+    #[allow(dead_code)]
+    pub password_confirm: Password,
+}
+
+#[cfg(test)]
+impl SetupValidated {
+    pub fn new_test_data() -> Self {
+        Self {
+            username: Username::parse(Some("username")).expect("test username"),
+            password: Password::parse(Some("aVHTsh_SEGW5[g_c`/uh>~0!YI0'~fJw"))
+                .expect("test password"),
+            password_confirm: Password::parse(Some("aVHTsh_SEGW5[g_c`/uh>~0!YI0'~fJw"))
+                .expect("test password confirm"),
+        }
+    }
+}
+
+pub struct SetupError {
+    pub username: Result<Username, UsernameError>,
+    pub password: Result<Password, PasswordError>,
+    pub password_confirm: Result<Password, PasswordError>,
+}
+
+impl SetupError {
+    pub fn as_message(&self, locale: &Locale) -> SetupMessage {
+        SetupMessage {
+            username: self.username.as_translated_message(locale),
+            password: self.password.as_translated_message(locale),
+            password_confirm: self.password_confirm.as_translated_message(locale),
+        }
+    }
+}
+
+pub struct SetupResult(pub Result<SetupValidated, SetupError>);
+
+#[derive(Debug, Clone, Serialize, Default)]
+pub struct SetupMessage {
+    pub username: Arc<[String]>,
+    pub password: Arc<[String]>,
+    pub password_confirm: Arc<[String]>,
+}
@@ -0,0 +1,7 @@
+use serde::Deserialize;
+
+/// Holds either a six digit TOTP code or a recovery code, the service tells them apart.
+#[derive(Deserialize, Default)]
+pub struct TwoFactorCodeForm {
+    pub code: String,
+}
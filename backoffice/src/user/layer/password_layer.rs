@@ -1,13 +1,20 @@
 use error_stack::Report;
+use shared::utils::config::ConfigPointer;
+use shared::utils::config::password::PasswordConfig;
 use shared::utils::context::{Context, ContextError, FromContext};
+use shared::utils::db::run_blocking;
 use shared::utils::password::{Password, PasswordError, PasswordState};
+use std::sync::Arc;
 
 #[mry::mry]
-pub struct PasswordLayer {}
+pub struct PasswordLayer {
+    config: Arc<PasswordConfig>,
+}
 
 impl PasswordLayer {
-    pub fn new() -> Self {
+    pub fn new(config: Arc<PasswordConfig>) -> Self {
         Self {
+            config,
             mry: Default::default(),
         }
     }
@@ -20,23 +27,28 @@ impl PasswordLayer {
         password_hash: Box<[u8]>,
         password: &str,
     ) -> Result<PasswordState, Report<PasswordError>> {
-        Password::verify_password(password_hash, password.to_string())
+        run_blocking(|| {
+            Password::verify_password(password_hash, password.to_string(), &self.config)
+        })
     }
 
     pub fn hash_password(&self, password: &str) -> Result<Password, Report<PasswordError>> {
-        Password::hash_password(password.to_string())
+        run_blocking(|| Password::hash_password(password.to_string(), &self.config))
     }
 }
 
 #[cfg(test)]
 impl PasswordLayer {
     pub fn new_mock() -> Self {
-        mry::new!(Self {})
+        mry::new!(Self {
+            config: Default::default()
+        })
     }
 }
 
 impl FromContext for PasswordLayer {
-    async fn from_context(_ctx: &'_ Context<'_>) -> Result<Self, Report<ContextError>> {
-        Ok(Self::new())
+    async fn from_context(ctx: &'_ Context<'_>) -> Result<Self, Report<ContextError>> {
+        let config: ConfigPointer = ctx.inject().await?;
+        Ok(Self::new(Arc::clone(&config.password)))
     }
 }
@@ -22,6 +22,7 @@ impl LoginLocale {
 pub struct LoginPostLocale {
     pub flash_success: String,
     pub flash_failed: String,
+    pub flash_throttled: String,
 }
 
 impl LoginPostLocale {
@@ -29,6 +30,10 @@ impl LoginPostLocale {
         Self {
             flash_success: locale.text_with_default("login-post-flash-success", "Login success"),
             flash_failed: locale.text_with_default("login-post-flash-failed", "Login failed"),
+            flash_throttled: locale.text_with_default(
+                "login-post-flash-throttled",
+                "Too many failed attempts, please try again later",
+            ),
         }
     }
 }
@@ -44,3 +49,21 @@ impl LogoutLocale {
         }
     }
 }
+
+pub struct ChangePasswordLocale {
+    pub flash_required: String,
+    pub flash_success: String,
+}
+
+impl ChangePasswordLocale {
+    pub fn new(locale: &Locale) -> Self {
+        Self {
+            flash_required: locale.text_with_default(
+                "login-change-password-flash-required",
+                "You must change your password before continuing",
+            ),
+            flash_success: locale
+                .text_with_default("login-change-password-flash-success", "Password changed"),
+        }
+    }
+}
@@ -1,2 +1,5 @@
 pub mod login;
+pub mod session;
+pub mod setup;
+pub mod two_factor;
 pub mod user;
@@ -0,0 +1,45 @@
+use poem::i18n::Locale;
+use shared::utils::locale::LocaleExt;
+
+pub struct SessionLocale {
+    pub title: String,
+    pub intro: String,
+    pub head_device: String,
+    pub head_ip: String,
+    pub head_created: String,
+    pub head_last_seen: String,
+    pub head_action: String,
+    pub unknown_device: String,
+    pub current: String,
+    pub revoke: String,
+    pub revoke_confirm: String,
+    pub flash_revoked: String,
+    pub flash_not_found: String,
+}
+
+impl SessionLocale {
+    pub fn new(l: &Locale) -> Self {
+        Self {
+            title: l.text_with_default("session-title", "Active Sessions"),
+            intro: l.text_with_default(
+                "session-intro",
+                "Every device signed in to your account, revoke any you do not recognise.",
+            ),
+            head_device: l.text_with_default("session-head-device", "Device"),
+            head_ip: l.text_with_default("session-head-ip", "IP Address"),
+            head_created: l.text_with_default("session-head-created", "Signed In"),
+            head_last_seen: l.text_with_default("session-head-last-seen", "Last Seen"),
+            head_action: l.text_with_default("session-head-action", "Action"),
+            unknown_device: l.text_with_default("session-unknown-device", "Unknown device"),
+            current: l.text_with_default("session-current", "This device"),
+            revoke: l.text_with_default("session-revoke", "Revoke Session"),
+            revoke_confirm: l.text_with_default(
+                "session-revoke-confirm",
+                "Sign this device out of your account?",
+            ),
+            flash_revoked: l.text_with_default("session-flash-revoked", "Session revoked"),
+            flash_not_found: l
+                .text_with_default("session-flash-not-found", "That session has already ended"),
+        }
+    }
+}
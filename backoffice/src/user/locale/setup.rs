@@ -0,0 +1,36 @@
+use poem::i18n::Locale;
+use shared::utils::locale::LocaleExt;
+
+pub struct SetupLocale {
+    pub title: String,
+    pub paragraph: String,
+    pub submit: String,
+}
+
+impl SetupLocale {
+    pub fn new(locale: &Locale) -> Self {
+        Self {
+            title: locale.text_with_default("setup-title", "First Run Setup"),
+            paragraph: locale.text_with_default(
+                "setup-paragraph",
+                "Create the root account, this page goes away once it exists.",
+            ),
+            submit: locale.text_with_default("setup-submit", "Create Root Account"),
+        }
+    }
+}
+
+pub struct SetupPostLocale {
+    pub flash_success: String,
+}
+
+impl SetupPostLocale {
+    pub fn new(locale: &Locale) -> Self {
+        Self {
+            flash_success: locale.text_with_default(
+                "setup-post-flash-success",
+                "Root account created, please login",
+            ),
+        }
+    }
+}
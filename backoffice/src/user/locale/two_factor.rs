@@ -0,0 +1,133 @@
+use poem::i18n::{I18NArgs, Locale};
+use shared::utils::locale::LocaleExt;
+
+pub struct TwoFactorLocale {
+    pub title: String,
+    pub pending_intro: String,
+    pub pending_secret: String,
+    pub code: String,
+    pub code_placeholder: String,
+    pub confirm_button: String,
+    pub enabled_intro: String,
+    pub regenerate_button: String,
+    pub disable_button: String,
+    pub recovery_title: String,
+    pub recovery_intro: String,
+    pub recovery_continue: String,
+    pub flash_required: String,
+    pub flash_enabled: String,
+    pub flash_disabled: String,
+    pub flash_invalid_code: String,
+}
+
+impl TwoFactorLocale {
+    pub fn new(l: &Locale) -> Self {
+        Self {
+            title: l.text_with_default("two-factor-title", "Two-Factor Authentication"),
+            pending_intro: l.text_with_default(
+                "two-factor-pending-intro",
+                "Scan the QR code with an authenticator app, then enter the six digit code it shows.",
+            ),
+            pending_secret: l
+                .text_with_default("two-factor-pending-secret", "Or enter this key by hand:"),
+            code: l.text_with_default("two-factor-code", "Code"),
+            code_placeholder: l
+                .text_with_default("two-factor-code-placeholder", "Six digit code"),
+            confirm_button: l.text_with_default(
+                "two-factor-confirm-button",
+                "Enable two-factor authentication",
+            ),
+            enabled_intro: l.text_with_default(
+                "two-factor-enabled-intro",
+                "Two-factor authentication is enabled for your account.",
+            ),
+            regenerate_button: l.text_with_default(
+                "two-factor-regenerate-button",
+                "Generate new recovery codes",
+            ),
+            disable_button: l.text_with_default(
+                "two-factor-disable-button",
+                "Disable two-factor authentication",
+            ),
+            recovery_title: l.text_with_default("two-factor-recovery-title", "Recovery Codes"),
+            recovery_intro: l.text_with_default(
+                "two-factor-recovery-intro",
+                "Keep these codes somewhere safe, each one signs you in once without your authenticator app. They will not be shown again.",
+            ),
+            recovery_continue: l.text_with_default("two-factor-recovery-continue", "Continue"),
+            flash_required: l.text_with_default(
+                "two-factor-flash-required",
+                "You must enable two-factor authentication before continuing",
+            ),
+            flash_enabled: l.text_with_default(
+                "two-factor-flash-enabled",
+                "Two-factor authentication enabled",
+            ),
+            flash_disabled: l.text_with_default(
+                "two-factor-flash-disabled",
+                "Two-factor authentication disabled",
+            ),
+            flash_invalid_code: l.text_with_default(
+                "two-factor-flash-invalid-code",
+                "The code was not accepted",
+            ),
+        }
+    }
+}
+
+pub fn two_factor_remaining_message(l: &Locale, remaining: i64) -> String {
+    l.text_with_default_args(
+        "two-factor-enabled-remaining",
+        format!("Unused recovery codes: {remaining}").as_str(),
+        I18NArgs::from((("remaining", remaining),)),
+    )
+}
+
+pub struct TwoFactorVerifyLocale {
+    pub title: String,
+    pub intro: String,
+    pub code: String,
+    pub confirm_button: String,
+}
+
+impl TwoFactorVerifyLocale {
+    pub fn new(l: &Locale) -> Self {
+        Self {
+            title: l.text_with_default("two-factor-verify-title", "Two-Factor Verification"),
+            intro: l.text_with_default(
+                "two-factor-verify-intro",
+                "Enter the code from your authenticator app, or one of your recovery codes.",
+            ),
+            code: l.text_with_default("two-factor-code", "Code"),
+            confirm_button: l.text_with_default("two-factor-verify-button", "Verify"),
+        }
+    }
+}
+
+pub struct TwoFactorStatusLocale {
+    pub title: String,
+    pub enabled: String,
+    pub disabled: String,
+    pub reset: String,
+}
+
+impl TwoFactorStatusLocale {
+    pub fn new(l: &Locale) -> Self {
+        Self {
+            title: l.text_with_default("two-factor-status-title", "Two-Factor Authentication"),
+            enabled: l.text_with_default("two-factor-status-enabled", "Enabled"),
+            disabled: l.text_with_default("two-factor-status-disabled", "Not enabled"),
+            reset: l
+                .text_with_default("two-factor-status-reset", "Reset two-factor authentication"),
+        }
+    }
+}
+
+pub fn two_factor_reset_confirm_message(l: &Locale, username: &str) -> String {
+    l.text_with_default_args(
+        "two-factor-status-reset-confirm",
+        format!("Are you sure you want to reset two-factor authentication for '{username}'?")
+            .as_str(),
+        I18NArgs::from((("username", username),)),
+    )
+}
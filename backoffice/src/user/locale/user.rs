@@ -6,9 +6,11 @@ pub struct UserLocale {
     pub user_list_head_id: String,
     pub user_list_head_username: String,
     pub user_list_head_role: String,
+    pub user_list_filter_any_role: String,
     pub user_list_action_edit: String,
     pub user_list_action_password: String,
     pub user_list_action_sign_out: String,
+    pub user_list_action_unlock: String,
     pub user_list_action_add_user: String,
 }
 
@@ -20,11 +22,15 @@ impl UserLocale {
             user_list_head_username: l
                 .text_with_default("user-route-list-head-username", "Username"),
             user_list_head_role: l.text_with_default("user-route-list-head-role", "Role"),
+            user_list_filter_any_role: l
+                .text_with_default("user-route-list-filter-any-role", "Any role"),
             user_list_action_edit: l.text_with_default("user-route-list-action-edit", "Edit User"),
             user_list_action_password: l
                 .text_with_default("user-route-list-action-password", "Edit Password"),
             user_list_action_sign_out: l
                 .text_with_default("user-route-list-action-sign-out", "Sign Out User"),
+            user_list_action_unlock: l
+                .text_with_default("user-route-list-action-unlock", "Clear Login Lockout"),
             user_list_action_add_user: l
                 .text_with_default("user-route-list-action-add-user", "Add Users"),
         }
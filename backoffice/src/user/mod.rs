@@ -1,3 +1,8 @@
+use chrono::TimeDelta;
+use poem::web::RemoteAddr;
+use poem::web::cookie::Cookie;
+use shared::utils::cookie_builders::CookieBuilderExt;
+
 pub mod form;
 pub mod layer;
 pub mod locale;
@@ -8,5 +13,25 @@ pub mod role;
 pub mod route;
 pub mod rule;
 pub mod service;
+pub mod session;
 
 pub const LOGIN_TOKEN_COOKIE_NAME: &str = "login_token";
+pub const LOGIN_CHALLENGE_COOKIE_NAME: &str = "login_challenge";
+
+pub fn login_token_cookie(token: String, lifetime: TimeDelta) -> Cookie {
+    Cookie::new_with_str(LOGIN_TOKEN_COOKIE_NAME, token)
+        .into_builder()
+        .path("/")
+        .expires_by_delta(lifetime)
+        .secure()
+        .http_only()
+        .build()
+}
+
+/// The peer address, forwarded headers are ignored since anyone can set them.
+pub fn remote_ip(remote_addr: &RemoteAddr) -> String {
+    remote_addr
+        .as_socket_addr()
+        .map(|addr| addr.ip().to_string())
+        .unwrap_or_else(|| remote_addr.to_string())
+}
@@ -1,19 +1,50 @@
 use crate::user::role::Role;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
+use shared::utils::query_string::list_query::{ListQuery, SortKey, empty_as_none};
 
 #[derive(Serialize)]
 pub struct ListUser {
     pub id: i64,
     pub username: String,
     pub role: Role,
+    pub locked: bool,
 }
 
 pub struct FetchUser {
     pub username: String,
     pub role: Role,
+    pub two_factor_enabled: bool,
 }
 
-#[allow(dead_code)]
 pub struct FetchPassword {
     pub password: Box<[u8]>,
 }
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Default)]
+#[serde(default)]
+pub struct UserFilter {
+    #[serde(
+        deserialize_with = "empty_as_none",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub role: Option<Role>,
+}
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum UserSort {
+    #[default]
+    Id,
+    Username,
+}
+
+impl SortKey for UserSort {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::Id => "id",
+            Self::Username => "username",
+        }
+    }
+}
+
+pub type ListUserQuery = ListQuery<UserFilter, UserSort>;
@@ -6,9 +6,49 @@ pub struct UserIdContext {
     pub id: i64,
     pub username: String,
     pub role: Role,
+    pub must_change_password: bool,
+    pub two_factor_enabled: bool,
 }
 
 pub struct IdPassword {
     pub id: i64,
     pub password: Box<[u8]>,
+    pub two_factor_enabled: bool,
+}
+
+pub struct TwoFactorState {
+    pub secret: Option<Box<[u8]>>,
+    pub enabled: bool,
+    pub last_step: i64,
+}
+
+pub struct LoginChallenge {
+    pub user_id: i64,
+    pub username: String,
+}
+
+/// Failed logins are counted against both the username and the remote address.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ThrottleKind {
+    Username,
+    Ip,
+}
+
+impl ThrottleKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Username => "username",
+            Self::Ip => "ip",
+        }
+    }
+}
+
+pub struct LoginSession {
+    pub id: i64,
+    pub created_at: String,
+    pub last_seen_at: String,
+    pub ip: String,
+    pub user_agent: String,
+    /// The session the listing was requested from.
+    pub current: bool,
 }
@@ -1,4 +1,6 @@
 use crate::user::model::user_model::UserIdContext;
+#[cfg(test)]
+use crate::user::role::Role;
 use crate::user::service::user_check_service::UserCheckService;
 use error_stack::Report;
 use shared::utils::context::{Context, ContextError, FromContext};
@@ -21,6 +23,19 @@ impl Deref for UserPointer {
     }
 }
 
+#[cfg(test)]
+impl UserPointer {
+    pub fn new_mock(id: i64, role: Role) -> Self {
+        Self(Arc::new(UserIdContext {
+            id,
+            username: format!("user-{}", id),
+            role,
+            must_change_password: false,
+            two_factor_enabled: false,
+        }))
+    }
+}
+
 impl FromContext for UserPointer {
     async fn from_context(ctx: &'_ Context<'_>) -> Result<Self, Report<ContextError>> {
         let req = ctx.req_result()?;
@@ -0,0 +1,2 @@
+insert into user_login_attempts (username, ip, success)
+values (:username, :ip, :success);
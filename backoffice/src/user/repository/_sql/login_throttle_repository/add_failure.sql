@@ -0,0 +1,8 @@
+insert into user_login_throttle (kind, subject, failures, last_failure_at)
+values (:kind, :subject, 1, datetime('now'))
+on conflict (kind, subject) do update
+    set failures        = case
+                              when last_failure_at < datetime('now', '-' || :reset_after_secs || ' seconds') then 1
+                              else failures + 1 end,
+        last_failure_at = datetime('now')
+returning failures;
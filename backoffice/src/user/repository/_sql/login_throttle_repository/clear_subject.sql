@@ -0,0 +1,4 @@
+delete
+from user_login_throttle
+where kind = :kind
+  and subject = :subject;
@@ -0,0 +1,4 @@
+delete
+from user_login_throttle
+where kind = 'username'
+  and subject = (select username from backoffice_users where id = :user_id);
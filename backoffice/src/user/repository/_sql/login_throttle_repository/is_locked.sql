@@ -0,0 +1,4 @@
+select exists(select 1
+              from user_login_throttle
+              where ((kind = 'username' and subject = :username) or (kind = 'ip' and subject = :ip))
+                and locked_until > datetime('now')) as locked;
@@ -0,0 +1,4 @@
+update user_login_throttle
+set locked_until = datetime('now', '+' || :lockout_secs || ' seconds')
+where kind = :kind
+  and subject = :subject;
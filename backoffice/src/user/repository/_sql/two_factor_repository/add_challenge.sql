@@ -0,0 +1,2 @@
+insert into user_login_challenges(user_id, token, expire_after)
+values (:user_id, :token, datetime('now', '+5 minute'));
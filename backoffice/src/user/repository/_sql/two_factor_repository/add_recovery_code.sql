@@ -0,0 +1,2 @@
+insert into user_recovery_codes(user_id, code_hash)
+values (:user_id, :code_hash);
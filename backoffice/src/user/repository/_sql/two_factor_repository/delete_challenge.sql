@@ -0,0 +1,4 @@
+delete
+from user_login_challenges
+where token = :token
+   or expire_after <= datetime('now');
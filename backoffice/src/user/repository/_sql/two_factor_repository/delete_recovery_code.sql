@@ -0,0 +1,3 @@
+delete
+from user_recovery_codes
+where user_id = :user_id;
@@ -0,0 +1,5 @@
+update backoffice_users
+set totp_secret    = null,
+    totp_enabled   = 0,
+    totp_last_step = 0
+where id = :user_id;
@@ -0,0 +1,5 @@
+update backoffice_users
+set totp_enabled   = 1,
+    totp_last_step = :step
+where id = :user_id
+  and totp_enabled = 0;
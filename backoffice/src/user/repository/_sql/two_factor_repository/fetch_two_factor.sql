@@ -0,0 +1,4 @@
+select totp_secret, totp_enabled, totp_last_step
+from backoffice_users
+where id = :user_id
+limit 1;
@@ -0,0 +1,6 @@
+select ulc.user_id, u.username
+from user_login_challenges as ulc
+         inner join backoffice_users u on u.id = ulc.user_id
+where ulc.token = :token
+  and ulc.expire_after > datetime('now')
+limit 1;
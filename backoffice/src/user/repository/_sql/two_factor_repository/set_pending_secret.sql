@@ -0,0 +1,4 @@
+update backoffice_users
+set totp_secret = :secret
+where id = :user_id
+  and totp_enabled = 0;
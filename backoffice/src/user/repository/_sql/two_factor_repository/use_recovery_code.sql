@@ -0,0 +1,8 @@
+update user_recovery_codes
+set used_at = datetime('now')
+where rowid = (select rowid
+               from user_recovery_codes
+               where user_id = :user_id
+                 and code_hash = :code_hash
+                 and used_at is null
+               limit 1);
@@ -0,0 +1,3 @@
+insert into backoffice_users (username, password, role)
+select :username, :password, :role
+where not exists(select 1 from backoffice_users where role = :role)
\ No newline at end of file
@@ -1,2 +1,2 @@
-insert into backoffice_users (username, password, role)
-values (:username, :password, :role)
\ No newline at end of file
+insert into backoffice_users (username, password, role, must_change_password)
+values (:username, :password, :role, :must_change_password)
\ No newline at end of file
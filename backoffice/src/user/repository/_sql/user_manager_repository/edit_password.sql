@@ -1,3 +1,4 @@
 update backoffice_users
-set password = :password
+set password             = :password,
+    must_change_password = :must_change_password
 where id = :id
\ No newline at end of file
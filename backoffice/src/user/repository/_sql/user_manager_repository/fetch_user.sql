@@ -1,3 +1,3 @@
-select username, role
+select username, role, totp_enabled
 from backoffice_users
 where id = :id
\ No newline at end of file
@@ -1,3 +1,19 @@
-select id, username, role
+select id,
+       username,
+       role,
+       exists(select 1
+              from user_login_throttle ult
+              where ult.kind = 'username'
+                and ult.subject = backoffice_users.username
+                and ult.locked_until > datetime('now')) as locked,
+       case :sort when 'username' then username else '' end as sort_key
 from backoffice_users
-order by id
\ No newline at end of file
+where (:role is null or role = :role)
+  and (:cursor_id is null
+    or (:scan_desc and (sort_key, id) < (:cursor_key, :cursor_id))
+    or (not :scan_desc and (sort_key, id) > (:cursor_key, :cursor_id)))
+order by case when :scan_desc then sort_key end desc,
+         case when :scan_desc then id end desc,
+         sort_key,
+         id
+limit :limit
\ No newline at end of file
@@ -0,0 +1 @@
+select exists(select 1 from backoffice_users where role = :role) as root_exists
\ No newline at end of file
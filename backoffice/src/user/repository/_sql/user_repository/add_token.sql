@@ -1,2 +1,2 @@
-insert into user_login_tokens(user_id, token, expire_after)
-values (:user_id, :token, datetime('now', '+30 day'))
\ No newline at end of file
+insert into user_login_tokens(user_id, token, token_hashed, expire_after, ip, user_agent)
+values (:user_id, :token, 1, datetime('now', '+' || :lifetime_days || ' day'), :ip, :user_agent)
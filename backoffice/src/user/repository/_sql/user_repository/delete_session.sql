@@ -0,0 +1,4 @@
+delete
+from user_login_tokens
+where id = :session_id
+  and user_id = :user_id;
@@ -0,0 +1,3 @@
+delete
+from user_login_tokens
+where user_id = :user_id;
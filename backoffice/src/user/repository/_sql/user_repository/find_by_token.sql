@@ -1,4 +1,4 @@
-select u.id, u.username, u.role
+select u.id, u.username, u.role, u.must_change_password, u.totp_enabled
 from backoffice_users as u
          inner join user_login_tokens ult on u.id = ult.user_id
 where ult.token = :token
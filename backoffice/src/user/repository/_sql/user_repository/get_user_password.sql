@@ -1,4 +1,4 @@
-select id, password
+select id, password, totp_enabled
 from backoffice_users
 where username = :username
 limit 1;
\ No newline at end of file
@@ -0,0 +1,10 @@
+select id,
+       created_at,
+       last_seen_at,
+       ip,
+       user_agent,
+       token = :token as current
+from user_login_tokens
+where user_id = :user_id
+  and expire_after > datetime('now')
+order by last_seen_at desc, id desc;
@@ -0,0 +1,3 @@
+select token
+from user_login_tokens
+where token_hashed = 0;
@@ -0,0 +1,3 @@
+delete
+from user_login_tokens
+where expire_after <= datetime('now');
@@ -0,0 +1,6 @@
+update user_login_tokens
+set last_seen_at = CURRENT_TIMESTAMP,
+    expire_after = datetime('now', '+' || :lifetime_days || ' day')
+where token = :token
+  and expire_after > datetime('now')
+  and last_seen_at < datetime('now', '-' || :renew_interval_secs || ' seconds');
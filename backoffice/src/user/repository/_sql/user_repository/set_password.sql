@@ -0,0 +1,3 @@
+update backoffice_users
+set password = :password
+where id = :id
\ No newline at end of file
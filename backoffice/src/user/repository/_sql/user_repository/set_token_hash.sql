@@ -0,0 +1,5 @@
+update user_login_tokens
+set token        = :token_hash,
+    token_hashed = 1
+where token = :token
+  and token_hashed = 0;
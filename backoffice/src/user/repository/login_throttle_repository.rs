@@ -0,0 +1,197 @@
+use crate::user::model::user_model::ThrottleKind;
+use error_stack::{Report, ResultExt};
+use poem::http::StatusCode;
+use rusqlite::named_params;
+use shared::utils::context::{Context, ContextError, FromContext};
+use shared::utils::db::{BorrowConnectionExt, SqliteClient, SqliteConn, run_blocking};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum LoginThrottleRepositoryError {
+    #[error("Query error")]
+    QueryError,
+    #[error("Row Value error")]
+    RowValueError,
+    #[error("Borrow Conn error")]
+    BorrowConnError,
+}
+
+#[mry::mry]
+pub struct LoginThrottleRepository {
+    sqlite_client: Option<SqliteClient>,
+}
+
+impl LoginThrottleRepository {
+    pub fn new(sqlite_client: SqliteClient) -> Self {
+        Self {
+            sqlite_client: Some(sqlite_client),
+            mry: Default::default(),
+        }
+    }
+
+    fn borrow_conn(&'_ self) -> Result<SqliteConn, Report<LoginThrottleRepositoryError>> {
+        self.sqlite_client
+            .borrow_conn()
+            .change_context(LoginThrottleRepositoryError::BorrowConnError)
+    }
+}
+
+#[mry::mry]
+impl LoginThrottleRepository {
+    pub fn is_locked(
+        &self,
+        username: String,
+        ip: String,
+    ) -> Result<bool, Report<LoginThrottleRepositoryError>> {
+        run_blocking(|| {
+            let conn = self.borrow_conn()?;
+
+            let mut stmt = conn
+                .prepare_cached(include_str!("_sql/login_throttle_repository/is_locked.sql"))
+                .change_context(LoginThrottleRepositoryError::QueryError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)?;
+
+            stmt.query_one(
+                named_params! {
+                    ":username": username,
+                    ":ip": ip,
+                },
+                |row| row.get("locked"),
+            )
+            .change_context(LoginThrottleRepositoryError::RowValueError)
+            .attach(StatusCode::INTERNAL_SERVER_ERROR)
+        })
+    }
+
+    /// Returns the failure count, which starts over once the last failure is older than `reset_after_secs`.
+    pub fn add_failure(
+        &self,
+        kind: ThrottleKind,
+        subject: String,
+        reset_after_secs: i64,
+    ) -> Result<i64, Report<LoginThrottleRepositoryError>> {
+        run_blocking(|| {
+            let conn = self.borrow_conn()?;
+
+            let mut stmt = conn
+                .prepare_cached(include_str!(
+                    "_sql/login_throttle_repository/add_failure.sql"
+                ))
+                .change_context(LoginThrottleRepositoryError::QueryError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)?;
+
+            stmt.query_one(
+                named_params! {
+                    ":kind": kind.as_str(),
+                    ":subject": subject,
+                    ":reset_after_secs": reset_after_secs,
+                },
+                |row| row.get("failures"),
+            )
+            .change_context(LoginThrottleRepositoryError::RowValueError)
+            .attach(StatusCode::INTERNAL_SERVER_ERROR)
+        })
+    }
+
+    pub fn lock_subject(
+        &self,
+        kind: ThrottleKind,
+        subject: String,
+        lockout_secs: i64,
+    ) -> Result<(), Report<LoginThrottleRepositoryError>> {
+        run_blocking(|| {
+            let conn = self.borrow_conn()?;
+
+            conn.execute(
+                include_str!("_sql/login_throttle_repository/lock_subject.sql"),
+                named_params! {
+                    ":kind": kind.as_str(),
+                    ":subject": subject,
+                    ":lockout_secs": lockout_secs,
+                },
+            )
+            .change_context(LoginThrottleRepositoryError::QueryError)
+            .attach(StatusCode::INTERNAL_SERVER_ERROR)?;
+
+            Ok(())
+        })
+    }
+
+    pub fn clear_subject(
+        &self,
+        kind: ThrottleKind,
+        subject: String,
+    ) -> Result<(), Report<LoginThrottleRepositoryError>> {
+        run_blocking(|| {
+            let conn = self.borrow_conn()?;
+
+            conn.execute(
+                include_str!("_sql/login_throttle_repository/clear_subject.sql"),
+                named_params! {
+                    ":kind": kind.as_str(),
+                    ":subject": subject,
+                },
+            )
+            .change_context(LoginThrottleRepositoryError::QueryError)
+            .attach(StatusCode::INTERNAL_SERVER_ERROR)?;
+
+            Ok(())
+        })
+    }
+
+    pub fn clear_user(&self, user_id: i64) -> Result<(), Report<LoginThrottleRepositoryError>> {
+        run_blocking(|| {
+            let conn = self.borrow_conn()?;
+
+            conn.execute(
+                include_str!("_sql/login_throttle_repository/clear_user.sql"),
+                named_params! {
+                    ":user_id": user_id,
+                },
+            )
+            .change_context(LoginThrottleRepositoryError::QueryError)
+            .attach(StatusCode::INTERNAL_SERVER_ERROR)?;
+
+            Ok(())
+        })
+    }
+
+    pub fn add_attempt(
+        &self,
+        username: String,
+        ip: String,
+        success: bool,
+    ) -> Result<(), Report<LoginThrottleRepositoryError>> {
+        run_blocking(|| {
+            let conn = self.borrow_conn()?;
+
+            conn.execute(
+                include_str!("_sql/login_throttle_repository/add_attempt.sql"),
+                named_params! {
+                    ":username": username,
+                    ":ip": ip,
+                    ":success": success,
+                },
+            )
+            .change_context(LoginThrottleRepositoryError::QueryError)
+            .attach(StatusCode::INTERNAL_SERVER_ERROR)?;
+
+            Ok(())
+        })
+    }
+}
+
+#[cfg(test)]
+impl LoginThrottleRepository {
+    pub fn new_mock() -> Self {
+        mry::new!(Self {
+            sqlite_client: None
+        })
+    }
+}
+
+impl FromContext for LoginThrottleRepository {
+    async fn from_context(ctx: &'_ Context<'_>) -> Result<Self, Report<ContextError>> {
+        Ok(Self::new(ctx.inject().await?))
+    }
+}
@@ -1,2 +1,4 @@
+pub mod login_throttle_repository;
+pub mod two_factor_repository;
 pub mod user_manager_repository;
 pub mod user_repository;
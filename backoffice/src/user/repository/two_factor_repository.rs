@@ -0,0 +1,370 @@
+use crate::user::model::user_model::{LoginChallenge, TwoFactorState};
+use error_stack::{Report, ResultExt};
+use poem::http::StatusCode;
+use rusqlite::{OptionalExtension, Transaction, TransactionBehavior, named_params};
+use shared::utils::context::{Context, ContextError, FromContext};
+use shared::utils::db::{BorrowConnectionExt, SqliteClient, SqliteConn, run_blocking};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum TwoFactorRepositoryError {
+    #[error("Query error")]
+    QueryError,
+    #[error("Row Value error")]
+    RowValueError,
+    #[error("Borrow Conn error")]
+    BorrowConnError,
+    #[error("Not found error")]
+    NotFoundError,
+}
+
+#[mry::mry]
+pub struct TwoFactorRepository {
+    sqlite_client: Option<SqliteClient>,
+}
+
+impl TwoFactorRepository {
+    pub fn new(sqlite_client: SqliteClient) -> Self {
+        Self {
+            sqlite_client: Some(sqlite_client),
+            mry: Default::default(),
+        }
+    }
+
+    fn borrow_conn(&'_ self) -> Result<SqliteConn, Report<TwoFactorRepositoryError>> {
+        self.sqlite_client
+            .borrow_conn()
+            .change_context(TwoFactorRepositoryError::BorrowConnError)
+    }
+}
+
+fn replace_recovery_code(
+    tx: &Transaction,
+    user_id: i64,
+    code_hashes: &[String],
+) -> Result<(), Report<TwoFactorRepositoryError>> {
+    tx.execute(
+        include_str!("_sql/two_factor_repository/delete_recovery_code.sql"),
+        named_params! {
+            ":user_id": user_id,
+        },
+    )
+    .change_context(TwoFactorRepositoryError::QueryError)
+    .attach(StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    for code_hash in code_hashes {
+        tx.execute(
+            include_str!("_sql/two_factor_repository/add_recovery_code.sql"),
+            named_params! {
+                ":user_id": user_id,
+                ":code_hash": code_hash,
+            },
+        )
+        .change_context(TwoFactorRepositoryError::QueryError)
+        .attach(StatusCode::INTERNAL_SERVER_ERROR)?;
+    }
+
+    Ok(())
+}
+
+#[mry::mry]
+impl TwoFactorRepository {
+    pub fn fetch_two_factor(
+        &self,
+        user_id: i64,
+    ) -> Result<TwoFactorState, Report<TwoFactorRepositoryError>> {
+        run_blocking(|| {
+            let conn = self.borrow_conn()?;
+
+            let mut stmt = conn
+                .prepare_cached(include_str!(
+                    "_sql/two_factor_repository/fetch_two_factor.sql"
+                ))
+                .change_context(TwoFactorRepositoryError::QueryError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)?;
+
+            let row: Option<TwoFactorState> = stmt
+                .query_one(
+                    named_params! {
+                        ":user_id": user_id,
+                    },
+                    |row| {
+                        Ok(TwoFactorState {
+                            secret: row.get("totp_secret")?,
+                            enabled: row.get("totp_enabled")?,
+                            last_step: row.get("totp_last_step")?,
+                        })
+                    },
+                )
+                .optional()
+                .change_context(TwoFactorRepositoryError::RowValueError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)?;
+
+            match row {
+                Some(row) => Ok(row),
+                None => Err(Report::new(TwoFactorRepositoryError::NotFoundError)
+                    .attach(StatusCode::NOT_FOUND)),
+            }
+        })
+    }
+
+    /// Leaves an enabled secret alone, only an unconfirmed enrolment can be replaced.
+    pub fn set_pending_secret(
+        &self,
+        user_id: i64,
+        secret: Box<[u8]>,
+    ) -> Result<(), Report<TwoFactorRepositoryError>> {
+        run_blocking(|| {
+            let conn = self.borrow_conn()?;
+
+            conn.execute(
+                include_str!("_sql/two_factor_repository/set_pending_secret.sql"),
+                named_params! {
+                    ":user_id": user_id,
+                    ":secret": secret,
+                },
+            )
+            .change_context(TwoFactorRepositoryError::QueryError)
+            .attach(StatusCode::INTERNAL_SERVER_ERROR)?;
+
+            Ok(())
+        })
+    }
+
+    pub fn enable_two_factor(
+        &self,
+        user_id: i64,
+        step: i64,
+        code_hashes: Vec<String>,
+    ) -> Result<(), Report<TwoFactorRepositoryError>> {
+        run_blocking(|| {
+            let mut conn = self.borrow_conn()?;
+
+            let tx = conn
+                .transaction_with_behavior(TransactionBehavior::Immediate)
+                .change_context(TwoFactorRepositoryError::QueryError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)?;
+
+            tx.execute(
+                include_str!("_sql/two_factor_repository/enable_two_factor.sql"),
+                named_params! {
+                    ":user_id": user_id,
+                    ":step": step,
+                },
+            )
+            .change_context(TwoFactorRepositoryError::QueryError)
+            .attach(StatusCode::INTERNAL_SERVER_ERROR)?;
+
+            replace_recovery_code(&tx, user_id, &code_hashes)?;
+
+            tx.commit()
+                .change_context(TwoFactorRepositoryError::QueryError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)
+        })
+    }
+
+    pub fn replace_recovery_code(
+        &self,
+        user_id: i64,
+        code_hashes: Vec<String>,
+    ) -> Result<(), Report<TwoFactorRepositoryError>> {
+        run_blocking(|| {
+            let mut conn = self.borrow_conn()?;
+
+            let tx = conn
+                .transaction_with_behavior(TransactionBehavior::Immediate)
+                .change_context(TwoFactorRepositoryError::QueryError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)?;
+
+            replace_recovery_code(&tx, user_id, &code_hashes)?;
+
+            tx.commit()
+                .change_context(TwoFactorRepositoryError::QueryError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)
+        })
+    }
+
+    pub fn disable_two_factor(&self, user_id: i64) -> Result<(), Report<TwoFactorRepositoryError>> {
+        run_blocking(|| {
+            let mut conn = self.borrow_conn()?;
+
+            let tx = conn
+                .transaction_with_behavior(TransactionBehavior::Immediate)
+                .change_context(TwoFactorRepositoryError::QueryError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)?;
+
+            tx.execute(
+                include_str!("_sql/two_factor_repository/disable_two_factor.sql"),
+                named_params! {
+                    ":user_id": user_id,
+                },
+            )
+            .change_context(TwoFactorRepositoryError::QueryError)
+            .attach(StatusCode::INTERNAL_SERVER_ERROR)?;
+
+            replace_recovery_code(&tx, user_id, &[])?;
+
+            tx.commit()
+                .change_context(TwoFactorRepositoryError::QueryError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)
+        })
+    }
+
+    /// Returns false when the step was already spent, so a code cannot be replayed.
+    pub fn use_totp_step(
+        &self,
+        user_id: i64,
+        step: i64,
+    ) -> Result<bool, Report<TwoFactorRepositoryError>> {
+        run_blocking(|| {
+            let conn = self.borrow_conn()?;
+
+            let updated = conn
+                .execute(
+                    include_str!("_sql/two_factor_repository/use_totp_step.sql"),
+                    named_params! {
+                        ":user_id": user_id,
+                        ":step": step,
+                    },
+                )
+                .change_context(TwoFactorRepositoryError::QueryError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)?;
+
+            Ok(updated > 0)
+        })
+    }
+
+    pub fn use_recovery_code(
+        &self,
+        user_id: i64,
+        code_hash: String,
+    ) -> Result<bool, Report<TwoFactorRepositoryError>> {
+        run_blocking(|| {
+            let conn = self.borrow_conn()?;
+
+            let updated = conn
+                .execute(
+                    include_str!("_sql/two_factor_repository/use_recovery_code.sql"),
+                    named_params! {
+                        ":user_id": user_id,
+                        ":code_hash": code_hash,
+                    },
+                )
+                .change_context(TwoFactorRepositoryError::QueryError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)?;
+
+            Ok(updated > 0)
+        })
+    }
+
+    pub fn count_recovery_code(
+        &self,
+        user_id: i64,
+    ) -> Result<i64, Report<TwoFactorRepositoryError>> {
+        run_blocking(|| {
+            let conn = self.borrow_conn()?;
+
+            let mut stmt = conn
+                .prepare_cached(include_str!(
+                    "_sql/two_factor_repository/count_recovery_code.sql"
+                ))
+                .change_context(TwoFactorRepositoryError::QueryError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)?;
+
+            stmt.query_one(
+                named_params! {
+                    ":user_id": user_id,
+                },
+                |row| row.get("remaining"),
+            )
+            .change_context(TwoFactorRepositoryError::RowValueError)
+            .attach(StatusCode::INTERNAL_SERVER_ERROR)
+        })
+    }
+
+    pub fn add_challenge(
+        &self,
+        token: String,
+        user_id: i64,
+    ) -> Result<(), Report<TwoFactorRepositoryError>> {
+        run_blocking(|| {
+            let conn = self.borrow_conn()?;
+
+            conn.execute(
+                include_str!("_sql/two_factor_repository/add_challenge.sql"),
+                named_params! {
+                    ":token": token,
+                    ":user_id": user_id,
+                },
+            )
+            .change_context(TwoFactorRepositoryError::QueryError)
+            .attach(StatusCode::INTERNAL_SERVER_ERROR)?;
+
+            Ok(())
+        })
+    }
+
+    pub fn find_challenge(
+        &self,
+        token: String,
+    ) -> Result<Option<LoginChallenge>, Report<TwoFactorRepositoryError>> {
+        run_blocking(|| {
+            let conn = self.borrow_conn()?;
+
+            let mut stmt = conn
+                .prepare_cached(include_str!(
+                    "_sql/two_factor_repository/find_challenge.sql"
+                ))
+                .change_context(TwoFactorRepositoryError::QueryError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)?;
+
+            stmt.query_one(
+                named_params! {
+                    ":token": token,
+                },
+                |row| {
+                    Ok(LoginChallenge {
+                        user_id: row.get("user_id")?,
+                        username: row.get("username")?,
+                    })
+                },
+            )
+            .optional()
+            .change_context(TwoFactorRepositoryError::RowValueError)
+            .attach(StatusCode::INTERNAL_SERVER_ERROR)
+        })
+    }
+
+    /// Also sweeps challenges that expired without being answered.
+    pub fn delete_challenge(&self, token: String) -> Result<(), Report<TwoFactorRepositoryError>> {
+        run_blocking(|| {
+            let conn = self.borrow_conn()?;
+
+            conn.execute(
+                include_str!("_sql/two_factor_repository/delete_challenge.sql"),
+                named_params! {
+                    ":token": token,
+                },
+            )
+            .change_context(TwoFactorRepositoryError::QueryError)
+            .attach(StatusCode::INTERNAL_SERVER_ERROR)?;
+
+            Ok(())
+        })
+    }
+}
+
+#[cfg(test)]
+impl TwoFactorRepository {
+    pub fn new_mock() -> Self {
+        mry::new!(Self {
+            sqlite_client: None
+        })
+    }
+}
+
+impl FromContext for TwoFactorRepository {
+    async fn from_context(ctx: &'_ Context<'_>) -> Result<Self, Report<ContextError>> {
+        Ok(Self::new(ctx.inject().await?))
+    }
+}
@@ -1,11 +1,11 @@
-use crate::user::model::user_manager_model::{FetchPassword, FetchUser, ListUser};
+use crate::user::model::user_manager_model::{FetchPassword, FetchUser, ListUser, ListUserQuery};
 use crate::user::role::Role;
 use error_stack::{Report, ResultExt};
 use poem::http::StatusCode;
-use rusqlite::{Connection, OptionalExtension, named_params};
+use rusqlite::{OptionalExtension, named_params};
 use shared::utils::context::{Context, ContextError, FromContext};
-use shared::utils::db::{BorrowConnectionExt, SqliteClient};
-use std::sync::{Arc, MutexGuard};
+use shared::utils::db::{BorrowConnectionExt, SqliteClient, SqliteConn, run_blocking};
+use shared::utils::query_string::list_query::{Cursor, KeysetPage, SortKey};
 use thiserror::Error;
 
 #[derive(Debug, Error)]
@@ -31,9 +31,7 @@ impl UserManagerRepository {
         }
     }
 
-    fn borrow_conn(
-        &'_ self,
-    ) -> Result<MutexGuard<'_, Connection>, Report<UserManagerRepositoryError>> {
+    fn borrow_conn(&'_ self) -> Result<SqliteConn, Report<UserManagerRepositoryError>> {
         self.sqlite_client
             .borrow_conn()
             .change_context(UserManagerRepositoryError::BorrowConnError)
@@ -47,39 +45,94 @@ impl UserManagerRepository {
         username: String,
         password: Box<[u8]>,
         role: &Role,
+        must_change_password: bool,
     ) -> Result<(), Report<UserManagerRepositoryError>> {
-        let conn = self.borrow_conn()?;
-
-        conn.execute(
-            include_str!("_sql/user_manager_repository/add_user.sql"),
-            named_params! {
-                ":username": username,
-                ":password": password,
-                ":role": role.as_stringed(),
-            },
-        )
-        .change_context(UserManagerRepositoryError::QueryError)
-        .attach(StatusCode::INTERNAL_SERVER_ERROR)?;
-        Ok(())
+        run_blocking(|| {
+            let conn = self.borrow_conn()?;
+
+            conn.execute(
+                include_str!("_sql/user_manager_repository/add_user.sql"),
+                named_params! {
+                    ":username": username,
+                    ":password": password,
+                    ":role": role.as_stringed(),
+                    ":must_change_password": must_change_password,
+                },
+            )
+            .change_context(UserManagerRepositoryError::QueryError)
+            .attach(StatusCode::INTERNAL_SERVER_ERROR)?;
+            Ok(())
+        })
+    }
+
+    /// Only inserts while no root exists, returns false when another request got there first.
+    pub fn add_first_root(
+        &self,
+        username: String,
+        password: Box<[u8]>,
+    ) -> Result<bool, Report<UserManagerRepositoryError>> {
+        run_blocking(|| {
+            let conn = self.borrow_conn()?;
+
+            let inserted = conn
+                .execute(
+                    include_str!("_sql/user_manager_repository/add_first_root.sql"),
+                    named_params! {
+                        ":username": username,
+                        ":password": password,
+                        ":role": Role::Root.as_stringed(),
+                    },
+                )
+                .change_context(UserManagerRepositoryError::QueryError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)?;
+            Ok(inserted > 0)
+        })
+    }
+
+    pub fn root_exists(&self) -> Result<bool, Report<UserManagerRepositoryError>> {
+        run_blocking(|| {
+            let conn = self.borrow_conn()?;
+
+            let mut stmt = conn
+                .prepare_cached(include_str!("_sql/user_manager_repository/root_exists.sql"))
+                .change_context(UserManagerRepositoryError::QueryError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)?;
+
+            let root_exists: bool = stmt
+                .query_one(
+                    named_params! {
+                        ":role": Role::Root.as_stringed(),
+                    },
+                    |row| row.get("root_exists"),
+                )
+                .change_context(UserManagerRepositoryError::RowValueError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)?;
+
+            Ok(root_exists)
+        })
     }
 
     pub fn edit_password(
         &self,
         id: i64,
         password: Box<[u8]>,
+        must_change_password: bool,
     ) -> Result<(), Report<UserManagerRepositoryError>> {
-        let conn = self.borrow_conn()?;
-
-        conn.execute(
-            include_str!("_sql/user_manager_repository/edit_password.sql"),
-            named_params! {
-                ":id": id,
-                ":password": password,
-            },
-        )
-        .change_context(UserManagerRepositoryError::QueryError)
-        .attach(StatusCode::INTERNAL_SERVER_ERROR)?;
-        Ok(())
+        run_blocking(|| {
+            let conn = self.borrow_conn()?;
+
+            conn.execute(
+                include_str!("_sql/user_manager_repository/edit_password.sql"),
+                named_params! {
+                    ":id": id,
+                    ":password": password,
+                    ":must_change_password": must_change_password,
+                },
+            )
+            .change_context(UserManagerRepositoryError::QueryError)
+            .attach(StatusCode::INTERNAL_SERVER_ERROR)?;
+            Ok(())
+        })
     }
 
     pub fn edit_user(
@@ -88,145 +141,178 @@ impl UserManagerRepository {
         username: String,
         role: &Role,
     ) -> Result<(), Report<UserManagerRepositoryError>> {
-        let conn = self.borrow_conn()?;
-        conn.execute(
-            include_str!("_sql/user_manager_repository/edit_user.sql"),
-            named_params! {
-                ":id": id,
-                ":username": username,
-                ":role": role.as_stringed(),
-            },
-        )
-        .change_context(UserManagerRepositoryError::QueryError)
-        .attach(StatusCode::INTERNAL_SERVER_ERROR)?;
-        Ok(())
+        run_blocking(|| {
+            let conn = self.borrow_conn()?;
+            conn.execute(
+                include_str!("_sql/user_manager_repository/edit_user.sql"),
+                named_params! {
+                    ":id": id,
+                    ":username": username,
+                    ":role": role.as_stringed(),
+                },
+            )
+            .change_context(UserManagerRepositoryError::QueryError)
+            .attach(StatusCode::INTERNAL_SERVER_ERROR)?;
+            Ok(())
+        })
     }
 
     pub fn fetch_user(
         &self,
         id: i64,
     ) -> Result<Option<FetchUser>, Report<UserManagerRepositoryError>> {
-        let conn = self.borrow_conn()?;
-        let mut stmt = conn
-            .prepare_cached(include_str!("_sql/user_manager_repository/fetch_user.sql"))
-            .change_context(UserManagerRepositoryError::QueryError)
-            .attach(StatusCode::INTERNAL_SERVER_ERROR)?;
-        let row: Option<FetchUser> = stmt
-            .query_one(
-                named_params! {
-                    ":id": id
-                },
-                |row| {
-                    Ok(FetchUser {
-                        username: row.get("username")?,
-                        role: Role::try_from(row.get::<_, String>("role")?.as_str())
-                            .unwrap_or_default(),
-                    })
-                },
-            )
-            .optional()
-            .change_context(UserManagerRepositoryError::RowValueError)
-            .attach(StatusCode::INTERNAL_SERVER_ERROR)?;
-        Ok(row)
+        run_blocking(|| {
+            let conn = self.borrow_conn()?;
+            let mut stmt = conn
+                .prepare_cached(include_str!("_sql/user_manager_repository/fetch_user.sql"))
+                .change_context(UserManagerRepositoryError::QueryError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)?;
+            let row: Option<FetchUser> = stmt
+                .query_one(
+                    named_params! {
+                        ":id": id
+                    },
+                    |row| {
+                        Ok(FetchUser {
+                            username: row.get("username")?,
+                            role: Role::try_from(row.get::<_, String>("role")?.as_str())
+                                .unwrap_or_default(),
+                            two_factor_enabled: row.get("totp_enabled")?,
+                        })
+                    },
+                )
+                .optional()
+                .change_context(UserManagerRepositoryError::RowValueError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)?;
+            Ok(row)
+        })
     }
 
-    pub fn list_users(&self) -> Result<Arc<[ListUser]>, Report<UserManagerRepositoryError>> {
-        let conn = self.borrow_conn()?;
-        let mut stmt = conn
-            .prepare_cached(include_str!("_sql/user_manager_repository/list_users.sql"))
-            .change_context(UserManagerRepositoryError::QueryError)
-            .attach(StatusCode::INTERNAL_SERVER_ERROR)?;
-        let rows = stmt
-            .query_map(named_params! {}, |row| {
-                Ok(ListUser {
-                    id: row.get("id")?,
-                    username: row.get("username")?,
-                    role: Role::try_from(row.get::<_, String>("role")?.as_str())
-                        .unwrap_or_default(),
-                })
-            })
-            .change_context(UserManagerRepositoryError::RowValueError)?;
-
-        let users = rows
-            .collect::<Result<Vec<_>, _>>()
-            .change_context(UserManagerRepositoryError::RowValueError)?;
-
-        Ok(users.into())
+    pub fn list_users(
+        &self,
+        list_query: &ListUserQuery,
+    ) -> Result<KeysetPage<ListUser>, Report<UserManagerRepositoryError>> {
+        run_blocking(|| {
+            let conn = self.borrow_conn()?;
+            let mut stmt = conn
+                .prepare_cached(include_str!("_sql/user_manager_repository/list_users.sql"))
+                .change_context(UserManagerRepositoryError::QueryError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)?;
+            let cursor = list_query.cursor();
+            let rows = stmt
+                .query_map(
+                    named_params! {
+                        ":role": list_query.filter.role.map(|role| role.as_stringed()),
+                        ":sort": list_query.sort.as_str(),
+                        ":scan_desc": list_query.scan_desc(),
+                        ":cursor_key": cursor.map(|cursor| cursor.key.as_str()),
+                        ":cursor_id": cursor.map(|cursor| cursor.id),
+                        ":limit": list_query.fetch_limit(),
+                    },
+                    |row| {
+                        Ok((
+                            ListUser {
+                                id: row.get("id")?,
+                                username: row.get("username")?,
+                                role: Role::try_from(row.get::<_, String>("role")?.as_str())
+                                    .unwrap_or_default(),
+                                locked: row.get("locked")?,
+                            },
+                            Cursor {
+                                key: row.get("sort_key")?,
+                                id: row.get("id")?,
+                            },
+                        ))
+                    },
+                )
+                .change_context(UserManagerRepositoryError::RowValueError)?;
+
+            let users = rows
+                .collect::<Result<Vec<_>, _>>()
+                .change_context(UserManagerRepositoryError::RowValueError)?;
+
+            Ok(KeysetPage::from_rows(list_query, users))
+        })
     }
 
     pub fn revoke_all_token_by_id(
         &self,
         user_id: i64,
     ) -> Result<(), Report<UserManagerRepositoryError>> {
-        let conn = self.borrow_conn()?;
-
-        conn.execute(
-            include_str!("_sql/user_manager_repository/revoke_all_token_by_id.sql"),
-            named_params! {
-                ":user_id": user_id,
-            },
-        )
-        .change_context(UserManagerRepositoryError::QueryError)
-        .attach(StatusCode::INTERNAL_SERVER_ERROR)?;
-        Ok(())
+        run_blocking(|| {
+            let conn = self.borrow_conn()?;
+
+            conn.execute(
+                include_str!("_sql/user_manager_repository/revoke_all_token_by_id.sql"),
+                named_params! {
+                    ":user_id": user_id,
+                },
+            )
+            .change_context(UserManagerRepositoryError::QueryError)
+            .attach(StatusCode::INTERNAL_SERVER_ERROR)?;
+            Ok(())
+        })
     }
 
     pub fn username_taken(
         &self,
         username: String,
     ) -> Result<bool, Report<UserManagerRepositoryError>> {
-        let conn = self.borrow_conn()?;
+        run_blocking(|| {
+            let conn = self.borrow_conn()?;
 
-        let mut stmt = conn
-            .prepare_cached(include_str!(
-                "_sql/user_manager_repository/username_taken.sql"
-            ))
-            .change_context(UserManagerRepositoryError::QueryError)
-            .attach(StatusCode::INTERNAL_SERVER_ERROR)?;
+            let mut stmt = conn
+                .prepare_cached(include_str!(
+                    "_sql/user_manager_repository/username_taken.sql"
+                ))
+                .change_context(UserManagerRepositoryError::QueryError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)?;
 
-        let row: Option<bool> = stmt
-            .query_one(
-                named_params! {
-                    ":username": username
-                },
-                |row| Ok(row.get("taken")?),
-            )
-            .optional()
-            .change_context(UserManagerRepositoryError::RowValueError)
-            .attach(StatusCode::INTERNAL_SERVER_ERROR)?;
+            let row: Option<bool> = stmt
+                .query_one(
+                    named_params! {
+                        ":username": username
+                    },
+                    |row| Ok(row.get("taken")?),
+                )
+                .optional()
+                .change_context(UserManagerRepositoryError::RowValueError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)?;
 
-        Ok(row.unwrap_or_default())
+            Ok(row.unwrap_or_default())
+        })
     }
 
-    #[allow(dead_code)]
     pub fn fetch_password(
         &self,
         user_id: i64,
     ) -> Result<FetchPassword, Report<UserManagerRepositoryError>> {
-        let conn = self.borrow_conn()?;
+        run_blocking(|| {
+            let conn = self.borrow_conn()?;
 
-        let mut stmt = conn
-            .prepare_cached(include_str!(
-                "_sql/user_manager_repository/fetch_password.sql"
-            ))
-            .change_context(UserManagerRepositoryError::QueryError)
-            .attach(StatusCode::INTERNAL_SERVER_ERROR)?;
+            let mut stmt = conn
+                .prepare_cached(include_str!(
+                    "_sql/user_manager_repository/fetch_password.sql"
+                ))
+                .change_context(UserManagerRepositoryError::QueryError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)?;
 
-        let row = stmt
-            .query_one(
-                named_params! {
-                    ":id": user_id
-                },
-                |row| {
-                    Ok(FetchPassword {
-                        password: row.get("password")?,
-                    })
-                },
-            )
-            .change_context(UserManagerRepositoryError::RowValueError)
-            .attach(StatusCode::INTERNAL_SERVER_ERROR)?;
+            let row = stmt
+                .query_one(
+                    named_params! {
+                        ":id": user_id
+                    },
+                    |row| {
+                        Ok(FetchPassword {
+                            password: row.get("password")?,
+                        })
+                    },
+                )
+                .change_context(UserManagerRepositoryError::RowValueError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)?;
 
-        Ok(row)
+            Ok(row)
+        })
     }
 }
 
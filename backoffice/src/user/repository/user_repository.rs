@@ -1,11 +1,10 @@
-use crate::user::model::user_model::{IdPassword, UserIdContext};
+use crate::user::model::user_model::{IdPassword, LoginSession, UserIdContext};
 use crate::user::role::Role;
 use error_stack::{Report, ResultExt};
 use poem::http::StatusCode;
-use rusqlite::{Connection, OptionalExtension, named_params};
+use rusqlite::{OptionalExtension, named_params};
 use shared::utils::context::{Context, ContextError, FromContext};
-use shared::utils::db::{BorrowConnectionExt, SqliteClient};
-use std::sync::MutexGuard;
+use shared::utils::db::{BorrowConnectionExt, SqliteClient, SqliteConn, run_blocking};
 use thiserror::Error;
 
 #[derive(Debug, Error)]
@@ -33,7 +32,7 @@ impl UserRepository {
         }
     }
 
-    fn borrow_conn(&'_ self) -> Result<MutexGuard<'_, Connection>, Report<UserRepositoryError>> {
+    fn borrow_conn(&'_ self) -> Result<SqliteConn, Report<UserRepositoryError>> {
         self.sqlite_client
             .borrow_conn()
             .change_context(UserRepositoryError::BorrowConnError)
@@ -46,107 +45,311 @@ impl UserRepository {
         &self,
         token: String,
         user_id: i64,
+        ip: String,
+        user_agent: String,
+        lifetime_days: i64,
     ) -> Result<(), Report<UserRepositoryError>> {
-        let conn = self.borrow_conn()?;
+        run_blocking(|| {
+            let conn = self.borrow_conn()?;
 
-        conn.execute(
-            include_str!("_sql/user_repository/add_token.sql"),
-            named_params! {
-                ":token": token,
-                ":user_id": user_id,
-            },
-        )
-        .change_context(UserRepositoryError::QueryError)
-        .attach(StatusCode::INTERNAL_SERVER_ERROR)?;
+            conn.execute(
+                include_str!("_sql/user_repository/add_token.sql"),
+                named_params! {
+                    ":token": token,
+                    ":user_id": user_id,
+                    ":ip": ip,
+                    ":user_agent": user_agent,
+                    ":lifetime_days": lifetime_days,
+                },
+            )
+            .change_context(UserRepositoryError::QueryError)
+            .attach(StatusCode::INTERNAL_SERVER_ERROR)?;
 
-        Ok(())
+            Ok(())
+        })
     }
 
     pub fn delete_token(&self, token: String) -> Result<(), Report<UserRepositoryError>> {
-        let conn = self.borrow_conn()?;
+        run_blocking(|| {
+            let conn = self.borrow_conn()?;
 
-        conn.execute(
-            include_str!("_sql/user_repository/delete_token.sql"),
-            named_params! {
-                ":token": token,
-            },
-        )
-        .change_context(UserRepositoryError::QueryError)
-        .attach(StatusCode::INTERNAL_SERVER_ERROR)?;
+            conn.execute(
+                include_str!("_sql/user_repository/delete_token.sql"),
+                named_params! {
+                    ":token": token,
+                },
+            )
+            .change_context(UserRepositoryError::QueryError)
+            .attach(StatusCode::INTERNAL_SERVER_ERROR)?;
 
-        Ok(())
+            Ok(())
+        })
     }
 
-    pub fn find_by_token(
+    /// Pushes the expiry back, returns false when the token was renewed too recently to bother.
+    pub fn renew_token(
         &self,
         token: String,
-    ) -> Result<UserIdContext, Report<UserRepositoryError>> {
-        let conn = self.borrow_conn()?;
+        lifetime_days: i64,
+        renew_interval_secs: i64,
+    ) -> Result<bool, Report<UserRepositoryError>> {
+        run_blocking(|| {
+            let conn = self.borrow_conn()?;
+
+            let updated = conn
+                .execute(
+                    include_str!("_sql/user_repository/renew_token.sql"),
+                    named_params! {
+                        ":token": token,
+                        ":lifetime_days": lifetime_days,
+                        ":renew_interval_secs": renew_interval_secs,
+                    },
+                )
+                .change_context(UserRepositoryError::QueryError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)?;
 
-        let mut stmt = conn
-            .prepare_cached(include_str!("_sql/user_repository/find_by_token.sql"))
+            Ok(updated > 0)
+        })
+    }
+
+    pub fn list_session(
+        &self,
+        user_id: i64,
+        token: String,
+    ) -> Result<Vec<LoginSession>, Report<UserRepositoryError>> {
+        run_blocking(|| {
+            let conn = self.borrow_conn()?;
+
+            let mut stmt = conn
+                .prepare_cached(include_str!("_sql/user_repository/list_session.sql"))
+                .change_context(UserRepositoryError::QueryError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)?;
+
+            let rows = stmt
+                .query_map(
+                    named_params! {
+                        ":user_id": user_id,
+                        ":token": token,
+                    },
+                    |row| {
+                        Ok(LoginSession {
+                            id: row.get("id")?,
+                            created_at: row.get("created_at")?,
+                            last_seen_at: row.get("last_seen_at")?,
+                            ip: row.get("ip")?,
+                            user_agent: row.get("user_agent")?,
+                            current: row.get("current")?,
+                        })
+                    },
+                )
+                .change_context(UserRepositoryError::QueryError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)?;
+
+            rows.collect::<Result<Vec<_>, _>>()
+                .change_context(UserRepositoryError::RowValueError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)
+        })
+    }
+
+    /// Scoped to the user, so one user cannot revoke another user's session by id.
+    pub fn delete_session(
+        &self,
+        user_id: i64,
+        session_id: i64,
+    ) -> Result<bool, Report<UserRepositoryError>> {
+        run_blocking(|| {
+            let conn = self.borrow_conn()?;
+
+            let deleted = conn
+                .execute(
+                    include_str!("_sql/user_repository/delete_session.sql"),
+                    named_params! {
+                        ":user_id": user_id,
+                        ":session_id": session_id,
+                    },
+                )
+                .change_context(UserRepositoryError::QueryError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)?;
+
+            Ok(deleted > 0)
+        })
+    }
+
+    pub fn delete_user_token(&self, user_id: i64) -> Result<(), Report<UserRepositoryError>> {
+        run_blocking(|| {
+            let conn = self.borrow_conn()?;
+
+            conn.execute(
+                include_str!("_sql/user_repository/delete_user_token.sql"),
+                named_params! {
+                    ":user_id": user_id,
+                },
+            )
             .change_context(UserRepositoryError::QueryError)
             .attach(StatusCode::INTERNAL_SERVER_ERROR)?;
 
-        let row: Option<UserIdContext> = stmt
-            .query_one(
+            Ok(())
+        })
+    }
+
+    /// Tokens written before they were stored hashed.
+    pub fn list_unhashed_token(&self) -> Result<Vec<String>, Report<UserRepositoryError>> {
+        run_blocking(|| {
+            let conn = self.borrow_conn()?;
+
+            let mut stmt = conn
+                .prepare_cached(include_str!("_sql/user_repository/list_unhashed_token.sql"))
+                .change_context(UserRepositoryError::QueryError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)?;
+
+            let rows = stmt
+                .query_map(named_params! {}, |row| row.get("token"))
+                .change_context(UserRepositoryError::QueryError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)?;
+
+            rows.collect::<Result<Vec<_>, _>>()
+                .change_context(UserRepositoryError::RowValueError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)
+        })
+    }
+
+    pub fn set_token_hash(
+        &self,
+        token: String,
+        token_hash: String,
+    ) -> Result<(), Report<UserRepositoryError>> {
+        run_blocking(|| {
+            let conn = self.borrow_conn()?;
+
+            conn.execute(
+                include_str!("_sql/user_repository/set_token_hash.sql"),
                 named_params! {
                     ":token": token,
-                },
-                |row| {
-                    Ok(UserIdContext {
-                        id: row.get("id")?,
-                        username: row.get("username")?,
-                        role: Role::try_from(row.get::<_, String>("role")?.as_str())
-                            .unwrap_or_default(),
-                    })
+                    ":token_hash": token_hash,
                 },
             )
-            .optional()
-            .change_context(UserRepositoryError::RowValueError)
+            .change_context(UserRepositoryError::QueryError)
             .attach(StatusCode::INTERNAL_SERVER_ERROR)?;
 
-        match row {
-            Some(row) => Ok(row),
-            None => {
-                Err(Report::new(UserRepositoryError::NotFoundError).attach(StatusCode::NOT_FOUND))
+            Ok(())
+        })
+    }
+
+    pub fn purge_expired_token(&self) -> Result<usize, Report<UserRepositoryError>> {
+        run_blocking(|| {
+            let conn = self.borrow_conn()?;
+
+            conn.execute(
+                include_str!("_sql/user_repository/purge_expired_token.sql"),
+                named_params! {},
+            )
+            .change_context(UserRepositoryError::QueryError)
+            .attach(StatusCode::INTERNAL_SERVER_ERROR)
+        })
+    }
+
+    pub fn find_by_token(
+        &self,
+        token: String,
+    ) -> Result<UserIdContext, Report<UserRepositoryError>> {
+        run_blocking(|| {
+            let conn = self.borrow_conn()?;
+
+            let mut stmt = conn
+                .prepare_cached(include_str!("_sql/user_repository/find_by_token.sql"))
+                .change_context(UserRepositoryError::QueryError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)?;
+
+            let row: Option<UserIdContext> = stmt
+                .query_one(
+                    named_params! {
+                        ":token": token,
+                    },
+                    |row| {
+                        Ok(UserIdContext {
+                            id: row.get("id")?,
+                            username: row.get("username")?,
+                            role: Role::try_from(row.get::<_, String>("role")?.as_str())
+                                .unwrap_or_default(),
+                            must_change_password: row.get("must_change_password")?,
+                            two_factor_enabled: row.get("totp_enabled")?,
+                        })
+                    },
+                )
+                .optional()
+                .change_context(UserRepositoryError::RowValueError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)?;
+
+            match row {
+                Some(row) => Ok(row),
+                None => {
+                    Err(Report::new(UserRepositoryError::NotFoundError)
+                        .attach(StatusCode::NOT_FOUND))
+                }
             }
-        }
+        })
     }
 
     pub fn get_user_password(
         &self,
         username: String,
     ) -> Result<IdPassword, Report<UserRepositoryError>> {
-        let conn = self.borrow_conn()?;
+        run_blocking(|| {
+            let conn = self.borrow_conn()?;
 
-        let mut stmt = conn
-            .prepare_cached(include_str!("_sql/user_repository/get_user_password.sql"))
-            .change_context(UserRepositoryError::QueryError)
-            .attach(StatusCode::INTERNAL_SERVER_ERROR)?;
+            let mut stmt = conn
+                .prepare_cached(include_str!("_sql/user_repository/get_user_password.sql"))
+                .change_context(UserRepositoryError::QueryError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)?;
+
+            let row: Option<IdPassword> = stmt
+                .query_one(
+                    named_params! {
+                        ":username": username,
+                    },
+                    |row| {
+                        Ok(IdPassword {
+                            id: row.get("id")?,
+                            password: row.get("password")?,
+                            two_factor_enabled: row.get("totp_enabled")?,
+                        })
+                    },
+                )
+                .optional()
+                .change_context(UserRepositoryError::RowValueError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)?;
+
+            match row {
+                Some(row) => Ok(row),
+                None => {
+                    Err(Report::new(UserRepositoryError::NotFoundError)
+                        .attach(StatusCode::NOT_FOUND))
+                }
+            }
+        })
+    }
+
+    /// Swaps in a rehashed password, leaving `must_change_password` alone.
+    pub fn set_password(
+        &self,
+        user_id: i64,
+        password: Box<[u8]>,
+    ) -> Result<(), Report<UserRepositoryError>> {
+        run_blocking(|| {
+            let conn = self.borrow_conn()?;
 
-        let row: Option<IdPassword> = stmt
-            .query_one(
+            conn.execute(
+                include_str!("_sql/user_repository/set_password.sql"),
                 named_params! {
-                    ":username": username,
-                },
-                |row| {
-                    Ok(IdPassword {
-                        id: row.get("id")?,
-                        password: row.get("password")?,
-                    })
+                    ":id": user_id,
+                    ":password": password,
                 },
             )
-            .optional()
-            .change_context(UserRepositoryError::RowValueError)
+            .change_context(UserRepositoryError::QueryError)
             .attach(StatusCode::INTERNAL_SERVER_ERROR)?;
 
-        match row {
-            Some(row) => Ok(row),
-            None => {
-                Err(Report::new(UserRepositoryError::NotFoundError).attach(StatusCode::NOT_FOUND))
-            }
-        }
+            Ok(())
+        })
     }
 }
 
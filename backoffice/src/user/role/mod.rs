@@ -1,6 +1,7 @@
 use maud::{Markup, html};
 use serde::de::Visitor;
 use serde::{Deserialize, Serialize};
+use std::str::FromStr;
 
 pub mod user_role_check;
 pub mod visitor_only;
@@ -8,6 +9,7 @@ pub mod visitor_only;
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum Role {
     Root,
+    Editor,
     User,
     Visitor,
 }
@@ -58,16 +60,25 @@ impl TryFrom<&str> for Role {
     fn try_from(s: &str) -> Result<Self, Self::Error> {
         match s {
             "root" => Ok(Self::Root),
+            "editor" => Ok(Self::Editor),
             "user" => Ok(Self::User),
             _ => Err(()),
         }
     }
 }
 
+impl FromStr for Role {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::try_from(s).map_err(|_| "invalid role".to_string())
+    }
+}
+
 impl From<&Role> for String {
     fn from(r: &Role) -> Self {
         match r {
             Role::Root => "root".to_string(),
+            Role::Editor => "editor".to_string(),
             Role::User => "user".to_string(),
             Role::Visitor => "visitor".to_string(),
         }
@@ -77,14 +88,15 @@ impl From<&Role> for String {
 impl Role {
     pub fn level(&self) -> u8 {
         match self {
-            Self::Root => 2,
+            Self::Root => 3,
+            Self::Editor => 2,
             Self::User => 1,
             Self::Visitor => 0,
         }
     }
 
     pub fn all_roles() -> Vec<Self> {
-        vec![Self::Root, Self::User]
+        vec![Self::Root, Self::Editor, Self::User]
     }
 
     pub fn as_stringed(&self) -> String {
@@ -28,6 +28,14 @@ where
     UserRoleCheck(Role::User, endpoint.into_endpoint())
 }
 
+pub fn must_be_editor<E>(endpoint: E) -> impl Endpoint
+where
+    E: IntoEndpoint,
+    E::Endpoint: 'static,
+{
+    UserRoleCheck(Role::Editor, endpoint.into_endpoint())
+}
+
 pub fn must_be_root<E>(endpoint: E) -> impl Endpoint
 where
     E: IntoEndpoint,
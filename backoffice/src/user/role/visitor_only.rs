@@ -1,9 +1,11 @@
 use crate::user::pointer::user_pointer::UserPointer;
 use crate::user::role::Role;
-use crate::user::route::login::LOGIN_ROUTE;
+use crate::user::route::login::{CHANGE_PASSWORD_PATH, LOGIN_ROUTE};
+use crate::user::route::two_factor::TWO_FACTOR_PATH;
 use poem::http::StatusCode;
 use poem::web::Redirect;
 use poem::{Endpoint, Error, FromRequest, IntoEndpoint, IntoResponse, Request};
+use shared::utils::config::ConfigPointer;
 use shared::utils::context::Dep;
 
 struct VisitorOnly<E: Endpoint>(E);
@@ -42,6 +44,20 @@ impl<E: Endpoint> Endpoint for VisitorRedirect<E> {
                 Redirect::see_other(LOGIN_ROUTE).into_response(),
             ));
         }
+        if user_context.must_change_password {
+            return Err(Error::from_response(
+                Redirect::see_other(LOGIN_ROUTE.to_owned() + CHANGE_PASSWORD_PATH).into_response(),
+            ));
+        }
+        if !user_context.two_factor_enabled {
+            let Dep(config) = Dep::<ConfigPointer>::from_request_without_body(&req).await?;
+            if config.require_two_factor {
+                return Err(Error::from_response(
+                    Redirect::see_other(LOGIN_ROUTE.to_owned() + TWO_FACTOR_PATH + "/")
+                        .into_response(),
+                ));
+            }
+        }
         self.0.call(req).await
     }
 }
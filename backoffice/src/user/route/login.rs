@@ -1,32 +1,59 @@
 use crate::common::html::context_html::ContextHtmlBuilder;
-use crate::user::LOGIN_TOKEN_COOKIE_NAME;
+use crate::user::form::change_password::ChangePasswordForm;
 use crate::user::form::login::{UserLoginForm, UserLoginFormResult};
-use crate::user::locale::login::{LoginLocale, LoginPostLocale, LogoutLocale};
+use crate::user::form::two_factor::TwoFactorCodeForm;
+use crate::user::locale::login::{
+    ChangePasswordLocale, LoginLocale, LoginPostLocale, LogoutLocale,
+};
+use crate::user::locale::two_factor::TwoFactorVerifyLocale;
+use crate::user::pointer::user_pointer::UserPointer;
 use crate::user::role::user_role_check::must_be_user;
 use crate::user::role::visitor_only::visitor_only;
-use crate::user::service::user_login_service::UserLoginService;
+use crate::user::route::session::{SESSION_PATH, session_route};
+use crate::user::route::setup::SETUP_ROUTE;
+use crate::user::route::two_factor::{TWO_FACTOR_PATH, two_factor_route};
+use crate::user::service::change_password_service::ChangePasswordService;
+use crate::user::service::login_throttle_service::LoginThrottleService;
+use crate::user::service::setup_service::SetupService;
+use crate::user::service::user_login_service::{LoginStep, UserLoginService};
+use crate::user::{
+    LOGIN_CHALLENGE_COOKIE_NAME, LOGIN_TOKEN_COOKIE_NAME, login_token_cookie, remote_ip,
+};
 use chrono::TimeDelta;
 use maud::{Markup, html};
+use poem::http::StatusCode;
 use poem::i18n::Locale;
 use poem::session::Session;
 use poem::web::cookie::{Cookie, CookieJar};
-use poem::web::{CsrfToken, Redirect};
-use poem::{IntoResponse, Response, Route, get, handler};
+use poem::web::{CsrfToken, Redirect, RemoteAddr};
+use poem::{Error, IntoResponse, Response, Route, get, handler};
 use shared::utils::adapter::unified;
 use shared::utils::context::Dep;
 use shared::utils::cookie_builders::CookieBuilderExt;
-use shared::utils::csrf::{CsrfFormQs, CsrfTokenHtml};
+use shared::utils::csrf::{CsrfFormQs, CsrfTokenHtml, csrf_header_check};
+use shared::utils::error::{ExtraResultExt, FromErrorStack};
 use shared::utils::flash::{Flash, FlashMessage};
+use shared::utils::htmx::HtmxHeader;
 
 pub const LOGIN_ROUTE: &str = "/user-login";
+pub const CHANGE_PASSWORD_PATH: &str = "/change-password";
+pub const LOGIN_VERIFY_PATH: &str = "/verify";
 
 #[handler]
 async fn login(
     Dep(context_html_builder): Dep<ContextHtmlBuilder>,
+    Dep(setup_service): Dep<SetupService>,
     csrf_token: &CsrfToken,
-) -> Markup {
+) -> poem::Result<Response> {
+    if setup_service
+        .is_setup_required()
+        .map_err(Error::from_error_stack)?
+    {
+        return Ok(Redirect::see_other(SETUP_ROUTE.to_owned() + "/").into_response());
+    }
+
     let login_locale = LoginLocale::new(&context_html_builder.locale);
-    context_html_builder
+    Ok(context_html_builder
         .attach_title(&login_locale.title)
         .attach_content(html! {
             h1 .mt-3 { (login_locale.title) }
@@ -38,6 +65,7 @@ async fn login(
             }
         })
         .build()
+        .into_response())
 }
 
 enum LoginPostResponse {
@@ -55,32 +83,59 @@ impl IntoResponse for LoginPostResponse {
 #[handler]
 async fn login_post(
     Dep(user_login_service): Dep<UserLoginService>,
+    Dep(login_throttle_service): Dep<LoginThrottleService>,
     CsrfFormQs(user_login_form): CsrfFormQs<UserLoginForm>,
     session: &Session,
     cookie_jar: &CookieJar,
+    remote_addr: &RemoteAddr,
     locale: Locale,
 ) -> LoginPostResponse {
     unified(async {
         let login_post_locale = LoginPostLocale::new(&locale);
         if let UserLoginFormResult(Ok(user_login_form_validated)) = user_login_form.as_validated() {
+            let username = user_login_form_validated.username.as_str().to_string();
+            let ip = remote_ip(remote_addr);
+            // checked before the password so a locked out attacker no longer costs an argon2 verify
+            if login_throttle_service.is_locked(&username, &ip) {
+                session.flash(Flash::Error {
+                    msg: login_post_locale.flash_throttled,
+                });
+                return Err(LoginPostResponse::Redirect(Redirect::see_other(
+                    LOGIN_ROUTE.to_owned() + "/",
+                )));
+            }
+
             let token = user_login_service.validate_login(
-                user_login_form_validated.username.as_str().to_string(),
+                username.clone(),
                 user_login_form_validated.password.as_str().to_string(),
             );
-            if let Some(token) = token {
-                let new_cookie = Cookie::new_with_str(LOGIN_TOKEN_COOKIE_NAME, token)
-                    .into_builder()
-                    .path("/")
-                    .expires_by_delta(TimeDelta::days(30))
-                    .secure()
-                    .http_only()
-                    .build();
-
-                cookie_jar.add(new_cookie);
-                session.flash(Flash::Success {
-                    msg: login_post_locale.flash_success,
-                });
-                return Ok(LoginPostResponse::Redirect(Redirect::see_other("/")));
+            match token {
+                Some(LoginStep::Token(token)) => {
+                    login_throttle_service.record_success(&username, &ip);
+                    cookie_jar.add(login_token_cookie(
+                        token,
+                        user_login_service.session_lifetime(),
+                    ));
+                    session.flash(Flash::Success {
+                        msg: login_post_locale.flash_success,
+                    });
+                    return Ok(LoginPostResponse::Redirect(Redirect::see_other("/")));
+                }
+                Some(LoginStep::TwoFactor(challenge)) => {
+                    let new_cookie = Cookie::new_with_str(LOGIN_CHALLENGE_COOKIE_NAME, challenge)
+                        .into_builder()
+                        .path("/")
+                        .expires_by_delta(TimeDelta::minutes(5))
+                        .secure()
+                        .http_only()
+                        .build();
+
+                    cookie_jar.add(new_cookie);
+                    return Ok(LoginPostResponse::Redirect(Redirect::see_other(
+                        LOGIN_ROUTE.to_owned() + LOGIN_VERIFY_PATH,
+                    )));
+                }
+                None => login_throttle_service.record_failure(&username, &ip),
             }
         }
 
@@ -94,6 +149,88 @@ async fn login_post(
     .await
 }
 
+#[handler]
+async fn login_verify(
+    Dep(context_html_builder): Dep<ContextHtmlBuilder>,
+    cookie_jar: &CookieJar,
+    csrf_token: &CsrfToken,
+) -> Response {
+    if cookie_jar.get(LOGIN_CHALLENGE_COOKIE_NAME).is_none() {
+        return Redirect::see_other(LOGIN_ROUTE.to_owned() + "/").into_response();
+    }
+
+    let verify_locale = TwoFactorVerifyLocale::new(&context_html_builder.locale);
+    context_html_builder
+        .attach_title(&verify_locale.title)
+        .attach_content(html! {
+            h1 .mt-3 { (verify_locale.title) }
+            p { (verify_locale.intro) }
+            form method="post" .form {
+                (csrf_token.as_html_input())
+                input .form-item type="text" name="code" placeholder=(verify_locale.code)
+                    autocomplete="one-time-code" autofocus {}
+                button .btn .btn-sky-blue .mt-3 type="submit" { (verify_locale.confirm_button) }
+            }
+        })
+        .build()
+        .into_response()
+}
+
+#[handler]
+async fn login_verify_post(
+    Dep(user_login_service): Dep<UserLoginService>,
+    Dep(login_throttle_service): Dep<LoginThrottleService>,
+    CsrfFormQs(two_factor_code_form): CsrfFormQs<TwoFactorCodeForm>,
+    session: &Session,
+    cookie_jar: &CookieJar,
+    remote_addr: &RemoteAddr,
+    locale: Locale,
+) -> Redirect {
+    let login_post_locale = LoginPostLocale::new(&locale);
+    let challenge = cookie_jar
+        .get(LOGIN_CHALLENGE_COOKIE_NAME)
+        .map(|cookie| cookie.value_str().to_string());
+    let Some(challenge) = challenge else {
+        return Redirect::see_other(LOGIN_ROUTE.to_owned() + "/");
+    };
+    let Some(username) = user_login_service.challenge_username(&challenge) else {
+        cookie_jar.remove(LOGIN_CHALLENGE_COOKIE_NAME);
+        session.flash(Flash::Error {
+            msg: login_post_locale.flash_failed,
+        });
+        return Redirect::see_other(LOGIN_ROUTE.to_owned() + "/");
+    };
+    let ip = remote_ip(remote_addr);
+    if login_throttle_service.is_locked(&username, &ip) {
+        session.flash(Flash::Error {
+            msg: login_post_locale.flash_throttled,
+        });
+        return Redirect::see_other(LOGIN_ROUTE.to_owned() + LOGIN_VERIFY_PATH);
+    }
+
+    match user_login_service.validate_two_factor(&challenge, &two_factor_code_form.code) {
+        Some(token) => {
+            login_throttle_service.record_success(&username, &ip);
+            cookie_jar.remove(LOGIN_CHALLENGE_COOKIE_NAME);
+            cookie_jar.add(login_token_cookie(
+                token,
+                user_login_service.session_lifetime(),
+            ));
+            session.flash(Flash::Success {
+                msg: login_post_locale.flash_success,
+            });
+            Redirect::see_other("/")
+        }
+        None => {
+            login_throttle_service.record_failure(&username, &ip);
+            session.flash(Flash::Error {
+                msg: login_post_locale.flash_failed,
+            });
+            Redirect::see_other(LOGIN_ROUTE.to_owned() + LOGIN_VERIFY_PATH)
+        }
+    }
+}
+
 #[handler]
 async fn logout(
     Dep(user_login_service): Dep<UserLoginService>,
@@ -110,8 +247,79 @@ async fn logout(
     Redirect::see_other("/")
 }
 
+#[handler]
+async fn change_password_get(
+    Dep(context_html_builder): Dep<ContextHtmlBuilder>,
+    Dep(user_id_context): Dep<UserPointer>,
+    csrf_token: &CsrfToken,
+) -> Markup {
+    if user_id_context.must_change_password {
+        context_html_builder.attach_flash(Flash::Error {
+            msg: ChangePasswordLocale::new(&context_html_builder.locale).flash_required,
+        });
+    }
+
+    ChangePasswordForm::default()
+        .as_form_html(
+            &context_html_builder,
+            None,
+            Some(csrf_token.as_html_input()),
+        )
+        .await
+}
+
+#[handler]
+async fn change_password_post(
+    Dep(context_html_builder): Dep<ContextHtmlBuilder>,
+    Dep(change_password_service): Dep<ChangePasswordService>,
+    CsrfFormQs(change_password_form): CsrfFormQs<ChangePasswordForm>,
+    session: &Session,
+    htmx_header: HtmxHeader,
+    csrf_token: &CsrfToken,
+) -> poem::Result<Response> {
+    let validated_result = change_password_form
+        .as_validated(&change_password_service)
+        .await
+        .0;
+    match validated_result {
+        Ok(validated) => {
+            change_password_service
+                .change_password_submit(&validated)
+                .log_it()
+                .map_err(Error::from_error_stack)?;
+            session.flash(Flash::Success {
+                msg: ChangePasswordLocale::new(&context_html_builder.locale).flash_success,
+            });
+            Ok(htmx_header.do_location(Redirect::see_other("/"), "#main-content"))
+        }
+        Err(error) => {
+            let errors = error.as_message(&context_html_builder.locale);
+            context_html_builder.attach_form_flash_error();
+            Ok(change_password_form
+                .as_form_html(
+                    &context_html_builder,
+                    Some(errors),
+                    Some(csrf_token.as_html_input()),
+                )
+                .await
+                .with_status(StatusCode::UNPROCESSABLE_ENTITY)
+                .into_response())
+        }
+    }
+}
+
 pub fn login_route() -> Route {
     Route::new()
         .at("/", visitor_only(get(login).post(login_post)))
+        .at(
+            LOGIN_VERIFY_PATH,
+            visitor_only(get(login_verify).post(login_verify_post)),
+        )
         .at("/logout", must_be_user(get(logout)))
+        .at(
+            CHANGE_PASSWORD_PATH,
+            must_be_user(get(change_password_get).post(csrf_header_check(change_password_post))),
+        )
+        .nest(TWO_FACTOR_PATH, must_be_user(two_factor_route()))
+        .nest(SESSION_PATH, must_be_user(session_route()))
 }
@@ -0,0 +1,111 @@
+use crate::common::html::context_html::ContextHtmlBuilder;
+use crate::common::icon::trash_icon;
+use crate::user::locale::session::SessionLocale;
+use crate::user::pointer::user_pointer::UserPointer;
+use crate::user::route::login::LOGIN_ROUTE;
+use crate::user::service::session_service::SessionService;
+use maud::{Markup, html};
+use poem::session::Session;
+use poem::web::{Path, Redirect};
+use poem::{Error, Response, Route, delete, get, handler};
+use shared::utils::context::Dep;
+use shared::utils::csrf::csrf_header_check_strict;
+use shared::utils::error::FromErrorStack;
+use shared::utils::flash::{Flash, FlashMessage};
+use shared::utils::htmx::HtmxHeader;
+
+pub const SESSION_PATH: &str = "/sessions";
+
+fn session_path(action: &str) -> String {
+    format!("{}{}{}", LOGIN_ROUTE, SESSION_PATH, action)
+}
+
+#[handler]
+async fn list_session(
+    Dep(context_html_builder): Dep<ContextHtmlBuilder>,
+    Dep(session_service): Dep<SessionService>,
+    Dep(user_pointer): Dep<UserPointer>,
+) -> poem::Result<Markup> {
+    let login_sessions = session_service
+        .list_session(user_pointer.id)
+        .map_err(Error::from_error_stack)?;
+    let revoke_icon = trash_icon();
+
+    let session_locale = SessionLocale::new(&context_html_builder.locale);
+    Ok(context_html_builder
+        .attach_title(&session_locale.title)
+        .attach_content(html! {
+            h1 .mt-3 { (session_locale.title) }
+            p { (session_locale.intro) }
+            table .table-full {
+                thead {
+                    tr {
+                        th { (session_locale.head_device) }
+                        th { (session_locale.head_ip) }
+                        th { (session_locale.head_created) }
+                        th { (session_locale.head_last_seen) }
+                        th .action { (session_locale.head_action) }
+                    }
+                }
+                tbody {
+                    @for login_session in login_sessions.iter() {
+                        tr {
+                            td {
+                                @if login_session.user_agent.is_empty() {
+                                    (session_locale.unknown_device)
+                                } @else {
+                                    (login_session.user_agent)
+                                }
+                            }
+                            td { (login_session.ip) }
+                            td { (login_session.created_at) }
+                            td { (login_session.last_seen_at) }
+                            td .action {
+                                @if login_session.current {
+                                    (session_locale.current)
+                                } @else {
+                                    a .icon .cursor-pointer hx-delete=(session_path(&format!("/{}", login_session.id)))
+                                        hx-confirm=(session_locale.revoke_confirm) title=(session_locale.revoke) {
+                                        (revoke_icon) }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        })
+        .build())
+}
+
+#[handler]
+async fn revoke_session(
+    Dep(context_html_builder): Dep<ContextHtmlBuilder>,
+    Dep(session_service): Dep<SessionService>,
+    Dep(user_pointer): Dep<UserPointer>,
+    Path(session_id): Path<i64>,
+    session: &Session,
+    htmx_header: HtmxHeader,
+) -> poem::Result<Response> {
+    let revoked = session_service
+        .revoke_session(user_pointer.id, session_id)
+        .map_err(Error::from_error_stack)?;
+
+    let session_locale = SessionLocale::new(&context_html_builder.locale);
+    if revoked {
+        session.flash(Flash::Success {
+            msg: session_locale.flash_revoked,
+        });
+    } else {
+        session.flash(Flash::Error {
+            msg: session_locale.flash_not_found,
+        });
+    }
+    Ok(htmx_header.do_location(Redirect::see_other(session_path("/")), "#main-content"))
+}
+
+pub fn session_route() -> Route {
+    Route::new().at("/", get(list_session)).at(
+        "/:session_id",
+        delete(csrf_header_check_strict(revoke_session)),
+    )
+}
@@ -0,0 +1,79 @@
+use crate::common::html::context_html::ContextHtmlBuilder;
+use crate::user::form::setup::SetupForm;
+use crate::user::locale::setup::SetupPostLocale;
+use crate::user::role::visitor_only::visitor_only;
+use crate::user::route::login::LOGIN_ROUTE;
+use crate::user::service::setup_service::SetupService;
+use maud::Markup;
+use poem::http::StatusCode;
+use poem::session::Session;
+use poem::web::{CsrfToken, Redirect};
+use poem::{Error, IntoResponse, Response, Route, get, handler};
+use shared::utils::context::Dep;
+use shared::utils::csrf::{CsrfFormQs, CsrfTokenHtml};
+use shared::utils::error::{ExtraResultExt, FromErrorStack};
+use shared::utils::flash::{Flash, FlashMessage};
+
+pub const SETUP_ROUTE: &str = "/setup";
+
+#[handler]
+async fn setup_get(
+    Dep(context_html_builder): Dep<ContextHtmlBuilder>,
+    Dep(setup_service): Dep<SetupService>,
+    csrf_token: &CsrfToken,
+) -> poem::Result<Markup> {
+    setup_service
+        .ensure_setup_required()
+        .map_err(Error::from_error_stack)?;
+
+    Ok(SetupForm::default()
+        .as_form_html(
+            &context_html_builder,
+            None,
+            Some(csrf_token.as_html_input()),
+        )
+        .await)
+}
+
+#[handler]
+async fn setup_post(
+    Dep(context_html_builder): Dep<ContextHtmlBuilder>,
+    Dep(setup_service): Dep<SetupService>,
+    CsrfFormQs(setup_form): CsrfFormQs<SetupForm>,
+    session: &Session,
+    csrf_token: &CsrfToken,
+) -> poem::Result<Response> {
+    setup_service
+        .ensure_setup_required()
+        .map_err(Error::from_error_stack)?;
+
+    match setup_form.as_validated(&setup_service).await.0 {
+        Ok(validated) => {
+            setup_service
+                .setup_submit(&validated)
+                .log_it()
+                .map_err(Error::from_error_stack)?;
+            session.flash(Flash::Success {
+                msg: SetupPostLocale::new(&context_html_builder.locale).flash_success,
+            });
+            Ok(Redirect::see_other(LOGIN_ROUTE.to_owned() + "/").into_response())
+        }
+        Err(error) => {
+            let errors = error.as_message(&context_html_builder.locale);
+            context_html_builder.attach_form_flash_error();
+            Ok(setup_form
+                .as_form_html(
+                    &context_html_builder,
+                    Some(errors),
+                    Some(csrf_token.as_html_input()),
+                )
+                .await
+                .with_status(StatusCode::UNPROCESSABLE_ENTITY)
+                .into_response())
+        }
+    }
+}
+
+pub fn setup_route() -> Route {
+    Route::new().at("/", visitor_only(get(setup_get).post(setup_post)))
+}
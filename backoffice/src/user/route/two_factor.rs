@@ -0,0 +1,261 @@
+use crate::common::html::context_html::ContextHtmlBuilder;
+use crate::user::form::two_factor::TwoFactorCodeForm;
+use crate::user::locale::two_factor::{
+    TwoFactorLocale, TwoFactorStatusLocale, two_factor_remaining_message,
+    two_factor_reset_confirm_message,
+};
+use crate::user::pointer::user_pointer::UserPointer;
+use crate::user::route::login::LOGIN_ROUTE;
+use crate::user::route::user::USER_ROUTE;
+use crate::user::service::two_factor_service::{TwoFactorEnrolment, TwoFactorService};
+use maud::{Markup, PreEscaped, html};
+use poem::http::StatusCode;
+use poem::i18n::Locale;
+use poem::session::Session;
+use poem::web::{CsrfToken, Redirect};
+use poem::{Error, IntoResponse, Response, Route, get, handler, post};
+use qrcode::QrCode;
+use qrcode::render::svg;
+use shared::utils::config::ConfigPointer;
+use shared::utils::context::Dep;
+use shared::utils::csrf::{CsrfFormQs, CsrfTokenHtml, csrf_header_check};
+use shared::utils::error::FromErrorStack;
+use shared::utils::flash::{Flash, FlashMessage};
+use shared::utils::htmx::HtmxHeader;
+
+pub const TWO_FACTOR_PATH: &str = "/two-factor";
+
+fn two_factor_path(action: &str) -> String {
+    format!("{}{}{}", LOGIN_ROUTE, TWO_FACTOR_PATH, action)
+}
+
+fn qr_code_svg(provisioning_uri: &str) -> Markup {
+    match QrCode::new(provisioning_uri.as_bytes()) {
+        Ok(code) => PreEscaped(code.render::<svg::Color>().min_dimensions(200, 200).build()),
+        Err(_) => html! {},
+    }
+}
+
+fn code_form_partial(
+    two_factor_locale: &TwoFactorLocale,
+    action: &str,
+    button: &str,
+    csrf_token: &CsrfToken,
+) -> Markup {
+    html! {
+        form hx-boost="true" hx-target="#main-content" .form method="post" action=(two_factor_path(action)) {
+            (csrf_token.as_html_input())
+            div .form-group {
+                label .label { (two_factor_locale.code) }
+                input .form-item .w-full type="text" name="code" autocomplete="one-time-code"
+                    placeholder=(two_factor_locale.code_placeholder) {}
+            }
+            div .form-group {
+                input .btn .btn-sky-blue type="submit" value=(button) {}
+            }
+        }
+    }
+}
+
+fn enrolment_html(
+    context_html_builder: &ContextHtmlBuilder,
+    enrolment: &TwoFactorEnrolment,
+    csrf_token: &CsrfToken,
+) -> Markup {
+    let two_factor_locale = TwoFactorLocale::new(&context_html_builder.locale);
+    context_html_builder
+        .attach_title(&two_factor_locale.title)
+        .attach_content(html! {
+            h1 .mt-3 { (two_factor_locale.title) }
+            @match enrolment {
+                TwoFactorEnrolment::Pending { secret, provisioning_uri } => {
+                    p { (two_factor_locale.pending_intro) }
+                    div .mt-3 .bg-white .inline-block .p-3 { (qr_code_svg(provisioning_uri)) }
+                    p .mt-3 { (two_factor_locale.pending_secret) " " code { (secret) } }
+                    (code_form_partial(&two_factor_locale, "/", &two_factor_locale.confirm_button, csrf_token))
+                }
+                TwoFactorEnrolment::Enabled { remaining_recovery_codes } => {
+                    p { (two_factor_locale.enabled_intro) }
+                    p { (two_factor_remaining_message(&context_html_builder.locale, *remaining_recovery_codes)) }
+                    (code_form_partial(&two_factor_locale, "/recovery-codes", &two_factor_locale.regenerate_button, csrf_token))
+                    (code_form_partial(&two_factor_locale, "/disable", &two_factor_locale.disable_button, csrf_token))
+                }
+            }
+        })
+        .build()
+}
+
+fn recovery_codes_html(
+    context_html_builder: &ContextHtmlBuilder,
+    recovery_codes: &[String],
+) -> Markup {
+    let two_factor_locale = TwoFactorLocale::new(&context_html_builder.locale);
+    context_html_builder
+        .attach_title(&two_factor_locale.recovery_title)
+        .attach_content(html! {
+            h1 .mt-3 { (two_factor_locale.recovery_title) }
+            p { (two_factor_locale.recovery_intro) }
+            ul .mt-3 .font-mono {
+                @for recovery_code in recovery_codes.iter() {
+                    li { (recovery_code) }
+                }
+            }
+            a .btn .btn-sky-blue .inline-block .mt-3 href="/"
+                hx-boost="true" hx-push-url="true" hx-target="#main-content" { (two_factor_locale.recovery_continue) }
+        })
+        .build()
+}
+
+/// The two-factor section of the root's edit user page, enrolment itself is left to the user.
+pub fn two_factor_status_partial(
+    locale: &Locale,
+    user_id: i64,
+    username: &str,
+    enabled: bool,
+) -> Markup {
+    let status_locale = TwoFactorStatusLocale::new(locale);
+    html! {
+        h2 .mt-3 { (status_locale.title) }
+        p {
+            @if enabled { (status_locale.enabled) } @else { (status_locale.disabled) }
+        }
+        @if enabled {
+            a .inline-block .mt-3 href=(format!("{}/reset-two-factor/{}", USER_ROUTE, user_id))
+                hx-confirm=(two_factor_reset_confirm_message(locale, username))
+                hx-boost="true" hx-push-url="true" hx-target="#main-content" { (status_locale.reset) }
+        }
+    }
+}
+
+fn invalid_code_response(
+    context_html_builder: &ContextHtmlBuilder,
+    two_factor_service: &TwoFactorService,
+    user_pointer: &UserPointer,
+    csrf_token: &CsrfToken,
+) -> poem::Result<Response> {
+    let enrolment = two_factor_service
+        .enrolment(user_pointer.id, &user_pointer.username)
+        .map_err(Error::from_error_stack)?;
+    context_html_builder.attach_flash(Flash::Error {
+        msg: TwoFactorLocale::new(&context_html_builder.locale).flash_invalid_code,
+    });
+    Ok(enrolment_html(context_html_builder, &enrolment, csrf_token)
+        .with_status(StatusCode::UNPROCESSABLE_ENTITY)
+        .into_response())
+}
+
+#[handler]
+async fn two_factor_get(
+    Dep(context_html_builder): Dep<ContextHtmlBuilder>,
+    Dep(two_factor_service): Dep<TwoFactorService>,
+    Dep(user_pointer): Dep<UserPointer>,
+    Dep(config): Dep<ConfigPointer>,
+    csrf_token: &CsrfToken,
+) -> poem::Result<Markup> {
+    let enrolment = two_factor_service
+        .enrolment(user_pointer.id, &user_pointer.username)
+        .map_err(Error::from_error_stack)?;
+    if config.require_two_factor && !user_pointer.two_factor_enabled {
+        context_html_builder.attach_flash(Flash::Error {
+            msg: TwoFactorLocale::new(&context_html_builder.locale).flash_required,
+        });
+    }
+
+    Ok(enrolment_html(
+        &context_html_builder,
+        &enrolment,
+        csrf_token,
+    ))
+}
+
+#[handler]
+async fn two_factor_post(
+    Dep(context_html_builder): Dep<ContextHtmlBuilder>,
+    Dep(two_factor_service): Dep<TwoFactorService>,
+    Dep(user_pointer): Dep<UserPointer>,
+    CsrfFormQs(two_factor_code_form): CsrfFormQs<TwoFactorCodeForm>,
+    csrf_token: &CsrfToken,
+) -> poem::Result<Response> {
+    let recovery_codes = two_factor_service
+        .confirm_enrolment(user_pointer.id, &two_factor_code_form.code)
+        .map_err(Error::from_error_stack)?;
+    match recovery_codes {
+        Some(recovery_codes) => {
+            context_html_builder.attach_flash(Flash::Success {
+                msg: TwoFactorLocale::new(&context_html_builder.locale).flash_enabled,
+            });
+            Ok(recovery_codes_html(&context_html_builder, &recovery_codes).into_response())
+        }
+        None => invalid_code_response(
+            &context_html_builder,
+            &two_factor_service,
+            &user_pointer,
+            csrf_token,
+        ),
+    }
+}
+
+#[handler]
+async fn recovery_codes_post(
+    Dep(context_html_builder): Dep<ContextHtmlBuilder>,
+    Dep(two_factor_service): Dep<TwoFactorService>,
+    Dep(user_pointer): Dep<UserPointer>,
+    CsrfFormQs(two_factor_code_form): CsrfFormQs<TwoFactorCodeForm>,
+    csrf_token: &CsrfToken,
+) -> poem::Result<Response> {
+    let recovery_codes = two_factor_service
+        .regenerate_recovery_codes(user_pointer.id, &two_factor_code_form.code)
+        .map_err(Error::from_error_stack)?;
+    match recovery_codes {
+        Some(recovery_codes) => {
+            Ok(recovery_codes_html(&context_html_builder, &recovery_codes).into_response())
+        }
+        None => invalid_code_response(
+            &context_html_builder,
+            &two_factor_service,
+            &user_pointer,
+            csrf_token,
+        ),
+    }
+}
+
+#[handler]
+async fn disable_post(
+    Dep(context_html_builder): Dep<ContextHtmlBuilder>,
+    Dep(two_factor_service): Dep<TwoFactorService>,
+    Dep(user_pointer): Dep<UserPointer>,
+    CsrfFormQs(two_factor_code_form): CsrfFormQs<TwoFactorCodeForm>,
+    session: &Session,
+    htmx_header: HtmxHeader,
+    csrf_token: &CsrfToken,
+) -> poem::Result<Response> {
+    let disabled = two_factor_service
+        .disable(user_pointer.id, &two_factor_code_form.code)
+        .map_err(Error::from_error_stack)?;
+    if !disabled {
+        return invalid_code_response(
+            &context_html_builder,
+            &two_factor_service,
+            &user_pointer,
+            csrf_token,
+        );
+    }
+
+    session.flash(Flash::Success {
+        msg: TwoFactorLocale::new(&context_html_builder.locale).flash_disabled,
+    });
+    Ok(htmx_header.do_location(Redirect::see_other(two_factor_path("/")), "#main-content"))
+}
+
+pub fn two_factor_route() -> Route {
+    Route::new()
+        .at(
+            "/",
+            get(two_factor_get).post(csrf_header_check(two_factor_post)),
+        )
+        .at(
+            "/recovery-codes",
+            post(csrf_header_check(recovery_codes_post)),
+        )
+        .at("/disable", post(csrf_header_check(disable_post)))
+}
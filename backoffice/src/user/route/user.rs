@@ -1,13 +1,18 @@
 use crate::common::html::context_html::ContextHtmlBuilder;
-use crate::common::icon::{flag_icon, key_icon, pencil_square_icon, plus_icon};
+use crate::common::html::partial::{pagination_partial, sort_heading_partial};
+use crate::common::icon::{flag_icon, key_icon, lock_open_icon, pencil_square_icon, plus_icon};
 use crate::user::form::add_user::AddUserForm;
 use crate::user::form::edit_password_manager::EditPasswordManagerForm;
 use crate::user::form::edit_user::EditUserForm;
 use crate::user::locale::user::{UserLocale, user_logout_confirm_message};
+use crate::user::model::user_manager_model::{ListUserQuery, UserSort};
 use crate::user::pointer::user_pointer::UserPointer;
 use crate::user::repository::user_manager_repository::UserManagerRepository;
 use crate::user::role::Role;
 use crate::user::role::user_role_check::{must_be_root, must_be_user};
+use crate::user::route::two_factor::two_factor_status_partial;
+use crate::user::service::login_throttle_service::LoginThrottleService;
+use crate::user::service::two_factor_service::TwoFactorService;
 use crate::user::service::user_manager_service::add_user_service::AddUserService;
 use crate::user::service::user_manager_service::edit_password_service::EditPasswordService;
 use crate::user::service::user_manager_service::edit_service::EditUserService;
@@ -18,25 +23,32 @@ use poem::i18n::{I18NArgs, Locale};
 use poem::session::Session;
 use poem::web::{CsrfToken, Path, Redirect};
 use poem::{Error, IntoResponse, Response, Route, get, handler};
+use serde_qs::Config;
 use shared::utils::context::Dep;
 use shared::utils::csrf::{CsrfFormQs, CsrfTokenHtml, csrf_header_check};
 use shared::utils::error::{ExtraResultExt, FromErrorStack};
 use shared::utils::flash::{Flash, FlashMessage};
 use shared::utils::htmx::HtmxHeader;
 use shared::utils::locale::LocaleExt;
+use shared::utils::query_string::list_query::SortKey;
+use shared::utils::query_string::query::QueryQs;
+use shared::utils::query_string::serde_qs_config::with_serde_qs_config;
 
 pub const USER_ROUTE: &str = "/user";
 
 #[handler]
 async fn list_users(
+    QueryQs(query): QueryQs<ListUserQuery>,
     Dep(list_user_service): Dep<ListUserService>,
     Dep(context_html_builder): Dep<ContextHtmlBuilder>,
     Dep(user_id_context): Dep<UserPointer>,
 ) -> Markup {
-    let list_user = list_user_service.list_users();
+    let list_user = list_user_service.list_users(&query);
+    let list_path = format!("{}/", USER_ROUTE);
     let edit_icon = pencil_square_icon();
     let password_icon = key_icon();
     let flag_icon = flag_icon();
+    let unlock_icon = lock_open_icon();
 
     let user_locale = UserLocale::new(&context_html_builder.locale);
 
@@ -45,11 +57,28 @@ async fn list_users(
         .set_current_tag("id-tag-user")
         .attach_content(html! {
             h1 { (&user_locale.user_list_title) }
+            form .form hx-boost="true" hx-target="#main-content" hx-push-url="true" method="get"
+                action=(list_path) {
+                input type="hidden" name="sort" value=(query.sort.as_str());
+                input type="hidden" name="order" value=(query.order.as_str());
+                div .form-group {
+                    select .form-item name="filter[role]" {
+                        option value="" { (&user_locale.user_list_filter_any_role) }
+                        @for role in Role::all_roles().iter() {
+                            option value=(role.as_stringed()) selected[query.filter.role == Some(*role)] {
+                                (role.as_stringed()) }
+                        }
+                    }
+                    " "
+                    button .btn .btn-sky-blue .cursor-pointer type="submit" {
+                        (context_html_builder.locale.text_with_default("top-list-filter", "Filter")) }
+                }
+            }
             table .table-full {
                 thead {
                     tr {
-                        th { (&user_locale.user_list_head_id) }
-                        th { (&user_locale.user_list_head_username) }
+                        th { (sort_heading_partial(&list_path, &query, UserSort::Id, &user_locale.user_list_head_id)) }
+                        th { (sort_heading_partial(&list_path, &query, UserSort::Username, &user_locale.user_list_head_username)) }
                         th { (&user_locale.user_list_head_role) }
                         @if user_id_context.role == Role::Root {
                             th .action { "Action" }
@@ -57,7 +86,7 @@ async fn list_users(
                     }
                 }
                 tbody {
-                    @for user in list_user.iter() {
+                    @for user in list_user.items.iter() {
                         tr {
                             td { (user.id) }
                             td { (&user.username) }
@@ -73,12 +102,18 @@ async fn list_users(
                                     a .icon hx-confirm=(user_logout_confirm_message(&context_html_builder.locale, &user.username))
                                         href=(format!("{}/sign-out/{}", USER_ROUTE, user.id)) title=(&user_locale.user_list_action_sign_out)
                                         hx-boost="true" hx-push-url="true" hx-target="#main-content" { (flag_icon) }
+                                    @if user.locked {
+                                        " "
+                                        a .icon href=(format!("{}/unlock/{}", USER_ROUTE, user.id)) title=(&user_locale.user_list_action_unlock)
+                                            hx-boost="true" hx-push-url="true" hx-target="#main-content" { (unlock_icon) }
+                                    }
                                 }
                             }
                         }
                     }
                 }
             }
+            (pagination_partial(&context_html_builder.locale, &list_path, &query, &list_user))
             @if user_id_context.role == Role::Root {
                 div .text-right .mt-3 {
                     a .inline-block href=(format!("{}/add-user", USER_ROUTE)) title=(&user_locale.user_list_action_add_user)
@@ -117,6 +152,12 @@ async fn edit_user_get(
     let mut edit_user = EditUserForm::default();
     edit_user.username = subject_user.username.to_string();
     edit_user.role = subject_user.role;
+    let two_factor_status = two_factor_status_partial(
+        &context_html_builder.locale,
+        user_id,
+        &subject_user.username,
+        subject_user.two_factor_enabled,
+    );
 
     Ok(edit_user
         .as_form_html(
@@ -124,6 +165,7 @@ async fn edit_user_get(
             None,
             Some(subject_user.username),
             Some(csrf_token.as_html_input()),
+            two_factor_status,
         )
         .await)
 }
@@ -167,6 +209,12 @@ async fn edit_user_post(
         Err(error) => {
             let errors = error.as_message(&context_html_builder.locale);
             context_html_builder.attach_form_flash_error();
+            let two_factor_status = two_factor_status_partial(
+                &context_html_builder.locale,
+                user_id,
+                &subject_user.username,
+                subject_user.two_factor_enabled,
+            );
             Ok(PostResponse::Validation(
                 edit_user_form
                     .as_form_html(
@@ -174,6 +222,7 @@ async fn edit_user_post(
                         Some(errors),
                         Some(subject_user.username),
                         Some(csrf_token.as_html_input()),
+                        two_factor_status,
                     )
                     .await,
             )
@@ -355,9 +404,91 @@ fn sign_out_user(
     )
 }
 
+#[handler]
+fn unlock_user(
+    Dep(login_throttle_service): Dep<LoginThrottleService>,
+    Path(user_id): Path<i64>,
+    session: &Session,
+    locale: Locale,
+    htmx_header: HtmxHeader,
+) -> Response {
+    let result = login_throttle_service.unlock_user(user_id);
+    let l = &locale;
+    if result.is_err() {
+        session.flash(Flash::Error {
+            msg: l.text_with_default_args(
+                "user-route-flash-unlock-error",
+                format!("Failed to clear the login lockout for user id: {}", user_id).as_str(),
+                I18NArgs::from((("user_id", user_id),)),
+            ),
+        });
+        return htmx_header.do_location(
+            Redirect::see_other(USER_ROUTE.to_owned() + "/"),
+            "#main-content",
+        );
+    }
+    session.flash(Flash::Success {
+        msg: l.text_with_default_args(
+            "user-route-flash-unlock-success",
+            format!("Login lockout cleared for user id: {}", user_id).as_str(),
+            I18NArgs::from((("user_id", user_id),)),
+        ),
+    });
+    htmx_header.do_location(
+        Redirect::see_other(USER_ROUTE.to_owned() + "/"),
+        "#main-content",
+    )
+}
+
+#[handler]
+fn reset_two_factor_user(
+    Dep(two_factor_service): Dep<TwoFactorService>,
+    Path(user_id): Path<i64>,
+    session: &Session,
+    locale: Locale,
+    htmx_header: HtmxHeader,
+) -> Response {
+    let result = two_factor_service.reset(user_id);
+    let l = &locale;
+    if result.is_err() {
+        session.flash(Flash::Error {
+            msg: l.text_with_default_args(
+                "two-factor-status-flash-reset-error",
+                format!(
+                    "Failed to reset two-factor authentication for user id: {}",
+                    user_id
+                )
+                .as_str(),
+                I18NArgs::from((("user_id", user_id),)),
+            ),
+        });
+        return htmx_header.do_location(
+            Redirect::see_other(format!("{}/edit/{}", USER_ROUTE, user_id)),
+            "#main-content",
+        );
+    }
+    session.flash(Flash::Success {
+        msg: l.text_with_default_args(
+            "two-factor-status-flash-reset-success",
+            format!("Two-factor authentication reset for user id: {}", user_id).as_str(),
+            I18NArgs::from((("user_id", user_id),)),
+        ),
+    });
+    htmx_header.do_location(
+        Redirect::see_other(format!("{}/edit/{}", USER_ROUTE, user_id)),
+        "#main-content",
+    )
+}
+
 pub fn user_route() -> Route {
     Route::new()
-        .at("/", get(must_be_user(list_users)))
+        .at(
+            "/",
+            get(must_be_user(with_serde_qs_config(
+                Config::default().use_form_encoding(true),
+                list_users,
+            ))),
+        )
         .at(
             "/edit/:user_id",
             must_be_root(get(edit_user_get).post(csrf_header_check(edit_user_post))),
@@ -375,4 +506,9 @@ pub fn user_route() -> Route {
             ),
         )
         .at("/sign-out/:user_id", must_be_root(get(sign_out_user)))
+        .at("/unlock/:user_id", must_be_root(get(unlock_user)))
+        .at(
+            "/reset-two-factor/:user_id",
+            must_be_root(get(reset_two_factor_user)),
+        )
 }
@@ -1,3 +1,4 @@
+use crate::user::rule::login::PasswordRulesForLoginExt;
 use cjtoolkit_structured_validator::common::locale::{LocaleData, LocaleMessage, LocaleValue};
 use cjtoolkit_structured_validator::common::validation_check::ValidationCheck;
 use cjtoolkit_structured_validator::common::validation_collector::ValidateErrorCollector;
@@ -124,3 +125,40 @@ impl PasswordStatus {
         Ok(())
     }
 }
+
+struct PasswordCurrentLocale;
+
+impl LocaleMessage for PasswordCurrentLocale {
+    fn get_locale_data(&self) -> Arc<LocaleData> {
+        LocaleData::new("validate-password-current")
+    }
+}
+
+pub trait IsPasswordCurrentAsync {
+    fn is_password_current_async(&self, password: &str) -> impl Future<Output = bool>;
+}
+
+pub trait PasswordCurrentRulesExt {
+    fn parse_password_current<T: IsPasswordCurrentAsync>(
+        password: Option<&str>,
+        service: &T,
+    ) -> impl Future<Output = Result<Password, PasswordError>>;
+}
+
+impl PasswordCurrentRulesExt for Password {
+    async fn parse_password_current<T: IsPasswordCurrentAsync>(
+        password: Option<&str>,
+        service: &T,
+    ) -> Result<Password, PasswordError> {
+        let password = Password::parse_user_login(password)?;
+        if !service.is_password_current_async(password.as_str()).await {
+            let mut messages = ValidateErrorCollector::new();
+            messages.push((
+                "Does not match the current password".to_string(),
+                Box::new(PasswordCurrentLocale),
+            ));
+            PasswordError::validate_check(messages)?;
+        }
+        Ok(password)
+    }
+}
@@ -0,0 +1,161 @@
+use crate::user::form::change_password::ChangePasswordValidated;
+use crate::user::layer::password_layer::PasswordLayer;
+use crate::user::pointer::user_pointer::UserPointer;
+use crate::user::repository::user_manager_repository::UserManagerRepository;
+use crate::user::rule::user_manager::IsPasswordCurrentAsync;
+use error_stack::{Report, ResultExt};
+use poem::http::StatusCode;
+use shared::utils::context::{Context, ContextError, FromContext};
+use shared::utils::error::ExtraResultExt;
+use shared::utils::password::Password;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum ChangePasswordServiceError {
+    #[error("Password change failed")]
+    SubmitFailed,
+    #[error("Password Hash Error")]
+    PasswordHashError,
+    #[error("Password Serialize Error")]
+    PasswordSerializeError,
+}
+
+/// Lets the logged-in user replace their own password, clearing the must change flag.
+pub struct ChangePasswordService {
+    user_manager_repository: UserManagerRepository,
+    password_layer: PasswordLayer,
+    user_id: i64,
+}
+
+impl ChangePasswordService {
+    pub fn new(
+        user_manager_repository: UserManagerRepository,
+        password_layer: PasswordLayer,
+        user_id: i64,
+    ) -> Self {
+        Self {
+            user_manager_repository,
+            password_layer,
+            user_id,
+        }
+    }
+
+    pub fn change_password_submit(
+        &self,
+        password: &ChangePasswordValidated,
+    ) -> Result<(), Report<ChangePasswordServiceError>> {
+        self.user_manager_repository
+            .edit_password(
+                self.user_id,
+                self.hash_password(password.password.as_str())?
+                    .encode_to_msg_pack()
+                    .change_context(ChangePasswordServiceError::PasswordSerializeError)
+                    .log_it()
+                    .attach(StatusCode::INTERNAL_SERVER_ERROR)?,
+                false,
+            )
+            .change_context(ChangePasswordServiceError::SubmitFailed)?;
+
+        Ok(())
+    }
+
+    fn hash_password(
+        &self,
+        password: &str,
+    ) -> Result<Password, Report<ChangePasswordServiceError>> {
+        self.password_layer
+            .hash_password(password)
+            .change_context(ChangePasswordServiceError::PasswordHashError)
+            .attach(StatusCode::INTERNAL_SERVER_ERROR)
+    }
+}
+
+impl IsPasswordCurrentAsync for ChangePasswordService {
+    async fn is_password_current_async(&self, password: &str) -> bool {
+        self.user_manager_repository
+            .fetch_password(self.user_id)
+            .ok()
+            .and_then(|fetch_password| {
+                self.password_layer
+                    .verify_password(fetch_password.password, password)
+                    .ok()
+            })
+            .is_some_and(|password_state| password_state.is_valid())
+    }
+}
+
+impl FromContext for ChangePasswordService {
+    async fn from_context(ctx: &'_ Context<'_>) -> Result<Self, Report<ContextError>> {
+        let user_pointer: UserPointer = ctx.inject().await?;
+        Ok(Self::new(
+            ctx.inject().await?,
+            ctx.inject().await?,
+            user_pointer.id,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::user::model::user_manager_model::FetchPassword;
+    use crate::user::repository::user_manager_repository::UserManagerRepositoryError;
+    use mry::Any;
+    use shared::utils::password::PasswordState;
+
+    #[test]
+    fn test_change_password_submit_clears_flag() {
+        let password = ChangePasswordValidated::new_test_data();
+        let mut user_manager_repository = UserManagerRepository::new_mock();
+        let mut password_layer = PasswordLayer::new_mock();
+
+        password_layer
+            .mock_hash_password(password.password.as_str())
+            .returns_once(Ok(Password::Version1 {
+                argon2: password.password.as_str().to_string(),
+            }));
+        user_manager_repository
+            .mock_edit_password(3, Any, false)
+            .returns_once(Ok(()));
+
+        let service = ChangePasswordService::new(user_manager_repository, password_layer, 3);
+        assert!(service.change_password_submit(&password).is_ok());
+    }
+
+    #[test]
+    fn test_change_password_submit_fail() {
+        let password = ChangePasswordValidated::new_test_data();
+        let mut user_manager_repository = UserManagerRepository::new_mock();
+        let mut password_layer = PasswordLayer::new_mock();
+
+        password_layer
+            .mock_hash_password(password.password.as_str())
+            .returns_once(Ok(Password::Version1 {
+                argon2: password.password.as_str().to_string(),
+            }));
+        user_manager_repository
+            .mock_edit_password(3, Any, false)
+            .returns_once(Err(Report::new(UserManagerRepositoryError::QueryError)));
+
+        let service = ChangePasswordService::new(user_manager_repository, password_layer, 3);
+        assert!(service.change_password_submit(&password).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_is_password_current() {
+        let mut user_manager_repository = UserManagerRepository::new_mock();
+        let mut password_layer = PasswordLayer::new_mock();
+
+        user_manager_repository
+            .mock_fetch_password(3)
+            .returns_once(Ok(FetchPassword {
+                password: Default::default(),
+            }));
+        password_layer
+            .mock_verify_password(Any, "current")
+            .returns_once(Ok(PasswordState::Invalid));
+
+        let service = ChangePasswordService::new(user_manager_repository, password_layer, 3);
+        assert!(!service.is_password_current_async("current").await);
+    }
+}
@@ -0,0 +1,163 @@
+use crate::user::model::user_model::ThrottleKind;
+use crate::user::repository::login_throttle_repository::LoginThrottleRepository;
+use error_stack::{Report, ResultExt};
+use shared::utils::config::ConfigPointer;
+use shared::utils::config::login_throttle::LoginThrottleConfig;
+use shared::utils::context::{Context, ContextError, FromContext};
+use std::sync::Arc;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum LoginThrottleServiceError {
+    #[error("Db error")]
+    DbError,
+}
+
+/// Counts failed logins per username and per remote address, locking either out with a doubling delay.
+pub struct LoginThrottleService {
+    login_throttle_repository: LoginThrottleRepository,
+    config: Arc<LoginThrottleConfig>,
+}
+
+impl LoginThrottleService {
+    pub fn new(
+        login_throttle_repository: LoginThrottleRepository,
+        config: Arc<LoginThrottleConfig>,
+    ) -> Self {
+        Self {
+            login_throttle_repository,
+            config,
+        }
+    }
+
+    /// Fails closed, a broken throttle table must not turn into unlimited attempts.
+    pub fn is_locked(&self, username: &str, ip: &str) -> bool {
+        self.login_throttle_repository
+            .is_locked(username.to_string(), ip.to_string())
+            .unwrap_or(true)
+    }
+
+    pub fn record_failure(&self, username: &str, ip: &str) {
+        log::warn!("Failed login for '{}' from {}", username, ip);
+        _ = self
+            .login_throttle_repository
+            .add_attempt(username.to_string(), ip.to_string(), false);
+
+        for (kind, subject) in [(ThrottleKind::Username, username), (ThrottleKind::Ip, ip)] {
+            let failures = self.login_throttle_repository.add_failure(
+                kind,
+                subject.to_string(),
+                self.config.reset_after_secs,
+            );
+            if let Some(lockout_secs) = failures.ok().and_then(|f| self.lockout_secs(f)) {
+                log::warn!(
+                    "Login locked for {} '{}' for {}s",
+                    kind.as_str(),
+                    subject,
+                    lockout_secs
+                );
+                _ = self.login_throttle_repository.lock_subject(
+                    kind,
+                    subject.to_string(),
+                    lockout_secs,
+                );
+            }
+        }
+    }
+
+    pub fn record_success(&self, username: &str, ip: &str) {
+        log::info!("Successful login for '{}' from {}", username, ip);
+        _ = self
+            .login_throttle_repository
+            .add_attempt(username.to_string(), ip.to_string(), true);
+        _ = self
+            .login_throttle_repository
+            .clear_subject(ThrottleKind::Username, username.to_string());
+        _ = self
+            .login_throttle_repository
+            .clear_subject(ThrottleKind::Ip, ip.to_string());
+    }
+
+    /// Lets a root clear a username lockout before it runs out.
+    pub fn unlock_user(&self, user_id: i64) -> Result<(), Report<LoginThrottleServiceError>> {
+        self.login_throttle_repository
+            .clear_user(user_id)
+            .change_context(LoginThrottleServiceError::DbError)
+    }
+
+    fn lockout_secs(&self, failures: i64) -> Option<i64> {
+        let over = failures - self.config.free_attempts;
+        if over <= 0 {
+            return None;
+        }
+        let doublings = (over - 1).min(32) as u32;
+        Some(
+            self.config
+                .base_lockout_secs
+                .saturating_mul(1i64 << doublings)
+                .min(self.config.max_lockout_secs),
+        )
+    }
+}
+
+impl FromContext for LoginThrottleService {
+    async fn from_context(ctx: &'_ Context<'_>) -> Result<Self, Report<ContextError>> {
+        let config: ConfigPointer = ctx.inject().await?;
+        Ok(Self::new(
+            ctx.inject().await?,
+            Arc::clone(&config.login_throttle),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::user::repository::login_throttle_repository::LoginThrottleRepositoryError;
+    use mry::Any;
+
+    fn service(repository: LoginThrottleRepository) -> LoginThrottleService {
+        LoginThrottleService::new(repository, Arc::new(LoginThrottleConfig::default()))
+    }
+
+    #[test]
+    fn test_lockout_secs_doubles_up_to_max() {
+        let service = service(LoginThrottleRepository::new_mock());
+        assert_eq!(service.lockout_secs(5), None);
+        assert_eq!(service.lockout_secs(6), Some(30));
+        assert_eq!(service.lockout_secs(7), Some(60));
+        assert_eq!(service.lockout_secs(9), Some(240));
+        assert_eq!(service.lockout_secs(100), Some(3600));
+    }
+
+    #[test]
+    fn test_is_locked_fails_closed() {
+        let mut repository = LoginThrottleRepository::new_mock();
+        repository
+            .mock_is_locked("hello".to_string(), "127.0.0.1".to_string())
+            .returns_once(Err(Report::new(
+                LoginThrottleRepositoryError::BorrowConnError,
+            )));
+
+        assert!(service(repository).is_locked("hello", "127.0.0.1"));
+    }
+
+    #[test]
+    fn test_record_failure_locks_after_free_attempts() {
+        let mut repository = LoginThrottleRepository::new_mock();
+        repository
+            .mock_add_attempt("hello".to_string(), "127.0.0.1".to_string(), false)
+            .returns_once(Ok(()));
+        repository
+            .mock_add_failure(ThrottleKind::Username, "hello".to_string(), Any)
+            .returns_once(Ok(6));
+        repository
+            .mock_add_failure(ThrottleKind::Ip, "127.0.0.1".to_string(), Any)
+            .returns_once(Ok(2));
+        repository
+            .mock_lock_subject(ThrottleKind::Username, "hello".to_string(), 30)
+            .returns_once(Ok(()));
+
+        service(repository).record_failure("hello", "127.0.0.1");
+    }
+}
@@ -1,3 +1,8 @@
+pub mod change_password_service;
+pub mod login_throttle_service;
+pub mod session_service;
+pub mod setup_service;
+pub mod two_factor_service;
 pub mod user_check_service;
 pub mod user_login_service;
 pub mod user_manager_service;
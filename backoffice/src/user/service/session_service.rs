@@ -0,0 +1,287 @@
+use crate::user::model::user_model::{LoginSession, UserIdContext};
+use crate::user::repository::user_repository::UserRepository;
+use crate::user::{LOGIN_TOKEN_COOKIE_NAME, login_token_cookie, remote_ip};
+use chrono::TimeDelta;
+use error_stack::{Report, ResultExt};
+use poem::http::header;
+use poem::web::cookie::CookieJar;
+use shared::utils::config::ConfigPointer;
+use shared::utils::config::session::SessionConfig;
+use shared::utils::context::{Context, ContextError, FromContext};
+use shared::utils::token::hash_token;
+use std::sync::Arc;
+use thiserror::Error;
+use uuid::Uuid;
+
+#[derive(Debug, Error)]
+pub enum SessionServiceError {
+    #[error("Db error")]
+    DbError,
+}
+
+/// Where a login came from, recorded on the token so a user can tell their devices apart.
+#[derive(Default)]
+pub struct SessionOrigin {
+    pub ip: String,
+    pub user_agent: String,
+}
+
+/// Login tokens seen as sessions, one per signed in device.
+///
+/// Only the keyed hash of a token reaches the database, the raw value lives in the cookie.
+pub struct SessionService {
+    user_repository: UserRepository,
+    session_config: Arc<SessionConfig>,
+    origin: SessionOrigin,
+    token_cookie: Option<String>,
+    cookie_jar: CookieJar,
+}
+
+impl SessionService {
+    pub fn new(
+        user_repository: UserRepository,
+        session_config: Arc<SessionConfig>,
+        origin: SessionOrigin,
+        token_cookie: Option<String>,
+        cookie_jar: CookieJar,
+    ) -> Self {
+        Self {
+            user_repository,
+            session_config,
+            origin,
+            token_cookie,
+            cookie_jar,
+        }
+    }
+
+    fn hash(&self, token: &str) -> String {
+        hash_token(&self.session_config.token_secret, token)
+    }
+
+    fn token_hash(&self) -> Option<String> {
+        self.token_cookie.as_ref().map(|token| self.hash(token))
+    }
+
+    pub fn lifetime(&self) -> TimeDelta {
+        TimeDelta::days(self.session_config.lifetime_days)
+    }
+
+    pub fn issue_token(&self, user_id: i64) -> Option<String> {
+        let uuid = Uuid::new_v4().to_string();
+
+        if self
+            .user_repository
+            .add_token(
+                self.hash(&uuid),
+                user_id,
+                self.origin.ip.clone(),
+                self.origin.user_agent.clone(),
+                self.session_config.lifetime_days,
+            )
+            .is_err()
+        {
+            return None;
+        }
+
+        Some(uuid)
+    }
+
+    pub fn current_user(&self) -> Option<UserIdContext> {
+        self.user_repository.find_by_token(self.token_hash()?).ok()
+    }
+
+    /// Pushes the expiry back and sends the cookie again so it lasts as long as the token.
+    pub fn renew(&self) -> bool {
+        let (Some(token), Some(token_hash)) = (self.token_cookie.as_ref(), self.token_hash())
+        else {
+            return false;
+        };
+        let renewed = self
+            .user_repository
+            .renew_token(
+                token_hash,
+                self.session_config.lifetime_days,
+                self.session_config.renew_interval_secs,
+            )
+            .unwrap_or(false);
+        if renewed {
+            self.cookie_jar
+                .add(login_token_cookie(token.to_string(), self.lifetime()));
+        }
+        renewed
+    }
+
+    /// Signs the user out everywhere after a password or privilege change,
+    /// the current device is handed a fresh token when it belongs to that user.
+    pub fn rotate(&self, user_id: i64) -> Result<(), Report<SessionServiceError>> {
+        let is_current = self
+            .current_user()
+            .is_some_and(|user_context| user_context.id == user_id);
+        self.user_repository
+            .delete_user_token(user_id)
+            .change_context(SessionServiceError::DbError)?;
+        if !is_current {
+            return Ok(());
+        }
+        if let Some(token) = self.issue_token(user_id) {
+            self.cookie_jar
+                .add(login_token_cookie(token, self.lifetime()));
+        }
+        Ok(())
+    }
+
+    pub fn list_session(
+        &self,
+        user_id: i64,
+    ) -> Result<Vec<LoginSession>, Report<SessionServiceError>> {
+        self.user_repository
+            .list_session(user_id, self.token_hash().unwrap_or_default())
+            .change_context(SessionServiceError::DbError)
+    }
+
+    pub fn revoke_session(
+        &self,
+        user_id: i64,
+        session_id: i64,
+    ) -> Result<bool, Report<SessionServiceError>> {
+        self.user_repository
+            .delete_session(user_id, session_id)
+            .change_context(SessionServiceError::DbError)
+    }
+
+    pub fn logout(&self) -> bool {
+        if let Some(token_hash) = self.token_hash() {
+            self.user_repository.delete_token(token_hash).is_ok()
+        } else {
+            false
+        }
+    }
+}
+
+impl FromContext for SessionService {
+    async fn from_context(ctx: &'_ Context<'_>) -> Result<Self, Report<ContextError>> {
+        let req = ctx.req_result()?;
+        let config: ConfigPointer = ctx.inject().await?;
+        Ok(Self::new(
+            ctx.inject().await?,
+            Arc::clone(&config.session),
+            SessionOrigin {
+                ip: remote_ip(req.remote_addr()),
+                user_agent: req
+                    .header(header::USER_AGENT)
+                    .unwrap_or_default()
+                    .to_string(),
+            },
+            req.cookie()
+                .get(LOGIN_TOKEN_COOKIE_NAME)
+                .map(|v| v.value_str().to_string()),
+            req.cookie().clone(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::user::repository::user_repository::UserRepositoryError;
+    use crate::user::role::Role;
+    use mry::Any;
+
+    fn service(user_repository: UserRepository, cookie_jar: CookieJar) -> SessionService {
+        SessionService::new(
+            user_repository,
+            Arc::new(SessionConfig::default()),
+            SessionOrigin::default(),
+            Some("hello".to_string()),
+            cookie_jar,
+        )
+    }
+
+    fn user_context(id: i64) -> UserIdContext {
+        UserIdContext {
+            id,
+            username: "".to_string(),
+            role: Role::User,
+            must_change_password: false,
+            two_factor_enabled: false,
+        }
+    }
+
+    fn cookie_value(cookie_jar: &CookieJar) -> Option<String> {
+        cookie_jar
+            .get(LOGIN_TOKEN_COOKIE_NAME)
+            .map(|cookie| cookie.value_str().to_string())
+    }
+
+    #[test]
+    fn test_current_user_looks_up_hash() {
+        let mut user_repository = UserRepository::new_mock();
+        user_repository
+            .mock_find_by_token(hash_token("", "hello"))
+            .returns_once(Ok(user_context(1)));
+
+        let service = service(user_repository, CookieJar::default());
+        assert_eq!(service.current_user().map(|user| user.id), Some(1));
+    }
+
+    #[test]
+    fn test_renew_sends_cookie() {
+        let mut user_repository = UserRepository::new_mock();
+        user_repository
+            .mock_renew_token(hash_token("", "hello"), 30, 300)
+            .returns_once(Ok(true));
+
+        let cookie_jar = CookieJar::default();
+        assert!(service(user_repository, cookie_jar.clone()).renew());
+        assert_eq!(cookie_value(&cookie_jar), Some("hello".to_string()));
+    }
+
+    #[test]
+    fn test_renew_skipped() {
+        let mut user_repository = UserRepository::new_mock();
+        user_repository
+            .mock_renew_token(hash_token("", "hello"), 30, 300)
+            .returns_once(Err(Report::new(UserRepositoryError::QueryError)));
+
+        let cookie_jar = CookieJar::default();
+        assert!(!service(user_repository, cookie_jar.clone()).renew());
+        assert_eq!(cookie_value(&cookie_jar), None);
+    }
+
+    #[test]
+    fn test_rotate_reissues_current_device() {
+        let mut user_repository = UserRepository::new_mock();
+        user_repository
+            .mock_find_by_token(hash_token("", "hello"))
+            .returns_once(Ok(user_context(1)));
+        user_repository
+            .mock_delete_user_token(1)
+            .returns_once(Ok(()));
+        user_repository
+            .mock_add_token(Any, 1, Any, Any, Any)
+            .returns_once(Ok(()));
+
+        let cookie_jar = CookieJar::default();
+        service(user_repository, cookie_jar.clone())
+            .rotate(1)
+            .unwrap();
+        assert!(cookie_value(&cookie_jar).is_some_and(|token| token != "hello"));
+    }
+
+    #[test]
+    fn test_rotate_other_user() {
+        let mut user_repository = UserRepository::new_mock();
+        user_repository
+            .mock_find_by_token(hash_token("", "hello"))
+            .returns_once(Ok(user_context(2)));
+        user_repository
+            .mock_delete_user_token(1)
+            .returns_once(Ok(()));
+
+        let cookie_jar = CookieJar::default();
+        service(user_repository, cookie_jar.clone())
+            .rotate(1)
+            .unwrap();
+        assert_eq!(cookie_value(&cookie_jar), None);
+    }
+}
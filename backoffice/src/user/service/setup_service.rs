@@ -0,0 +1,185 @@
+use crate::user::form::setup::SetupValidated;
+use crate::user::layer::password_layer::PasswordLayer;
+use crate::user::repository::user_manager_repository::UserManagerRepository;
+use cjtoolkit_structured_validator::types::username::IsUsernameTakenAsync;
+use error_stack::{Report, ResultExt};
+use poem::http::StatusCode;
+use shared::utils::context::{Context, ContextError, FromContext};
+use shared::utils::error::ExtraResultExt;
+use shared::utils::password::Password;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum SetupServiceError {
+    #[error("Database error")]
+    DbError,
+    #[error("Setup already done")]
+    AlreadyDone,
+    #[error("Password Hash Error")]
+    PasswordHashError,
+    #[error("Password Serialize Error")]
+    PasswordSerializeError,
+}
+
+/// First run only, creates the root account while the database has none.
+pub struct SetupService {
+    user_manager_repository: UserManagerRepository,
+    password_layer: PasswordLayer,
+}
+
+impl SetupService {
+    pub fn new(
+        user_manager_repository: UserManagerRepository,
+        password_layer: PasswordLayer,
+    ) -> Self {
+        Self {
+            user_manager_repository,
+            password_layer,
+        }
+    }
+
+    pub fn is_setup_required(&self) -> Result<bool, Report<SetupServiceError>> {
+        Ok(!self
+            .user_manager_repository
+            .root_exists()
+            .change_context(SetupServiceError::DbError)?)
+    }
+
+    pub fn ensure_setup_required(&self) -> Result<(), Report<SetupServiceError>> {
+        if !self.is_setup_required()? {
+            return Err(Report::new(SetupServiceError::AlreadyDone).attach(StatusCode::NOT_FOUND));
+        }
+        Ok(())
+    }
+
+    pub fn setup_submit(
+        &self,
+        setup_validated: &SetupValidated,
+    ) -> Result<(), Report<SetupServiceError>> {
+        let inserted = self
+            .user_manager_repository
+            .add_first_root(
+                setup_validated.username.as_str().to_string(),
+                self.hash_password(setup_validated.password.as_str())?
+                    .encode_to_msg_pack()
+                    .change_context(SetupServiceError::PasswordSerializeError)
+                    .log_it()
+                    .attach(StatusCode::INTERNAL_SERVER_ERROR)?,
+            )
+            .change_context(SetupServiceError::DbError)?;
+        if !inserted {
+            return Err(Report::new(SetupServiceError::AlreadyDone).attach(StatusCode::NOT_FOUND));
+        }
+        Ok(())
+    }
+
+    fn hash_password(&self, password: &str) -> Result<Password, Report<SetupServiceError>> {
+        self.password_layer
+            .hash_password(password)
+            .change_context(SetupServiceError::PasswordHashError)
+            .attach(StatusCode::INTERNAL_SERVER_ERROR)
+    }
+}
+
+impl IsUsernameTakenAsync for SetupService {
+    async fn is_username_taken_async(&self, username: &str) -> bool {
+        self.user_manager_repository
+            .username_taken(username.to_string())
+            .ok()
+            .unwrap_or_default()
+    }
+}
+
+impl FromContext for SetupService {
+    async fn from_context(ctx: &'_ Context<'_>) -> Result<Self, Report<ContextError>> {
+        Ok(Self::new(ctx.inject().await?, ctx.inject().await?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::user::repository::user_manager_repository::UserManagerRepositoryError;
+    use mry::Any;
+
+    #[test]
+    fn test_is_setup_required_without_root() {
+        let mut user_manager_repository = UserManagerRepository::new_mock();
+        user_manager_repository
+            .mock_root_exists()
+            .returns_once(Ok(false));
+
+        let service = SetupService::new(user_manager_repository, PasswordLayer::new_mock());
+        assert!(service.is_setup_required().unwrap());
+    }
+
+    #[test]
+    fn test_ensure_setup_required_with_root() {
+        let mut user_manager_repository = UserManagerRepository::new_mock();
+        user_manager_repository
+            .mock_root_exists()
+            .returns_once(Ok(true));
+
+        let service = SetupService::new(user_manager_repository, PasswordLayer::new_mock());
+        let result = service.ensure_setup_required();
+        let status_code = *result.unwrap_err().downcast_ref::<StatusCode>().unwrap();
+        assert_eq!(status_code, StatusCode::NOT_FOUND);
+    }
+
+    #[test]
+    fn test_setup_submit_success() {
+        let setup_validated = SetupValidated::new_test_data();
+        let mut user_manager_repository = UserManagerRepository::new_mock();
+        let mut password_layer = PasswordLayer::new_mock();
+
+        password_layer
+            .mock_hash_password(setup_validated.password.as_str())
+            .returns_once(Ok(Password::Version1 {
+                argon2: setup_validated.password.as_str().to_string(),
+            }));
+        user_manager_repository
+            .mock_add_first_root(setup_validated.username.as_str().to_string(), Any)
+            .returns_once(Ok(true));
+
+        let service = SetupService::new(user_manager_repository, password_layer);
+        assert!(service.setup_submit(&setup_validated).is_ok());
+    }
+
+    #[test]
+    fn test_setup_submit_lost_race() {
+        let setup_validated = SetupValidated::new_test_data();
+        let mut user_manager_repository = UserManagerRepository::new_mock();
+        let mut password_layer = PasswordLayer::new_mock();
+
+        password_layer
+            .mock_hash_password(setup_validated.password.as_str())
+            .returns_once(Ok(Password::Version1 {
+                argon2: setup_validated.password.as_str().to_string(),
+            }));
+        user_manager_repository
+            .mock_add_first_root(setup_validated.username.as_str().to_string(), Any)
+            .returns_once(Ok(false));
+
+        let service = SetupService::new(user_manager_repository, password_layer);
+        assert!(service.setup_submit(&setup_validated).is_err());
+    }
+
+    #[test]
+    fn test_setup_submit_db_error() {
+        let setup_validated = SetupValidated::new_test_data();
+        let mut user_manager_repository = UserManagerRepository::new_mock();
+        let mut password_layer = PasswordLayer::new_mock();
+
+        password_layer
+            .mock_hash_password(setup_validated.password.as_str())
+            .returns_once(Ok(Password::Version1 {
+                argon2: setup_validated.password.as_str().to_string(),
+            }));
+        user_manager_repository
+            .mock_add_first_root(setup_validated.username.as_str().to_string(), Any)
+            .returns_once(Err(Report::new(UserManagerRepositoryError::QueryError)));
+
+        let service = SetupService::new(user_manager_repository, password_layer);
+        assert!(service.setup_submit(&setup_validated).is_err());
+    }
+}
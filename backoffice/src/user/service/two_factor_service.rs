@@ -0,0 +1,302 @@
+use crate::user::model::user_model::{LoginChallenge, TwoFactorState};
+use crate::user::repository::two_factor_repository::TwoFactorRepository;
+use chrono::Utc;
+use error_stack::{Report, ResultExt};
+use shared::utils::context::{Context, ContextError, FromContext};
+use shared::utils::totp::{TotpSecret, generate_recovery_codes, hash_recovery_code};
+use thiserror::Error;
+use uuid::Uuid;
+
+#[derive(Debug, Error)]
+pub enum TwoFactorServiceError {
+    #[error("Db error")]
+    DbError,
+}
+
+pub const TOTP_ISSUER: &str = "Rusty CMS";
+
+pub enum TwoFactorEnrolment {
+    Pending {
+        secret: String,
+        provisioning_uri: String,
+    },
+    Enabled {
+        remaining_recovery_codes: i64,
+    },
+}
+
+/// TOTP enrolment and code checks, shared by the account page and the second login step.
+pub struct TwoFactorService {
+    two_factor_repository: TwoFactorRepository,
+}
+
+impl TwoFactorService {
+    pub fn new(two_factor_repository: TwoFactorRepository) -> Self {
+        Self {
+            two_factor_repository,
+        }
+    }
+
+    /// Starts an enrolment on first visit, a pending secret is kept until it is confirmed.
+    pub fn enrolment(
+        &self,
+        user_id: i64,
+        username: &str,
+    ) -> Result<TwoFactorEnrolment, Report<TwoFactorServiceError>> {
+        let state = self.fetch_state(user_id)?;
+        if state.enabled {
+            return Ok(TwoFactorEnrolment::Enabled {
+                remaining_recovery_codes: self
+                    .two_factor_repository
+                    .count_recovery_code(user_id)
+                    .change_context(TwoFactorServiceError::DbError)?,
+            });
+        }
+
+        let secret = match state.secret {
+            Some(secret) => TotpSecret::from_bytes(secret),
+            None => {
+                let secret = TotpSecret::generate();
+                self.two_factor_repository
+                    .set_pending_secret(user_id, secret.as_bytes().into())
+                    .change_context(TwoFactorServiceError::DbError)?;
+                secret
+            }
+        };
+
+        Ok(TwoFactorEnrolment::Pending {
+            secret: secret.as_base32(),
+            provisioning_uri: secret.provisioning_uri(TOTP_ISSUER, username),
+        })
+    }
+
+    /// Returns the recovery codes to show once, or `None` when the code did not match.
+    pub fn confirm_enrolment(
+        &self,
+        user_id: i64,
+        code: &str,
+    ) -> Result<Option<Vec<String>>, Report<TwoFactorServiceError>> {
+        let state = self.fetch_state(user_id)?;
+        if state.enabled {
+            return Ok(None);
+        }
+        let Some(step) = Self::match_step(&state, code) else {
+            return Ok(None);
+        };
+
+        let recovery_codes = generate_recovery_codes();
+        self.two_factor_repository
+            .enable_two_factor(user_id, step, Self::hash_codes(&recovery_codes))
+            .change_context(TwoFactorServiceError::DbError)?;
+        Ok(Some(recovery_codes))
+    }
+
+    pub fn regenerate_recovery_codes(
+        &self,
+        user_id: i64,
+        code: &str,
+    ) -> Result<Option<Vec<String>>, Report<TwoFactorServiceError>> {
+        if !self.verify_totp(user_id, code)? {
+            return Ok(None);
+        }
+
+        let recovery_codes = generate_recovery_codes();
+        self.two_factor_repository
+            .replace_recovery_code(user_id, Self::hash_codes(&recovery_codes))
+            .change_context(TwoFactorServiceError::DbError)?;
+        Ok(Some(recovery_codes))
+    }
+
+    pub fn disable(&self, user_id: i64, code: &str) -> Result<bool, Report<TwoFactorServiceError>> {
+        if !self.verify_code(user_id, code)? {
+            return Ok(false);
+        }
+        self.reset(user_id)?;
+        Ok(true)
+    }
+
+    /// Drops the secret and recovery codes without a code, for a root helping a locked out user.
+    pub fn reset(&self, user_id: i64) -> Result<(), Report<TwoFactorServiceError>> {
+        self.two_factor_repository
+            .disable_two_factor(user_id)
+            .change_context(TwoFactorServiceError::DbError)
+    }
+
+    /// Accepts a current TOTP code or an unused recovery code, each only once.
+    pub fn verify_code(
+        &self,
+        user_id: i64,
+        code: &str,
+    ) -> Result<bool, Report<TwoFactorServiceError>> {
+        if self.verify_totp(user_id, code)? {
+            return Ok(true);
+        }
+        self.two_factor_repository
+            .use_recovery_code(user_id, hash_recovery_code(code))
+            .change_context(TwoFactorServiceError::DbError)
+    }
+
+    fn verify_totp(&self, user_id: i64, code: &str) -> Result<bool, Report<TwoFactorServiceError>> {
+        let state = self.fetch_state(user_id)?;
+        if !state.enabled {
+            return Ok(false);
+        }
+        match Self::match_step(&state, code) {
+            Some(step) if step > state.last_step => self
+                .two_factor_repository
+                .use_totp_step(user_id, step)
+                .change_context(TwoFactorServiceError::DbError),
+            _ => Ok(false),
+        }
+    }
+
+    /// Parks a user who passed the password check until the second step answers.
+    pub fn start_challenge(&self, user_id: i64) -> Result<String, Report<TwoFactorServiceError>> {
+        let token = Uuid::new_v4().to_string();
+        self.two_factor_repository
+            .add_challenge(token.clone(), user_id)
+            .change_context(TwoFactorServiceError::DbError)?;
+        Ok(token)
+    }
+
+    pub fn challenge_user(
+        &self,
+        token: &str,
+    ) -> Result<Option<LoginChallenge>, Report<TwoFactorServiceError>> {
+        self.two_factor_repository
+            .find_challenge(token.to_string())
+            .change_context(TwoFactorServiceError::DbError)
+    }
+
+    pub fn finish_challenge(&self, token: &str) -> Result<(), Report<TwoFactorServiceError>> {
+        self.two_factor_repository
+            .delete_challenge(token.to_string())
+            .change_context(TwoFactorServiceError::DbError)
+    }
+
+    fn fetch_state(&self, user_id: i64) -> Result<TwoFactorState, Report<TwoFactorServiceError>> {
+        self.two_factor_repository
+            .fetch_two_factor(user_id)
+            .change_context(TwoFactorServiceError::DbError)
+    }
+
+    fn match_step(state: &TwoFactorState, code: &str) -> Option<i64> {
+        let secret = TotpSecret::from_bytes(state.secret.clone()?);
+        secret.verify(code, Utc::now().timestamp())
+    }
+
+    fn hash_codes(recovery_codes: &[String]) -> Vec<String> {
+        recovery_codes
+            .iter()
+            .map(|code| hash_recovery_code(code))
+            .collect()
+    }
+}
+
+impl FromContext for TwoFactorService {
+    async fn from_context(ctx: &'_ Context<'_>) -> Result<Self, Report<ContextError>> {
+        Ok(Self::new(ctx.inject().await?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mry::Any;
+
+    fn secret() -> TotpSecret {
+        TotpSecret::from_bytes(b"12345678901234567890".to_vec().into_boxed_slice())
+    }
+
+    fn current_code() -> (i64, String) {
+        let step = Utc::now().timestamp() / 30;
+        (step, secret().code_at_step(step))
+    }
+
+    fn state(enabled: bool, last_step: i64) -> TwoFactorState {
+        TwoFactorState {
+            secret: Some(secret().as_bytes().into()),
+            enabled,
+            last_step,
+        }
+    }
+
+    #[test]
+    fn test_enrolment_generates_pending_secret() {
+        let mut repository = TwoFactorRepository::new_mock();
+        repository
+            .mock_fetch_two_factor(1)
+            .returns_once(Ok(TwoFactorState {
+                secret: None,
+                enabled: false,
+                last_step: 0,
+            }));
+        repository
+            .mock_set_pending_secret(1, Any)
+            .returns_once(Ok(()));
+
+        let service = TwoFactorService::new(repository);
+        let enrolment = service.enrolment(1, "alice").unwrap();
+        assert!(matches!(
+            enrolment,
+            TwoFactorEnrolment::Pending { provisioning_uri, .. }
+                if provisioning_uri.starts_with("otpauth://totp/Rusty%20CMS:alice?")
+        ));
+    }
+
+    #[test]
+    fn test_confirm_enrolment_issues_recovery_codes() {
+        let mut repository = TwoFactorRepository::new_mock();
+        let (_, code) = current_code();
+        repository
+            .mock_fetch_two_factor(1)
+            .returns_once(Ok(state(false, 0)));
+        repository
+            .mock_enable_two_factor(1, Any, Any)
+            .returns_once(Ok(()));
+
+        let service = TwoFactorService::new(repository);
+        let recovery_codes = service.confirm_enrolment(1, &code).unwrap();
+        assert_eq!(recovery_codes.map(|codes| codes.len()), Some(10));
+    }
+
+    #[test]
+    fn test_confirm_enrolment_wrong_code() {
+        let mut repository = TwoFactorRepository::new_mock();
+        repository
+            .mock_fetch_two_factor(1)
+            .returns_once(Ok(state(false, 0)));
+
+        let service = TwoFactorService::new(repository);
+        assert!(service.confirm_enrolment(1, "000000x").unwrap().is_none());
+    }
+
+    #[test]
+    fn test_verify_code_refuses_replayed_step() {
+        let mut repository = TwoFactorRepository::new_mock();
+        let (step, code) = current_code();
+        repository
+            .mock_fetch_two_factor(1)
+            .returns_once(Ok(state(true, step + 1)));
+        repository
+            .mock_use_recovery_code(1, Any)
+            .returns_once(Ok(false));
+
+        let service = TwoFactorService::new(repository);
+        assert!(!service.verify_code(1, &code).unwrap());
+    }
+
+    #[test]
+    fn test_verify_code_accepts_recovery_code() {
+        let mut repository = TwoFactorRepository::new_mock();
+        repository
+            .mock_fetch_two_factor(1)
+            .returns_once(Ok(state(true, 0)));
+        repository
+            .mock_use_recovery_code(1, hash_recovery_code("abcde-fghij"))
+            .returns_once(Ok(true));
+
+        let service = TwoFactorService::new(repository);
+        assert!(service.verify_code(1, "ABCDE FGHIJ").unwrap());
+    }
+}
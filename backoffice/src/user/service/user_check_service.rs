@@ -1,21 +1,16 @@
-use crate::user::LOGIN_TOKEN_COOKIE_NAME;
 use crate::user::model::user_model::UserIdContext;
-use crate::user::repository::user_repository::UserRepository;
 use crate::user::role::Role;
+use crate::user::service::session_service::SessionService;
 use error_stack::Report;
 use shared::utils::context::{Context, ContextError, FromContext};
 
 pub struct UserCheckService {
-    user_repository: UserRepository,
-    token_cookie: Option<String>,
+    session_service: SessionService,
 }
 
 impl UserCheckService {
-    pub fn new(user_repository: UserRepository, token_cookie: Option<String>) -> Self {
-        Self {
-            user_repository,
-            token_cookie,
-        }
+    pub fn new(session_service: SessionService) -> Self {
+        Self { session_service }
     }
 
     pub fn get_user_context(&self) -> UserIdContext {
@@ -26,64 +21,129 @@ impl UserCheckService {
                 id: 0,
                 username: "visitor".to_string(),
                 role: Role::Visitor,
+                must_change_password: false,
+                two_factor_enabled: false,
             }
         }
     }
 
+    /// A sliding expiry, every visit renews the token.
     fn is_logged_in(&self) -> Option<UserIdContext> {
-        if let Some(token) = self.token_cookie.as_ref() {
-            self.user_repository.find_by_token(token.to_string()).ok()
-        } else {
-            None
-        }
+        let user_context = self.session_service.current_user()?;
+        self.session_service.renew();
+        Some(user_context)
     }
 }
 
 impl FromContext for UserCheckService {
     async fn from_context(ctx: &'_ Context<'_>) -> Result<Self, Report<ContextError>> {
-        let req = ctx.req_result()?;
-        let cookie = req.cookie();
-        Ok(Self::new(
-            ctx.inject().await?,
-            cookie
-                .get(LOGIN_TOKEN_COOKIE_NAME)
-                .map(|v| v.value_str().to_string()),
-        ))
+        Ok(Self::new(ctx.inject().await?))
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::user::repository::user_repository::UserRepositoryError;
+    use crate::user::LOGIN_TOKEN_COOKIE_NAME;
+    use crate::user::repository::user_repository::{UserRepository, UserRepositoryError};
+    use crate::user::service::session_service::SessionOrigin;
+    use poem::web::cookie::CookieJar;
+    use shared::utils::config::session::SessionConfig;
+    use shared::utils::token::hash_token;
+    use std::sync::Arc;
+
+    fn service(user_repository: UserRepository, cookie_jar: CookieJar) -> UserCheckService {
+        UserCheckService::new(SessionService::new(
+            user_repository,
+            Arc::new(SessionConfig::default()),
+            SessionOrigin::default(),
+            Some("hello".to_string()),
+            cookie_jar,
+        ))
+    }
 
     #[test]
     fn test_get_user_context_user() {
         let mut user_repository = UserRepository::new_mock();
 
         user_repository
-            .mock_find_by_token("hello".to_string())
+            .mock_find_by_token(hash_token("", "hello"))
             .returns_once(Ok(UserIdContext {
                 id: 5,
                 username: "".to_string(),
                 role: Default::default(),
+                must_change_password: false,
+                two_factor_enabled: false,
             }));
+        user_repository
+            .mock_renew_token(hash_token("", "hello"), 30, 300)
+            .returns_once(Ok(false));
 
-        let service = UserCheckService::new(user_repository, Some("hello".to_string()));
+        let service = service(user_repository, CookieJar::default());
         let result = service.get_user_context();
         assert_eq!(result.id, 5);
     }
 
+    #[test]
+    fn test_get_user_context_must_change_password() {
+        let mut user_repository = UserRepository::new_mock();
+
+        user_repository
+            .mock_find_by_token(hash_token("", "hello"))
+            .returns_once(Ok(UserIdContext {
+                id: 5,
+                username: "".to_string(),
+                role: Role::Root,
+                must_change_password: true,
+                two_factor_enabled: false,
+            }));
+        user_repository
+            .mock_renew_token(hash_token("", "hello"), 30, 300)
+            .returns_once(Ok(false));
+
+        let service = service(user_repository, CookieJar::default());
+        let result = service.get_user_context();
+        assert!(result.must_change_password);
+    }
+
     #[test]
     fn test_get_user_context_visitor() {
         let mut user_repository = UserRepository::new_mock();
 
         user_repository
-            .mock_find_by_token("hello".to_string())
+            .mock_find_by_token(hash_token("", "hello"))
             .returns_once(Err(Report::new(UserRepositoryError::QueryError)));
 
-        let service = UserCheckService::new(user_repository, Some("hello".to_string()));
+        let service = service(user_repository, CookieJar::default());
         let result = service.get_user_context();
         assert_eq!(result.id, 0);
     }
+
+    #[test]
+    fn test_get_user_context_renews_cookie() {
+        let mut user_repository = UserRepository::new_mock();
+
+        user_repository
+            .mock_find_by_token(hash_token("", "hello"))
+            .returns_once(Ok(UserIdContext {
+                id: 5,
+                username: "".to_string(),
+                role: Default::default(),
+                must_change_password: false,
+                two_factor_enabled: false,
+            }));
+        user_repository
+            .mock_renew_token(hash_token("", "hello"), 30, 300)
+            .returns_once(Ok(true));
+
+        let cookie_jar = CookieJar::default();
+        let service = service(user_repository, cookie_jar.clone());
+        service.get_user_context();
+        assert_eq!(
+            cookie_jar
+                .get(LOGIN_TOKEN_COOKIE_NAME)
+                .map(|cookie| cookie.value_str().to_string()),
+            Some("hello".to_string())
+        );
+    }
 }
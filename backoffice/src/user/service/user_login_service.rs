@@ -1,72 +1,121 @@
-use crate::user::LOGIN_TOKEN_COOKIE_NAME;
 use crate::user::layer::password_layer::PasswordLayer;
 use crate::user::repository::user_repository::UserRepository;
+use crate::user::service::session_service::SessionService;
+use crate::user::service::two_factor_service::TwoFactorService;
+use chrono::TimeDelta;
 use error_stack::Report;
 use shared::utils::context::{Context, ContextError, FromContext};
-use uuid::Uuid;
+use shared::utils::password::{Password, PasswordState};
+
+pub enum LoginStep {
+    Token(String),
+    /// The password matched, the challenge token waits for the TOTP or recovery code.
+    TwoFactor(String),
+}
 
 pub struct UserLoginService {
     user_repository: UserRepository,
     password_layer: PasswordLayer,
-    token_cookie: Option<String>,
+    two_factor_service: TwoFactorService,
+    session_service: SessionService,
 }
 
 impl UserLoginService {
     pub fn new(
         user_repository: UserRepository,
         password_layer: PasswordLayer,
-        token_cookie: Option<String>,
+        two_factor_service: TwoFactorService,
+        session_service: SessionService,
     ) -> Self {
         Self {
             user_repository,
             password_layer,
-            token_cookie,
+            two_factor_service,
+            session_service,
         }
     }
 
-    pub fn validate_login(&self, username: String, password: String) -> Option<String> {
+    pub fn validate_login(&self, username: String, password: String) -> Option<LoginStep> {
         if let Ok(id_password) = self.user_repository.get_user_password(username) {
             let password_status = self
                 .password_layer
                 .verify_password(id_password.password, password.as_str());
             if let Ok(password_state) = password_status {
                 if password_state.is_valid() {
-                    let uuid = Uuid::new_v4().to_string();
-
-                    if self
-                        .user_repository
-                        .add_token(uuid.clone(), id_password.id)
-                        .is_err()
-                    {
-                        return None;
+                    if let PasswordState::ValidRehashed(rehashed) = password_state {
+                        self.save_rehashed_password(id_password.id, rehashed);
                     }
-
-                    return Some(uuid);
+                    if id_password.two_factor_enabled {
+                        return self
+                            .two_factor_service
+                            .start_challenge(id_password.id)
+                            .ok()
+                            .map(LoginStep::TwoFactor);
+                    }
+                    return self
+                        .session_service
+                        .issue_token(id_password.id)
+                        .map(LoginStep::Token);
                 }
             }
         }
         None
     }
 
-    pub fn logout(&self) -> bool {
-        if let Some(token) = self.token_cookie.as_ref() {
-            self.user_repository.delete_token(token.to_string()).is_ok()
-        } else {
-            false
+    /// A failed save is only logged, the old hash still verifies and is retried on the next login.
+    fn save_rehashed_password(&self, user_id: i64, password: Password) {
+        let saved = password
+            .encode_to_msg_pack()
+            .map(|encoded| self.user_repository.set_password(user_id, encoded));
+        match saved {
+            Ok(Ok(())) => log::info!("Rehashed password for user {}", user_id),
+            Ok(Err(report)) => log::error!("Failed to store rehashed password\n{:?}", report),
+            Err(report) => log::error!("Failed to encode rehashed password\n{:?}", report),
+        }
+    }
+
+    /// The second step, a wrong code leaves the challenge open until it expires.
+    pub fn validate_two_factor(&self, challenge: &str, code: &str) -> Option<String> {
+        let user_id = self
+            .two_factor_service
+            .challenge_user(challenge)
+            .ok()??
+            .user_id;
+        if !self
+            .two_factor_service
+            .verify_code(user_id, code)
+            .unwrap_or(false)
+        {
+            return None;
         }
+        self.two_factor_service.finish_challenge(challenge).ok()?;
+        self.session_service.issue_token(user_id)
+    }
+
+    /// The username behind an open challenge, so the second step is throttled like the first.
+    pub fn challenge_username(&self, challenge: &str) -> Option<String> {
+        self.two_factor_service
+            .challenge_user(challenge)
+            .ok()?
+            .map(|login_challenge| login_challenge.username)
+    }
+
+    pub fn session_lifetime(&self) -> TimeDelta {
+        self.session_service.lifetime()
+    }
+
+    pub fn logout(&self) -> bool {
+        self.session_service.logout()
     }
 }
 
 impl FromContext for UserLoginService {
     async fn from_context(ctx: &'_ Context<'_>) -> Result<Self, Report<ContextError>> {
-        let req = ctx.req_result()?;
-        let cookie = req.cookie();
         Ok(Self::new(
             ctx.inject().await?,
             ctx.inject().await?,
-            cookie
-                .get(LOGIN_TOKEN_COOKIE_NAME)
-                .map(|v| v.value_str().to_string()),
+            ctx.inject().await?,
+            ctx.inject().await?,
         ))
     }
 }
@@ -75,9 +124,28 @@ impl FromContext for UserLoginService {
 mod tests {
     use super::*;
     use crate::user::model::user_model::IdPassword;
+    use crate::user::repository::two_factor_repository::TwoFactorRepository;
     use crate::user::repository::user_repository::UserRepositoryError;
+    use crate::user::service::session_service::SessionOrigin;
     use mry::Any;
-    use shared::utils::password::PasswordState;
+    use poem::web::cookie::CookieJar;
+    use shared::utils::config::session::SessionConfig;
+    use shared::utils::token::hash_token;
+    use std::sync::Arc;
+
+    fn two_factor_service() -> TwoFactorService {
+        TwoFactorService::new(TwoFactorRepository::new_mock())
+    }
+
+    fn session_service(session_repository: UserRepository, token: Option<&str>) -> SessionService {
+        SessionService::new(
+            session_repository,
+            Arc::new(SessionConfig::default()),
+            SessionOrigin::default(),
+            token.map(str::to_string),
+            CookieJar::default(),
+        )
+    }
 
     #[test]
     fn test_validate_login_success() {
@@ -89,17 +157,63 @@ mod tests {
             .returns_once(Ok(IdPassword {
                 id: 1,
                 password: Default::default(),
+                two_factor_enabled: false,
             }));
 
         password_layer
             .mock_verify_password(Any, "password")
             .returns_once(Ok(PasswordState::Valid));
 
-        user_repository.mock_add_token(Any, 1).returns_once(Ok(()));
+        let mut session_repository = UserRepository::new_mock();
+        session_repository
+            .mock_add_token(Any, 1, Any, Any, Any)
+            .returns_once(Ok(()));
+
+        let service = UserLoginService::new(
+            user_repository,
+            password_layer,
+            two_factor_service(),
+            session_service(session_repository, None),
+        );
+        let str = service.validate_login("hello".to_string(), "password".to_string());
+        assert!(matches!(str, Some(LoginStep::Token(_))));
+    }
+
+    #[test]
+    fn test_validate_login_saves_rehashed_password() {
+        let mut user_repository = UserRepository::new_mock();
+        let mut password_layer = PasswordLayer::new_mock();
 
-        let service = UserLoginService::new(user_repository, password_layer, None);
+        user_repository
+            .mock_get_user_password("hello".to_string())
+            .returns_once(Ok(IdPassword {
+                id: 1,
+                password: Default::default(),
+                two_factor_enabled: false,
+            }));
+        user_repository
+            .mock_set_password(1, Any)
+            .returns_once(Ok(()));
+
+        password_layer
+            .mock_verify_password(Any, "password")
+            .returns_once(Ok(PasswordState::ValidRehashed(Password::Version1 {
+                argon2: "rehashed".to_string(),
+            })));
+
+        let mut session_repository = UserRepository::new_mock();
+        session_repository
+            .mock_add_token(Any, 1, Any, Any, Any)
+            .returns_once(Ok(()));
+
+        let service = UserLoginService::new(
+            user_repository,
+            password_layer,
+            two_factor_service(),
+            session_service(session_repository, None),
+        );
         let str = service.validate_login("hello".to_string(), "password".to_string());
-        assert!(str.is_some());
+        assert!(matches!(str, Some(LoginStep::Token(_))));
     }
 
     #[test]
@@ -111,7 +225,12 @@ mod tests {
             .mock_get_user_password("hello".to_string())
             .returns_once(Err(Report::new(UserRepositoryError::QueryError)));
 
-        let service = UserLoginService::new(user_repository, password_layer, None);
+        let service = UserLoginService::new(
+            user_repository,
+            password_layer,
+            two_factor_service(),
+            session_service(UserRepository::new_mock(), None),
+        );
         let str = service.validate_login("hello".to_string(), "password".to_string());
         assert!(str.is_none());
     }
@@ -126,13 +245,19 @@ mod tests {
             .returns_once(Ok(IdPassword {
                 id: 1,
                 password: Default::default(),
+                two_factor_enabled: false,
             }));
 
         password_layer
             .mock_verify_password(Any, "password")
             .returns_once(Ok(PasswordState::Invalid));
 
-        let service = UserLoginService::new(user_repository, password_layer, None);
+        let service = UserLoginService::new(
+            user_repository,
+            password_layer,
+            two_factor_service(),
+            session_service(UserRepository::new_mock(), None),
+        );
         let str = service.validate_login("hello".to_string(), "password".to_string());
         assert!(str.is_none());
     }
@@ -147,47 +272,115 @@ mod tests {
             .returns_once(Ok(IdPassword {
                 id: 1,
                 password: Default::default(),
+                two_factor_enabled: false,
             }));
 
         password_layer
             .mock_verify_password(Any, "password")
             .returns_once(Ok(PasswordState::Valid));
 
-        user_repository
-            .mock_add_token(Any, 1)
+        let mut session_repository = UserRepository::new_mock();
+        session_repository
+            .mock_add_token(Any, 1, Any, Any, Any)
             .returns_once(Err(Report::new(UserRepositoryError::QueryError)));
 
-        let service = UserLoginService::new(user_repository, password_layer, None);
+        let service = UserLoginService::new(
+            user_repository,
+            password_layer,
+            two_factor_service(),
+            session_service(session_repository, None),
+        );
         let str = service.validate_login("hello".to_string(), "password".to_string());
         assert!(str.is_none());
     }
 
     #[test]
-    fn test_logout_success() {
+    fn test_validate_login_two_factor_challenge() {
         let mut user_repository = UserRepository::new_mock();
-        let password_layer = PasswordLayer::new_mock();
+        let mut password_layer = PasswordLayer::new_mock();
+        let mut two_factor_repository = TwoFactorRepository::new_mock();
 
         user_repository
-            .mock_delete_token("hello".to_string())
+            .mock_get_user_password("hello".to_string())
+            .returns_once(Ok(IdPassword {
+                id: 1,
+                password: Default::default(),
+                two_factor_enabled: true,
+            }));
+
+        password_layer
+            .mock_verify_password(Any, "password")
+            .returns_once(Ok(PasswordState::Valid));
+
+        two_factor_repository
+            .mock_add_challenge(Any, 1)
+            .returns_once(Ok(()));
+
+        let service = UserLoginService::new(
+            user_repository,
+            password_layer,
+            TwoFactorService::new(two_factor_repository),
+            session_service(UserRepository::new_mock(), None),
+        );
+        let str = service.validate_login("hello".to_string(), "password".to_string());
+        assert!(matches!(str, Some(LoginStep::TwoFactor(_))));
+    }
+
+    #[test]
+    fn test_validate_two_factor_expired_challenge() {
+        let user_repository = UserRepository::new_mock();
+        let password_layer = PasswordLayer::new_mock();
+        let mut two_factor_repository = TwoFactorRepository::new_mock();
+
+        two_factor_repository
+            .mock_find_challenge("challenge".to_string())
+            .returns_once(Ok(None));
+
+        let service = UserLoginService::new(
+            user_repository,
+            password_layer,
+            TwoFactorService::new(two_factor_repository),
+            session_service(UserRepository::new_mock(), None),
+        );
+        assert!(service.validate_two_factor("challenge", "123456").is_none());
+    }
+
+    #[test]
+    fn test_logout_success() {
+        let user_repository = UserRepository::new_mock();
+        let password_layer = PasswordLayer::new_mock();
+        let mut session_repository = UserRepository::new_mock();
+
+        session_repository
+            .mock_delete_token(hash_token("", "hello"))
             .returns_once(Ok(()));
 
-        let service =
-            UserLoginService::new(user_repository, password_layer, Some("hello".to_string()));
+        let service = UserLoginService::new(
+            user_repository,
+            password_layer,
+            two_factor_service(),
+            session_service(session_repository, Some("hello")),
+        );
         let result = service.logout();
         assert_eq!(result, true);
     }
 
     #[test]
     fn test_logout_fail() {
-        let mut user_repository = UserRepository::new_mock();
+        let user_repository = UserRepository::new_mock();
         let password_layer = PasswordLayer::new_mock();
+        let mut session_repository = UserRepository::new_mock();
 
-        user_repository
-            .mock_delete_token("hello".to_string())
+        session_repository
+            .mock_delete_token(hash_token("", "hello"))
             .returns_once(Err(Report::new(UserRepositoryError::QueryError)));
 
-        let service =
-            UserLoginService::new(user_repository, password_layer, Some("hello".to_string()));
+        let service = UserLoginService::new(
+            user_repository,
+            password_layer,
+            two_factor_service(),
+            session_service(session_repository, Some("hello")),
+        );
         let result = service.logout();
         assert_eq!(result, false);
     }
@@ -48,6 +48,7 @@ impl AddUserService {
                     .log_it()
                     .attach(StatusCode::INTERNAL_SERVER_ERROR)?,
                 &add_user_validated.role,
+                add_user_validated.must_change_password,
             )
             .change_context(AddUserServiceError::SubmitFailed)?;
         Ok(())
@@ -101,6 +102,7 @@ mod tests {
                 add_user_validated.username.as_str().to_string(),
                 Any,
                 add_user_validated.role.clone(),
+                false,
             )
             .returns_once(Ok(()));
 
@@ -143,6 +145,7 @@ mod tests {
                 add_user_validated.username.as_str().to_string(),
                 Any,
                 add_user_validated.role.clone(),
+                false,
             )
             .returns_once(Err(Report::new(UserManagerRepositoryError::QueryError)));
 
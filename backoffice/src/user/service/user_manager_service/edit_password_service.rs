@@ -2,6 +2,7 @@ use crate::user::form::edit_password_manager::EditPasswordManagerValidated;
 use crate::user::layer::password_layer::PasswordLayer;
 use crate::user::model::user_manager_model::FetchUser;
 use crate::user::repository::user_manager_repository::UserManagerRepository;
+use crate::user::service::session_service::SessionService;
 use error_stack::{Report, ResultExt};
 use poem::http::StatusCode;
 use shared::utils::context::{Context, ContextError, FromContext};
@@ -24,16 +25,19 @@ pub enum EditPasswordServiceError {
 pub struct EditPasswordService {
     user_manager_repository: UserManagerRepository,
     password_layer: PasswordLayer,
+    session_service: SessionService,
 }
 
 impl EditPasswordService {
     pub fn new(
         user_manager_repository: UserManagerRepository,
         password_layer: PasswordLayer,
+        session_service: SessionService,
     ) -> Self {
         Self {
             user_manager_repository,
             password_layer,
+            session_service,
         }
     }
 
@@ -50,8 +54,12 @@ impl EditPasswordService {
                     .change_context(EditPasswordServiceError::PasswordSerializeError)
                     .log_it()
                     .attach(StatusCode::INTERNAL_SERVER_ERROR)?,
+                password.must_change_password,
             )
             .change_context(EditPasswordServiceError::DbError)?;
+        self.session_service
+            .rotate(user_id)
+            .change_context(EditPasswordServiceError::DbError)?;
 
         Ok(())
     }
@@ -75,13 +83,32 @@ impl EditPasswordService {
 
 impl FromContext for EditPasswordService {
     async fn from_context(ctx: &'_ Context<'_>) -> Result<Self, Report<ContextError>> {
-        Ok(Self::new(ctx.inject().await?, ctx.inject().await?))
+        Ok(Self::new(
+            ctx.inject().await?,
+            ctx.inject().await?,
+            ctx.inject().await?,
+        ))
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::user::repository::user_repository::UserRepository;
+    use crate::user::service::session_service::SessionOrigin;
+    use poem::web::cookie::CookieJar;
+    use shared::utils::config::session::SessionConfig;
+    use std::sync::Arc;
+
+    fn session_service(user_repository: UserRepository) -> SessionService {
+        SessionService::new(
+            user_repository,
+            Arc::new(SessionConfig::default()),
+            SessionOrigin::default(),
+            None,
+            CookieJar::default(),
+        )
+    }
 
     mod test_edit_password_submit {
         use super::*;
@@ -102,10 +129,19 @@ mod tests {
                 }));
 
             user_manager_repository
-                .mock_edit_password(1, Any)
+                .mock_edit_password(1, Any, false)
+                .returns_once(Ok(()));
+
+            let mut user_repository = UserRepository::new_mock();
+            user_repository
+                .mock_delete_user_token(1)
                 .returns_once(Ok(()));
 
-            let service = EditPasswordService::new(user_manager_repository, password_layer);
+            let service = EditPasswordService::new(
+                user_manager_repository,
+                password_layer,
+                session_service(user_repository),
+            );
             let result = service.edit_password_submit(1, &password);
             assert!(result.is_ok());
         }
@@ -120,7 +156,11 @@ mod tests {
                 .mock_hash_password(password.password.as_str())
                 .returns_once(Err(Report::new(PasswordError("Failed".to_string()))));
 
-            let service = EditPasswordService::new(user_manager_repository, password_layer);
+            let service = EditPasswordService::new(
+                user_manager_repository,
+                password_layer,
+                session_service(UserRepository::new_mock()),
+            );
             let result = service.edit_password_submit(1, &password);
             assert!(result.is_err());
         }
@@ -138,10 +178,14 @@ mod tests {
                 }));
 
             user_manager_repository
-                .mock_edit_password(1, Any)
+                .mock_edit_password(1, Any, false)
                 .returns_once(Err(Report::new(UserManagerRepositoryError::QueryError)));
 
-            let service = EditPasswordService::new(user_manager_repository, password_layer);
+            let service = EditPasswordService::new(
+                user_manager_repository,
+                password_layer,
+                session_service(UserRepository::new_mock()),
+            );
             let result = service.edit_password_submit(1, &password);
             assert!(result.is_err());
         }
@@ -160,9 +204,14 @@ mod tests {
                 .returns_once(Ok(Some(FetchUser {
                     username: "username".to_string(),
                     role: Default::default(),
+                    two_factor_enabled: false,
                 })));
 
-            let service = EditPasswordService::new(user_manager_repository, password_layer);
+            let service = EditPasswordService::new(
+                user_manager_repository,
+                password_layer,
+                session_service(UserRepository::new_mock()),
+            );
             let result = service.fetch_user(1);
             assert!(result.is_ok());
         }
@@ -175,7 +224,11 @@ mod tests {
                 .mock_fetch_user(1)
                 .returns_once(Ok(None));
 
-            let service = EditPasswordService::new(user_manager_repository, password_layer);
+            let service = EditPasswordService::new(
+                user_manager_repository,
+                password_layer,
+                session_service(UserRepository::new_mock()),
+            );
             let result = service.fetch_user(1);
             assert!(result.is_err());
             let result = result.err().unwrap();
@@ -191,7 +244,11 @@ mod tests {
                 .mock_fetch_user(1)
                 .returns_once(Err(Report::new(UserManagerRepositoryError::QueryError)));
 
-            let service = EditPasswordService::new(user_manager_repository, password_layer);
+            let service = EditPasswordService::new(
+                user_manager_repository,
+                password_layer,
+                session_service(UserRepository::new_mock()),
+            );
             let result = service.fetch_user(1);
             assert!(result.is_err());
         }
@@ -1,6 +1,7 @@
 use crate::user::form::edit_user::EditUserValidated;
 use crate::user::model::user_manager_model::FetchUser;
 use crate::user::repository::user_manager_repository::UserManagerRepository;
+use crate::user::service::session_service::SessionService;
 use cjtoolkit_structured_validator::types::username::IsUsernameTakenAsync;
 use error_stack::{Report, ResultExt};
 use poem::http::StatusCode;
@@ -17,12 +18,17 @@ pub enum EditUserServiceError {
 
 pub struct EditUserService {
     user_manager_repository: UserManagerRepository,
+    session_service: SessionService,
 }
 
 impl EditUserService {
-    pub fn new(user_manager_repository: UserManagerRepository) -> Self {
+    pub fn new(
+        user_manager_repository: UserManagerRepository,
+        session_service: SessionService,
+    ) -> Self {
         Self {
             user_manager_repository,
+            session_service,
         }
     }
 
@@ -31,6 +37,11 @@ impl EditUserService {
         user_id: i64,
         edit_user_validated: &EditUserValidated,
     ) -> Result<(), Report<EditUserServiceError>> {
+        let previous_role = self
+            .user_manager_repository
+            .fetch_user(user_id)
+            .change_context(EditUserServiceError::SubmitFailed)?
+            .map(|user| user.role);
         self.user_manager_repository
             .edit_user(
                 user_id,
@@ -38,6 +49,11 @@ impl EditUserService {
                 &edit_user_validated.role,
             )
             .change_context(EditUserServiceError::SubmitFailed)?;
+        if previous_role != Some(edit_user_validated.role) {
+            self.session_service
+                .rotate(user_id)
+                .change_context(EditUserServiceError::SubmitFailed)?;
+        }
         Ok(())
     }
 
@@ -62,13 +78,37 @@ impl IsUsernameTakenAsync for EditUserService {
 
 impl FromContext for EditUserService {
     async fn from_context(ctx: &'_ Context<'_>) -> Result<Self, Report<ContextError>> {
-        Ok(Self::new(ctx.inject().await?))
+        Ok(Self::new(ctx.inject().await?, ctx.inject().await?))
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::user::repository::user_repository::UserRepository;
+    use crate::user::role::Role;
+    use crate::user::service::session_service::SessionOrigin;
+    use poem::web::cookie::CookieJar;
+    use shared::utils::config::session::SessionConfig;
+    use std::sync::Arc;
+
+    fn session_service(user_repository: UserRepository) -> SessionService {
+        SessionService::new(
+            user_repository,
+            Arc::new(SessionConfig::default()),
+            SessionOrigin::default(),
+            None,
+            CookieJar::default(),
+        )
+    }
+
+    fn fetch_user(role: Role) -> FetchUser {
+        FetchUser {
+            username: "username".to_string(),
+            role,
+            two_factor_enabled: false,
+        }
+    }
 
     mod test_edit_user_submit {
         use super::*;
@@ -79,6 +119,32 @@ mod tests {
         fn test_submit_success() {
             let edit_user_validated = EditUserValidated::new_test_data();
             let mut user_manager_repository = UserManagerRepository::new_mock();
+            user_manager_repository
+                .mock_fetch_user(1)
+                .returns_once(Ok(Some(fetch_user(edit_user_validated.role))));
+            user_manager_repository
+                .mock_edit_user(
+                    1,
+                    edit_user_validated.username.as_str().to_string(),
+                    edit_user_validated.role.clone(),
+                )
+                .returns_once(Ok(()));
+
+            let service = EditUserService::new(
+                user_manager_repository,
+                session_service(UserRepository::new_mock()),
+            );
+            let result = service.edit_user_submit(1, &edit_user_validated);
+            assert!(result.is_ok());
+        }
+
+        #[test]
+        fn test_submit_role_change_rotates_tokens() {
+            let edit_user_validated = EditUserValidated::new_test_data();
+            let mut user_manager_repository = UserManagerRepository::new_mock();
+            user_manager_repository
+                .mock_fetch_user(1)
+                .returns_once(Ok(Some(fetch_user(Role::Root))));
             user_manager_repository
                 .mock_edit_user(
                     1,
@@ -86,8 +152,13 @@ mod tests {
                     edit_user_validated.role.clone(),
                 )
                 .returns_once(Ok(()));
+            let mut user_repository = UserRepository::new_mock();
+            user_repository
+                .mock_delete_user_token(1)
+                .returns_once(Ok(()));
 
-            let service = EditUserService::new(user_manager_repository);
+            let service =
+                EditUserService::new(user_manager_repository, session_service(user_repository));
             let result = service.edit_user_submit(1, &edit_user_validated);
             assert!(result.is_ok());
         }
@@ -96,6 +167,9 @@ mod tests {
         fn test_submit_fail() {
             let edit_user_validated = EditUserValidated::new_test_data();
             let mut user_manager_repository = UserManagerRepository::new_mock();
+            user_manager_repository
+                .mock_fetch_user(1)
+                .returns_once(Ok(Some(fetch_user(edit_user_validated.role))));
             user_manager_repository
                 .mock_edit_user(
                     1,
@@ -104,7 +178,10 @@ mod tests {
                 )
                 .returns_once(Err(Report::new(UserManagerRepositoryError::QueryError)));
 
-            let service = EditUserService::new(user_manager_repository);
+            let service = EditUserService::new(
+                user_manager_repository,
+                session_service(UserRepository::new_mock()),
+            );
             let result = service.edit_user_submit(1, &edit_user_validated);
             assert!(result.is_err());
         }
@@ -122,9 +199,13 @@ mod tests {
                 .returns_once(Ok(Some(FetchUser {
                     username: "username".to_string(),
                     role: Default::default(),
+                    two_factor_enabled: false,
                 })));
 
-            let service = EditUserService::new(user_manager_repository);
+            let service = EditUserService::new(
+                user_manager_repository,
+                session_service(UserRepository::new_mock()),
+            );
             let result = service.fetch_user(1);
             assert!(result.is_ok());
         }
@@ -136,7 +217,10 @@ mod tests {
                 .mock_fetch_user(1)
                 .returns_once(Ok(None));
 
-            let service = EditUserService::new(user_manager_repository);
+            let service = EditUserService::new(
+                user_manager_repository,
+                session_service(UserRepository::new_mock()),
+            );
             let result = service.fetch_user(1);
             assert!(result.is_err());
             let result = result.err().unwrap();
@@ -151,7 +235,10 @@ mod tests {
                 .mock_fetch_user(1)
                 .returns_once(Err(Report::new(UserManagerRepositoryError::QueryError)));
 
-            let service = EditUserService::new(user_manager_repository);
+            let service = EditUserService::new(
+                user_manager_repository,
+                session_service(UserRepository::new_mock()),
+            );
             let result = service.fetch_user(1);
             assert!(result.is_err());
         }
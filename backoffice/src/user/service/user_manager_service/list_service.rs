@@ -1,8 +1,8 @@
-use crate::user::model::user_manager_model::ListUser;
+use crate::user::model::user_manager_model::{ListUser, ListUserQuery};
 use crate::user::repository::user_manager_repository::UserManagerRepository;
 use error_stack::Report;
 use shared::utils::context::{Context, ContextError, FromContext};
-use std::sync::Arc;
+use shared::utils::query_string::list_query::KeysetPage;
 
 pub struct ListUserService {
     user_manager_repository: UserManagerRepository,
@@ -15,9 +15,9 @@ impl ListUserService {
         }
     }
 
-    pub fn list_users(&self) -> Arc<[ListUser]> {
+    pub fn list_users(&self, list_query: &ListUserQuery) -> KeysetPage<ListUser> {
         self.user_manager_repository
-            .list_users()
+            .list_users(list_query)
             .unwrap_or_default()
     }
 }
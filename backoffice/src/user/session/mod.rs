@@ -0,0 +1,68 @@
+use crate::user::repository::user_repository::UserRepository;
+use error_stack::{Report, ResultExt};
+use log::{error, info};
+use shared::utils::config::Config;
+use shared::utils::db::SqliteClient;
+use shared::utils::error::boot_error::MainError;
+use shared::utils::token::hash_token;
+use std::time::Duration;
+
+/// Replaces login tokens stored before hashing with their keyed hash, so existing sessions stay signed in.
+pub async fn hash_legacy_login_token() -> Result<(), Report<MainError>> {
+    let config = Config::fetch()
+        .await
+        .change_context(MainError::ConfigError)?
+        .upgrade()
+        .ok_or_else(|| Report::new(MainError::ConfigError))?;
+    config
+        .session
+        .validate()
+        .change_context(MainError::ConfigError)?;
+    let repository = UserRepository::new(
+        SqliteClient::fetch(&config.sqlite)
+            .await
+            .change_context(MainError::DbError)?,
+    );
+
+    let tokens = repository
+        .list_unhashed_token()
+        .change_context(MainError::DbError)?;
+    for token in tokens.iter() {
+        repository
+            .set_token_hash(
+                token.clone(),
+                hash_token(&config.session.token_secret, token),
+            )
+            .change_context(MainError::DbError)?;
+    }
+    if !tokens.is_empty() {
+        info!("Hashed {} stored login token(s)", tokens.len());
+    }
+    Ok(())
+}
+
+/// Deletes login tokens past their expiry, a failed run is logged and retried on the next tick.
+pub async fn run_session_purge() -> Result<(), Report<MainError>> {
+    let config = Config::fetch()
+        .await
+        .change_context(MainError::ConfigError)?
+        .upgrade()
+        .ok_or_else(|| Report::new(MainError::ConfigError))?;
+    let repository = UserRepository::new(
+        SqliteClient::fetch(&config.sqlite)
+            .await
+            .change_context(MainError::DbError)?,
+    );
+
+    let mut interval = tokio::time::interval(Duration::from_secs(
+        config.session.purge_interval_secs.max(1),
+    ));
+    loop {
+        interval.tick().await;
+        match repository.purge_expired_token() {
+            Ok(0) => {}
+            Ok(purged) => info!("Purged {} expired login session(s)", purged),
+            Err(report) => error!("Login session purge failed\n{:?}", report),
+        }
+    }
+}
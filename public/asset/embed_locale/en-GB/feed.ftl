@@ -0,0 +1,2 @@
+# Feed
+feed-title = Latest Pages
\ No newline at end of file
@@ -0,0 +1,3 @@
+# Search
+search-title = Search
+search-no-result = No page matches your search
\ No newline at end of file
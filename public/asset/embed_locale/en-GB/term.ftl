@@ -0,0 +1,2 @@
+# Term
+term-no-page = No page has been published here yet
\ No newline at end of file
@@ -0,0 +1,132 @@
+use chrono::{DateTime, Utc};
+use poem::http::{HeaderMap, StatusCode, header};
+use poem::{IntoResponse, Response};
+use sha2::{Digest, Sha256};
+
+/// A generated document served with `ETag` and `Last-Modified`, answering 304 when the client's copy is current.
+pub struct ConditionalBody {
+    pub content_type: &'static str,
+    pub body: String,
+    pub last_modified: Option<DateTime<Utc>>,
+}
+
+impl ConditionalBody {
+    /// The ETag hashes the body, so a page dropping out changes it even when no date moved.
+    pub fn etag(&self) -> String {
+        format!("\"{:x}\"", Sha256::digest(self.body.as_bytes()))
+    }
+
+    fn is_not_modified(&self, headers: &HeaderMap) -> bool {
+        // If-None-Match wins over If-Modified-Since when both are sent.
+        if let Some(if_none_match) = headers
+            .get(header::IF_NONE_MATCH)
+            .and_then(|value| value.to_str().ok())
+        {
+            let etag = self.etag();
+            return if_none_match
+                .split(',')
+                .map(str::trim)
+                .any(|candidate| candidate == "*" || candidate.trim_start_matches("W/") == etag);
+        }
+
+        let if_modified_since = headers
+            .get(header::IF_MODIFIED_SINCE)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| DateTime::parse_from_rfc2822(value).ok());
+        match (self.last_modified, if_modified_since) {
+            (Some(last_modified), Some(if_modified_since)) => {
+                last_modified.timestamp() <= if_modified_since.timestamp()
+            }
+            _ => false,
+        }
+    }
+
+    pub fn into_response_for(self, headers: &HeaderMap) -> Response {
+        let etag = self.etag();
+        let last_modified = self.last_modified.map(http_date);
+        let response = match self.is_not_modified(headers) {
+            true => StatusCode::NOT_MODIFIED.into_response(),
+            false => self
+                .body
+                .with_content_type(self.content_type)
+                .into_response(),
+        };
+
+        let mut response = response
+            .with_header(header::ETAG, etag)
+            .with_header(header::CACHE_CONTROL, "no-cache")
+            .into_response();
+        if let Some(last_modified) = last_modified {
+            response.headers_mut().insert(
+                header::LAST_MODIFIED,
+                last_modified.parse().expect("valid http date"),
+            );
+        }
+        response
+    }
+}
+
+pub fn http_date(date: DateTime<Utc>) -> String {
+    date.format("%a, %d %b %Y %H:%M:%S GMT").to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn body() -> ConditionalBody {
+        ConditionalBody {
+            content_type: "application/xml",
+            body: "<feed></feed>".to_string(),
+            last_modified: Some(Utc.with_ymd_and_hms(2026, 1, 2, 3, 4, 5).unwrap()),
+        }
+    }
+
+    fn headers(name: header::HeaderName, value: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(name, value.parse().unwrap());
+        headers
+    }
+
+    #[test]
+    fn test_http_date() {
+        assert_eq!(
+            http_date(Utc.with_ymd_and_hms(2026, 1, 2, 3, 4, 5).unwrap()),
+            "Fri, 02 Jan 2026 03:04:05 GMT"
+        );
+    }
+
+    #[test]
+    fn test_etag_match_is_not_modified() {
+        let body = body();
+        let etag = format!("W/{}", body.etag());
+        assert!(body.is_not_modified(&headers(header::IF_NONE_MATCH, &etag)));
+        assert!(!body.is_not_modified(&headers(header::IF_NONE_MATCH, "\"other\"")));
+    }
+
+    #[test]
+    fn test_if_modified_since() {
+        let body = body();
+        assert!(body.is_not_modified(&headers(
+            header::IF_MODIFIED_SINCE,
+            "Fri, 02 Jan 2026 03:04:05 GMT"
+        )));
+        assert!(!body.is_not_modified(&headers(
+            header::IF_MODIFIED_SINCE,
+            "Fri, 02 Jan 2026 03:04:04 GMT"
+        )));
+    }
+
+    #[test]
+    fn test_not_modified_response() {
+        let body = body();
+        let etag = body.etag();
+        let response = body.into_response_for(&headers(header::IF_NONE_MATCH, &etag));
+        assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+        assert_eq!(
+            response.headers().get(header::LAST_MODIFIED).unwrap(),
+            "Fri, 02 Jan 2026 03:04:05 GMT"
+        );
+    }
+}
@@ -1,3 +1,4 @@
+pub mod conditional;
 pub mod embed;
 pub mod html;
 pub mod js;
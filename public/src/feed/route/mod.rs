@@ -0,0 +1,220 @@
+use crate::common::conditional::ConditionalBody;
+use crate::feed::service::feed_service::{Feed, FeedService};
+use chrono::{DateTime, Utc};
+use maud::{Markup, PreEscaped, html};
+use poem::http::HeaderMap;
+use poem::i18n::Locale;
+use poem::web::{Data, Path};
+use poem::{EndpointExt, IntoEndpoint, Response, RouteMethod, get, handler};
+use shared::cms::taxonomy::TermKind;
+use shared::utils::context::Dep;
+use shared::utils::error::FromErrorStack;
+use shared::utils::locale::LocaleExt;
+
+pub const ATOM_FEED_ROUTE: &str = "/feed.atom";
+pub const RSS_FEED_ROUTE: &str = "/feed.rss";
+
+const XML_DECLARATION: &str = r#"<?xml version="1.0" encoding="utf-8"?>"#;
+
+/// An empty feed still needs a date, the epoch keeps its ETag stable.
+fn feed_updated(feed: &Feed) -> DateTime<Utc> {
+    feed.updated.unwrap_or(DateTime::UNIX_EPOCH)
+}
+
+fn atom_partial(feed: &Feed) -> Markup {
+    html! {
+        (PreEscaped(XML_DECLARATION))
+        feed xmlns="http://www.w3.org/2005/Atom" {
+            title { (feed.title) }
+            id { (feed.feed_url("atom")) }
+            link rel="self" type="application/atom+xml" href=(feed.feed_url("atom")) {}
+            link rel="alternate" type="text/html" href=(feed.url) {}
+            updated { (feed_updated(feed).to_rfc3339()) }
+            author { name { (feed.title) } }
+            @for entry in feed.entries.iter() {
+                entry {
+                    title { (entry.title) }
+                    id { (entry.url) }
+                    link rel="alternate" type="text/html" href=(entry.url) {}
+                    published { (entry.added.to_rfc3339()) }
+                    updated { (entry.updated.to_rfc3339()) }
+                    @if !entry.summary.is_empty() {
+                        summary { (entry.summary) }
+                    }
+                    content type="html" { (entry.content_html) }
+                    @for enclosure in entry.enclosures.iter() {
+                        link rel="enclosure" type=(enclosure.file_type) length=(enclosure.length)
+                            href=(enclosure.url) {}
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn rss_partial(feed: &Feed) -> Markup {
+    html! {
+        (PreEscaped(XML_DECLARATION))
+        rss version="2.0" xmlns:atom="http://www.w3.org/2005/Atom"
+            xmlns:content="http://purl.org/rss/1.0/modules/content/" {
+            channel {
+                title { (feed.title) }
+                link { (feed.url) }
+                description { (feed.title) }
+                atom:link rel="self" type="application/rss+xml" href=(feed.feed_url("rss")) {}
+                lastBuildDate { (feed_updated(feed).to_rfc2822()) }
+                @for entry in feed.entries.iter() {
+                    item {
+                        title { (entry.title) }
+                        link { (entry.url) }
+                        guid isPermaLink="true" { (entry.url) }
+                        pubDate { (entry.added.to_rfc2822()) }
+                        description { (entry.summary) }
+                        content:encoded { (entry.content_html) }
+                        @for enclosure in entry.enclosures.iter() {
+                            enclosure url=(enclosure.url) length=(enclosure.length)
+                                type=(enclosure.file_type) {}
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn atom_response(feed: &Feed, headers: &HeaderMap) -> Response {
+    ConditionalBody {
+        content_type: "application/atom+xml; charset=utf-8",
+        body: atom_partial(feed).into_string(),
+        last_modified: feed.updated,
+    }
+    .into_response_for(headers)
+}
+
+fn rss_response(feed: &Feed, headers: &HeaderMap) -> Response {
+    ConditionalBody {
+        content_type: "application/rss+xml; charset=utf-8",
+        body: rss_partial(feed).into_string(),
+        last_modified: feed.updated,
+    }
+    .into_response_for(headers)
+}
+
+fn site_feed(feed_service: &FeedService, locale: &Locale) -> poem::Result<Feed> {
+    feed_service
+        .site_feed(locale.text_with_default("feed-title", "Latest Pages"))
+        .map_err(poem::Error::from_error_stack)
+}
+
+#[handler]
+async fn atom_feed(
+    Dep(feed_service): Dep<FeedService>,
+    locale: Locale,
+    headers: &HeaderMap,
+) -> poem::Result<Response> {
+    Ok(atom_response(&site_feed(&feed_service, &locale)?, headers))
+}
+
+#[handler]
+async fn rss_feed(
+    Dep(feed_service): Dep<FeedService>,
+    locale: Locale,
+    headers: &HeaderMap,
+) -> poem::Result<Response> {
+    Ok(rss_response(&site_feed(&feed_service, &locale)?, headers))
+}
+
+#[handler]
+async fn term_atom_feed(
+    Dep(feed_service): Dep<FeedService>,
+    Data(kind): Data<&TermKind>,
+    Path(slug): Path<String>,
+    headers: &HeaderMap,
+) -> poem::Result<Response> {
+    let feed = feed_service
+        .term_feed(*kind, &slug)
+        .map_err(poem::Error::from_error_stack)?;
+    Ok(atom_response(&feed, headers))
+}
+
+#[handler]
+async fn term_rss_feed(
+    Dep(feed_service): Dep<FeedService>,
+    Data(kind): Data<&TermKind>,
+    Path(slug): Path<String>,
+    headers: &HeaderMap,
+) -> poem::Result<Response> {
+    let feed = feed_service
+        .term_feed(*kind, &slug)
+        .map_err(poem::Error::from_error_stack)?;
+    Ok(rss_response(&feed, headers))
+}
+
+pub fn atom_feed_route() -> RouteMethod {
+    get(atom_feed)
+}
+
+pub fn rss_feed_route() -> RouteMethod {
+    get(rss_feed)
+}
+
+/// Nested under the term route, at `/:slug/feed.atom`.
+pub fn term_atom_feed_route(kind: TermKind) -> impl IntoEndpoint {
+    get(term_atom_feed).data(kind)
+}
+
+/// Nested under the term route, at `/:slug/feed.rss`.
+pub fn term_rss_feed_route(kind: TermKind) -> impl IntoEndpoint {
+    get(term_rss_feed).data(kind)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::feed::service::feed_service::{FeedEnclosure, FeedEntry};
+    use chrono::TimeZone;
+
+    fn feed() -> Feed {
+        let added = Utc.with_ymd_and_hms(2026, 1, 2, 3, 4, 5).unwrap();
+        Feed {
+            title: "Tag: Rust".to_string(),
+            url: "http://example.com/tag/rust".to_string(),
+            updated: Some(added),
+            entries: vec![FeedEntry {
+                url: "http://example.com/docs".to_string(),
+                title: "Docs & Guides".to_string(),
+                summary: "All about it".to_string(),
+                content_html: "<p>Hello</p>".to_string(),
+                added,
+                updated: added,
+                enclosures: vec![FeedEnclosure {
+                    url: "http://example.com/files/cms/a.pdf".to_string(),
+                    file_type: "application/pdf".to_string(),
+                    length: 42,
+                }],
+            }],
+        }
+    }
+
+    #[test]
+    fn test_atom_partial_escapes_content() {
+        let atom = atom_partial(&feed()).into_string();
+        assert!(atom.starts_with(XML_DECLARATION));
+        assert!(atom.contains("<title>Docs &amp; Guides</title>"));
+        assert!(atom.contains(r#"<content type="html">&lt;p&gt;Hello&lt;/p&gt;</content>"#));
+        assert!(atom.contains(
+            r#"<link rel="enclosure" type="application/pdf" length="42" href="http://example.com/files/cms/a.pdf"></link>"#
+        ));
+        assert!(atom.contains("<id>http://example.com/tag/rust/feed.atom</id>"));
+    }
+
+    #[test]
+    fn test_rss_partial_dates_and_enclosure() {
+        let rss = rss_partial(&feed()).into_string();
+        assert!(rss.contains("<pubDate>Fri, 2 Jan 2026 03:04:05 +0000</pubDate>"));
+        assert!(rss.contains("<content:encoded>&lt;p&gt;Hello&lt;/p&gt;</content:encoded>"));
+        assert!(rss.contains(
+            r#"<enclosure url="http://example.com/files/cms/a.pdf" length="42" type="application/pdf"></enclosure>"#
+        ));
+    }
+}
@@ -0,0 +1,170 @@
+use crate::FILES_ROUTE;
+use chrono::{DateTime, Utc};
+use error_stack::{Report, ResultExt};
+use poem::http::StatusCode;
+use shared::cms::components::markdown::MarkdownComponent;
+use shared::cms::data_model::published_cms_data::PublishedPageModel;
+use shared::cms::markers::ComponentInfoMarker;
+use shared::cms::repository::published_cms_repository::PublishedCmsRepository;
+use shared::cms::taxonomy::TermKind;
+use shared::utils::config::ConfigPointer;
+use shared::utils::context::{Context, ContextError, FromContext};
+use std::fs;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum FeedServiceError {
+    #[error("Db error")]
+    DbError,
+    #[error("Not found error")]
+    NotFoundError,
+}
+
+const FEED_LIMIT: i64 = 20;
+
+pub struct FeedEnclosure {
+    pub url: String,
+    pub file_type: String,
+    pub length: u64,
+}
+
+pub struct FeedEntry {
+    pub url: String,
+    pub title: String,
+    pub summary: String,
+    pub content_html: String,
+    pub added: DateTime<Utc>,
+    pub updated: DateTime<Utc>,
+    pub enclosures: Vec<FeedEnclosure>,
+}
+
+pub struct Feed {
+    pub title: String,
+    /// The page the feed follows, the feed itself sits at `{url}/feed.atom` and `{url}/feed.rss`.
+    pub url: String,
+    pub updated: Option<DateTime<Utc>>,
+    pub entries: Vec<FeedEntry>,
+}
+
+impl Feed {
+    pub fn feed_url(&self, extension: &str) -> String {
+        format!("{}/feed.{}", self.url.trim_end_matches('/'), extension)
+    }
+}
+
+pub struct FeedService {
+    public_url: String,
+    file_upload_path: String,
+    published_cms_repository: PublishedCmsRepository,
+}
+
+impl FeedService {
+    pub fn new(
+        public_url: String,
+        file_upload_path: String,
+        published_cms_repository: PublishedCmsRepository,
+    ) -> Self {
+        Self {
+            public_url: public_url.trim_end_matches('/').to_string(),
+            file_upload_path,
+            published_cms_repository,
+        }
+    }
+
+    pub fn site_feed(&self, title: String) -> Result<Feed, Report<FeedServiceError>> {
+        self.build_feed(title, format!("{}/", self.public_url), None)
+    }
+
+    pub fn term_feed(&self, kind: TermKind, slug: &str) -> Result<Feed, Report<FeedServiceError>> {
+        let term = self
+            .published_cms_repository
+            .fetch_term(kind, slug)
+            .change_context(FeedServiceError::DbError)?
+            .ok_or_else(|| {
+                Report::new(FeedServiceError::NotFoundError).attach(StatusCode::NOT_FOUND)
+            })?;
+        self.build_feed(
+            format!("{}: {}", term.kind.label(), term.name),
+            format!("{}{}", self.public_url, term.kind.as_uri(&term.slug)),
+            Some(term.id),
+        )
+    }
+
+    fn build_feed(
+        &self,
+        title: String,
+        url: String,
+        term_id: Option<i64>,
+    ) -> Result<Feed, Report<FeedServiceError>> {
+        let page_list = self
+            .published_cms_repository
+            .list_feed_page(term_id, FEED_LIMIT)
+            .change_context(FeedServiceError::DbError)?;
+
+        let mut entries = Vec::with_capacity(page_list.len());
+        for page in page_list.iter() {
+            entries.push(self.build_entry(page)?);
+        }
+
+        Ok(Feed {
+            title,
+            url,
+            updated: entries.iter().map(|entry| entry.updated).max(),
+            entries,
+        })
+    }
+
+    fn build_entry(
+        &self,
+        page: &PublishedPageModel,
+    ) -> Result<FeedEntry, Report<FeedServiceError>> {
+        let markdown_kind_uuid = MarkdownComponent::component_info().kind_uuid;
+        let content_html = self
+            .published_cms_repository
+            .list_component(page.id)
+            .change_context(FeedServiceError::DbError)?
+            .iter()
+            .filter(|component| component.kind_uuid == markdown_kind_uuid)
+            .map(|component| MarkdownComponent::from(component.raw_data.clone()).parse_html)
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        let enclosures = self
+            .published_cms_repository
+            .list_page_file_attachment(page.id)
+            .change_context(FeedServiceError::DbError)?
+            .iter()
+            // a file missing from disk would only hand readers a broken download.
+            .filter_map(|file| {
+                let metadata =
+                    fs::metadata(format!("{}{}", self.file_upload_path, file.file_path)).ok()?;
+                Some(FeedEnclosure {
+                    url: format!("{}{}{}", self.public_url, FILES_ROUTE, file.file_path),
+                    file_type: file.file_type.clone(),
+                    length: metadata.len(),
+                })
+            })
+            .collect();
+
+        Ok(FeedEntry {
+            url: format!("{}/{}", self.public_url, page.path),
+            title: page.title.clone(),
+            summary: page.summary.clone(),
+            content_html,
+            added: page.added,
+            updated: page.updated.unwrap_or(page.added),
+            enclosures,
+        })
+    }
+}
+
+impl FromContext for FeedService {
+    async fn from_context(ctx: &'_ Context<'_>) -> Result<Self, Report<ContextError>> {
+        let config: ConfigPointer = ctx.inject().await?;
+        Ok(Self::new(
+            config.public_url.clone(),
+            config.file_path.clone(),
+            ctx.inject().await?,
+        ))
+    }
+}
@@ -0,0 +1 @@
+pub mod feed_service;
@@ -0,0 +1,29 @@
+use crate::file::service::file_service::FileService;
+use poem::http::header;
+use poem::web::{Path, StaticFileRequest};
+use poem::{IntoResponse, Response, Route, get, handler};
+use shared::utils::context::Dep;
+use shared::utils::error::FromErrorStack;
+
+#[handler]
+async fn file(
+    Dep(file_service): Dep<FileService>,
+    Path(path): Path<String>,
+    static_file_request: StaticFileRequest,
+) -> poem::Result<Response> {
+    let file = file_service
+        .fetch_file(&path)
+        .map_err(poem::Error::from_error_stack)?;
+
+    // no-cache keeps clients revalidating through the ETag, so unpublishing takes effect at once.
+    Ok(static_file_request
+        .create_response(&file.disk_path, false, true)?
+        .with_content_type(file.content_type)
+        .with_header(header::CONTENT_DISPOSITION, file.content_disposition)
+        .with_header(header::X_CONTENT_TYPE_OPTIONS, "nosniff")
+        .into_response())
+}
+
+pub fn file_route() -> Route {
+    Route::new().at("/*path", get(file))
+}
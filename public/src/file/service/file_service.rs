@@ -0,0 +1,144 @@
+use error_stack::{Report, ResultExt};
+use poem::http::StatusCode;
+use shared::cms::data_model::published_cms_data::PublishedFileAttachmentModel;
+use shared::cms::file_path::disk_path;
+use shared::cms::repository::published_cms_repository::PublishedCmsRepository;
+use shared::utils::config::ConfigPointer;
+use shared::utils::context::{Context, ContextError, FromContext};
+use std::path::PathBuf;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum FileServiceError {
+    #[error("Db error")]
+    DbError,
+    #[error("Not found error")]
+    NotFoundError,
+}
+
+/// Types a browser may render in place, anything else is forced to download.
+const INLINE_FILE_TYPES: [&str; 5] = [
+    "image/jpeg",
+    "image/png",
+    "image/gif",
+    "image/webp",
+    "application/pdf",
+];
+
+pub struct PublishedFile {
+    pub disk_path: PathBuf,
+    pub content_type: String,
+    pub content_disposition: String,
+}
+
+pub struct FileService {
+    file_upload_path: String,
+    published_cms_repository: PublishedCmsRepository,
+}
+
+impl FileService {
+    pub fn new(file_upload_path: String, published_cms_repository: PublishedCmsRepository) -> Self {
+        Self {
+            file_upload_path,
+            published_cms_repository,
+        }
+    }
+
+    pub fn fetch_file(&self, path: &str) -> Result<PublishedFile, Report<FileServiceError>> {
+        let file = self
+            .published_cms_repository
+            .fetch_file_attachment(&format!("/{}", path.trim_start_matches('/')))
+            .change_context(FileServiceError::DbError)?
+            .ok_or_else(|| {
+                Report::new(FileServiceError::NotFoundError).attach(StatusCode::NOT_FOUND)
+            })?;
+
+        Ok(PublishedFile {
+            // the stored path is used rather than the requested one, so nothing outside the table is reachable.
+            disk_path: disk_path(&self.file_upload_path, &file.file_path),
+            content_type: content_type(&file),
+            content_disposition: content_disposition(&file),
+        })
+    }
+}
+
+fn content_type(file: &PublishedFileAttachmentModel) -> String {
+    match file.file_type.is_empty() {
+        true => "application/octet-stream".to_string(),
+        false => file.file_type.clone(),
+    }
+}
+
+fn content_disposition(file: &PublishedFileAttachmentModel) -> String {
+    let disposition = match INLINE_FILE_TYPES.contains(&file.file_type.as_str()) {
+        true => "inline",
+        false => "attachment",
+    };
+    let fallback: String = file
+        .file_name
+        .chars()
+        .map(
+            |c| match c.is_ascii_graphic() && c != '"' && c != '\\' || c == ' ' {
+                true => c,
+                false => '_',
+            },
+        )
+        .collect();
+    let encoded: String = file
+        .file_name
+        .bytes()
+        .map(
+            |b| match b.is_ascii_alphanumeric() || b"!#$&+-.^_`|~".contains(&b) {
+                true => (b as char).to_string(),
+                false => format!("%{:02X}", b),
+            },
+        )
+        .collect();
+    format!(
+        "{}; filename=\"{}\"; filename*=UTF-8''{}",
+        disposition, fallback, encoded
+    )
+}
+
+impl FromContext for FileService {
+    async fn from_context(ctx: &'_ Context<'_>) -> Result<Self, Report<ContextError>> {
+        let config: ConfigPointer = ctx.inject().await?;
+        Ok(Self::new(config.file_path.clone(), ctx.inject().await?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file(file_name: &str, file_type: &str) -> PublishedFileAttachmentModel {
+        PublishedFileAttachmentModel {
+            id: 1,
+            file_name: file_name.to_string(),
+            file_path: "/cms/files/a".to_string(),
+            file_type: file_type.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_content_disposition_inline_image() {
+        assert_eq!(
+            content_disposition(&file("photo.jpg", "image/jpeg")),
+            "inline; filename=\"photo.jpg\"; filename*=UTF-8''photo.jpg"
+        );
+    }
+
+    #[test]
+    fn test_content_disposition_escapes_name() {
+        assert_eq!(
+            content_disposition(&file("my \"résumé\".html", "text/html")),
+            "attachment; filename=\"my _r_sum__.html\"; filename*=UTF-8''my%20%22r%C3%A9sum%C3%A9%22.html"
+        );
+    }
+
+    #[test]
+    fn test_content_type_fallback() {
+        assert_eq!(content_type(&file("a", "")), "application/octet-stream");
+        assert_eq!(content_type(&file("a", "text/plain")), "text/plain");
+    }
+}
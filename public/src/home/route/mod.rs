@@ -1,5 +1,10 @@
 use crate::common::embed::AssetFileEndPoint;
 use crate::common::html::HtmlBuilder;
+use crate::feed::route::{ATOM_FEED_ROUTE, RSS_FEED_ROUTE};
+use crate::sitemap::route::{
+    ROBOTS_ROUTE, SITEMAP_CHUNK_ROUTE, SITEMAP_ROUTE, robots_route, sitemap_chunk_route,
+    sitemap_route,
+};
 use maud::{Markup, html};
 use poem::i18n::Locale;
 use poem::{get, handler};
@@ -20,14 +25,22 @@ async fn home(locale: Locale) -> Markup {
             }
         },
     )
-    .attach_head(html! {})
+    .attach_head(html! {
+        link rel="alternate" type="application/atom+xml" href=(ATOM_FEED_ROUTE);
+        link rel="alternate" type="application/rss+xml" href=(RSS_FEED_ROUTE);
+    })
     .attach_footer(html! {})
     .build()
 }
 
 pub fn home_route() -> poem::Route {
-    poem::Route::new().at("/", get(home)).at(
-        "/favicon.ico",
-        AssetFileEndPoint::new("favicon/favicon.ico"),
-    )
+    poem::Route::new()
+        .at("/", get(home))
+        .at(
+            "/favicon.ico",
+            AssetFileEndPoint::new("favicon/favicon.ico"),
+        )
+        .at(ROBOTS_ROUTE, robots_route())
+        .at(SITEMAP_ROUTE, sitemap_route())
+        .at(SITEMAP_CHUNK_ROUTE, sitemap_chunk_route())
 }
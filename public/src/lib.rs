@@ -1,18 +1,32 @@
 pub(crate) mod common;
+pub(crate) mod feed;
+pub(crate) mod file;
 pub(crate) mod home;
+pub(crate) mod page;
+pub(crate) mod search;
+pub(crate) mod sitemap;
+pub(crate) mod term;
 
 use crate::common::embed::{AssetFilesEndPoint, EMBED_PATH};
 use crate::common::locale::build_locale_resources;
+use crate::feed::route::{ATOM_FEED_ROUTE, RSS_FEED_ROUTE, atom_feed_route, rss_feed_route};
+use crate::file::route::file_route;
 use crate::home::route::home_route;
+use crate::page::route::{PAGE_ROUTE, page_route};
+use crate::search::route::{SEARCH_ROUTE, search_route};
+use crate::term::route::term_route;
 use error_stack::{Report, ResultExt};
 use poem::middleware::CatchPanic;
 use poem::{EndpointExt, IntoResponse, Server};
+use shared::cms::taxonomy::TermKind;
 use shared::utils::config::Config;
 use shared::utils::embed::enforce_min_js_on_prod;
 use shared::utils::error::boot_error::MainError;
 use shared::utils::log::log_poem_error;
 use shared::utils::request_cache::init_request_cache;
 
+pub const FILES_ROUTE: &str = "/files";
+
 pub async fn boot() -> Result<(), Report<MainError>> {
     let config = Config::fetch()
         .await
@@ -20,10 +34,24 @@ pub async fn boot() -> Result<(), Report<MainError>> {
 
     let route = home_route();
 
-    let route = route.nest(
-        EMBED_PATH,
-        enforce_min_js_on_prod(AssetFilesEndPoint::new()),
-    );
+    let route = route
+        .at(ATOM_FEED_ROUTE, atom_feed_route())
+        .at(RSS_FEED_ROUTE, rss_feed_route())
+        .at(PAGE_ROUTE, page_route());
+
+    let route = route
+        .nest(
+            EMBED_PATH,
+            enforce_min_js_on_prod(AssetFilesEndPoint::new()),
+        )
+        .nest(FILES_ROUTE, file_route())
+        .nest(SEARCH_ROUTE, search_route());
+
+    let route = TermKind::all_kinds()
+        .into_iter()
+        .fold(route, |route, kind| {
+            route.nest(kind.route(), term_route(kind))
+        });
 
     let route = route
         .around(init_request_cache)
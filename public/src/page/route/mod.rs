@@ -0,0 +1,131 @@
+use crate::FILES_ROUTE;
+use crate::common::html::HtmlBuilder;
+use crate::page::service::page_service::{PageLookup, PageService};
+use maud::{Markup, PreEscaped, html};
+use poem::web::{Path, Redirect};
+use poem::{IntoResponse, Response, RouteMethod, get, handler};
+use shared::cms::components::file_attachments::FileAttachmentsComponent;
+use shared::cms::components::image::ImageComponent;
+use shared::cms::components::markdown::MarkdownComponent;
+use shared::cms::data_model::published_cms_data::PublishedComponentModel;
+use shared::cms::markers::ComponentInfoMarker;
+use shared::utils::context::Dep;
+use shared::utils::error::FromErrorStack;
+
+pub const PAGE_ROUTE: &str = "/:page_path<^.+>";
+
+fn component_partial(
+    page_service: &PageService,
+    component: &PublishedComponentModel,
+) -> poem::Result<Markup> {
+    let kind_uuid = component.kind_uuid.as_str();
+
+    if kind_uuid == MarkdownComponent::component_info().kind_uuid {
+        let markdown = MarkdownComponent::from(component.raw_data.clone());
+        return Ok(html! {
+            section .page-component .page-markdown {
+                (PreEscaped(markdown.parse_html))
+            }
+        });
+    }
+
+    if kind_uuid == FileAttachmentsComponent::component_info().kind_uuid {
+        let file_list = page_service
+            .list_file_attachment(component.id)
+            .map_err(poem::Error::from_error_stack)?;
+        return Ok(html! {
+            @if !file_list.is_empty() {
+                section .page-component .page-file-attachments {
+                    h2 { (component.label) }
+                    ul {
+                        @for file in file_list.iter() {
+                            li {
+                                a href=(format!("{}{}", FILES_ROUTE, file.file_path)) title=(file.file_type) { (file.file_name) }
+                            }
+                        }
+                    }
+                }
+            }
+        });
+    }
+
+    if kind_uuid == ImageComponent::component_info().kind_uuid {
+        let image = ImageComponent::from(component.raw_data.clone());
+        let Some(fallback) = image.fallback("image/jpeg") else {
+            return Ok(html! {});
+        };
+        return Ok(html! {
+            figure .page-component .page-image {
+                picture {
+                    source type="image/jpeg" srcset=(image.srcset("image/jpeg", FILES_ROUTE)) sizes="100vw";
+                    img src=(format!("{}{}", FILES_ROUTE, fallback.file_path)) alt=(image.alt)
+                        width=(fallback.width) height=(fallback.height) loading="lazy";
+                }
+                @if !image.caption.is_empty() {
+                    figcaption { (image.caption) }
+                }
+            }
+        });
+    }
+
+    Ok(html! {})
+}
+
+#[handler]
+async fn page(
+    Dep(page_service): Dep<PageService>,
+    Path(page_path): Path<String>,
+) -> poem::Result<Response> {
+    let page = match page_service
+        .fetch_page(&page_path)
+        .map_err(poem::Error::from_error_stack)?
+    {
+        PageLookup::Found(page) => page,
+        PageLookup::Redirect(path) => {
+            return Ok(Redirect::moved_permanent(path).into_response());
+        }
+    };
+    let component_list = page_service
+        .list_component(page.id)
+        .map_err(poem::Error::from_error_stack)?;
+    let term_list = page_service
+        .list_term(page.id)
+        .map_err(poem::Error::from_error_stack)?;
+
+    let mut components = Vec::with_capacity(component_list.len());
+    for component in component_list.iter() {
+        components.push(component_partial(&page_service, component)?);
+    }
+
+    Ok(HtmlBuilder::new(
+        page.title.clone(),
+        html! {
+            article .page-content {
+                h1 .page-title { (page.title) }
+                @if !page.summary.is_empty() {
+                    p .page-summary { (page.summary) }
+                }
+                @for component in components {
+                    (component)
+                }
+                @if !term_list.is_empty() {
+                    ul .page-terms {
+                        @for term in term_list.iter() {
+                            li {
+                                a href=(term.kind.as_uri(&term.slug)) title=(term.kind.label()) { (term.name) }
+                            }
+                        }
+                    }
+                }
+            }
+        },
+    )
+    .attach_head(html! {})
+    .attach_footer(html! {})
+    .build()
+    .into_response())
+}
+
+pub fn page_route() -> RouteMethod {
+    get(page)
+}
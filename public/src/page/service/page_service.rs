@@ -0,0 +1,88 @@
+use error_stack::{Report, ResultExt};
+use poem::http::StatusCode;
+use shared::cms::data_model::published_cms_data::{
+    PublishedComponentModel, PublishedFileAttachmentModel, PublishedPageModel, PublishedTermModel,
+};
+use shared::cms::repository::published_cms_repository::PublishedCmsRepository;
+use shared::cms::slug::normalize_path;
+use shared::utils::context::{Context, ContextError, FromContext};
+use std::sync::Arc;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum PageServiceError {
+    #[error("Db error")]
+    DbError,
+    #[error("Not found error")]
+    NotFoundError,
+}
+
+pub enum PageLookup {
+    Found(PublishedPageModel),
+    Redirect(String),
+}
+
+pub struct PageService {
+    published_cms_repository: PublishedCmsRepository,
+}
+
+impl PageService {
+    pub fn new(published_cms_repository: PublishedCmsRepository) -> Self {
+        Self {
+            published_cms_repository,
+        }
+    }
+
+    pub fn fetch_page(&self, path: &str) -> Result<PageLookup, Report<PageServiceError>> {
+        let path = normalize_path(path);
+
+        if let Some(page) = self
+            .published_cms_repository
+            .fetch_page(&path)
+            .change_context(PageServiceError::DbError)?
+        {
+            return Ok(PageLookup::Found(page));
+        }
+
+        self.published_cms_repository
+            .fetch_redirect_path(&path)
+            .change_context(PageServiceError::DbError)?
+            .map(|redirect_path| PageLookup::Redirect(format!("/{}", redirect_path.0)))
+            .ok_or_else(|| {
+                Report::new(PageServiceError::NotFoundError).attach(StatusCode::NOT_FOUND)
+            })
+    }
+
+    pub fn list_component(
+        &self,
+        page_id: i64,
+    ) -> Result<Arc<[PublishedComponentModel]>, Report<PageServiceError>> {
+        self.published_cms_repository
+            .list_component(page_id)
+            .change_context(PageServiceError::DbError)
+    }
+
+    pub fn list_term(
+        &self,
+        page_id: i64,
+    ) -> Result<Arc<[PublishedTermModel]>, Report<PageServiceError>> {
+        self.published_cms_repository
+            .list_page_term(page_id)
+            .change_context(PageServiceError::DbError)
+    }
+
+    pub fn list_file_attachment(
+        &self,
+        component_id: i64,
+    ) -> Result<Arc<[PublishedFileAttachmentModel]>, Report<PageServiceError>> {
+        self.published_cms_repository
+            .list_file_attachment(component_id)
+            .change_context(PageServiceError::DbError)
+    }
+}
+
+impl FromContext for PageService {
+    async fn from_context(ctx: &'_ Context<'_>) -> Result<Self, Report<ContextError>> {
+        Ok(Self::new(ctx.inject().await?))
+    }
+}
@@ -0,0 +1,71 @@
+use crate::common::html::HtmlBuilder;
+use crate::search::service::search_service::SearchService;
+use maud::{Markup, html};
+use poem::i18n::Locale;
+use poem::{Route, get, handler};
+use serde::Deserialize;
+use shared::cms::search::highlight_snippet;
+use shared::utils::context::Dep;
+use shared::utils::error::FromErrorStack;
+use shared::utils::locale::LocaleExt;
+use shared::utils::query_string::query::QueryQs;
+
+pub const SEARCH_ROUTE: &str = "/search";
+
+#[derive(Deserialize, Default)]
+struct SearchQuery {
+    #[serde(default)]
+    q: String,
+}
+
+#[handler]
+async fn search(
+    QueryQs(query): QueryQs<SearchQuery>,
+    Dep(search_service): Dep<SearchService>,
+    locale: Locale,
+) -> poem::Result<Markup> {
+    let result_list = search_service
+        .search_page(&query.q)
+        .map_err(poem::Error::from_error_stack)?;
+
+    let title = locale.text_with_default("search-title", "Search");
+    let no_result = locale.text_with_default("search-no-result", "No page matches your search");
+
+    Ok(HtmlBuilder::new(
+        title.clone(),
+        html! {
+            div .search-content {
+                h1 .search-title { (title) }
+                form .search-form method="get" action=(SEARCH_ROUTE) {
+                    input type="search" name="q" value=(query.q) aria-label=(title) {}
+                    " "
+                    button type="submit" { (title) }
+                }
+                @if !query.q.trim().is_empty() {
+                    @if result_list.is_empty() {
+                        p .search-empty { (no_result) }
+                    } @else {
+                        ol .search-result {
+                            @for result in result_list.iter() {
+                                li {
+                                    a href=(format!("/{}", result.path)) { (result.title) }
+                                    @if !result.summary.is_empty() {
+                                        p .search-summary { (result.summary) }
+                                    }
+                                    p .search-snippet { (highlight_snippet(&result.snippet)) }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        },
+    )
+    .attach_head(html! {})
+    .attach_footer(html! {})
+    .build())
+}
+
+pub fn search_route() -> Route {
+    Route::new().at("/", get(search))
+}
@@ -0,0 +1,47 @@
+use error_stack::{Report, ResultExt};
+use shared::cms::data_model::published_cms_data::PublishedSearchResultModel;
+use shared::cms::repository::published_cms_repository::PublishedCmsRepository;
+use shared::cms::search::match_query;
+use shared::utils::context::{Context, ContextError, FromContext};
+use std::sync::Arc;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum SearchServiceError {
+    #[error("Db error")]
+    DbError,
+}
+
+const SEARCH_LIMIT: i64 = 20;
+
+pub struct SearchService {
+    published_cms_repository: PublishedCmsRepository,
+}
+
+impl SearchService {
+    pub fn new(published_cms_repository: PublishedCmsRepository) -> Self {
+        Self {
+            published_cms_repository,
+        }
+    }
+
+    /// Published pages only, best match first.
+    pub fn search_page(
+        &self,
+        q: &str,
+    ) -> Result<Arc<[PublishedSearchResultModel]>, Report<SearchServiceError>> {
+        match match_query(q) {
+            Some(query) => self
+                .published_cms_repository
+                .search_page(&query, SEARCH_LIMIT)
+                .change_context(SearchServiceError::DbError),
+            None => Ok(Arc::new([])),
+        }
+    }
+}
+
+impl FromContext for SearchService {
+    async fn from_context(ctx: &'_ Context<'_>) -> Result<Self, Report<ContextError>> {
+        Ok(Self::new(ctx.inject().await?))
+    }
+}
@@ -0,0 +1,175 @@
+use crate::common::conditional::ConditionalBody;
+use crate::sitemap::service::sitemap_service::{Sitemap, SitemapService};
+use maud::{Markup, PreEscaped, html};
+use poem::http::{HeaderMap, StatusCode};
+use poem::web::Path;
+use poem::{Response, RouteMethod, get, handler};
+use shared::utils::config::ConfigPointer;
+use shared::utils::config::robots::RobotsConfig;
+use shared::utils::context::Dep;
+use shared::utils::error::FromErrorStack;
+
+pub const SITEMAP_ROUTE: &str = "/sitemap.xml";
+pub const SITEMAP_CHUNK_ROUTE: &str = "/sitemap/:file";
+pub const ROBOTS_ROUTE: &str = "/robots.txt";
+
+const XML_DECLARATION: &str = r#"<?xml version="1.0" encoding="utf-8"?>"#;
+const SITEMAP_XMLNS: &str = "http://www.sitemaps.org/schemas/sitemap/0.9";
+
+fn sitemap_partial(sitemap: &Sitemap) -> Markup {
+    html! {
+        (PreEscaped(XML_DECLARATION))
+        @match sitemap {
+            Sitemap::UrlSet(urls) => {
+                urlset xmlns=(SITEMAP_XMLNS) {
+                    @for url in urls.iter() {
+                        url {
+                            loc { (url.loc) }
+                            lastmod { (url.lastmod.to_rfc3339()) }
+                        }
+                    }
+                }
+            }
+            Sitemap::Index(sitemaps) => {
+                sitemapindex xmlns=(SITEMAP_XMLNS) {
+                    @for sitemap in sitemaps.iter() {
+                        sitemap {
+                            loc { (sitemap.loc) }
+                            lastmod { (sitemap.lastmod.to_rfc3339()) }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn robots_txt(robots: &RobotsConfig, sitemap_url: &str) -> String {
+    let mut body = String::new();
+    for rule in robots.rules.iter() {
+        body.push_str(&format!("User-agent: {}\n", rule.user_agent));
+        for allow in rule.allow.iter() {
+            body.push_str(&format!("Allow: {}\n", allow));
+        }
+        for disallow in rule.disallow.iter() {
+            body.push_str(&format!("Disallow: {}\n", disallow));
+        }
+        body.push('\n');
+    }
+    body.push_str(&format!("Sitemap: {}\n", sitemap_url));
+    body
+}
+
+fn sitemap_response(sitemap: &Sitemap, headers: &HeaderMap) -> Response {
+    ConditionalBody {
+        content_type: "application/xml; charset=utf-8",
+        body: sitemap_partial(sitemap).into_string(),
+        last_modified: sitemap.last_modified(),
+    }
+    .into_response_for(headers)
+}
+
+#[handler]
+async fn sitemap_xml(
+    Dep(sitemap_service): Dep<SitemapService>,
+    headers: &HeaderMap,
+) -> poem::Result<Response> {
+    let sitemap = sitemap_service
+        .sitemap()
+        .map_err(poem::Error::from_error_stack)?;
+    Ok(sitemap_response(&sitemap, headers))
+}
+
+#[handler]
+async fn sitemap_chunk_xml(
+    Dep(sitemap_service): Dep<SitemapService>,
+    Path(file): Path<String>,
+    headers: &HeaderMap,
+) -> poem::Result<Response> {
+    let number = file
+        .strip_suffix(".xml")
+        .and_then(|number| number.parse::<i64>().ok())
+        .ok_or_else(|| poem::Error::from_status(StatusCode::NOT_FOUND))?;
+    let sitemap = sitemap_service
+        .sitemap_chunk(number)
+        .map_err(poem::Error::from_error_stack)?;
+    Ok(sitemap_response(&sitemap, headers))
+}
+
+#[handler]
+async fn robots_text(
+    Dep(config): Dep<ConfigPointer>,
+    Dep(sitemap_service): Dep<SitemapService>,
+    headers: &HeaderMap,
+) -> Response {
+    ConditionalBody {
+        content_type: "text/plain; charset=utf-8",
+        body: robots_txt(&config.robots, &sitemap_service.sitemap_url()),
+        last_modified: None,
+    }
+    .into_response_for(headers)
+}
+
+pub fn sitemap_route() -> RouteMethod {
+    get(sitemap_xml)
+}
+
+pub fn sitemap_chunk_route() -> RouteMethod {
+    get(sitemap_chunk_xml)
+}
+
+pub fn robots_route() -> RouteMethod {
+    get(robots_text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sitemap::service::sitemap_service::SitemapUrl;
+    use chrono::{TimeZone, Utc};
+    use shared::utils::config::robots::RobotsRule;
+
+    fn url(loc: &str) -> SitemapUrl {
+        SitemapUrl {
+            loc: loc.to_string(),
+            lastmod: Utc.with_ymd_and_hms(2026, 1, 2, 3, 4, 5).unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_sitemap_partial_url_set() {
+        let body =
+            sitemap_partial(&Sitemap::UrlSet(vec![url("http://example.com/a?b&c")])).into_string();
+        assert!(body.starts_with(XML_DECLARATION));
+        assert!(body.contains("<urlset xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\">"));
+        assert!(body.contains("<loc>http://example.com/a?b&amp;c</loc>"));
+        assert!(body.contains("<lastmod>2026-01-02T03:04:05+00:00</lastmod>"));
+    }
+
+    #[test]
+    fn test_sitemap_partial_index() {
+        let body = sitemap_partial(&Sitemap::Index(vec![
+            url("http://example.com/sitemap/1.xml"),
+            url("http://example.com/sitemap/2.xml"),
+        ]))
+        .into_string();
+        assert!(body.contains("<sitemapindex"));
+        assert!(body.contains("<sitemap><loc>http://example.com/sitemap/2.xml</loc>"));
+        assert!(!body.contains("<urlset"));
+    }
+
+    #[test]
+    fn test_robots_txt() {
+        let robots = RobotsConfig {
+            rules: vec![RobotsRule {
+                user_agent: "*".to_string(),
+                allow: vec!["/files".to_string()],
+                disallow: vec!["/search".to_string()],
+            }],
+        };
+        assert_eq!(
+            robots_txt(&robots, "http://example.com/sitemap.xml"),
+            "User-agent: *\nAllow: /files\nDisallow: /search\n\nSitemap: http://example.com/sitemap.xml\n"
+        );
+    }
+}
@@ -0,0 +1,111 @@
+use chrono::{DateTime, Utc};
+use error_stack::{Report, ResultExt};
+use poem::http::StatusCode;
+use shared::cms::repository::published_cms_repository::PublishedCmsRepository;
+use shared::utils::config::ConfigPointer;
+use shared::utils::context::{Context, ContextError, FromContext};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum SitemapServiceError {
+    #[error("Db error")]
+    DbError,
+    #[error("Not found error")]
+    NotFoundError,
+}
+
+/// The protocol caps a single sitemap at 50,000 URLs.
+const SITEMAP_LIMIT: i64 = 50_000;
+
+pub struct SitemapUrl {
+    pub loc: String,
+    pub lastmod: DateTime<Utc>,
+}
+
+pub enum Sitemap {
+    UrlSet(Vec<SitemapUrl>),
+    /// Points at each chunk under `/sitemap/{number}.xml`, counted from one.
+    Index(Vec<SitemapUrl>),
+}
+
+impl Sitemap {
+    pub fn last_modified(&self) -> Option<DateTime<Utc>> {
+        match self {
+            Self::UrlSet(urls) | Self::Index(urls) => urls.iter().map(|url| url.lastmod).max(),
+        }
+    }
+}
+
+pub struct SitemapService {
+    public_url: String,
+    published_cms_repository: PublishedCmsRepository,
+}
+
+impl SitemapService {
+    pub fn new(public_url: String, published_cms_repository: PublishedCmsRepository) -> Self {
+        Self {
+            public_url: public_url.trim_end_matches('/').to_string(),
+            published_cms_repository,
+        }
+    }
+
+    pub fn sitemap_url(&self) -> String {
+        format!("{}/sitemap.xml", self.public_url)
+    }
+
+    pub fn sitemap(&self) -> Result<Sitemap, Report<SitemapServiceError>> {
+        let chunk_list = self
+            .published_cms_repository
+            .list_sitemap_chunk(SITEMAP_LIMIT)
+            .change_context(SitemapServiceError::DbError)?;
+
+        if chunk_list.len() <= 1 {
+            return Ok(Sitemap::UrlSet(self.list_url(0)?));
+        }
+
+        Ok(Sitemap::Index(
+            chunk_list
+                .iter()
+                .map(|chunk| SitemapUrl {
+                    loc: format!("{}/sitemap/{}.xml", self.public_url, chunk.chunk + 1),
+                    lastmod: chunk.lastmod,
+                })
+                .collect(),
+        ))
+    }
+
+    pub fn sitemap_chunk(&self, number: i64) -> Result<Sitemap, Report<SitemapServiceError>> {
+        if number < 1 {
+            return Err(
+                Report::new(SitemapServiceError::NotFoundError).attach(StatusCode::NOT_FOUND)
+            );
+        }
+        let url_list = self.list_url((number - 1) * SITEMAP_LIMIT)?;
+        if url_list.is_empty() {
+            return Err(
+                Report::new(SitemapServiceError::NotFoundError).attach(StatusCode::NOT_FOUND)
+            );
+        }
+        Ok(Sitemap::UrlSet(url_list))
+    }
+
+    fn list_url(&self, offset: i64) -> Result<Vec<SitemapUrl>, Report<SitemapServiceError>> {
+        Ok(self
+            .published_cms_repository
+            .list_sitemap_page(offset, SITEMAP_LIMIT)
+            .change_context(SitemapServiceError::DbError)?
+            .iter()
+            .map(|page| SitemapUrl {
+                loc: format!("{}/{}", self.public_url, page.path),
+                lastmod: page.lastmod,
+            })
+            .collect())
+    }
+}
+
+impl FromContext for SitemapService {
+    async fn from_context(ctx: &'_ Context<'_>) -> Result<Self, Report<ContextError>> {
+        let config: ConfigPointer = ctx.inject().await?;
+        Ok(Self::new(config.public_url.clone(), ctx.inject().await?))
+    }
+}
@@ -0,0 +1,68 @@
+use crate::common::html::HtmlBuilder;
+use crate::feed::route::{term_atom_feed_route, term_rss_feed_route};
+use crate::term::service::term_service::TermService;
+use maud::{Markup, html};
+use poem::i18n::Locale;
+use poem::web::{Data, Path};
+use poem::{EndpointExt, Route, get, handler};
+use shared::cms::taxonomy::TermKind;
+use shared::utils::context::Dep;
+use shared::utils::error::FromErrorStack;
+use shared::utils::locale::LocaleExt;
+
+#[handler]
+async fn term(
+    Dep(term_service): Dep<TermService>,
+    Data(kind): Data<&TermKind>,
+    Path(slug): Path<String>,
+    locale: Locale,
+) -> poem::Result<Markup> {
+    let term = term_service
+        .fetch_term(*kind, &slug)
+        .map_err(poem::Error::from_error_stack)?;
+    let page_list = term_service
+        .list_page(term.id)
+        .map_err(poem::Error::from_error_stack)?;
+
+    let title = format!("{}: {}", term.kind.label(), term.name);
+    let no_page = locale.text_with_default("term-no-page", "No page has been published here yet");
+
+    Ok(HtmlBuilder::new(
+        title.clone(),
+        html! {
+            div .term-content {
+                h1 .term-title { (title) }
+                @if page_list.is_empty() {
+                    p .term-empty { (no_page) }
+                } @else {
+                    ul .term-page-list {
+                        @for page in page_list.iter() {
+                            li {
+                                a href=(format!("/{}", page.path)) { (page.title) }
+                                @if !page.summary.is_empty() {
+                                    p .term-summary { (page.summary) }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        },
+    )
+    .attach_head(html! {
+        link rel="alternate" type="application/atom+xml" title=(title)
+            href=(format!("{}/feed.atom", term.kind.as_uri(&term.slug)));
+        link rel="alternate" type="application/rss+xml" title=(title)
+            href=(format!("{}/feed.rss", term.kind.as_uri(&term.slug)));
+    })
+    .attach_footer(html! {})
+    .build())
+}
+
+/// Nested at `kind.route()`, one per term kind.
+pub fn term_route(kind: TermKind) -> Route {
+    Route::new()
+        .at("/:slug", get(term).data(kind))
+        .at("/:slug/feed.atom", term_atom_feed_route(kind))
+        .at("/:slug/feed.rss", term_rss_feed_route(kind))
+}
@@ -0,0 +1 @@
+pub mod term_service;
@@ -0,0 +1,56 @@
+use error_stack::{Report, ResultExt};
+use poem::http::StatusCode;
+use shared::cms::data_model::published_cms_data::{PublishedPageModel, PublishedTermModel};
+use shared::cms::repository::published_cms_repository::PublishedCmsRepository;
+use shared::cms::taxonomy::TermKind;
+use shared::utils::context::{Context, ContextError, FromContext};
+use std::sync::Arc;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum TermServiceError {
+    #[error("Db error")]
+    DbError,
+    #[error("Not found error")]
+    NotFoundError,
+}
+
+pub struct TermService {
+    published_cms_repository: PublishedCmsRepository,
+}
+
+impl TermService {
+    pub fn new(published_cms_repository: PublishedCmsRepository) -> Self {
+        Self {
+            published_cms_repository,
+        }
+    }
+
+    pub fn fetch_term(
+        &self,
+        kind: TermKind,
+        slug: &str,
+    ) -> Result<PublishedTermModel, Report<TermServiceError>> {
+        self.published_cms_repository
+            .fetch_term(kind, slug)
+            .change_context(TermServiceError::DbError)?
+            .ok_or_else(|| {
+                Report::new(TermServiceError::NotFoundError).attach(StatusCode::NOT_FOUND)
+            })
+    }
+
+    pub fn list_page(
+        &self,
+        term_id: i64,
+    ) -> Result<Arc<[PublishedPageModel]>, Report<TermServiceError>> {
+        self.published_cms_repository
+            .list_term_page(term_id)
+            .change_context(TermServiceError::DbError)
+    }
+}
+
+impl FromContext for TermService {
+    async fn from_context(ctx: &'_ Context<'_>) -> Result<Self, Report<ContextError>> {
+        Ok(Self::new(ctx.inject().await?))
+    }
+}
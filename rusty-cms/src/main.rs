@@ -1,6 +1,10 @@
-use backoffice::export::{MainError, init_log};
+use backoffice::export::{
+    MainError, backfill_search_index, hash_legacy_login_token, init_database, init_log,
+    print_migration_status, print_password_benchmark, run_file_gc, run_schedule, run_session_purge,
+};
 use error_stack::Report;
 use error_stack::fmt::ColorMode;
+use std::time::Duration;
 use tokio::task::JoinHandle;
 
 #[tokio::main]
@@ -8,9 +12,33 @@ async fn main() -> Result<(), Report<MainError>> {
     init_log();
     Report::set_color_mode(ColorMode::None);
 
+    if std::env::args().nth(1).as_deref() == Some("migrate-status") {
+        return print_migration_status().await;
+    }
+    if std::env::args().nth(1).as_deref() == Some("password-bench") {
+        let target_ms = std::env::args()
+            .nth(2)
+            .and_then(|arg| arg.parse().ok())
+            .unwrap_or(500);
+        return print_password_benchmark(Duration::from_millis(target_ms)).await;
+    }
+    init_database().await?;
+    if std::env::args().nth(1).as_deref() == Some("file-gc") {
+        return run_file_gc(std::env::args().any(|arg| arg == "--delete")).await;
+    }
+    backfill_search_index().await?;
+    hash_legacy_login_token().await?;
+
     let backoffice_handle = tokio::spawn(backoffice::boot());
     let public_handle = tokio::spawn(public::boot());
-    match tokio::try_join!(flatten(backoffice_handle), flatten(public_handle)) {
+    let schedule_handle = tokio::spawn(run_schedule());
+    let session_purge_handle = tokio::spawn(run_session_purge());
+    match tokio::try_join!(
+        flatten(backoffice_handle),
+        flatten(public_handle),
+        flatten(schedule_handle),
+        flatten(session_purge_handle)
+    ) {
         Ok(_) => Ok(()),
         Err(err) => Err(err),
     }
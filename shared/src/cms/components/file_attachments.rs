@@ -3,6 +3,29 @@ use crate::cms::markers::{ComponentDataMarker, ComponentInfoMarker};
 
 pub struct FileAttachmentsComponent;
 
+impl FileAttachmentsComponent {
+    /// Types detected from the uploaded content, anything a browser would run as a document (HTML, SVG) is left out.
+    pub const ALLOWED_FILE_TYPES: &'static [&'static str] = &[
+        "image/jpeg",
+        "image/png",
+        "image/gif",
+        "image/webp",
+        "application/pdf",
+        "text/plain",
+        "application/zip",
+        "application/gzip",
+        "application/msword",
+        "application/vnd.ms-excel",
+        "application/vnd.ms-powerpoint",
+        "application/vnd.openxmlformats-officedocument.wordprocessingml.document",
+        "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet",
+        "application/vnd.openxmlformats-officedocument.presentationml.presentation",
+        "application/vnd.oasis.opendocument.text",
+        "application/vnd.oasis.opendocument.spreadsheet",
+        "application/vnd.oasis.opendocument.presentation",
+    ];
+}
+
 impl ComponentDataMarker for FileAttachmentsComponent {
     fn into_data(self) -> Vec<u8> {
         vec![]
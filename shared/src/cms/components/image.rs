@@ -0,0 +1,124 @@
+use crate::cms::CmsComponentInfo;
+use crate::cms::markers::{ComponentDataMarker, ComponentInfoMarker};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct ImageVariant {
+    pub width: u32,
+    pub height: u32,
+    pub file_type: String,
+    pub file_path: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct ImageComponent {
+    pub alt: String,
+    pub caption: String,
+    pub width: u32,
+    pub height: u32,
+    pub variants: Vec<ImageVariant>,
+}
+
+impl ImageComponent {
+    pub const ALLOWED_FILE_TYPES: &'static [&'static str] =
+        &["image/jpeg", "image/png", "image/webp", "image/gif"];
+
+    pub fn has_image(&self) -> bool {
+        !self.variants.is_empty()
+    }
+
+    /// Variants of `file_type` as a `srcset` value, narrowest first, each path prefixed with `route`.
+    pub fn srcset(&self, file_type: &str, route: &str) -> String {
+        let mut variants: Vec<&ImageVariant> = self
+            .variants
+            .iter()
+            .filter(|variant| variant.file_type == file_type)
+            .collect();
+        variants.sort_by_key(|variant| variant.width);
+        variants
+            .iter()
+            .map(|variant| format!("{}{} {}w", route, variant.file_path, variant.width))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    /// The widest variant of `file_type`, used for the plain `src` fallback.
+    pub fn fallback(&self, file_type: &str) -> Option<&ImageVariant> {
+        self.variants
+            .iter()
+            .filter(|variant| variant.file_type == file_type)
+            .max_by_key(|variant| variant.width)
+    }
+}
+
+impl ComponentDataMarker for ImageComponent {
+    fn into_data(self) -> Vec<u8> {
+        rmp_serde::to_vec(&self).expect("failed to serialize image")
+    }
+}
+
+impl From<Vec<u8>> for ImageComponent {
+    fn from(data: Vec<u8>) -> Self {
+        // a freshly created component has no data until the first upload.
+        if data.is_empty() {
+            return Self::default();
+        }
+        rmp_serde::from_slice(&data).expect("failed to deserialize image")
+    }
+}
+
+impl ComponentInfoMarker for ImageComponent {
+    fn component_info() -> CmsComponentInfo {
+        CmsComponentInfo {
+            kind: "Image".to_string(),
+            kind_uuid: "5b0e7f7c-2d3e-4f8a-9c61-0a4d7e9b2f13".to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn variant(width: u32, file_type: &str) -> ImageVariant {
+        ImageVariant {
+            width,
+            height: width / 2,
+            file_type: file_type.to_string(),
+            file_path: format!("/cms/files/a-{}", width),
+        }
+    }
+
+    #[test]
+    fn test_image_round_trip() {
+        let image = ImageComponent {
+            alt: "alt".to_string(),
+            caption: "caption".to_string(),
+            width: 960,
+            height: 480,
+            variants: vec![variant(480, "image/webp")],
+        };
+
+        assert_eq!(ImageComponent::from(image.clone().into_data()), image);
+        assert_eq!(ImageComponent::from(vec![]), ImageComponent::default());
+    }
+
+    #[test]
+    fn test_image_srcset_and_fallback() {
+        let image = ImageComponent {
+            variants: vec![
+                variant(960, "image/jpeg"),
+                variant(480, "image/jpeg"),
+                variant(480, "image/webp"),
+            ],
+            ..Default::default()
+        };
+
+        assert_eq!(
+            image.srcset("image/jpeg", "/files"),
+            "/files/cms/files/a-480 480w, /files/cms/files/a-960 960w"
+        );
+        assert_eq!(image.fallback("image/jpeg").map(|v| v.width), Some(960));
+        assert!(image.fallback("image/png").is_none());
+    }
+}
@@ -1,6 +1,11 @@
 use crate::cms::CmsComponentInfo;
 use crate::cms::markers::{ComponentDataMarker, ComponentInfoMarker};
+use ammonia::Builder;
+use comrak::nodes::NodeValue;
+use comrak::{Arena, Options, markdown_to_html, parse_document};
 use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::sync::OnceLock;
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct MarkdownComponent {
@@ -8,6 +13,16 @@ pub struct MarkdownComponent {
     pub parse_html: String,
 }
 
+impl MarkdownComponent {
+    pub fn new(content: String) -> Self {
+        let parse_html = render_markdown(&content);
+        Self {
+            content,
+            parse_html,
+        }
+    }
+}
+
 impl ComponentDataMarker for MarkdownComponent {
     fn into_data(self) -> Vec<u8> {
         rmp_serde::to_vec(&self).expect("failed to serialize markdown")
@@ -28,3 +43,112 @@ impl ComponentInfoMarker for MarkdownComponent {
         }
     }
 }
+
+fn markdown_options() -> Options<'static> {
+    let mut options = Options::default();
+    options.extension.table = true;
+    options.extension.strikethrough = true;
+    options.extension.autolink = true;
+    options.extension.footnotes = true;
+    options.extension.header_ids = Some(String::new());
+    options.render.github_pre_lang = true;
+    // raw html is allowed through comrak, the sanitizer decides what survives.
+    options.render.unsafe_ = true;
+    options
+}
+
+fn sanitizer() -> &'static Builder<'static> {
+    static SANITIZER: OnceLock<Builder<'static>> = OnceLock::new();
+    SANITIZER.get_or_init(|| {
+        let mut builder = Builder::default();
+        builder
+            .add_tag_attributes("a", ["id", "aria-hidden"])
+            .add_tag_attributes("h1", ["id"])
+            .add_tag_attributes("h2", ["id"])
+            .add_tag_attributes("h3", ["id"])
+            .add_tag_attributes("h4", ["id"])
+            .add_tag_attributes("h5", ["id"])
+            .add_tag_attributes("h6", ["id"])
+            .add_tag_attributes("sup", ["id"])
+            .add_tag_attributes("li", ["id"])
+            .add_allowed_classes("a", ["anchor"])
+            .add_allowed_classes("section", ["footnotes"])
+            .url_schemes(HashSet::from(["http", "https", "mailto"]));
+        builder
+    })
+}
+
+pub fn render_markdown(content: &str) -> String {
+    let html = markdown_to_html(content, &markdown_options());
+    sanitizer().clean(&html).to_string()
+}
+
+/// The text a reader sees, without markup or raw html, on a single line.
+pub fn markdown_plain_text(content: &str) -> String {
+    let arena = Arena::new();
+    let root = parse_document(&arena, content, &markdown_options());
+    let mut text = String::new();
+    for node in root.descendants() {
+        match &node.data.borrow().value {
+            NodeValue::Text(literal) => text.push_str(literal),
+            NodeValue::Code(code) => text.push_str(&code.literal),
+            NodeValue::CodeBlock(code_block) => {
+                text.push(' ');
+                text.push_str(&code_block.literal);
+            }
+            NodeValue::SoftBreak | NodeValue::LineBreak => text.push(' '),
+            value if value.block() => text.push(' '),
+            _ => {}
+        }
+    }
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_markdown_plain_text() {
+        let text = markdown_plain_text(
+            "# Title\n\nSome **bold** and `code`.\n\n- one\n- two <span>raw</span>\n\n```\nlet a = 1;\n```",
+        );
+        assert_eq!(text, "Title Some bold and code. one two raw let a = 1;");
+    }
+
+    #[test]
+    fn test_render_markdown_table() {
+        let html = render_markdown("| a | b |\n|---|---|\n| 1 | 2 |");
+        assert!(html.contains("<table>"));
+        assert!(html.contains("<td>1</td>"));
+    }
+
+    #[test]
+    fn test_render_markdown_fenced_code() {
+        let html = render_markdown("```rust\nlet a = 1;\n```");
+        assert!(html.contains("<pre lang=\"rust\"><code>"));
+    }
+
+    #[test]
+    fn test_render_markdown_autolink() {
+        let html = render_markdown("visit https://example.com now");
+        assert!(html.contains("href=\"https://example.com\""));
+    }
+
+    #[test]
+    fn test_render_markdown_heading_anchor() {
+        let html = render_markdown("## Hello World");
+        assert!(html.contains("id=\"hello-world\""));
+        assert!(html.contains("href=\"#hello-world\""));
+    }
+
+    #[test]
+    fn test_render_markdown_strip_script() {
+        let html = render_markdown(
+            "hi <script>alert(1)</script> <a href=\"javascript:alert(1)\" onclick=\"x()\">x</a>",
+        );
+        assert!(!html.contains("<script"));
+        assert!(!html.contains("javascript:"));
+        assert!(!html.contains("onclick"));
+    }
+}
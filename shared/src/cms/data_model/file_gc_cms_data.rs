@@ -0,0 +1,7 @@
+#[derive(Debug, Clone)]
+pub struct FileGcAttachmentModel {
+    pub id: i64,
+    pub component_id: i64,
+    pub file_name: String,
+    pub file_path: String,
+}
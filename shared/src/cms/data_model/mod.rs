@@ -0,0 +1,3 @@
+pub mod file_gc_cms_data;
+pub mod published_cms_data;
+pub mod search_cms_data;
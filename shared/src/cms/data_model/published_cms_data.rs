@@ -0,0 +1,60 @@
+use crate::cms::taxonomy::TermKind;
+use chrono::{DateTime, Utc};
+
+#[derive(Debug)]
+pub struct PublishedPageModel {
+    pub id: i64,
+    pub path: String,
+    pub title: String,
+    pub summary: String,
+    pub added: DateTime<Utc>,
+    pub updated: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug)]
+pub struct PublishedPagePath(pub String);
+
+#[derive(Debug)]
+pub struct PublishedComponentModel {
+    pub id: i64,
+    pub kind_uuid: String,
+    pub label: String,
+    pub raw_data: Vec<u8>,
+}
+
+#[derive(Debug)]
+pub struct PublishedFileAttachmentModel {
+    pub id: i64,
+    pub file_name: String,
+    pub file_path: String,
+    pub file_type: String,
+}
+
+#[derive(Debug)]
+pub struct PublishedSearchResultModel {
+    pub path: String,
+    pub title: String,
+    pub summary: String,
+    pub snippet: String,
+}
+
+#[derive(Debug)]
+pub struct PublishedTermModel {
+    pub id: i64,
+    pub kind: TermKind,
+    pub slug: String,
+    pub name: String,
+}
+
+#[derive(Debug)]
+pub struct PublishedSitemapPageModel {
+    pub path: String,
+    /// `updated`, or `added` for a page never amended.
+    pub lastmod: DateTime<Utc>,
+}
+
+#[derive(Debug)]
+pub struct PublishedSitemapChunkModel {
+    pub chunk: i64,
+    pub lastmod: DateTime<Utc>,
+}
@@ -0,0 +1,5 @@
+#[derive(Debug)]
+pub struct SearchComponentModel {
+    pub kind_uuid: String,
+    pub raw_data: Vec<u8>,
+}
@@ -0,0 +1,253 @@
+use crate::cms::data_model::file_gc_cms_data::FileGcAttachmentModel;
+use crate::cms::repository::file_gc_cms_repository::FileGcCmsRepository;
+use crate::utils::config::Config;
+use crate::utils::db::SqliteClient;
+use crate::utils::error::boot_error::MainError;
+use error_stack::{Report, ResultExt};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum FileGcError {
+    #[error("Db error")]
+    DbError,
+    #[error("IO error")]
+    IoError,
+}
+
+/// Everything the backoffice stores lives under this folder of `file_path`.
+const SCAN_PATH: &str = "cms";
+/// A file this fresh may belong to an upload whose row is not written yet.
+const ORPHAN_GRACE: Duration = Duration::from_secs(60 * 60);
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct OrphanFile {
+    pub file_path: String,
+    pub size: u64,
+}
+
+#[derive(Debug, Default)]
+pub struct FileGcReport {
+    pub orphan_files: Vec<OrphanFile>,
+    pub missing_files: Vec<FileGcAttachmentModel>,
+    pub removed_files: usize,
+    pub removed_rows: usize,
+    pub failures: Vec<String>,
+}
+
+fn walk_files(dir: &Path, files: &mut Vec<PathBuf>) -> std::io::Result<()> {
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+        Err(err) => return Err(err),
+    };
+    for entry in entries {
+        let entry = entry?;
+        let file_type = entry.file_type()?;
+        if file_type.is_dir() {
+            walk_files(&entry.path(), files)?;
+        } else if file_type.is_file() {
+            files.push(entry.path());
+        }
+    }
+    Ok(())
+}
+
+/// The path as stored in `cms_file_attachment`, relative to `file_path` with a leading slash.
+fn stored_file_path(file_upload_path: &Path, path: &Path) -> Option<String> {
+    let relative = path.strip_prefix(file_upload_path).ok()?;
+    let segments: Vec<&str> = relative
+        .components()
+        .map(|component| component.as_os_str().to_str())
+        .collect::<Option<_>>()?;
+    Some(format!("/{}", segments.join("/")))
+}
+
+fn find_orphan_files(
+    file_upload_path: &Path,
+    known_file_paths: &HashSet<&str>,
+    now: SystemTime,
+) -> std::io::Result<Vec<OrphanFile>> {
+    let mut files = Vec::new();
+    walk_files(&file_upload_path.join(SCAN_PATH), &mut files)?;
+
+    let mut orphan_files = Vec::new();
+    for path in files {
+        let Some(file_path) = stored_file_path(file_upload_path, &path) else {
+            continue;
+        };
+        if known_file_paths.contains(file_path.trim_start_matches('/')) {
+            continue;
+        }
+        let metadata = std::fs::metadata(&path)?;
+        let is_recent = metadata
+            .modified()
+            .ok()
+            .and_then(|modified| now.duration_since(modified).ok())
+            .is_none_or(|age| age < ORPHAN_GRACE);
+        if !is_recent {
+            orphan_files.push(OrphanFile {
+                file_path,
+                size: metadata.len(),
+            });
+        }
+    }
+    orphan_files.sort_by(|a, b| a.file_path.cmp(&b.file_path));
+    Ok(orphan_files)
+}
+
+/// Finds files on disk no attachment or revision points at and attachments whose file is gone,
+/// removing both when `delete` is set. Failed removals are reported, not fatal.
+pub fn reconcile_files(
+    repository: &FileGcCmsRepository,
+    file_upload_path: &str,
+    delete: bool,
+) -> Result<FileGcReport, Report<FileGcError>> {
+    let file_upload_path = Path::new(file_upload_path);
+    let attachments = repository
+        .list_file_attachment()
+        .change_context(FileGcError::DbError)?;
+
+    let revision_file_paths = repository
+        .list_revision_file_path()
+        .change_context(FileGcError::DbError)?;
+
+    let known_file_paths: HashSet<&str> = attachments
+        .iter()
+        .map(|attachment| attachment.file_path.as_str())
+        .chain(revision_file_paths.iter().map(String::as_str))
+        .map(|file_path| file_path.trim_start_matches('/'))
+        .collect();
+    let orphan_files = find_orphan_files(file_upload_path, &known_file_paths, SystemTime::now())
+        .change_context(FileGcError::IoError)?;
+    let missing_files: Vec<FileGcAttachmentModel> = attachments
+        .iter()
+        .filter(|attachment| {
+            !file_upload_path
+                .join(attachment.file_path.trim_start_matches('/'))
+                .is_file()
+        })
+        .cloned()
+        .collect();
+
+    let mut report = FileGcReport {
+        orphan_files,
+        missing_files,
+        ..Default::default()
+    };
+    if !delete {
+        return Ok(report);
+    }
+
+    for orphan_file in report.orphan_files.iter() {
+        match std::fs::remove_file(
+            file_upload_path.join(orphan_file.file_path.trim_start_matches('/')),
+        ) {
+            Ok(_) => report.removed_files += 1,
+            Err(err) => report
+                .failures
+                .push(format!("{}: {}", orphan_file.file_path, err)),
+        }
+    }
+    let missing_ids: Vec<i64> = report.missing_files.iter().map(|file| file.id).collect();
+    report.removed_rows = repository
+        .delete_file_attachment(&missing_ids)
+        .change_context(FileGcError::DbError)?;
+
+    Ok(report)
+}
+
+/// The `file-gc` command, a dry run unless `delete` is set.
+pub async fn run_file_gc(delete: bool) -> Result<(), Report<MainError>> {
+    let config = Config::fetch()
+        .await
+        .change_context(MainError::ConfigError)?
+        .upgrade()
+        .ok_or_else(|| Report::new(MainError::ConfigError))?;
+    let repository = FileGcCmsRepository::new(
+        SqliteClient::fetch(&config.sqlite)
+            .await
+            .change_context(MainError::DbError)?,
+    );
+
+    let report = reconcile_files(&repository, &config.file_path, delete)
+        .change_context(MainError::IoError)?;
+
+    println!("Files: {}", config.file_path);
+    for orphan_file in report.orphan_files.iter() {
+        println!(
+            "orphan  {} ({} bytes)",
+            orphan_file.file_path, orphan_file.size
+        );
+    }
+    for missing_file in report.missing_files.iter() {
+        println!(
+            "missing {} (attachment {}, component {}, {})",
+            missing_file.file_path,
+            missing_file.id,
+            missing_file.component_id,
+            missing_file.file_name
+        );
+    }
+    println!(
+        "{} orphaned file(s), {} attachment(s) without a file",
+        report.orphan_files.len(),
+        report.missing_files.len()
+    );
+    match delete {
+        true => println!(
+            "Removed {} file(s) and {} attachment(s)",
+            report.removed_files, report.removed_rows
+        ),
+        false => println!("Dry run, pass --delete to remove them"),
+    }
+    for failure in report.failures.iter() {
+        eprintln!("failed  {}", failure);
+    }
+
+    match report.failures.is_empty() {
+        true => Ok(()),
+        false => Err(Report::new(MainError::IoError)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    #[test]
+    fn test_stored_file_path() {
+        let root = Path::new("./files");
+        assert_eq!(
+            stored_file_path(root, &root.join("cms/files/ab/abcd.txt")),
+            Some("/cms/files/ab/abcd.txt".to_string())
+        );
+        assert_eq!(stored_file_path(root, Path::new("/elsewhere/a")), None);
+    }
+
+    #[test]
+    fn test_find_orphan_files_skips_known_and_recent() {
+        let root = std::env::temp_dir().join(format!("rusty-cms-{}", Uuid::new_v4()));
+        std::fs::create_dir_all(root.join("cms/files/ab")).unwrap();
+        std::fs::write(root.join("cms/files/ab/known.txt"), b"known").unwrap();
+        std::fs::write(root.join("cms/files/ab/orphan.txt"), b"orphan").unwrap();
+        let known_file_paths = HashSet::from(["cms/files/ab/known.txt"]);
+
+        let later = SystemTime::now() + ORPHAN_GRACE * 2;
+        let orphan_files = find_orphan_files(&root, &known_file_paths, later).unwrap();
+        let recent_files = find_orphan_files(&root, &known_file_paths, SystemTime::now()).unwrap();
+
+        assert_eq!(
+            orphan_files,
+            vec![OrphanFile {
+                file_path: "/cms/files/ab/orphan.txt".to_string(),
+                size: 6,
+            }]
+        );
+        assert!(recent_files.is_empty());
+        std::fs::remove_dir_all(root).unwrap();
+    }
+}
@@ -0,0 +1,24 @@
+use std::path::{Path, PathBuf};
+
+/// Where a path stored in `cms_file_attachment` lives on disk. Stored paths start with a slash,
+/// the backoffice writing the file and the public site serving it both resolve them here.
+pub fn disk_path(file_upload_path: &str, file_path: &str) -> PathBuf {
+    Path::new(file_upload_path).join(file_path.trim_start_matches('/'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_disk_path_joins_with_one_separator() {
+        assert_eq!(
+            disk_path("./files", "/cms/files/ab/abcd.txt"),
+            PathBuf::from("./files/cms/files/ab/abcd.txt")
+        );
+        assert_eq!(
+            disk_path("./files/", "cms/files/ab/abcd.txt"),
+            PathBuf::from("./files/cms/files/ab/abcd.txt")
+        );
+    }
+}
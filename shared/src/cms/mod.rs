@@ -2,8 +2,16 @@ use crate::utils::context::{Context, ContextError, FromContext};
 use error_stack::Report;
 
 pub mod components;
+pub mod data_model;
+pub mod file_gc;
+pub mod file_path;
 pub mod markers;
+pub mod repository;
+pub mod schedule;
+pub mod search;
+pub mod slug;
 pub mod status;
+pub mod taxonomy;
 
 #[derive(Clone, PartialEq, PartialOrd)]
 pub struct CmsComponentInfo {
@@ -0,0 +1 @@
+delete from cms_file_attachment where id = :id
\ No newline at end of file
@@ -0,0 +1,3 @@
+select id, component_id, file_name, file_path
+from cms_file_attachment
+order by id
\ No newline at end of file
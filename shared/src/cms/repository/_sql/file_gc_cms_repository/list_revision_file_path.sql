@@ -0,0 +1,3 @@
+select distinct file_path
+from cms_file_attachment_revision
+order by file_path
\ No newline at end of file
@@ -0,0 +1,9 @@
+select cfa.id, cfa.file_name, cfa.file_path, cfa.file_type
+from cms_file_attachment as cfa
+         inner join cms_component cc on cc.id = cfa.component_id
+         inner join cms_page cp on cp.id = cc.page_id
+where cfa.file_path = :file_path
+  and (cp.status = :status or (cp.status = :draft_status and datetime(cp.publish_at) <= datetime(:now)))
+  and (cp.unpublish_at is null or datetime(cp.unpublish_at) > datetime(:now))
+order by cfa.id
+limit 1
\ No newline at end of file
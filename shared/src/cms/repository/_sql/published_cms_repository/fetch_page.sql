@@ -0,0 +1,5 @@
+select id, path, title, summary, added, updated
+from cms_page
+where path = :path
+  and (status = :status or (status = :draft_status and datetime(publish_at) <= datetime(:now)))
+  and (unpublish_at is null or datetime(unpublish_at) > datetime(:now))
\ No newline at end of file
@@ -0,0 +1,6 @@
+select cp.path
+from cms_page_redirect as cpr
+         inner join cms_page cp on cp.id = cpr.page_id
+where cpr.path = :path
+  and (cp.status = :status or (cp.status = :draft_status and datetime(cp.publish_at) <= datetime(:now)))
+  and (cp.unpublish_at is null or datetime(cp.unpublish_at) > datetime(:now))
\ No newline at end of file
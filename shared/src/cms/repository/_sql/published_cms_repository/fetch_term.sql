@@ -0,0 +1,4 @@
+select id, kind, slug, name
+from cms_term
+where kind = :kind
+  and slug = :slug
\ No newline at end of file
@@ -0,0 +1,4 @@
+select id, kind_uuid, label, raw_data
+from cms_component
+where page_id = :page_id
+order by position;
\ No newline at end of file
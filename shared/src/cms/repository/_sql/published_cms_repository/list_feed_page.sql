@@ -0,0 +1,7 @@
+select cp.id, cp.path, cp.title, cp.summary, cp.added, cp.updated
+from cms_page as cp
+where (:term_id is null or cp.id in (select page_id from cms_page_term where term_id = :term_id))
+  and (cp.status = :status or (cp.status = :draft_status and datetime(cp.publish_at) <= datetime(:now)))
+  and (cp.unpublish_at is null or datetime(cp.unpublish_at) > datetime(:now))
+order by coalesce(cp.updated, cp.added) desc, cp.id desc
+limit :limit;
\ No newline at end of file
@@ -0,0 +1,4 @@
+select id, file_name, file_path, file_type
+from cms_file_attachment
+where component_id = :component_id
+order by id;
\ No newline at end of file
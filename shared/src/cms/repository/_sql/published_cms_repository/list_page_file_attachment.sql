@@ -0,0 +1,6 @@
+select cfa.id, cfa.file_name, cfa.file_path, cfa.file_type
+from cms_file_attachment as cfa
+         inner join cms_component cc on cc.id = cfa.component_id
+where cc.page_id = :page_id
+  and cc.kind_uuid = :kind_uuid
+order by cc.position, cfa.id;
\ No newline at end of file
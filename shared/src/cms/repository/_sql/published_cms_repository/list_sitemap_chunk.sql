@@ -0,0 +1,8 @@
+select chunk, max(lastmod) as lastmod
+from (select (row_number() over (order by cp.id) - 1) / :limit as chunk,
+             coalesce(cp.updated, cp.added)                    as lastmod
+      from cms_page as cp
+      where (cp.status = :status or (cp.status = :draft_status and datetime(cp.publish_at) <= datetime(:now)))
+        and (cp.unpublish_at is null or datetime(cp.unpublish_at) > datetime(:now)))
+group by chunk
+order by chunk;
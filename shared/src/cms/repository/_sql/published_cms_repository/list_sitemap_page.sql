@@ -0,0 +1,6 @@
+select cp.path, coalesce(cp.updated, cp.added) as lastmod
+from cms_page as cp
+where (cp.status = :status or (cp.status = :draft_status and datetime(cp.publish_at) <= datetime(:now)))
+  and (cp.unpublish_at is null or datetime(cp.unpublish_at) > datetime(:now))
+order by cp.id
+limit :limit offset :offset;
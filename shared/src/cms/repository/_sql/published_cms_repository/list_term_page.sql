@@ -0,0 +1,7 @@
+select cp.id, cp.path, cp.title, cp.summary, cp.added, cp.updated
+from cms_page_term as cpt
+         inner join cms_page cp on cp.id = cpt.page_id
+where cpt.term_id = :term_id
+  and (cp.status = :status or (cp.status = :draft_status and datetime(cp.publish_at) <= datetime(:now)))
+  and (cp.unpublish_at is null or datetime(cp.unpublish_at) > datetime(:now))
+order by coalesce(cp.updated, cp.added) desc, cp.id desc;
\ No newline at end of file
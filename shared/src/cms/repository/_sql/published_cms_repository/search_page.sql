@@ -0,0 +1,11 @@
+select cp.path,
+       cp.title,
+       cp.summary,
+       snippet(cms_page_search, -1, char(2), char(3), '…', 16) as snippet
+from cms_page_search
+         inner join cms_page cp on cp.id = cms_page_search.rowid
+where cms_page_search match :query
+  and (cp.status = :status or (cp.status = :draft_status and datetime(cp.publish_at) <= datetime(:now)))
+  and (cp.unpublish_at is null or datetime(cp.unpublish_at) > datetime(:now))
+order by bm25(cms_page_search, 10.0, 5.0, 1.0)
+limit :limit
\ No newline at end of file
@@ -0,0 +1,2 @@
+insert into cms_page_status_transition (page_id, from_status, to_status)
+values (:page_id, :from_status, :to_status)
\ No newline at end of file
@@ -0,0 +1,7 @@
+update cms_page
+set status=:to_status,
+    publish_at=null,
+    updated=datetime('now')
+where status = :from_status
+  and datetime(publish_at) <= datetime(:now)
+returning id
\ No newline at end of file
@@ -0,0 +1,7 @@
+update cms_page
+set status=:to_status,
+    unpublish_at=null,
+    updated=datetime('now')
+where status = :from_status
+  and datetime(unpublish_at) <= datetime(:now)
+returning id
\ No newline at end of file
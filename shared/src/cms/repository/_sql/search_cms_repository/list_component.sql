@@ -0,0 +1,4 @@
+select kind_uuid, raw_data
+from cms_component
+where page_id = :page_id
+order by position, id
\ No newline at end of file
@@ -0,0 +1,3 @@
+select rowid as page_id
+from cms_page_search
+where body is null
\ No newline at end of file
@@ -0,0 +1,3 @@
+update cms_page_search
+set body = :body
+where rowid = :page_id
\ No newline at end of file
@@ -0,0 +1,146 @@
+use crate::cms::data_model::file_gc_cms_data::FileGcAttachmentModel;
+use crate::utils::context::{Context, ContextError, FromContext};
+use crate::utils::db::{BorrowConnectionExt, SqliteClient, SqliteConn, run_blocking};
+use crate::utils::error::ExtraResultExt;
+use error_stack::{Report, ResultExt};
+use poem::http::StatusCode;
+use rusqlite::{TransactionBehavior, named_params};
+use std::sync::Arc;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum FileGcCmsRepositoryError {
+    #[error("Query error")]
+    QueryError,
+    #[error("Row Value error")]
+    RowValueError,
+    #[error("Borrow Conn error")]
+    BorrowConnError,
+}
+
+pub struct FileGcCmsRepository {
+    sqlite_client: Option<SqliteClient>,
+}
+
+impl FileGcCmsRepository {
+    pub fn new(sqlite_client: SqliteClient) -> Self {
+        Self {
+            sqlite_client: Some(sqlite_client),
+        }
+    }
+
+    fn borrow_conn(&self) -> Result<SqliteConn, Report<FileGcCmsRepositoryError>> {
+        self.sqlite_client
+            .borrow_conn()
+            .change_context(FileGcCmsRepositoryError::BorrowConnError)
+    }
+}
+
+impl FileGcCmsRepository {
+    pub fn delete_file_attachment(
+        &self,
+        ids: &[i64],
+    ) -> Result<usize, Report<FileGcCmsRepositoryError>> {
+        run_blocking(|| {
+            let mut conn = self.borrow_conn()?;
+            let tx = conn
+                .transaction_with_behavior(TransactionBehavior::Immediate)
+                .change_context(FileGcCmsRepositoryError::QueryError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)
+                .log_it()?;
+
+            let mut deleted = 0;
+            for id in ids {
+                deleted += tx
+                    .execute(
+                        include_str!("_sql/file_gc_cms_repository/delete_file_attachment.sql"),
+                        named_params! {
+                            ":id": id,
+                        },
+                    )
+                    .change_context(FileGcCmsRepositoryError::QueryError)
+                    .attach(StatusCode::INTERNAL_SERVER_ERROR)
+                    .log_it()?;
+            }
+
+            tx.commit()
+                .change_context(FileGcCmsRepositoryError::QueryError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)
+                .log_it()?;
+
+            Ok(deleted)
+        })
+    }
+
+    pub fn list_file_attachment(
+        &self,
+    ) -> Result<Arc<[FileGcAttachmentModel]>, Report<FileGcCmsRepositoryError>> {
+        run_blocking(|| {
+            let conn = self.borrow_conn()?;
+
+            let mut stmt = conn
+                .prepare(include_str!(
+                    "_sql/file_gc_cms_repository/list_file_attachment.sql"
+                ))
+                .change_context(FileGcCmsRepositoryError::QueryError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)
+                .log_it()?;
+
+            let rows = stmt
+                .query_map([], |row| {
+                    Ok(FileGcAttachmentModel {
+                        id: row.get("id")?,
+                        component_id: row.get("component_id")?,
+                        file_name: row.get("file_name")?,
+                        file_path: row.get("file_path")?,
+                    })
+                })
+                .change_context(FileGcCmsRepositoryError::RowValueError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)
+                .log_it()?;
+
+            Ok(rows
+                .collect::<Result<Vec<_>, _>>()
+                .change_context(FileGcCmsRepositoryError::RowValueError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)
+                .log_it()?
+                .into())
+        })
+    }
+
+    /// Files only an older revision points at, they stay until that revision is gone.
+    pub fn list_revision_file_path(
+        &self,
+    ) -> Result<Arc<[String]>, Report<FileGcCmsRepositoryError>> {
+        run_blocking(|| {
+            let conn = self.borrow_conn()?;
+
+            let mut stmt = conn
+                .prepare(include_str!(
+                    "_sql/file_gc_cms_repository/list_revision_file_path.sql"
+                ))
+                .change_context(FileGcCmsRepositoryError::QueryError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)
+                .log_it()?;
+
+            let rows = stmt
+                .query_map([], |row| row.get("file_path"))
+                .change_context(FileGcCmsRepositoryError::RowValueError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)
+                .log_it()?;
+
+            Ok(rows
+                .collect::<Result<Vec<_>, _>>()
+                .change_context(FileGcCmsRepositoryError::RowValueError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)
+                .log_it()?
+                .into())
+        })
+    }
+}
+
+impl FromContext for FileGcCmsRepository {
+    async fn from_context(ctx: &'_ Context<'_>) -> Result<Self, Report<ContextError>> {
+        Ok(Self::new(ctx.inject().await?))
+    }
+}
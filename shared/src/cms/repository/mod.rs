@@ -0,0 +1,4 @@
+pub mod file_gc_cms_repository;
+pub mod published_cms_repository;
+pub mod schedule_cms_repository;
+pub mod search_cms_repository;
@@ -0,0 +1,625 @@
+use crate::cms::components::file_attachments::FileAttachmentsComponent;
+use crate::cms::data_model::published_cms_data::{
+    PublishedComponentModel, PublishedFileAttachmentModel, PublishedPageModel, PublishedPagePath,
+    PublishedSearchResultModel, PublishedSitemapChunkModel, PublishedSitemapPageModel,
+    PublishedTermModel,
+};
+use crate::cms::markers::ComponentInfoMarker;
+use crate::cms::status::CmsPageStatus;
+use crate::cms::taxonomy::TermKind;
+use crate::utils::context::{Context, ContextError, FromContext};
+use crate::utils::db::{BorrowConnectionExt, SqliteClient, SqliteConn, run_blocking};
+use crate::utils::error::ExtraResultExt;
+use chrono::Utc;
+use error_stack::{Report, ResultExt};
+use poem::http::StatusCode;
+use rusqlite::{OptionalExtension, named_params};
+use std::sync::Arc;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum PublishedCmsRepositoryError {
+    #[error("Query error")]
+    QueryError,
+    #[error("Row Value error")]
+    RowValueError,
+    #[error("Borrow Conn error")]
+    BorrowConnError,
+}
+
+pub struct PublishedCmsRepository {
+    sqlite_client: Option<SqliteClient>,
+}
+
+impl PublishedCmsRepository {
+    pub fn new(sqlite_client: SqliteClient) -> Self {
+        Self {
+            sqlite_client: Some(sqlite_client),
+        }
+    }
+
+    fn borrow_conn(&self) -> Result<SqliteConn, Report<PublishedCmsRepositoryError>> {
+        self.sqlite_client
+            .borrow_conn()
+            .change_context(PublishedCmsRepositoryError::BorrowConnError)
+    }
+}
+
+impl PublishedCmsRepository {
+    /// Resolves an attachment only while the page holding its component is publicly visible.
+    pub fn fetch_file_attachment(
+        &self,
+        file_path: &str,
+    ) -> Result<Option<PublishedFileAttachmentModel>, Report<PublishedCmsRepositoryError>> {
+        run_blocking(|| {
+            let conn = self.borrow_conn()?;
+
+            let mut stmt = conn
+                .prepare(include_str!(
+                    "_sql/published_cms_repository/fetch_file_attachment.sql"
+                ))
+                .change_context(PublishedCmsRepositoryError::QueryError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)
+                .log_it()?;
+
+            let row: Option<PublishedFileAttachmentModel> = stmt
+                .query_one(
+                    named_params! {
+                        ":file_path": file_path,
+                        ":status": CmsPageStatus::Published.as_stringed(),
+                        ":draft_status": CmsPageStatus::Draft.as_stringed(),
+                        ":now": Utc::now(),
+                    },
+                    |row| {
+                        Ok(PublishedFileAttachmentModel {
+                            id: row.get("id")?,
+                            file_name: row.get("file_name")?,
+                            file_path: row.get("file_path")?,
+                            file_type: row.get("file_type")?,
+                        })
+                    },
+                )
+                .optional()
+                .change_context(PublishedCmsRepositoryError::RowValueError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)
+                .log_it()?;
+
+            Ok(row)
+        })
+    }
+
+    pub fn fetch_page(
+        &self,
+        path: &str,
+    ) -> Result<Option<PublishedPageModel>, Report<PublishedCmsRepositoryError>> {
+        run_blocking(|| {
+            let conn = self.borrow_conn()?;
+
+            let mut stmt = conn
+                .prepare(include_str!("_sql/published_cms_repository/fetch_page.sql"))
+                .change_context(PublishedCmsRepositoryError::QueryError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)
+                .log_it()?;
+
+            let row: Option<PublishedPageModel> = stmt
+                .query_one(
+                    named_params! {
+                        ":path": path,
+                        ":status": CmsPageStatus::Published.as_stringed(),
+                        ":draft_status": CmsPageStatus::Draft.as_stringed(),
+                        ":now": Utc::now(),
+                    },
+                    |row| {
+                        Ok(PublishedPageModel {
+                            id: row.get("id")?,
+                            path: row.get("path")?,
+                            title: row.get("title")?,
+                            summary: row.get("summary")?,
+                            added: row.get("added")?,
+                            updated: row.get("updated")?,
+                        })
+                    },
+                )
+                .optional()
+                .change_context(PublishedCmsRepositoryError::RowValueError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)
+                .log_it()?;
+
+            Ok(row)
+        })
+    }
+
+    pub fn fetch_redirect_path(
+        &self,
+        path: &str,
+    ) -> Result<Option<PublishedPagePath>, Report<PublishedCmsRepositoryError>> {
+        run_blocking(|| {
+            let conn = self.borrow_conn()?;
+
+            let mut stmt = conn
+                .prepare(include_str!(
+                    "_sql/published_cms_repository/fetch_redirect_path.sql"
+                ))
+                .change_context(PublishedCmsRepositoryError::QueryError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)
+                .log_it()?;
+
+            let row: Option<PublishedPagePath> = stmt
+                .query_one(
+                    named_params! {
+                        ":path": path,
+                        ":status": CmsPageStatus::Published.as_stringed(),
+                        ":draft_status": CmsPageStatus::Draft.as_stringed(),
+                        ":now": Utc::now(),
+                    },
+                    |row| Ok(PublishedPagePath(row.get("path")?)),
+                )
+                .optional()
+                .change_context(PublishedCmsRepositoryError::RowValueError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)
+                .log_it()?;
+
+            Ok(row)
+        })
+    }
+
+    pub fn fetch_term(
+        &self,
+        kind: TermKind,
+        slug: &str,
+    ) -> Result<Option<PublishedTermModel>, Report<PublishedCmsRepositoryError>> {
+        run_blocking(|| {
+            let conn = self.borrow_conn()?;
+
+            let mut stmt = conn
+                .prepare(include_str!("_sql/published_cms_repository/fetch_term.sql"))
+                .change_context(PublishedCmsRepositoryError::QueryError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)
+                .log_it()?;
+
+            let row: Option<PublishedTermModel> = stmt
+                .query_one(
+                    named_params! {
+                        ":kind": kind.as_stringed(),
+                        ":slug": slug,
+                    },
+                    |row| {
+                        Ok(PublishedTermModel {
+                            id: row.get("id")?,
+                            kind: TermKind::try_from(row.get::<_, String>("kind")?)
+                                .unwrap_or_default(),
+                            slug: row.get("slug")?,
+                            name: row.get("name")?,
+                        })
+                    },
+                )
+                .optional()
+                .change_context(PublishedCmsRepositoryError::RowValueError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)
+                .log_it()?;
+
+            Ok(row)
+        })
+    }
+
+    pub fn list_component(
+        &self,
+        page_id: i64,
+    ) -> Result<Arc<[PublishedComponentModel]>, Report<PublishedCmsRepositoryError>> {
+        run_blocking(|| {
+            let conn = self.borrow_conn()?;
+
+            let mut stmt = conn
+                .prepare(include_str!(
+                    "_sql/published_cms_repository/list_component.sql"
+                ))
+                .change_context(PublishedCmsRepositoryError::QueryError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)
+                .log_it()?;
+
+            let rows = stmt
+                .query_map(
+                    named_params! {
+                        ":page_id": page_id,
+                    },
+                    |row| {
+                        Ok(PublishedComponentModel {
+                            id: row.get("id")?,
+                            kind_uuid: row.get("kind_uuid")?,
+                            label: row.get("label")?,
+                            raw_data: row.get("raw_data")?,
+                        })
+                    },
+                )
+                .change_context(PublishedCmsRepositoryError::RowValueError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)
+                .log_it()?;
+
+            let rows = rows
+                .collect::<Result<Vec<_>, _>>()
+                .change_context(PublishedCmsRepositoryError::RowValueError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)
+                .log_it()?;
+
+            Ok(rows.into())
+        })
+    }
+
+    /// Publicly visible pages for a feed, most recently changed first, `term_id` narrows it to one term.
+    pub fn list_feed_page(
+        &self,
+        term_id: Option<i64>,
+        limit: i64,
+    ) -> Result<Arc<[PublishedPageModel]>, Report<PublishedCmsRepositoryError>> {
+        run_blocking(|| {
+            let conn = self.borrow_conn()?;
+
+            let mut stmt = conn
+                .prepare(include_str!(
+                    "_sql/published_cms_repository/list_feed_page.sql"
+                ))
+                .change_context(PublishedCmsRepositoryError::QueryError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)
+                .log_it()?;
+
+            let rows = stmt
+                .query_map(
+                    named_params! {
+                        ":term_id": term_id,
+                        ":limit": limit,
+                        ":status": CmsPageStatus::Published.as_stringed(),
+                        ":draft_status": CmsPageStatus::Draft.as_stringed(),
+                        ":now": Utc::now(),
+                    },
+                    |row| {
+                        Ok(PublishedPageModel {
+                            id: row.get("id")?,
+                            path: row.get("path")?,
+                            title: row.get("title")?,
+                            summary: row.get("summary")?,
+                            added: row.get("added")?,
+                            updated: row.get("updated")?,
+                        })
+                    },
+                )
+                .change_context(PublishedCmsRepositoryError::RowValueError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)
+                .log_it()?;
+
+            let rows = rows
+                .collect::<Result<Vec<_>, _>>()
+                .change_context(PublishedCmsRepositoryError::RowValueError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)
+                .log_it()?;
+
+            Ok(rows.into())
+        })
+    }
+
+    pub fn list_file_attachment(
+        &self,
+        component_id: i64,
+    ) -> Result<Arc<[PublishedFileAttachmentModel]>, Report<PublishedCmsRepositoryError>> {
+        run_blocking(|| {
+            let conn = self.borrow_conn()?;
+
+            let mut stmt = conn
+                .prepare(include_str!(
+                    "_sql/published_cms_repository/list_file_attachment.sql"
+                ))
+                .change_context(PublishedCmsRepositoryError::QueryError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)
+                .log_it()?;
+
+            let rows = stmt
+                .query_map(
+                    named_params! {
+                        ":component_id": component_id,
+                    },
+                    |row| {
+                        Ok(PublishedFileAttachmentModel {
+                            id: row.get("id")?,
+                            file_name: row.get("file_name")?,
+                            file_path: row.get("file_path")?,
+                            file_type: row.get("file_type")?,
+                        })
+                    },
+                )
+                .change_context(PublishedCmsRepositoryError::RowValueError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)
+                .log_it()?;
+
+            let rows = rows
+                .collect::<Result<Vec<_>, _>>()
+                .change_context(PublishedCmsRepositoryError::RowValueError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)
+                .log_it()?;
+
+            Ok(rows.into())
+        })
+    }
+
+    /// Files of the page's file attachment components, image variants are left out.
+    pub fn list_page_file_attachment(
+        &self,
+        page_id: i64,
+    ) -> Result<Arc<[PublishedFileAttachmentModel]>, Report<PublishedCmsRepositoryError>> {
+        run_blocking(|| {
+            let conn = self.borrow_conn()?;
+
+            let mut stmt = conn
+                .prepare(include_str!(
+                    "_sql/published_cms_repository/list_page_file_attachment.sql"
+                ))
+                .change_context(PublishedCmsRepositoryError::QueryError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)
+                .log_it()?;
+
+            let rows = stmt
+                .query_map(
+                    named_params! {
+                        ":page_id": page_id,
+                        ":kind_uuid": FileAttachmentsComponent::component_info().kind_uuid,
+                    },
+                    |row| {
+                        Ok(PublishedFileAttachmentModel {
+                            id: row.get("id")?,
+                            file_name: row.get("file_name")?,
+                            file_path: row.get("file_path")?,
+                            file_type: row.get("file_type")?,
+                        })
+                    },
+                )
+                .change_context(PublishedCmsRepositoryError::RowValueError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)
+                .log_it()?;
+
+            let rows = rows
+                .collect::<Result<Vec<_>, _>>()
+                .change_context(PublishedCmsRepositoryError::RowValueError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)
+                .log_it()?;
+
+            Ok(rows.into())
+        })
+    }
+
+    pub fn list_page_term(
+        &self,
+        page_id: i64,
+    ) -> Result<Arc<[PublishedTermModel]>, Report<PublishedCmsRepositoryError>> {
+        run_blocking(|| {
+            let conn = self.borrow_conn()?;
+
+            let mut stmt = conn
+                .prepare(include_str!(
+                    "_sql/published_cms_repository/list_page_term.sql"
+                ))
+                .change_context(PublishedCmsRepositoryError::QueryError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)
+                .log_it()?;
+
+            let rows = stmt
+                .query_map(
+                    named_params! {
+                        ":page_id": page_id,
+                    },
+                    |row| {
+                        Ok(PublishedTermModel {
+                            id: row.get("id")?,
+                            kind: TermKind::try_from(row.get::<_, String>("kind")?)
+                                .unwrap_or_default(),
+                            slug: row.get("slug")?,
+                            name: row.get("name")?,
+                        })
+                    },
+                )
+                .change_context(PublishedCmsRepositoryError::RowValueError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)
+                .log_it()?;
+
+            let rows = rows
+                .collect::<Result<Vec<_>, _>>()
+                .change_context(PublishedCmsRepositoryError::RowValueError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)
+                .log_it()?;
+
+            Ok(rows.into())
+        })
+    }
+
+    /// Publicly visible pages of a term, most recently changed first.
+    pub fn list_term_page(
+        &self,
+        term_id: i64,
+    ) -> Result<Arc<[PublishedPageModel]>, Report<PublishedCmsRepositoryError>> {
+        run_blocking(|| {
+            let conn = self.borrow_conn()?;
+
+            let mut stmt = conn
+                .prepare(include_str!(
+                    "_sql/published_cms_repository/list_term_page.sql"
+                ))
+                .change_context(PublishedCmsRepositoryError::QueryError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)
+                .log_it()?;
+
+            let rows = stmt
+                .query_map(
+                    named_params! {
+                        ":term_id": term_id,
+                        ":status": CmsPageStatus::Published.as_stringed(),
+                        ":draft_status": CmsPageStatus::Draft.as_stringed(),
+                        ":now": Utc::now(),
+                    },
+                    |row| {
+                        Ok(PublishedPageModel {
+                            id: row.get("id")?,
+                            path: row.get("path")?,
+                            title: row.get("title")?,
+                            summary: row.get("summary")?,
+                            added: row.get("added")?,
+                            updated: row.get("updated")?,
+                        })
+                    },
+                )
+                .change_context(PublishedCmsRepositoryError::RowValueError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)
+                .log_it()?;
+
+            let rows = rows
+                .collect::<Result<Vec<_>, _>>()
+                .change_context(PublishedCmsRepositoryError::RowValueError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)
+                .log_it()?;
+
+            Ok(rows.into())
+        })
+    }
+
+    /// `query` is an FTS5 match expression, matched terms in `snippet` sit between `\u{2}` and `\u{3}`.
+    pub fn search_page(
+        &self,
+        query: &str,
+        limit: i64,
+    ) -> Result<Arc<[PublishedSearchResultModel]>, Report<PublishedCmsRepositoryError>> {
+        run_blocking(|| {
+            let conn = self.borrow_conn()?;
+
+            let mut stmt = conn
+                .prepare(include_str!(
+                    "_sql/published_cms_repository/search_page.sql"
+                ))
+                .change_context(PublishedCmsRepositoryError::QueryError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)
+                .log_it()?;
+
+            let rows = stmt
+                .query_map(
+                    named_params! {
+                        ":query": query,
+                        ":limit": limit,
+                        ":status": CmsPageStatus::Published.as_stringed(),
+                        ":draft_status": CmsPageStatus::Draft.as_stringed(),
+                        ":now": Utc::now(),
+                    },
+                    |row| {
+                        Ok(PublishedSearchResultModel {
+                            path: row.get("path")?,
+                            title: row.get("title")?,
+                            summary: row.get("summary")?,
+                            snippet: row.get("snippet")?,
+                        })
+                    },
+                )
+                .change_context(PublishedCmsRepositoryError::RowValueError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)
+                .log_it()?;
+
+            let rows = rows
+                .collect::<Result<Vec<_>, _>>()
+                .change_context(PublishedCmsRepositoryError::RowValueError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)
+                .log_it()?;
+
+            Ok(rows.into())
+        })
+    }
+
+    /// Groups the visible pages into chunks of `limit` in sitemap order, with the latest date of each.
+    pub fn list_sitemap_chunk(
+        &self,
+        limit: i64,
+    ) -> Result<Arc<[PublishedSitemapChunkModel]>, Report<PublishedCmsRepositoryError>> {
+        run_blocking(|| {
+            let conn = self.borrow_conn()?;
+
+            let mut stmt = conn
+                .prepare(include_str!(
+                    "_sql/published_cms_repository/list_sitemap_chunk.sql"
+                ))
+                .change_context(PublishedCmsRepositoryError::QueryError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)
+                .log_it()?;
+
+            let rows = stmt
+                .query_map(
+                    named_params! {
+                        ":limit": limit,
+                        ":status": CmsPageStatus::Published.as_stringed(),
+                        ":draft_status": CmsPageStatus::Draft.as_stringed(),
+                        ":now": Utc::now(),
+                    },
+                    |row| {
+                        Ok(PublishedSitemapChunkModel {
+                            chunk: row.get("chunk")?,
+                            lastmod: row.get("lastmod")?,
+                        })
+                    },
+                )
+                .change_context(PublishedCmsRepositoryError::RowValueError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)
+                .log_it()?;
+
+            let rows = rows
+                .collect::<Result<Vec<_>, _>>()
+                .change_context(PublishedCmsRepositoryError::RowValueError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)
+                .log_it()?;
+
+            Ok(rows.into())
+        })
+    }
+
+    pub fn list_sitemap_page(
+        &self,
+        offset: i64,
+        limit: i64,
+    ) -> Result<Arc<[PublishedSitemapPageModel]>, Report<PublishedCmsRepositoryError>> {
+        run_blocking(|| {
+            let conn = self.borrow_conn()?;
+
+            let mut stmt = conn
+                .prepare(include_str!(
+                    "_sql/published_cms_repository/list_sitemap_page.sql"
+                ))
+                .change_context(PublishedCmsRepositoryError::QueryError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)
+                .log_it()?;
+
+            let rows = stmt
+                .query_map(
+                    named_params! {
+                        ":offset": offset,
+                        ":limit": limit,
+                        ":status": CmsPageStatus::Published.as_stringed(),
+                        ":draft_status": CmsPageStatus::Draft.as_stringed(),
+                        ":now": Utc::now(),
+                    },
+                    |row| {
+                        Ok(PublishedSitemapPageModel {
+                            path: row.get("path")?,
+                            lastmod: row.get("lastmod")?,
+                        })
+                    },
+                )
+                .change_context(PublishedCmsRepositoryError::RowValueError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)
+                .log_it()?;
+
+            let rows = rows
+                .collect::<Result<Vec<_>, _>>()
+                .change_context(PublishedCmsRepositoryError::RowValueError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)
+                .log_it()?;
+
+            Ok(rows.into())
+        })
+    }
+}
+
+impl FromContext for PublishedCmsRepository {
+    async fn from_context(ctx: &'_ Context<'_>) -> Result<Self, Report<ContextError>> {
+        Ok(Self::new(ctx.inject().await?))
+    }
+}
@@ -0,0 +1,199 @@
+use crate::cms::status::CmsPageStatus;
+use crate::utils::db::{BorrowConnectionExt, SqliteClient, SqliteConn, run_blocking};
+use crate::utils::error::ExtraResultExt;
+use chrono::{DateTime, Utc};
+use error_stack::{Report, ResultExt};
+use rusqlite::{Connection, TransactionBehavior, named_params};
+use std::sync::Arc;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum ScheduleCmsRepositoryError {
+    #[error("Query error")]
+    QueryError,
+    #[error("Row Value error")]
+    RowValueError,
+    #[error("Borrow Conn error")]
+    BorrowConnError,
+}
+
+pub struct ScheduleCmsRepository {
+    sqlite_client: Option<SqliteClient>,
+}
+
+impl ScheduleCmsRepository {
+    pub fn new(sqlite_client: SqliteClient) -> Self {
+        Self {
+            sqlite_client: Some(sqlite_client),
+        }
+    }
+
+    fn borrow_conn(&self) -> Result<SqliteConn, Report<ScheduleCmsRepositoryError>> {
+        self.sqlite_client
+            .borrow_conn()
+            .change_context(ScheduleCmsRepositoryError::BorrowConnError)
+    }
+}
+
+/// Flips every page matched by `sql` and records one transition per page, in one transaction.
+fn transition_due(
+    conn: &mut Connection,
+    sql: &str,
+    from_status: CmsPageStatus,
+    to_status: CmsPageStatus,
+    now: DateTime<Utc>,
+) -> Result<Arc<[i64]>, Report<ScheduleCmsRepositoryError>> {
+    let tx = conn
+        .transaction_with_behavior(TransactionBehavior::Immediate)
+        .change_context(ScheduleCmsRepositoryError::QueryError)
+        .log_it()?;
+
+    let page_ids = {
+        let mut stmt = tx
+            .prepare(sql)
+            .change_context(ScheduleCmsRepositoryError::QueryError)
+            .log_it()?;
+
+        let rows = stmt
+            .query_map(
+                named_params! {
+                    ":from_status": from_status.as_stringed(),
+                    ":to_status": to_status.as_stringed(),
+                    ":now": now,
+                },
+                |row| row.get::<_, i64>("id"),
+            )
+            .change_context(ScheduleCmsRepositoryError::RowValueError)
+            .log_it()?;
+
+        rows.collect::<Result<Vec<_>, _>>()
+            .change_context(ScheduleCmsRepositoryError::RowValueError)
+            .log_it()?
+    };
+
+    for page_id in page_ids.iter() {
+        tx.execute(
+            include_str!("_sql/schedule_cms_repository/add_status_transition.sql"),
+            named_params! {
+                ":page_id": page_id,
+                ":from_status": from_status.as_stringed(),
+                ":to_status": to_status.as_stringed(),
+            },
+        )
+        .change_context(ScheduleCmsRepositoryError::QueryError)
+        .log_it()?;
+    }
+
+    tx.commit()
+        .change_context(ScheduleCmsRepositoryError::QueryError)
+        .log_it()?;
+
+    Ok(page_ids.into())
+}
+
+impl ScheduleCmsRepository {
+    pub fn publish_due(
+        &self,
+        now: DateTime<Utc>,
+    ) -> Result<Arc<[i64]>, Report<ScheduleCmsRepositoryError>> {
+        run_blocking(|| {
+            transition_due(
+                &mut *self.borrow_conn()?,
+                include_str!("_sql/schedule_cms_repository/publish_due.sql"),
+                CmsPageStatus::Draft,
+                CmsPageStatus::Published,
+                now,
+            )
+        })
+    }
+
+    pub fn unpublish_due(
+        &self,
+        now: DateTime<Utc>,
+    ) -> Result<Arc<[i64]>, Report<ScheduleCmsRepositoryError>> {
+        run_blocking(|| {
+            transition_due(
+                &mut *self.borrow_conn()?,
+                include_str!("_sql/schedule_cms_repository/unpublish_due.sql"),
+                CmsPageStatus::Published,
+                CmsPageStatus::Draft,
+                now,
+            )
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::db::migration::migrate;
+    use chrono::Duration;
+
+    fn setup(publish_at: DateTime<Utc>) -> Connection {
+        let mut conn = Connection::open_in_memory().unwrap();
+        migrate(&mut conn).unwrap();
+        conn.execute_batch(
+            "insert into backoffice_users (id, username, password, role) values (1, 'a', x'00', 'root');
+             insert into cms_page (id, user_id, title, summary, status, slug, path)
+                values (1, 1, 't', 's', 'draft', 'page', 'page');",
+        )
+        .unwrap();
+        conn.execute(
+            "update cms_page set publish_at = ?1 where id = 1",
+            [publish_at],
+        )
+        .unwrap();
+        conn
+    }
+
+    fn status(conn: &Connection) -> String {
+        conn.query_row("select status from cms_page where id = 1", [], |row| {
+            row.get(0)
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_publish_due_flips_status_and_records_transition() {
+        let now = Utc::now();
+        let mut conn = setup(now - Duration::minutes(1));
+
+        let page_ids = transition_due(
+            &mut conn,
+            include_str!("_sql/schedule_cms_repository/publish_due.sql"),
+            CmsPageStatus::Draft,
+            CmsPageStatus::Published,
+            now,
+        )
+        .unwrap();
+
+        assert_eq!(page_ids.as_ref(), &[1]);
+        assert_eq!(status(&conn), "published");
+        let transition: (String, String) = conn
+            .query_row(
+                "select from_status, to_status from cms_page_status_transition where page_id = 1",
+                [],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .unwrap();
+        assert_eq!(transition, ("draft".to_string(), "published".to_string()));
+    }
+
+    #[test]
+    fn test_publish_due_skips_future_schedule() {
+        let now = Utc::now();
+        let mut conn = setup(now + Duration::minutes(1));
+
+        let page_ids = transition_due(
+            &mut conn,
+            include_str!("_sql/schedule_cms_repository/publish_due.sql"),
+            CmsPageStatus::Draft,
+            CmsPageStatus::Published,
+            now,
+        )
+        .unwrap();
+
+        assert!(page_ids.is_empty());
+        assert_eq!(status(&conn), "draft");
+    }
+}
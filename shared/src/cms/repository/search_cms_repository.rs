@@ -0,0 +1,137 @@
+use crate::cms::data_model::search_cms_data::SearchComponentModel;
+use crate::utils::context::{Context, ContextError, FromContext};
+use crate::utils::db::{BorrowConnectionExt, SqliteClient, SqliteConn, run_blocking};
+use crate::utils::error::ExtraResultExt;
+use error_stack::{Report, ResultExt};
+use poem::http::StatusCode;
+use rusqlite::named_params;
+use std::sync::Arc;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum SearchCmsRepositoryError {
+    #[error("Query error")]
+    QueryError,
+    #[error("Row Value error")]
+    RowValueError,
+    #[error("Borrow Conn error")]
+    BorrowConnError,
+}
+
+pub struct SearchCmsRepository {
+    sqlite_client: Option<SqliteClient>,
+}
+
+impl SearchCmsRepository {
+    pub fn new(sqlite_client: SqliteClient) -> Self {
+        Self {
+            sqlite_client: Some(sqlite_client),
+        }
+    }
+
+    fn borrow_conn(&self) -> Result<SqliteConn, Report<SearchCmsRepositoryError>> {
+        self.sqlite_client
+            .borrow_conn()
+            .change_context(SearchCmsRepositoryError::BorrowConnError)
+    }
+}
+
+impl SearchCmsRepository {
+    pub fn list_component(
+        &self,
+        page_id: i64,
+    ) -> Result<Arc<[SearchComponentModel]>, Report<SearchCmsRepositoryError>> {
+        run_blocking(|| {
+            let conn = self.borrow_conn()?;
+
+            let mut stmt = conn
+                .prepare(include_str!(
+                    "_sql/search_cms_repository/list_component.sql"
+                ))
+                .change_context(SearchCmsRepositoryError::QueryError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)
+                .log_it()?;
+
+            let rows = stmt
+                .query_map(
+                    named_params! {
+                        ":page_id": page_id,
+                    },
+                    |row| {
+                        Ok(SearchComponentModel {
+                            kind_uuid: row.get("kind_uuid")?,
+                            raw_data: row.get("raw_data")?,
+                        })
+                    },
+                )
+                .change_context(SearchCmsRepositoryError::RowValueError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)
+                .log_it()?;
+
+            let rows = rows
+                .collect::<Result<Vec<_>, _>>()
+                .change_context(SearchCmsRepositoryError::RowValueError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)
+                .log_it()?;
+
+            Ok(rows.into())
+        })
+    }
+
+    pub fn list_unindexed_page(&self) -> Result<Arc<[i64]>, Report<SearchCmsRepositoryError>> {
+        run_blocking(|| {
+            let conn = self.borrow_conn()?;
+
+            let mut stmt = conn
+                .prepare(include_str!(
+                    "_sql/search_cms_repository/list_unindexed_page.sql"
+                ))
+                .change_context(SearchCmsRepositoryError::QueryError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)
+                .log_it()?;
+
+            let rows = stmt
+                .query_map(named_params! {}, |row| row.get("page_id"))
+                .change_context(SearchCmsRepositoryError::RowValueError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)
+                .log_it()?;
+
+            let rows = rows
+                .collect::<Result<Vec<_>, _>>()
+                .change_context(SearchCmsRepositoryError::RowValueError)
+                .attach(StatusCode::INTERNAL_SERVER_ERROR)
+                .log_it()?;
+
+            Ok(rows.into())
+        })
+    }
+
+    pub fn update_body(
+        &self,
+        page_id: i64,
+        body: &str,
+    ) -> Result<(), Report<SearchCmsRepositoryError>> {
+        run_blocking(|| {
+            let conn = self.borrow_conn()?;
+
+            conn.execute(
+                include_str!("_sql/search_cms_repository/update_body.sql"),
+                named_params! {
+                    ":page_id": page_id,
+                    ":body": body,
+                },
+            )
+            .change_context(SearchCmsRepositoryError::QueryError)
+            .attach(StatusCode::INTERNAL_SERVER_ERROR)
+            .log_it()?;
+
+            Ok(())
+        })
+    }
+}
+
+impl FromContext for SearchCmsRepository {
+    async fn from_context(ctx: &'_ Context<'_>) -> Result<Self, Report<ContextError>> {
+        Ok(Self::new(ctx.inject().await?))
+    }
+}
@@ -0,0 +1,48 @@
+use crate::cms::repository::schedule_cms_repository::{
+    ScheduleCmsRepository, ScheduleCmsRepositoryError,
+};
+use crate::utils::config::Config;
+use crate::utils::db::SqliteClient;
+use crate::utils::error::boot_error::MainError;
+use chrono::{DateTime, Utc};
+use error_stack::{Report, ResultExt};
+use log::{error, info};
+use std::time::Duration;
+
+fn apply_schedule(
+    repository: &ScheduleCmsRepository,
+    now: DateTime<Utc>,
+) -> Result<(), Report<ScheduleCmsRepositoryError>> {
+    // publish first, a page whose whole window passed while the server was down ends up draft.
+    for page_id in repository.publish_due(now)?.iter() {
+        info!("Scheduled publish of page {}", page_id);
+    }
+    for page_id in repository.unpublish_due(now)?.iter() {
+        info!("Scheduled unpublish of page {}", page_id);
+    }
+    Ok(())
+}
+
+/// Flips the stored status of pages whose `publish_at` or `unpublish_at` has passed,
+/// a failed run is logged and retried on the next tick.
+pub async fn run_schedule() -> Result<(), Report<MainError>> {
+    let config = Config::fetch()
+        .await
+        .change_context(MainError::ConfigError)?
+        .upgrade()
+        .ok_or_else(|| Report::new(MainError::ConfigError))?;
+    let repository = ScheduleCmsRepository::new(
+        SqliteClient::fetch(&config.sqlite)
+            .await
+            .change_context(MainError::DbError)?,
+    );
+
+    let mut interval =
+        tokio::time::interval(Duration::from_secs(config.schedule_interval_secs.max(1)));
+    loop {
+        interval.tick().await;
+        if let Err(report) = apply_schedule(&repository, Utc::now()) {
+            error!("Scheduled publishing failed\n{:?}", report);
+        }
+    }
+}
@@ -0,0 +1,127 @@
+use crate::cms::components::markdown::{MarkdownComponent, markdown_plain_text};
+use crate::cms::data_model::search_cms_data::SearchComponentModel;
+use crate::cms::markers::ComponentInfoMarker;
+use crate::cms::repository::search_cms_repository::{
+    SearchCmsRepository, SearchCmsRepositoryError,
+};
+use crate::utils::config::Config;
+use crate::utils::db::SqliteClient;
+use crate::utils::error::boot_error::MainError;
+use error_stack::{Report, ResultExt};
+use log::info;
+use maud::{Markup, PreEscaped, html};
+
+/// Marks a matched term in a snippet, see `search_page.sql`.
+const SNIPPET_START: char = '\u{2}';
+const SNIPPET_END: char = '\u{3}';
+const MAX_QUERY_TERMS: usize = 8;
+
+/// Turns free text into an FTS5 expression of quoted prefix terms, so no input is a syntax error.
+pub fn match_query(q: &str) -> Option<String> {
+    let terms: Vec<String> = q
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|term| !term.is_empty())
+        .take(MAX_QUERY_TERMS)
+        .map(|term| format!("\"{}\"*", term))
+        .collect();
+    (!terms.is_empty()).then(|| terms.join(" "))
+}
+
+/// Escapes a snippet and wraps its matched terms in `mark`.
+pub fn highlight_snippet(snippet: &str) -> Markup {
+    let mut highlighted = String::new();
+    // parts alternate between plain text and a matched term.
+    for (index, part) in snippet.split([SNIPPET_START, SNIPPET_END]).enumerate() {
+        let part = html! { (part) }.into_string();
+        match index % 2 == 1 {
+            true => highlighted.push_str(&format!("<mark>{}</mark>", part)),
+            false => highlighted.push_str(&part),
+        }
+    }
+    PreEscaped(highlighted)
+}
+
+/// Only markdown components carry text worth searching.
+pub fn search_body(component_list: &[SearchComponentModel]) -> String {
+    let markdown_kind_uuid = MarkdownComponent::component_info().kind_uuid;
+    component_list
+        .iter()
+        .filter(|component| component.kind_uuid == markdown_kind_uuid)
+        .map(|component| {
+            markdown_plain_text(&MarkdownComponent::from(component.raw_data.clone()).content)
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+pub fn index_page_body(
+    repository: &SearchCmsRepository,
+    page_id: i64,
+) -> Result<(), Report<SearchCmsRepositoryError>> {
+    let component_list = repository.list_component(page_id)?;
+    repository.update_body(page_id, &search_body(&component_list))
+}
+
+/// Indexes the body of pages that predate the search index, a no-op once every page has one.
+pub async fn backfill_search_index() -> Result<(), Report<MainError>> {
+    let config = Config::fetch()
+        .await
+        .change_context(MainError::ConfigError)?
+        .upgrade()
+        .ok_or_else(|| Report::new(MainError::ConfigError))?;
+    let repository = SearchCmsRepository::new(
+        SqliteClient::fetch(&config.sqlite)
+            .await
+            .change_context(MainError::DbError)?,
+    );
+
+    let page_ids = repository
+        .list_unindexed_page()
+        .change_context(MainError::DbError)?;
+    for page_id in page_ids.iter() {
+        index_page_body(&repository, *page_id).change_context(MainError::DbError)?;
+    }
+    if !page_ids.is_empty() {
+        info!("Indexed {} page(s) for search", page_ids.len());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cms::markers::ComponentDataMarker;
+
+    #[test]
+    fn test_match_query_quotes_terms() {
+        assert_eq!(
+            match_query("rust \"web\" OR cms-page"),
+            Some("\"rust\"* \"web\"* \"OR\"* \"cms\"* \"page\"*".to_string())
+        );
+        assert_eq!(match_query(" *() "), None);
+    }
+
+    #[test]
+    fn test_highlight_snippet_escapes_text() {
+        let snippet = format!("a <b> {}match{} end", SNIPPET_START, SNIPPET_END);
+        assert_eq!(
+            highlight_snippet(&snippet).into_string(),
+            "a &lt;b&gt; <mark>match</mark> end"
+        );
+    }
+
+    #[test]
+    fn test_search_body_keeps_markdown_only() {
+        let component_list = [
+            SearchComponentModel {
+                kind_uuid: MarkdownComponent::component_info().kind_uuid,
+                raw_data: MarkdownComponent::new("Hello *world*".to_string()).into_data(),
+            },
+            SearchComponentModel {
+                kind_uuid: "other".to_string(),
+                raw_data: vec![],
+            },
+        ];
+        assert_eq!(search_body(&component_list), "Hello world");
+    }
+}
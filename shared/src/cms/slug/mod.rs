@@ -0,0 +1,66 @@
+pub const RESERVED_ROOT_SLUGS: [&str; 5] = ["assets", "category", "files", "search", "tag"];
+
+pub fn slugify(s: &str) -> String {
+    ::slug::slugify(s)
+}
+
+pub fn is_valid_slug(s: &str) -> bool {
+    !s.is_empty()
+        && !s.starts_with('-')
+        && !s.ends_with('-')
+        && !s.contains("--")
+        && s.chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+}
+
+pub fn is_reserved_root_slug(s: &str) -> bool {
+    RESERVED_ROOT_SLUGS.contains(&s)
+}
+
+pub fn join_path(parent_path: Option<&str>, slug: &str) -> String {
+    match parent_path {
+        Some(parent_path) => format!("{}/{}", parent_path, slug),
+        None => slug.to_string(),
+    }
+}
+
+pub fn normalize_path(path: &str) -> String {
+    path.split('/')
+        .filter(|segment| !segment.is_empty())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_slugify() {
+        assert_eq!(slugify("Hello World!"), "hello-world");
+        assert!(is_valid_slug(&slugify("  Install on Linux  ")));
+    }
+
+    #[test]
+    fn test_is_valid_slug() {
+        assert!(is_valid_slug("install-linux-2"));
+        assert!(!is_valid_slug(""));
+        assert!(!is_valid_slug("-install"));
+        assert!(!is_valid_slug("install--linux"));
+        assert!(!is_valid_slug("Install"));
+        assert!(!is_valid_slug("docs/install"));
+    }
+
+    #[test]
+    fn test_join_and_normalize_path() {
+        assert_eq!(join_path(None, "docs"), "docs");
+        assert_eq!(
+            join_path(Some("docs/install"), "linux"),
+            "docs/install/linux"
+        );
+        assert_eq!(
+            normalize_path("/docs//install/linux/"),
+            "docs/install/linux"
+        );
+    }
+}
@@ -1,11 +1,15 @@
 use maud::{Markup, html};
 use serde::de::Visitor;
 use serde::{Deserialize, Serialize};
+use std::str::FromStr;
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum CmsPageStatus {
     Draft,
+    InReview,
+    ChangesRequested,
     Published,
+    Trash,
 }
 
 impl Default for CmsPageStatus {
@@ -19,7 +23,10 @@ impl TryFrom<&str> for CmsPageStatus {
     fn try_from(s: &str) -> Result<Self, Self::Error> {
         match s {
             "draft" => Ok(Self::Draft),
+            "in_review" => Ok(Self::InReview),
+            "changes_requested" => Ok(Self::ChangesRequested),
             "published" => Ok(Self::Published),
+            "trash" => Ok(Self::Trash),
             _ => Err(()),
         }
     }
@@ -32,28 +39,60 @@ impl TryFrom<String> for CmsPageStatus {
     }
 }
 
+impl FromStr for CmsPageStatus {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::try_from(s).map_err(|_| "invalid cms page status".to_string())
+    }
+}
+
 impl From<&CmsPageStatus> for String {
     fn from(s: &CmsPageStatus) -> Self {
         match s {
             CmsPageStatus::Draft => "draft".to_string(),
+            CmsPageStatus::InReview => "in_review".to_string(),
+            CmsPageStatus::ChangesRequested => "changes_requested".to_string(),
             CmsPageStatus::Published => "published".to_string(),
+            CmsPageStatus::Trash => "trash".to_string(),
         }
     }
 }
 
 impl CmsPageStatus {
     pub fn all_statuses() -> Vec<Self> {
-        vec![Self::Draft, Self::Published]
+        vec![
+            Self::Draft,
+            Self::InReview,
+            Self::ChangesRequested,
+            Self::Published,
+            Self::Trash,
+        ]
+    }
+
+    /// Statuses that can be picked from the amend form, trash is only reachable by deleting.
+    pub fn editable_statuses() -> Vec<Self> {
+        vec![Self::Draft, Self::InReview, Self::Published]
+    }
+
+    /// Statuses an author without publishing rights can pick, publishing goes through review.
+    pub fn author_statuses() -> Vec<Self> {
+        vec![Self::Draft, Self::InReview]
     }
 
     pub fn as_stringed(&self) -> String {
         String::from(self)
     }
 
-    pub fn html_option(&self) -> Markup {
+    /// The current status is always listed, so saving the form keeps it when it is not in `statuses`.
+    pub fn html_option(&self, statuses: &[Self]) -> Markup {
         html! {
-            @for status in Self::all_statuses() {
-                @if self == &status {
+            @if !statuses.contains(self) {
+                option value=(self.as_stringed()) selected {
+                    (self.as_stringed())
+                }
+            }
+            @for status in statuses {
+                @if self == status {
                     option value=(status.as_stringed()) selected {
                         (status.as_stringed())
                     }
@@ -101,3 +140,25 @@ impl<'de> Deserialize<'de> for CmsPageStatus {
         deserializer.deserialize_str(CmsPageStatusVisitor)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_html_option_keeps_current_status() {
+        let html = CmsPageStatus::Published
+            .html_option(&CmsPageStatus::author_statuses())
+            .into_string();
+
+        assert!(html.starts_with("<option value=\"published\" selected>"));
+        assert!(html.contains("<option value=\"in_review\">"));
+    }
+
+    #[test]
+    fn test_status_round_trip() {
+        for status in CmsPageStatus::all_statuses() {
+            assert_eq!(CmsPageStatus::try_from(status.as_stringed()), Ok(status));
+        }
+    }
+}
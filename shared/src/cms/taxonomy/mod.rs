@@ -0,0 +1,127 @@
+use serde::de::Visitor;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+/// How a term groups pages, a page has any number of both.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum TermKind {
+    Category,
+    #[default]
+    Tag,
+}
+
+impl TryFrom<&str> for TermKind {
+    type Error = ();
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        match s {
+            "category" => Ok(Self::Category),
+            "tag" => Ok(Self::Tag),
+            _ => Err(()),
+        }
+    }
+}
+
+impl TryFrom<String> for TermKind {
+    type Error = ();
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        Self::try_from(s.as_str())
+    }
+}
+
+impl FromStr for TermKind {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::try_from(s).map_err(|_| "invalid term kind".to_string())
+    }
+}
+
+impl From<&TermKind> for String {
+    fn from(k: &TermKind) -> Self {
+        match k {
+            TermKind::Category => "category".to_string(),
+            TermKind::Tag => "tag".to_string(),
+        }
+    }
+}
+
+impl TermKind {
+    pub fn all_kinds() -> Vec<Self> {
+        vec![Self::Category, Self::Tag]
+    }
+
+    pub fn as_stringed(&self) -> String {
+        String::from(self)
+    }
+
+    /// The public route listing the pages of a kind, terms sit under it at `/{kind}/{slug}`.
+    pub fn route(&self) -> String {
+        format!("/{}", self.as_stringed())
+    }
+
+    pub fn as_uri(&self, slug: &str) -> String {
+        format!("{}/{}", self.route(), slug)
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Category => "Category",
+            Self::Tag => "Tag",
+        }
+    }
+}
+
+impl Serialize for TermKind {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(String::from(self).as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for TermKind {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        struct TermKindVisitor;
+
+        impl<'de> Visitor<'de> for TermKindVisitor {
+            type Value = TermKind;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("a term kind")
+            }
+
+            fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                TermKind::try_from(v).map_err(|_| E::custom("invalid term kind"))
+            }
+        }
+
+        deserializer.deserialize_str(TermKindVisitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cms::slug::is_reserved_root_slug;
+
+    #[test]
+    fn test_term_kind_round_trip() {
+        for kind in TermKind::all_kinds() {
+            assert_eq!(TermKind::try_from(kind.as_stringed()), Ok(kind));
+        }
+    }
+
+    #[test]
+    fn test_term_kind_routes_are_reserved() {
+        for kind in TermKind::all_kinds() {
+            assert!(is_reserved_root_slug(&kind.as_stringed()));
+        }
+        assert_eq!(TermKind::Tag.as_uri("rust"), "/tag/rust");
+    }
+}
@@ -0,0 +1,23 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct LoginThrottleConfig {
+    /// Failures allowed per username or IP before the first lockout.
+    pub free_attempts: i64,
+    /// The first lockout, doubled with each further failure.
+    pub base_lockout_secs: i64,
+    pub max_lockout_secs: i64,
+    /// A quiet period after which the failure counter starts over.
+    pub reset_after_secs: i64,
+}
+
+impl Default for LoginThrottleConfig {
+    fn default() -> Self {
+        Self {
+            free_attempts: 5,
+            base_lockout_secs: 30,
+            max_lockout_secs: 3600,
+            reset_after_secs: 86400,
+        }
+    }
+}
@@ -2,8 +2,12 @@ use crate::utils::context::{Context, ContextError, FromContext};
 use error_stack::{FutureExt, Report, ResultExt};
 use figment::providers::{Format, Serialized, Toml};
 use figment::{Figment, Profile};
+use login_throttle::LoginThrottleConfig;
+use password::PasswordConfig;
 use poem::PoemConfig;
+use robots::RobotsConfig;
 use serde::{Deserialize, Serialize};
+use session::SessionConfig;
 use sqlite::SqliteConfig;
 use std::env::var;
 use std::ops::Deref;
@@ -11,13 +15,19 @@ use std::sync::{Arc, Weak};
 use thiserror::Error;
 use tokio::sync::OnceCell;
 
+pub mod login_throttle;
+pub mod password;
 pub mod poem;
+pub mod robots;
+pub mod session;
 pub mod sqlite;
 
 #[derive(Debug, Error)]
 pub enum ConfigError {
     #[error("Config did not parse")]
     ParseError,
+    #[error("Config has an invalid value")]
+    InvalidValue,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -25,7 +35,18 @@ pub struct Config {
     pub poem_public: Arc<PoemConfig>,
     pub poem_backoffice: Arc<PoemConfig>,
     pub sqlite: Arc<SqliteConfig>,
+    pub robots: Arc<RobotsConfig>,
+    pub login_throttle: Arc<LoginThrottleConfig>,
+    pub session: Arc<SessionConfig>,
+    pub password: Arc<PasswordConfig>,
     pub file_path: String,
+    /// Base of the absolute links handed to feed readers, without a trailing slash.
+    pub public_url: String,
+    pub schedule_interval_secs: u64,
+    pub max_file_size_mb: u64,
+    pub max_request_size_mb: u64,
+    /// Sends every backoffice user without TOTP to enrolment before anything else.
+    pub require_two_factor: bool,
 }
 
 impl Default for Config {
@@ -37,7 +58,16 @@ impl Default for Config {
                 port: 8001,
             }),
             sqlite: Arc::new(SqliteConfig::default()),
+            robots: Arc::new(RobotsConfig::default()),
+            login_throttle: Arc::new(LoginThrottleConfig::default()),
+            session: Arc::new(SessionConfig::default()),
+            password: Arc::new(PasswordConfig::default()),
             file_path: "./files".to_string(),
+            public_url: "http://127.0.0.1:8000".to_string(),
+            schedule_interval_secs: 30,
+            max_file_size_mb: 20,
+            max_request_size_mb: 100,
+            require_two_factor: false,
         }
     }
 }
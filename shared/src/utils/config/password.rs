@@ -0,0 +1,55 @@
+use crate::utils::config::ConfigError;
+use argon2::Params;
+use error_stack::Report;
+use serde::{Deserialize, Serialize};
+
+/// Argon2id costs for new password hashes, `rusty-cms password-bench` suggests values for the host.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct PasswordConfig {
+    pub memory_kib: u32,
+    pub time_cost: u32,
+    pub parallelism: u32,
+}
+
+impl Default for PasswordConfig {
+    fn default() -> Self {
+        Self {
+            memory_kib: 19456,
+            time_cost: 2,
+            parallelism: 1,
+        }
+    }
+}
+
+impl PasswordConfig {
+    /// Costs argon2 rejects would only show up on the next password change or login.
+    pub fn validate(&self) -> Result<(), Report<ConfigError>> {
+        Params::new(self.memory_kib, self.time_cost, self.parallelism, None).map_err(|e| {
+            Report::new(ConfigError::InvalidValue)
+                .attach(format!("password costs are not valid for argon2: {}", e))
+        })?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_refuses_costs_argon2_rejects() {
+        assert!(PasswordConfig::default().validate().is_ok());
+
+        let password_config = PasswordConfig {
+            memory_kib: 1,
+            ..PasswordConfig::default()
+        };
+        assert!(password_config.validate().is_err());
+
+        let password_config = PasswordConfig {
+            time_cost: 0,
+            ..PasswordConfig::default()
+        };
+        assert!(password_config.validate().is_err());
+    }
+}
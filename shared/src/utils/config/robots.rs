@@ -0,0 +1,27 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct RobotsRule {
+    pub user_agent: String,
+    #[serde(default)]
+    pub allow: Vec<String>,
+    #[serde(default)]
+    pub disallow: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct RobotsConfig {
+    pub rules: Vec<RobotsRule>,
+}
+
+impl Default for RobotsConfig {
+    fn default() -> Self {
+        Self {
+            rules: vec![RobotsRule {
+                user_agent: "*".to_string(),
+                allow: vec![],
+                disallow: vec!["/search".to_string()],
+            }],
+        }
+    }
+}
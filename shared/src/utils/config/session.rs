@@ -0,0 +1,53 @@
+use crate::utils::config::ConfigError;
+use error_stack::Report;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SessionConfig {
+    /// How long a login token lives without being used, every use pushes it back.
+    pub lifetime_days: i64,
+    /// The least time between two renewals of a token, keeps page loads from writing each time.
+    pub renew_interval_secs: i64,
+    pub purge_interval_secs: u64,
+    /// Keys the hash login tokens are stored under, changing it signs everyone out.
+    pub token_secret: String,
+}
+
+impl Default for SessionConfig {
+    fn default() -> Self {
+        Self {
+            lifetime_days: 30,
+            renew_interval_secs: 300,
+            purge_interval_secs: 3600,
+            token_secret: String::new(),
+        }
+    }
+}
+
+impl SessionConfig {
+    /// Without a secret anyone holding a copy of the database could check guessed tokens.
+    pub fn validate(&self) -> Result<(), Report<ConfigError>> {
+        if self.token_secret.trim().is_empty() {
+            return Err(Report::new(ConfigError::InvalidValue)
+                .attach("session.token_secret is empty, set it to a long random value"));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_refuses_empty_token_secret() {
+        let mut session_config = SessionConfig::default();
+        assert!(session_config.validate().is_err());
+
+        session_config.token_secret = "  ".to_string();
+        assert!(session_config.validate().is_err());
+
+        session_config.token_secret = "a-long-random-value".to_string();
+        assert!(session_config.validate().is_ok());
+    }
+}
@@ -0,0 +1,2 @@
+insert into schema_migration (version, name)
+values (:version, :name);
\ No newline at end of file
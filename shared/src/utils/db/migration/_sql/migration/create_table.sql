@@ -0,0 +1,6 @@
+create table if not exists schema_migration
+(
+    version    integer primary key not null,
+    name       text                not null,
+    applied_at text                not null default CURRENT_TIMESTAMP
+);
\ No newline at end of file
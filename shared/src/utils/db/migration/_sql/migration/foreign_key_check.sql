@@ -0,0 +1,2 @@
+select count(*) as violation_count
+from pragma_foreign_key_check;
\ No newline at end of file
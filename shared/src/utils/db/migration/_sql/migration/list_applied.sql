@@ -0,0 +1,3 @@
+select version, name, applied_at
+from schema_migration
+order by version;
\ No newline at end of file
@@ -0,0 +1,4 @@
+select count(*) as table_count
+from sqlite_master
+where type = 'table'
+  and name = :name;
\ No newline at end of file
@@ -0,0 +1,31 @@
+create table cms_page_next
+(
+    id        integer primary key autoincrement not null,
+    user_id   integer                           not null,
+    parent_id integer,
+    slug      text                              not null,
+    path      text unique                       not null,
+    title     text                              not null,
+    summary   text                              not null,
+    added     text                              not null default CURRENT_TIMESTAMP,
+    updated   text,
+    status    text                              not null,
+    foreign key (user_id) references backoffice_users (id) on delete cascade,
+    foreign key (parent_id) references cms_page (id) on delete set null
+);
+
+insert into cms_page_next (id, user_id, parent_id, slug, path, title, summary, added, updated, status)
+select id, user_id, null, 'page-' || id, 'page-' || id, title, summary, added, updated, status
+from cms_page;
+
+drop table cms_page;
+
+alter table cms_page_next
+    rename to cms_page;
+
+create table cms_page_redirect
+(
+    path    text primary key not null,
+    page_id integer          not null,
+    foreign key (page_id) references cms_page (id) on delete cascade
+);
\ No newline at end of file
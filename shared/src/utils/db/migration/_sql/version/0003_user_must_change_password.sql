@@ -0,0 +1,7 @@
+alter table backoffice_users
+    add column must_change_password integer not null default 0;
+
+-- accounts left over from the admin/banana bootstrap must pick their own password.
+update backoffice_users
+set must_change_password = 1
+where username = 'admin';
\ No newline at end of file
@@ -0,0 +1,52 @@
+create table cms_page_revision
+(
+    id      integer primary key autoincrement not null,
+    page_id integer                           not null,
+    user_id integer                           not null,
+    title   text                              not null,
+    summary text                              not null,
+    added   text                              not null default CURRENT_TIMESTAMP,
+    foreign key (page_id) references cms_page (id) on delete cascade,
+    foreign key (user_id) references backoffice_users (id) on delete cascade
+);
+
+create index cms_page_revision_page_id on cms_page_revision (page_id);
+
+-- component_id is not a foreign key, the revision outlives a deleted component.
+create table cms_component_revision
+(
+    id           integer primary key autoincrement not null,
+    revision_id  integer                           not null,
+    component_id integer                           not null,
+    kind_uuid    text                              not null,
+    position     integer                           not null,
+    label        text                              not null,
+    raw_data     blob                              not null,
+    foreign key (revision_id) references cms_page_revision (id) on delete cascade
+);
+
+create index cms_component_revision_revision_id on cms_component_revision (revision_id);
+
+create trigger cms_page_revision_immutable
+    before update
+    on cms_page_revision
+begin
+    select raise(abort, 'cms_page_revision is immutable');
+end;
+
+create trigger cms_component_revision_immutable
+    before update
+    on cms_component_revision
+begin
+    select raise(abort, 'cms_component_revision is immutable');
+end;
+
+-- existing pages start their history from what they hold now.
+insert into cms_page_revision (page_id, user_id, title, summary, added)
+select id, user_id, title, summary, coalesce(updated, added)
+from cms_page;
+
+insert into cms_component_revision (revision_id, component_id, kind_uuid, position, label, raw_data)
+select cpr.id, cc.id, cc.kind_uuid, cc.position, cc.label, cc.raw_data
+from cms_component as cc
+         inner join cms_page_revision cpr on cpr.page_id = cc.page_id;
\ No newline at end of file
@@ -0,0 +1,17 @@
+alter table cms_page
+    add column publish_at text;
+
+alter table cms_page
+    add column unpublish_at text;
+
+create table cms_page_status_transition
+(
+    id          integer primary key autoincrement not null,
+    page_id     integer                           not null,
+    from_status text                              not null,
+    to_status   text                              not null,
+    added       text                              not null default CURRENT_TIMESTAMP,
+    foreign key (page_id) references cms_page (id) on delete cascade
+);
+
+create index cms_page_status_transition_page_id on cms_page_status_transition (page_id);
\ No newline at end of file
@@ -0,0 +1,13 @@
+create table cms_page_review
+(
+    id          integer primary key autoincrement not null,
+    page_id     integer                           not null,
+    reviewer_id integer                           not null,
+    status      text                              not null,
+    comment     text                              not null,
+    added       text                              not null default CURRENT_TIMESTAMP,
+    foreign key (page_id) references cms_page (id) on delete cascade,
+    foreign key (reviewer_id) references backoffice_users (id) on delete cascade
+);
+
+create index cms_page_review_page_id on cms_page_review (page_id);
\ No newline at end of file
@@ -0,0 +1,5 @@
+alter table cms_file_attachment
+    add column file_hash text;
+
+create index cms_file_attachment_file_hash on cms_file_attachment (file_hash);
+create index cms_file_attachment_file_path on cms_file_attachment (file_path);
\ No newline at end of file
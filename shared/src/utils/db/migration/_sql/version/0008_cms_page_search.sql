@@ -0,0 +1,34 @@
+-- title and summary follow cms_page through the triggers, the body holds the text of the
+-- page's markdown components and is written by the application, null until first indexed.
+create virtual table cms_page_search using fts5
+(
+    title,
+    summary,
+    body,
+    tokenize = 'porter unicode61 remove_diacritics 2'
+);
+
+insert into cms_page_search (rowid, title, summary, body)
+select id, title, summary, null
+from cms_page;
+
+create trigger cms_page_search_insert
+    after insert
+    on cms_page
+begin
+    insert into cms_page_search (rowid, title, summary, body) values (new.id, new.title, new.summary, '');
+end;
+
+create trigger cms_page_search_update
+    after update of title, summary
+    on cms_page
+begin
+    update cms_page_search set title = new.title, summary = new.summary where rowid = new.id;
+end;
+
+create trigger cms_page_search_delete
+    after delete
+    on cms_page
+begin
+    delete from cms_page_search where rowid = old.id;
+end;
\ No newline at end of file
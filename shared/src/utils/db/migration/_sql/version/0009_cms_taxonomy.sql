@@ -0,0 +1,18 @@
+-- a term is a category or a tag, its slug is unique within its kind and names its public route.
+create table cms_term
+(
+    id   integer primary key autoincrement,
+    kind text not null,
+    slug text not null,
+    name text not null,
+    unique (kind, slug)
+);
+
+create table cms_page_term
+(
+    page_id integer not null references cms_page (id) on delete cascade,
+    term_id integer not null references cms_term (id) on delete cascade,
+    primary key (page_id, term_id)
+);
+
+create index cms_page_term_term_id on cms_page_term (term_id);
\ No newline at end of file
@@ -0,0 +1,24 @@
+-- a secret with totp_enabled = 0 is an enrolment still waiting for its first code.
+alter table backoffice_users
+    add column totp_secret blob;
+alter table backoffice_users
+    add column totp_enabled integer not null default 0;
+alter table backoffice_users
+    add column totp_last_step integer not null default 0;
+
+create table user_recovery_codes
+(
+    user_id   integer not null references backoffice_users (id) on delete cascade,
+    code_hash text    not null,
+    used_at   text
+);
+
+create index user_recovery_codes_user_id on user_recovery_codes (user_id);
+
+-- the password step of a two-factor login, exchanged for a login token once the code checks out.
+create table user_login_challenges
+(
+    user_id      integer     not null references backoffice_users (id) on delete cascade,
+    token        text unique not null,
+    expire_after text        not null
+);
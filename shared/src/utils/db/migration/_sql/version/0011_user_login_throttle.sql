@@ -0,0 +1,23 @@
+-- one counter per username and per remote ip, kind is 'username' or 'ip'.
+create table user_login_throttle
+(
+    kind            text    not null,
+    subject         text    not null,
+    failures        integer not null default 0,
+    last_failure_at text    not null,
+    locked_until    text,
+    primary key (kind, subject)
+);
+
+-- every login attempt, kept for auditing.
+create table user_login_attempts
+(
+    id           integer primary key autoincrement not null,
+    username     text    not null,
+    ip           text    not null,
+    success      integer not null,
+    attempted_at text    not null default (datetime('now'))
+);
+
+create index user_login_attempts_username on user_login_attempts (username, attempted_at);
+create index user_login_attempts_ip on user_login_attempts (ip, attempted_at);
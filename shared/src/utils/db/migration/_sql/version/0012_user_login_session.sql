@@ -0,0 +1,25 @@
+-- each token is one signed in device, the id lets a user revoke it without knowing the token.
+create table user_login_tokens_next
+(
+    id           integer primary key autoincrement not null,
+    user_id      integer                           not null,
+    token        text unique                       not null,
+    expire_after text                              not null,
+    created_at   text                              not null default CURRENT_TIMESTAMP,
+    last_seen_at text                              not null default CURRENT_TIMESTAMP,
+    ip           text                              not null default '',
+    user_agent   text                              not null default '',
+    foreign key (user_id) references backoffice_users (id) on delete cascade
+);
+
+insert into user_login_tokens_next (user_id, token, expire_after)
+select user_id, token, expire_after
+from user_login_tokens;
+
+drop table user_login_tokens;
+
+alter table user_login_tokens_next
+    rename to user_login_tokens;
+
+create index user_login_tokens_user_id on user_login_tokens (user_id);
+create index user_login_tokens_expire_after on user_login_tokens (expire_after);
@@ -0,0 +1,4 @@
+-- tokens are stored as a keyed hash, the server secret lives in the config so older rows
+-- are hashed on boot and flagged here once done.
+alter table user_login_tokens
+    add column token_hashed integer not null default 0;
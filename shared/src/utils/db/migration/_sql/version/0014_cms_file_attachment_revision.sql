@@ -0,0 +1,33 @@
+-- attachments are kept with every revision, so restoring one links its files again and
+-- the blobs stay on disk for as long as any revision points at them.
+create table cms_file_attachment_revision
+(
+    id            integer primary key autoincrement not null,
+    revision_id   integer                           not null,
+    attachment_id integer                           not null,
+    component_id  integer                           not null,
+    file_name     text                              not null,
+    file_path     text                              not null,
+    file_type     text                              not null,
+    file_hash     text,
+    uploaded      text                              not null,
+    foreign key (revision_id) references cms_page_revision (id) on delete cascade
+);
+
+create index cms_file_attachment_revision_revision_id on cms_file_attachment_revision (revision_id);
+create index cms_file_attachment_revision_file_path on cms_file_attachment_revision (file_path);
+
+create trigger cms_file_attachment_revision_immutable
+    before update
+    on cms_file_attachment_revision
+begin
+    select raise(abort, 'cms_file_attachment_revision is immutable');
+end;
+
+-- older revisions only know the attachments their components hold now.
+insert into cms_file_attachment_revision (revision_id, attachment_id, component_id, file_name, file_path, file_type,
+                                          file_hash, uploaded)
+select ccr.revision_id, cfa.id, cfa.component_id, cfa.file_name, cfa.file_path, cfa.file_type, cfa.file_hash,
+       cfa.uploaded
+from cms_file_attachment as cfa
+         inner join cms_component_revision ccr on ccr.component_id = cfa.component_id;
\ No newline at end of file
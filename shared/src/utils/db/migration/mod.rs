@@ -0,0 +1,355 @@
+use crate::utils::config::Config;
+use crate::utils::error::boot_error::MainError;
+use error_stack::{Report, ResultExt};
+use rusqlite::{Connection, OpenFlags, named_params};
+use std::collections::BTreeMap;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum MigrationError {
+    #[error("Query error")]
+    QueryError,
+    #[error("Row Value error")]
+    RowValueError,
+    #[error("Foreign key check failed")]
+    ForeignKeyError,
+}
+
+pub struct Migration {
+    pub version: i64,
+    pub name: &'static str,
+    sql: &'static str,
+}
+
+/// Ordered list of every schema change, append only; never edit a migration once released.
+pub const MIGRATIONS: &[Migration] = &[
+    Migration {
+        version: 1,
+        name: "baseline",
+        sql: include_str!("_sql/version/0001_baseline.sql"),
+    },
+    Migration {
+        version: 2,
+        name: "cms_page_path",
+        sql: include_str!("_sql/version/0002_cms_page_path.sql"),
+    },
+    Migration {
+        version: 3,
+        name: "user_must_change_password",
+        sql: include_str!("_sql/version/0003_user_must_change_password.sql"),
+    },
+    Migration {
+        version: 4,
+        name: "cms_revision",
+        sql: include_str!("_sql/version/0004_cms_revision.sql"),
+    },
+    Migration {
+        version: 5,
+        name: "cms_page_schedule",
+        sql: include_str!("_sql/version/0005_cms_page_schedule.sql"),
+    },
+    Migration {
+        version: 6,
+        name: "cms_page_review",
+        sql: include_str!("_sql/version/0006_cms_page_review.sql"),
+    },
+    Migration {
+        version: 7,
+        name: "cms_file_attachment_hash",
+        sql: include_str!("_sql/version/0007_cms_file_attachment_hash.sql"),
+    },
+    Migration {
+        version: 8,
+        name: "cms_page_search",
+        sql: include_str!("_sql/version/0008_cms_page_search.sql"),
+    },
+    Migration {
+        version: 9,
+        name: "cms_taxonomy",
+        sql: include_str!("_sql/version/0009_cms_taxonomy.sql"),
+    },
+    Migration {
+        version: 10,
+        name: "user_two_factor",
+        sql: include_str!("_sql/version/0010_user_two_factor.sql"),
+    },
+    Migration {
+        version: 11,
+        name: "user_login_throttle",
+        sql: include_str!("_sql/version/0011_user_login_throttle.sql"),
+    },
+    Migration {
+        version: 12,
+        name: "user_login_session",
+        sql: include_str!("_sql/version/0012_user_login_session.sql"),
+    },
+    Migration {
+        version: 13,
+        name: "user_login_token_hash",
+        sql: include_str!("_sql/version/0013_user_login_token_hash.sql"),
+    },
+    Migration {
+        version: 14,
+        name: "cms_file_attachment_revision",
+        sql: include_str!("_sql/version/0014_cms_file_attachment_revision.sql"),
+    },
+];
+
+#[derive(Debug, Clone)]
+pub struct MigrationStatus {
+    pub version: i64,
+    pub name: &'static str,
+    pub applied_at: Option<String>,
+}
+
+impl MigrationStatus {
+    pub fn is_pending(&self) -> bool {
+        self.applied_at.is_none()
+    }
+}
+
+fn table_exists(conn: &Connection, name: &str) -> Result<bool, Report<MigrationError>> {
+    let table_count: i64 = conn
+        .query_row(
+            include_str!("_sql/migration/table_exists.sql"),
+            named_params! {
+                ":name": name,
+            },
+            |row| row.get("table_count"),
+        )
+        .change_context(MigrationError::QueryError)?;
+
+    Ok(table_count > 0)
+}
+
+/// Databases created before migrations existed have the baseline schema but no
+/// `schema_migration` table, those are treated as already on the baseline.
+fn applied_versions(conn: &Connection) -> Result<BTreeMap<i64, String>, Report<MigrationError>> {
+    if !table_exists(conn, "schema_migration")? {
+        let mut applied = BTreeMap::new();
+        if table_exists(conn, "backoffice_users")? {
+            applied.insert(1, "before migrations".to_string());
+        }
+        return Ok(applied);
+    }
+
+    let mut stmt = conn
+        .prepare(include_str!("_sql/migration/list_applied.sql"))
+        .change_context(MigrationError::QueryError)?;
+
+    let rows = stmt
+        .query_map(named_params! {}, |row| {
+            Ok((row.get::<_, i64>("version")?, row.get("applied_at")?))
+        })
+        .change_context(MigrationError::RowValueError)?;
+
+    rows.collect::<Result<BTreeMap<_, _>, _>>()
+        .change_context(MigrationError::RowValueError)
+}
+
+pub fn migration_status(conn: &Connection) -> Result<Vec<MigrationStatus>, Report<MigrationError>> {
+    let applied = applied_versions(conn)?;
+    Ok(MIGRATIONS
+        .iter()
+        .map(|migration| MigrationStatus {
+            version: migration.version,
+            name: migration.name,
+            applied_at: applied.get(&migration.version).cloned(),
+        })
+        .collect())
+}
+
+fn apply_pending(
+    conn: &mut Connection,
+    adopt: &[i64],
+    pending: &[&Migration],
+) -> Result<(), Report<MigrationError>> {
+    let tx = conn
+        .transaction()
+        .change_context(MigrationError::QueryError)?;
+
+    tx.execute_batch(include_str!("_sql/migration/create_table.sql"))
+        .change_context(MigrationError::QueryError)?;
+
+    for migration in MIGRATIONS.iter() {
+        let is_pending = pending
+            .iter()
+            .any(|pending| pending.version == migration.version);
+        // adopted versions are already in the schema, only the record is missing.
+        if !is_pending && !adopt.contains(&migration.version) {
+            continue;
+        }
+        if is_pending {
+            tx.execute_batch(migration.sql)
+                .change_context(MigrationError::QueryError)
+                .attach_with(|| format!("Migration {:04} {}", migration.version, migration.name))?;
+        }
+        tx.execute(
+            include_str!("_sql/migration/add_applied.sql"),
+            named_params! {
+                ":version": migration.version,
+                ":name": migration.name,
+            },
+        )
+        .change_context(MigrationError::QueryError)?;
+    }
+
+    let violation_count: i64 = tx
+        .query_row(
+            include_str!("_sql/migration/foreign_key_check.sql"),
+            named_params! {},
+            |row| row.get("violation_count"),
+        )
+        .change_context(MigrationError::QueryError)?;
+    if violation_count > 0 {
+        return Err(Report::new(MigrationError::ForeignKeyError)
+            .attach(format!("{} foreign key violation(s)", violation_count)));
+    }
+
+    tx.commit().change_context(MigrationError::QueryError)
+}
+
+/// Applies every pending migration in a single transaction, returns the versions applied.
+///
+/// Foreign keys are switched off while migrating so tables can be rebuilt without
+/// cascading deletes, then checked before commit.
+pub fn migrate(conn: &mut Connection) -> Result<Vec<i64>, Report<MigrationError>> {
+    let tracked = table_exists(conn, "schema_migration")?;
+    let applied = applied_versions(conn)?;
+    let adopt: Vec<i64> = match tracked {
+        true => Vec::new(),
+        false => applied.keys().copied().collect(),
+    };
+    let pending: Vec<&Migration> = MIGRATIONS
+        .iter()
+        .filter(|migration| !applied.contains_key(&migration.version))
+        .collect();
+
+    if tracked && pending.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    conn.pragma_update(None, "foreign_keys", false)
+        .change_context(MigrationError::QueryError)?;
+    let result = apply_pending(conn, &adopt, &pending);
+    conn.pragma_update(None, "foreign_keys", true)
+        .change_context(MigrationError::QueryError)?;
+    result?;
+
+    Ok(pending.iter().map(|migration| migration.version).collect())
+}
+
+/// Dry run for deployments, lists applied and pending migrations without touching the database.
+pub async fn print_migration_status() -> Result<(), Report<MainError>> {
+    let config = Config::fetch()
+        .await
+        .change_context(MainError::ConfigError)?
+        .upgrade()
+        .ok_or_else(|| Report::new(MainError::ConfigError))?;
+    let sqlite_path = config.sqlite.path.as_str();
+
+    let status_list = match std::fs::metadata(sqlite_path).is_ok() {
+        true => {
+            let conn = Connection::open_with_flags(sqlite_path, OpenFlags::SQLITE_OPEN_READ_ONLY)
+                .change_context(MainError::DbError)?;
+            migration_status(&conn).change_context(MainError::DbError)?
+        }
+        false => {
+            migration_status(&Connection::open_in_memory().change_context(MainError::DbError)?)
+                .change_context(MainError::DbError)?
+        }
+    };
+
+    println!("Database: {}", sqlite_path);
+    for status in status_list.iter() {
+        println!(
+            "{:04} {:<24} {}",
+            status.version,
+            status.name,
+            status.applied_at.as_deref().unwrap_or("pending")
+        );
+    }
+    println!(
+        "{} pending migration(s)",
+        status_list
+            .iter()
+            .filter(|status| status.is_pending())
+            .count()
+    );
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn count(conn: &Connection, sql: &str) -> i64 {
+        conn.query_row(sql, [], |row| row.get(0)).unwrap()
+    }
+
+    #[test]
+    fn test_migrate_fresh_database() {
+        let mut conn = Connection::open_in_memory().unwrap();
+
+        let applied = migrate(&mut conn).unwrap();
+
+        assert_eq!(applied, vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14]);
+        assert!(table_exists(&conn, "cms_page_redirect").unwrap());
+        assert!(table_exists(&conn, "cms_page_term").unwrap());
+        assert!(
+            migration_status(&conn)
+                .unwrap()
+                .iter()
+                .all(|status| !status.is_pending())
+        );
+    }
+
+    #[test]
+    fn test_migrate_is_idempotent() {
+        let mut conn = Connection::open_in_memory().unwrap();
+
+        migrate(&mut conn).unwrap();
+        let applied = migrate(&mut conn).unwrap();
+
+        assert!(applied.is_empty());
+        assert_eq!(count(&conn, "select count(*) from schema_migration"), 14);
+    }
+
+    #[test]
+    fn test_migrate_adopts_database_without_migration_table() {
+        let mut conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(MIGRATIONS[0].sql).unwrap();
+        conn.execute_batch(
+            "insert into backoffice_users (id, username, password, role) values (1, 'a', x'00', 'root');
+             insert into cms_page (id, user_id, title, summary, status) values (7, 1, 't', 's', 'draft');
+             insert into cms_component (page_id, kind_uuid, position, label, raw_data) values (7, 'k', 0, 'l', x'00');",
+        )
+        .unwrap();
+
+        let status = migration_status(&conn).unwrap();
+        assert!(!status[0].is_pending());
+        assert!(status[1].is_pending());
+
+        let applied = migrate(&mut conn).unwrap();
+
+        assert_eq!(applied, vec![2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14]);
+        assert_eq!(
+            count(&conn, "select count(*) from cms_page where path = 'page-7'"),
+            1
+        );
+        assert_eq!(count(&conn, "select count(*) from cms_component"), 1);
+        assert_eq!(
+            count(&conn, "select count(*) from cms_component_revision"),
+            1
+        );
+        assert_eq!(
+            count(
+                &conn,
+                "select count(*) from cms_page_search where rowid = 7 and body is null"
+            ),
+            1
+        );
+        assert_eq!(count(&conn, "select count(*) from schema_migration"), 14);
+    }
+}
@@ -1,13 +1,18 @@
-use crate::utils::config::ConfigPointer;
+pub mod migration;
+
+use crate::utils::config::sqlite::SqliteConfig;
+use crate::utils::config::{Config, ConfigPointer};
 use crate::utils::context::{Context, ContextError, FromContext};
+use crate::utils::error::boot_error::MainError;
 use crate::utils::error::{ExtraResultExt, FromIntoStackError, LogItExt};
-use crate::utils::password::Password;
 use error_stack::{Report, ResultExt};
 use poem::http::StatusCode;
-use rusqlite::{Connection, named_params};
+use r2d2::{Pool, PooledConnection};
+use r2d2_sqlite::SqliteConnectionManager;
 use std::marker::PhantomData;
-use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
 use thiserror::Error;
+use tokio::runtime::{Handle, RuntimeFlavor};
 use tokio::sync::OnceCell;
 
 pub trait ConnectionMarker: Send + Sync {}
@@ -22,90 +27,122 @@ pub enum SqliteClientError {
     SqliteFileEmpty,
     #[error("Connection error")]
     Connection,
-    #[error("Init failed")]
-    InitFailed,
+    #[error("Migration failed")]
+    MigrationFailed,
     #[error("Connection Option Empty error")]
     OptionEmpty,
-    #[error("Lock error: {0}")]
-    LockError(String),
+    #[error("Pool error: {0}")]
+    PoolError(String),
 }
 
 impl FromIntoStackError for SqliteClientError {}
 
-pub struct SqliteClient<T = DefaultConnection>(Arc<Mutex<Connection>>, PhantomData<T>)
+pub type SqliteConn = PooledConnection<SqliteConnectionManager>;
+
+/// Pool of connections in WAL mode, readers run side by side while sqlite lets one
+/// writer through at a time, waiting up to the busy timeout for the lock.
+pub struct SqliteClient<T = DefaultConnection>(Pool<SqliteConnectionManager>, PhantomData<T>)
 where
     T: ConnectionMarker;
 
 impl<T: ConnectionMarker> SqliteClient<T> {
-    pub fn new(sqlite_path: String) -> Result<Self, Report<SqliteClientError>> {
+    pub fn new(sqlite_config: &SqliteConfig) -> Result<Self, Report<SqliteClientError>> {
+        let sqlite_path = sqlite_config.path.clone();
         if sqlite_path.is_empty() {
             return Err(SqliteClientError::SqliteFileEmpty
                 .into_stack_error_critical("Sqlite file path is empty".to_string()));
         }
-        let file_exist = std::fs::metadata(&sqlite_path).is_ok();
 
-        let conn = Connection::open(sqlite_path)
+        let busy_timeout = Duration::from_millis(sqlite_config.busy_timeout_ms);
+        let manager = SqliteConnectionManager::file(sqlite_path).with_init(move |conn| {
+            conn.pragma_update_and_check(None, "journal_mode", "WAL", |row| {
+                row.get::<_, String>(0)
+            })?;
+            conn.pragma_update(None, "synchronous", "NORMAL")?;
+            conn.pragma_update(None, "foreign_keys", true)?;
+            conn.busy_timeout(busy_timeout)
+        });
+        let pool = Pool::builder()
+            .max_size(sqlite_config.pool_size)
+            .build(manager)
             .change_context(SqliteClientError::Connection)
             .attach_critical("Sqlite Connection failed".to_string())?;
-        if !file_exist {
-            conn.execute_batch(include_str!("_sql/init.sql"))
-                .change_context(SqliteClientError::InitFailed)
-                .attach_critical("Init failed".to_string())?;
-
-            let password = Password::hash_password("banana".to_string())
-                .change_context(SqliteClientError::InitFailed)
-                .attach_critical("Failed to hash password".to_string())?
-                .encode_to_msg_pack()
-                .change_context(SqliteClientError::InitFailed)
-                .attach_critical("Failed to encode password".to_string())?;
-
-            conn.execute(
-                include_str!("_sql/add_user.sql",),
-                named_params! {
-                    ":username": "admin",
-                    ":password": password.to_vec(),
-                },
-            )
-            .change_context(SqliteClientError::InitFailed)
-            .attach_critical("Failed to create default user".to_string())?;
-        }
 
-        Ok(SqliteClient(Arc::new(Mutex::new(conn)), PhantomData))
-    }
+        let mut conn = pool
+            .get()
+            .change_context(SqliteClientError::Connection)
+            .attach_critical("Sqlite Connection failed".to_string())?;
+
+        migration::migrate(&mut conn)
+            .change_context(SqliteClientError::MigrationFailed)
+            .attach_critical("Migration failed".to_string())?;
 
-    pub fn get_conn(&self) -> &Mutex<Connection> {
-        self.0.as_ref()
+        Ok(SqliteClient(pool, PhantomData))
     }
 }
 
 impl<T: ConnectionMarker> Clone for SqliteClient<T> {
     fn clone(&self) -> Self {
-        Self(Arc::clone(&self.0), PhantomData)
+        Self(self.0.clone(), PhantomData)
     }
 }
 
 static SQLITE_CLIENT_CACHE: OnceCell<SqliteClient> = OnceCell::const_new();
 
+impl SqliteClient {
+    pub async fn fetch(sqlite_config: &SqliteConfig) -> Result<Self, Report<SqliteClientError>> {
+        let sqlite_client = SQLITE_CLIENT_CACHE
+            .get_or_try_init(|| async { Self::new(sqlite_config) })
+            .await?;
+        Ok(sqlite_client.clone())
+    }
+}
+
+/// Checks the password costs, then opens the database and runs pending migrations before any
+/// server starts listening.
+pub async fn init_database() -> Result<(), Report<MainError>> {
+    let config = Config::fetch()
+        .await
+        .change_context(MainError::ConfigError)?
+        .upgrade()
+        .ok_or_else(|| Report::new(MainError::ConfigError))?;
+    config
+        .password
+        .validate()
+        .change_context(MainError::ConfigError)?;
+    SqliteClient::fetch(&config.sqlite)
+        .await
+        .change_context(MainError::DbError)?;
+    Ok(())
+}
+
 impl FromContext for SqliteClient {
     async fn from_context(ctx: &'_ Context<'_>) -> Result<Self, Report<ContextError>> {
-        let sqlite_client: Result<&Self, Report<ContextError>> = SQLITE_CLIENT_CACHE
-            .get_or_try_init(|| async {
-                let config: ConfigPointer = ctx.inject().await?;
-                Ok(Self::new(config.sqlite.path.clone()).change_context(ContextError::Other)?)
-            })
-            .await;
-        Ok(sqlite_client?.clone())
+        let config: ConfigPointer = ctx.inject().await?;
+        Self::fetch(&config.sqlite)
+            .await
+            .change_context(ContextError::Other)
+    }
+}
+
+/// Runs synchronous work, a sqlite query or a password hash, without stalling the async worker
+/// it is called from: on the multi thread runtime the worker hands its other tasks to another
+/// thread for the duration. The current thread runtime has nowhere to hand them, there it just runs.
+pub fn run_blocking<R>(f: impl FnOnce() -> R) -> R {
+    match Handle::try_current().map(|handle| handle.runtime_flavor()) {
+        Ok(RuntimeFlavor::MultiThread) => tokio::task::block_in_place(f),
+        _ => f(),
     }
 }
 
 pub trait BorrowConnectionExt {
-    fn borrow_conn(&'_ self) -> Result<MutexGuard<'_, Connection>, Report<SqliteClientError>>;
+    fn borrow_conn(&self) -> Result<SqliteConn, Report<SqliteClientError>>;
 }
 
 impl<T: ConnectionMarker> BorrowConnectionExt for SqliteClient<T> {
-    fn borrow_conn(&'_ self) -> Result<MutexGuard<'_, Connection>, Report<SqliteClientError>> {
-        self.0.lock().map_err(|err| {
-            Report::new(SqliteClientError::LockError(err.to_string()))
+    fn borrow_conn(&self) -> Result<SqliteConn, Report<SqliteClientError>> {
+        self.0.get().map_err(|err| {
+            Report::new(SqliteClientError::PoolError(err.to_string()))
                 .attach(StatusCode::INTERNAL_SERVER_ERROR)
                 .log_it()
         })
@@ -113,7 +150,7 @@ impl<T: ConnectionMarker> BorrowConnectionExt for SqliteClient<T> {
 }
 
 impl<T: ConnectionMarker> BorrowConnectionExt for Option<SqliteClient<T>> {
-    fn borrow_conn(&'_ self) -> Result<MutexGuard<'_, Connection>, Report<SqliteClientError>> {
+    fn borrow_conn(&self) -> Result<SqliteConn, Report<SqliteClientError>> {
         self.as_ref()
             .ok_or_else(|| {
                 Report::new(SqliteClientError::OptionEmpty)
@@ -123,3 +160,38 @@ impl<T: ConnectionMarker> BorrowConnectionExt for Option<SqliteClient<T>> {
             .borrow_conn()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Instant;
+
+    fn sleep_blocking() -> Instant {
+        std::thread::sleep(Duration::from_millis(300));
+        Instant::now()
+    }
+
+    #[test]
+    fn test_run_blocking_outside_runtime() {
+        assert_eq!(run_blocking(|| 1), 1);
+    }
+
+    #[tokio::test]
+    async fn test_run_blocking_on_current_thread_runtime() {
+        assert_eq!(run_blocking(|| run_blocking(|| 1)), 1);
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+    async fn test_run_blocking_keeps_multi_thread_worker_free() {
+        let blocking = tokio::spawn(async { run_blocking(sleep_blocking) });
+        let other = tokio::spawn(async {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            Instant::now()
+        });
+
+        let other_done = other.await.unwrap();
+        let blocking_done = blocking.await.unwrap();
+        assert!(other_done < blocking_done);
+        assert_eq!(run_blocking(|| run_blocking(|| 1)), 1);
+    }
+}
@@ -4,6 +4,8 @@ use thiserror::Error;
 pub enum MainError {
     #[error("Config error")]
     ConfigError,
+    #[error("Db error")]
+    DbError,
     #[error("IO error")]
     IoError,
     #[error("Locale error")]
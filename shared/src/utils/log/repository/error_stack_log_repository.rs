@@ -1,8 +1,7 @@
 use crate::utils::context::{Context, ContextError, FromContext};
-use crate::utils::db::{BorrowConnectionExt, SqliteClient};
+use crate::utils::db::{BorrowConnectionExt, SqliteClient, SqliteConn, run_blocking};
 use error_stack::{Report, ResultExt};
-use rusqlite::{Connection, named_params};
-use std::sync::MutexGuard;
+use rusqlite::named_params;
 use thiserror::Error;
 
 #[derive(Debug, Error)]
@@ -26,9 +25,7 @@ impl ErrorStackLogRepository {
         }
     }
 
-    fn borrow_conn(
-        &'_ self,
-    ) -> Result<MutexGuard<'_, Connection>, Report<ErrorStackLogRepositoryError>> {
+    fn borrow_conn(&'_ self) -> Result<SqliteConn, Report<ErrorStackLogRepositoryError>> {
         self.sqlite_client
             .borrow_conn()
             .change_context(ErrorStackLogRepositoryError::BorrowConnError)
@@ -43,19 +40,21 @@ impl ErrorStackLogRepository {
         error_summary: &str,
         error_stack: &str,
     ) -> Result<(), Report<ErrorStackLogRepositoryError>> {
-        let conn = self.borrow_conn()?;
+        run_blocking(|| {
+            let conn = self.borrow_conn()?;
 
-        conn.execute(
-            include_str!("_sql/error_stack_log_repository/add_to_log.sql"),
-            named_params! {
-                ":error_name": error_name,
-                ":error_summary": error_summary,
-                ":error_stack": error_stack,
-            },
-        )
-        .change_context(ErrorStackLogRepositoryError::QueryError)?;
+            conn.execute(
+                include_str!("_sql/error_stack_log_repository/add_to_log.sql"),
+                named_params! {
+                    ":error_name": error_name,
+                    ":error_summary": error_summary,
+                    ":error_stack": error_stack,
+                },
+            )
+            .change_context(ErrorStackLogRepositoryError::QueryError)?;
 
-        Ok(())
+            Ok(())
+        })
     }
 }
 
@@ -15,3 +15,5 @@ pub mod password;
 pub mod query_string;
 pub mod request_cache;
 pub mod route_header;
+pub mod token;
+pub mod totp;
@@ -1,8 +1,13 @@
+use crate::utils::config::Config;
+use crate::utils::config::password::PasswordConfig;
+use crate::utils::error::boot_error::MainError;
 use argon2::password_hash::SaltString;
 use argon2::password_hash::rand_core::OsRng;
-use argon2::{Argon2, PasswordHash, PasswordHasher, PasswordVerifier};
-use error_stack::Report;
+use argon2::{Algorithm, Argon2, Params, PasswordHash, PasswordHasher, PasswordVerifier, Version};
+use error_stack::{Report, ResultExt};
+use log::warn;
 use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -44,10 +49,13 @@ pub enum Password {
 }
 
 impl Password {
-    pub fn hash_password(password: String) -> Result<Self, Report<PasswordError>> {
+    pub fn hash_password(
+        password: String,
+        config: &PasswordConfig,
+    ) -> Result<Self, Report<PasswordError>> {
         let salt = SaltString::generate(&mut OsRng);
 
-        let argon2 = Argon2::default();
+        let argon2 = Argon2::new(Algorithm::Argon2id, Version::V0x13, params(config)?);
 
         let password_hash = argon2
             .hash_password(password.as_bytes(), &salt)
@@ -59,9 +67,13 @@ impl Password {
         })
     }
 
+    /// A match against a hash made with other costs than `config`, or an older version,
+    /// comes back as `ValidRehashed` carrying a fresh hash for the caller to store. A failed
+    /// rehash never turns a correct password away, it stays `Valid` on the old hash.
     pub fn verify_password(
         password_hash: Box<[u8]>,
         password: String,
+        config: &PasswordConfig,
     ) -> Result<PasswordState, Report<PasswordError>> {
         let password_data = rmp_serde::from_slice::<Password>(&password_hash)
             .map_err(|_| PasswordError("Failed to deserialize password hash".to_string()))?;
@@ -72,6 +84,15 @@ impl Password {
                     .map_err(|_| PasswordError("Failed to parse password hash".to_string()))?;
 
                 match Argon2::default().verify_password(password.as_bytes(), &parsed_hash) {
+                    Ok(_) if is_outdated(&parsed_hash, config) => {
+                        match Self::hash_password(password, config) {
+                            Ok(rehashed) => Ok(PasswordState::ValidRehashed(rehashed)),
+                            Err(report) => {
+                                warn!("Password rehash failed, keeping the old hash\n{:?}", report);
+                                Ok(PasswordState::Valid)
+                            }
+                        }
+                    }
                     Ok(_) => Ok(PasswordState::Valid),
                     Err(_) => Ok(PasswordState::Invalid),
                 }
@@ -90,3 +111,160 @@ impl Password {
             .into())
     }
 }
+
+fn params(config: &PasswordConfig) -> Result<Params, Report<PasswordError>> {
+    Params::new(
+        config.memory_kib,
+        config.time_cost,
+        config.parallelism,
+        None,
+    )
+    .map_err(|e| Report::new(PasswordError(format!("Invalid argon2 parameters: {}", e))))
+}
+
+fn is_outdated(parsed_hash: &PasswordHash, config: &PasswordConfig) -> bool {
+    if parsed_hash.algorithm != Algorithm::Argon2id.ident()
+        || parsed_hash.version != Some(Version::V0x13.into())
+    {
+        return true;
+    }
+    match Params::try_from(parsed_hash) {
+        Ok(params) => {
+            params.m_cost() != config.memory_kib
+                || params.t_cost() != config.time_cost
+                || params.p_cost() != config.parallelism
+        }
+        Err(_) => true,
+    }
+}
+
+const BENCHMARK_MAX_MEMORY_KIB: u32 = 1024 * 1024;
+const BENCHMARK_MAX_TIME_COST: u32 = 10;
+
+fn time_hash(config: &PasswordConfig) -> Result<Duration, Report<PasswordError>> {
+    let start = Instant::now();
+    Password::hash_password("benchmark".to_string(), config)?;
+    Ok(start.elapsed())
+}
+
+/// Raises memory first and then time cost, returning the costliest parameters that still hash within `target`.
+pub fn benchmark_params(target: Duration) -> Result<PasswordConfig, Report<PasswordError>> {
+    let mut best = PasswordConfig::default();
+    let mut candidate = best.clone();
+    while time_hash(&candidate)? <= target {
+        best = candidate.clone();
+        if candidate.memory_kib < BENCHMARK_MAX_MEMORY_KIB {
+            candidate.memory_kib = (candidate.memory_kib * 2).min(BENCHMARK_MAX_MEMORY_KIB);
+        } else if candidate.time_cost < BENCHMARK_MAX_TIME_COST {
+            candidate.time_cost += 1;
+        } else {
+            break;
+        }
+    }
+    Ok(best)
+}
+
+/// Times the configured parameters and prints a suggestion for `[default.password]`.
+pub async fn print_password_benchmark(target: Duration) -> Result<(), Report<MainError>> {
+    let config = Config::fetch()
+        .await
+        .change_context(MainError::ConfigError)?
+        .upgrade()
+        .ok_or_else(|| Report::new(MainError::ConfigError))?;
+
+    let current = time_hash(&config.password).change_context(MainError::ConfigError)?;
+    println!(
+        "Configured: memory_kib = {}, time_cost = {}, parallelism = {} ({} ms)",
+        config.password.memory_kib,
+        config.password.time_cost,
+        config.password.parallelism,
+        current.as_millis()
+    );
+
+    let suggested = benchmark_params(target).change_context(MainError::ConfigError)?;
+    let suggested_time = time_hash(&suggested).change_context(MainError::ConfigError)?;
+    println!(
+        "Suggested for {} ms ({} ms measured):",
+        target.as_millis(),
+        suggested_time.as_millis()
+    );
+    println!("[default.password]");
+    println!("memory_kib = {}", suggested.memory_kib);
+    println!("time_cost = {}", suggested.time_cost);
+    println!("parallelism = {}", suggested.parallelism);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(memory_kib: u32) -> PasswordConfig {
+        PasswordConfig {
+            memory_kib,
+            time_cost: 1,
+            parallelism: 1,
+        }
+    }
+
+    fn encoded(password: &str, config: &PasswordConfig) -> Box<[u8]> {
+        Password::hash_password(password.to_string(), config)
+            .unwrap()
+            .encode_to_msg_pack()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_verify_password_current_params() {
+        let state = Password::verify_password(
+            encoded("secret", &config(64)),
+            "secret".to_string(),
+            &config(64),
+        )
+        .unwrap();
+        assert!(matches!(state, PasswordState::Valid));
+    }
+
+    #[test]
+    fn test_verify_password_outdated_params_rehashes() {
+        let state = Password::verify_password(
+            encoded("secret", &config(64)),
+            "secret".to_string(),
+            &config(128),
+        )
+        .unwrap();
+        let PasswordState::ValidRehashed(rehashed) = state else {
+            panic!("expected a rehash");
+        };
+        let state = Password::verify_password(
+            rehashed.encode_to_msg_pack().unwrap(),
+            "secret".to_string(),
+            &config(128),
+        )
+        .unwrap();
+        assert!(matches!(state, PasswordState::Valid));
+    }
+
+    #[test]
+    fn test_verify_password_failed_rehash_stays_valid() {
+        let state = Password::verify_password(
+            encoded("secret", &config(64)),
+            "secret".to_string(),
+            &config(1),
+        )
+        .unwrap();
+        assert!(matches!(state, PasswordState::Valid));
+    }
+
+    #[test]
+    fn test_verify_password_wrong_password() {
+        let state = Password::verify_password(
+            encoded("secret", &config(64)),
+            "wrong".to_string(),
+            &config(128),
+        )
+        .unwrap();
+        assert!(state.is_invalid());
+    }
+}
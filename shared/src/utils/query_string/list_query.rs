@@ -0,0 +1,322 @@
+use serde::{Deserialize, Deserializer, Serialize};
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+use std::sync::Arc;
+
+/// Rows shown per page on a list screen.
+pub const PAGE_SIZE: usize = 25;
+
+/// A column a list screen can be sorted by, `as_str` is its value in the url and the sql.
+pub trait SortKey: Copy + PartialEq + Default {
+    fn as_str(&self) -> &'static str;
+}
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum SortOrder {
+    Asc,
+    #[default]
+    Desc,
+}
+
+impl SortOrder {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Asc => "asc",
+            Self::Desc => "desc",
+        }
+    }
+
+    pub fn reverse(&self) -> Self {
+        match self {
+            Self::Asc => Self::Desc,
+            Self::Desc => Self::Asc,
+        }
+    }
+}
+
+/// Where a page starts or ends, the sort column value and the id breaking ties.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cursor {
+    pub key: String,
+    pub id: i64,
+}
+
+impl Display for Cursor {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{}", self.id, self.key)
+    }
+}
+
+impl FromStr for Cursor {
+    type Err = std::num::ParseIntError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (id, key) = s.split_once('.').unwrap_or((s, ""));
+        Ok(Self {
+            key: key.to_string(),
+            id: id.parse()?,
+        })
+    }
+}
+
+impl<'de> Deserialize<'de> for Cursor {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+/// For filter fields, an empty value, as sent by an "any" option, is no filter at all.
+pub fn empty_as_none<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: FromStr,
+    T::Err: Display,
+{
+    match Option::<String>::deserialize(deserializer)? {
+        Some(value) if !value.is_empty() => {
+            value.parse().map(Some).map_err(serde::de::Error::custom)
+        }
+        _ => Ok(None),
+    }
+}
+
+/// The state of a list screen, kept in the url as `filter[..]`, `sort`, `order` and a cursor.
+#[derive(Deserialize, Debug, Clone, PartialEq, Default)]
+#[serde(default)]
+pub struct ListQuery<F, S> {
+    pub filter: F,
+    pub sort: S,
+    pub order: SortOrder,
+    pub after: Option<Cursor>,
+    pub before: Option<Cursor>,
+}
+
+#[derive(Serialize)]
+struct ListQueryString<'a, F> {
+    filter: &'a F,
+    sort: &'static str,
+    order: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    after: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    before: Option<String>,
+}
+
+impl<F: Serialize, S: SortKey> ListQuery<F, S> {
+    /// Rows are read nearest to the cursor first, so paging backwards reads against the order.
+    pub fn scan_desc(&self) -> bool {
+        (self.order == SortOrder::Desc) != self.before.is_some()
+    }
+
+    pub fn cursor(&self) -> Option<&Cursor> {
+        self.before.as_ref().or(self.after.as_ref())
+    }
+
+    /// One row past the page tells whether there is another one.
+    pub fn fetch_limit(&self) -> i64 {
+        PAGE_SIZE as i64 + 1
+    }
+
+    fn uri(
+        &self,
+        path: &str,
+        sort: S,
+        order: SortOrder,
+        after: Option<&Cursor>,
+        before: Option<&Cursor>,
+    ) -> String {
+        let query_string = ListQueryString {
+            filter: &self.filter,
+            sort: sort.as_str(),
+            order: order.as_str(),
+            after: after.map(|cursor| cursor.to_string()),
+            before: before.map(|cursor| cursor.to_string()),
+        };
+        format!(
+            "{}?{}",
+            path,
+            serde_qs::to_string(&query_string).unwrap_or_default()
+        )
+    }
+
+    pub fn page_uri(&self, path: &str, after: Option<&Cursor>, before: Option<&Cursor>) -> String {
+        self.uri(path, self.sort, self.order, after, before)
+    }
+
+    /// Back to the first page, sorting by `sort` or flipping the order when it already is.
+    pub fn sort_uri(&self, path: &str, sort: S) -> String {
+        let order = match self.sort == sort {
+            true => self.order.reverse(),
+            false => SortOrder::default(),
+        };
+        self.uri(path, sort, order, None, None)
+    }
+}
+
+/// One page of a keyset paginated list, with the cursors of the pages either side.
+#[derive(Debug)]
+pub struct KeysetPage<T> {
+    pub items: Arc<[T]>,
+    pub prev: Option<Cursor>,
+    pub next: Option<Cursor>,
+}
+
+impl<T> Default for KeysetPage<T> {
+    fn default() -> Self {
+        Self {
+            items: Arc::new([]),
+            prev: None,
+            next: None,
+        }
+    }
+}
+
+impl<T> KeysetPage<T> {
+    /// Takes the rows as read for `query`, at most `fetch_limit` of them.
+    pub fn from_rows<F: Serialize, S: SortKey>(
+        query: &ListQuery<F, S>,
+        mut rows: Vec<(T, Cursor)>,
+    ) -> Self {
+        let has_more = rows.len() > PAGE_SIZE;
+        rows.truncate(PAGE_SIZE);
+        let backward = query.before.is_some();
+        if backward {
+            rows.reverse();
+        }
+
+        let first = rows.first().map(|(_, cursor)| cursor.clone());
+        let last = rows.last().map(|(_, cursor)| cursor.clone());
+        let (prev, next) = match backward {
+            true => (first.filter(|_| has_more), last),
+            false => (
+                first.filter(|_| query.after.is_some()),
+                last.filter(|_| has_more),
+            ),
+        };
+
+        Self {
+            items: rows.into_iter().map(|(item, _)| item).collect(),
+            prev,
+            next,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_qs::Config;
+
+    #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Default)]
+    #[serde(default)]
+    struct TestFilter {
+        #[serde(
+            deserialize_with = "empty_as_none",
+            skip_serializing_if = "Option::is_none"
+        )]
+        author: Option<i64>,
+    }
+
+    #[derive(Deserialize, Debug, Clone, Copy, PartialEq, Default)]
+    #[serde(rename_all = "lowercase")]
+    enum TestSort {
+        #[default]
+        Id,
+        Updated,
+    }
+
+    impl SortKey for TestSort {
+        fn as_str(&self) -> &'static str {
+            match self {
+                Self::Id => "id",
+                Self::Updated => "updated",
+            }
+        }
+    }
+
+    type TestQuery = ListQuery<TestFilter, TestSort>;
+
+    fn parse(query_string: &str) -> TestQuery {
+        Config::default()
+            .use_form_encoding(true)
+            .deserialize_str(query_string)
+            .unwrap()
+    }
+
+    fn rows(ids: impl Iterator<Item = i64>) -> Vec<(i64, Cursor)> {
+        ids.map(|id| {
+            (
+                id,
+                Cursor {
+                    key: String::new(),
+                    id,
+                },
+            )
+        })
+        .collect()
+    }
+
+    #[test]
+    fn test_list_query_round_trips_through_uri() {
+        let query = parse("filter%5Bauthor%5D=2&sort=updated&order=asc");
+        assert_eq!(query.filter.author, Some(2));
+        assert_eq!(query.sort, TestSort::Updated);
+        assert_eq!(query.order, SortOrder::Asc);
+
+        let cursor = Cursor {
+            key: "2025-01-02 03:04:05.6+00:00".to_string(),
+            id: 7,
+        };
+        let uri = query.page_uri("/cms/list-page", Some(&cursor), None);
+        let path_query = uri.split_once('?').unwrap();
+        assert_eq!(path_query.0, "/cms/list-page");
+        let reparsed = parse(path_query.1);
+        assert_eq!(reparsed.filter, query.filter);
+        assert_eq!(reparsed.after, Some(cursor));
+        assert_eq!(reparsed.before, None);
+    }
+
+    #[test]
+    fn test_list_query_empty_filter_is_none() {
+        let query = parse("filter%5Bauthor%5D=&sort=id");
+        assert_eq!(query.filter.author, None);
+        assert_eq!(parse(""), TestQuery::default());
+    }
+
+    #[test]
+    fn test_sort_uri_flips_order_of_current_sort() {
+        let query = parse("sort=updated&order=desc&after=3.x");
+        assert!(query.sort_uri("/", TestSort::Updated).contains("order=asc"));
+        assert!(query.sort_uri("/", TestSort::Id).contains("order=desc"));
+        assert!(!query.sort_uri("/", TestSort::Id).contains("after"));
+    }
+
+    #[test]
+    fn test_keyset_page_forward() {
+        let first = KeysetPage::from_rows(&parse(""), rows(1..=PAGE_SIZE as i64 + 1));
+        assert_eq!(first.items.len(), PAGE_SIZE);
+        assert_eq!(first.prev, None);
+        assert_eq!(first.next.map(|cursor| cursor.id), Some(PAGE_SIZE as i64));
+
+        let last = KeysetPage::from_rows(&parse("after=30."), rows(31..=35));
+        assert_eq!(last.prev.map(|cursor| cursor.id), Some(31));
+        assert_eq!(last.next, None);
+    }
+
+    #[test]
+    fn test_keyset_page_backward_restores_order() {
+        let query = parse("order=asc&before=26.");
+        assert!(query.scan_desc());
+
+        let page = KeysetPage::from_rows(&query, rows((1..=25).rev()));
+        assert_eq!(page.items.first(), Some(&1));
+        assert_eq!(page.prev, None);
+        assert_eq!(page.next.map(|cursor| cursor.id), Some(25));
+    }
+}
@@ -1,3 +1,4 @@
 pub mod form;
+pub mod list_query;
 pub mod query;
 pub mod serde_qs_config;
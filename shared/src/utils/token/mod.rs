@@ -0,0 +1,29 @@
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+/// Keyed with the server secret, so the stored value is useless without the config.
+pub fn hash_token(secret: &str, token: &str) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC takes keys of any size");
+    mac.update(token.as_bytes());
+    format!("{:x}", mac.finalize().into_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hash_token_rfc_4231_vector() {
+        assert_eq!(
+            hash_token("Jefe", "what do ya want for nothing?"),
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+    }
+
+    #[test]
+    fn test_hash_token_depends_on_secret() {
+        assert_ne!(hash_token("one", "token"), hash_token("two", "token"));
+        assert_eq!(hash_token("one", "token").len(), 64);
+    }
+}
@@ -0,0 +1,166 @@
+use argon2::password_hash::rand_core::{OsRng, RngCore};
+use data_encoding::BASE32_NOPAD;
+use hmac::{Hmac, Mac};
+use sha1::Sha1;
+use sha2::{Digest, Sha256};
+
+/// RFC 6238 defaults, the only parameters authenticator apps reliably support.
+const TOTP_STEP_SECS: i64 = 30;
+const TOTP_DIGITS: u32 = 6;
+/// Accepts one step either side to absorb clock drift on the phone.
+const TOTP_SKEW_STEPS: i64 = 1;
+const SECRET_LEN: usize = 20;
+
+const RECOVERY_CODE_COUNT: usize = 10;
+const RECOVERY_CODE_LEN: usize = 10;
+
+pub struct TotpSecret(Box<[u8]>);
+
+impl TotpSecret {
+    pub fn generate() -> Self {
+        let mut secret = [0u8; SECRET_LEN];
+        OsRng.fill_bytes(&mut secret);
+        Self(Box::new(secret))
+    }
+
+    pub fn from_bytes(secret: Box<[u8]>) -> Self {
+        Self(secret)
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+
+    pub fn as_base32(&self) -> String {
+        BASE32_NOPAD.encode(&self.0)
+    }
+
+    /// The `otpauth://` URI authenticator apps read from the QR code.
+    pub fn provisioning_uri(&self, issuer: &str, account: &str) -> String {
+        format!(
+            "otpauth://totp/{}:{}?secret={}&issuer={}&algorithm=SHA1&digits={}&period={}",
+            percent_encode(issuer),
+            percent_encode(account),
+            self.as_base32(),
+            percent_encode(issuer),
+            TOTP_DIGITS,
+            TOTP_STEP_SECS
+        )
+    }
+
+    pub fn code_at_step(&self, step: i64) -> String {
+        let mut mac = Hmac::<Sha1>::new_from_slice(&self.0).expect("HMAC takes keys of any size");
+        mac.update(&step.to_be_bytes());
+        let hash = mac.finalize().into_bytes();
+
+        let offset = (hash[hash.len() - 1] & 0x0f) as usize;
+        let binary = u32::from_be_bytes([
+            hash[offset] & 0x7f,
+            hash[offset + 1],
+            hash[offset + 2],
+            hash[offset + 3],
+        ]);
+        format!(
+            "{:0width$}",
+            binary % 10u32.pow(TOTP_DIGITS),
+            width = TOTP_DIGITS as usize
+        )
+    }
+
+    /// Returns the matched time step, so the caller can refuse to accept it twice.
+    pub fn verify(&self, code: &str, unix_time: i64) -> Option<i64> {
+        let code = code.trim();
+        if code.len() != TOTP_DIGITS as usize || !code.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+        let current = unix_time.div_euclid(TOTP_STEP_SECS);
+        (current - TOTP_SKEW_STEPS..=current + TOTP_SKEW_STEPS)
+            .find(|step| constant_time_eq(self.code_at_step(*step).as_bytes(), code.as_bytes()))
+    }
+}
+
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+fn percent_encode(value: &str) -> String {
+    value
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                (b as char).to_string()
+            }
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+/// Fresh one-time codes, handed to the user once and only stored hashed.
+pub fn generate_recovery_codes() -> Vec<String> {
+    (0..RECOVERY_CODE_COUNT)
+        .map(|_| {
+            let mut bytes = [0u8; RECOVERY_CODE_LEN];
+            OsRng.fill_bytes(&mut bytes);
+            let code = BASE32_NOPAD.encode(&bytes)[..RECOVERY_CODE_LEN].to_lowercase();
+            format!("{}-{}", &code[..5], &code[5..])
+        })
+        .collect()
+}
+
+/// Ignores case, spaces and dashes, so a code typed back by hand still matches.
+pub fn hash_recovery_code(code: &str) -> String {
+    let normalized: String = code
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .map(|c| c.to_ascii_lowercase())
+        .collect();
+    format!("{:x}", Sha256::digest(normalized.as_bytes()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rfc_secret() -> TotpSecret {
+        TotpSecret::from_bytes(b"12345678901234567890".to_vec().into_boxed_slice())
+    }
+
+    #[test]
+    fn test_rfc_6238_vectors() {
+        let secret = rfc_secret();
+        assert_eq!(secret.code_at_step(59 / 30), "287082");
+        assert_eq!(secret.code_at_step(1111111109 / 30), "081804");
+        assert_eq!(secret.code_at_step(2000000000 / 30), "279037");
+    }
+
+    #[test]
+    fn test_verify_allows_one_step_of_skew() {
+        let secret = rfc_secret();
+        assert_eq!(secret.verify("287082", 59), Some(1));
+        assert_eq!(secret.verify("287082", 89), Some(1));
+        assert_eq!(secret.verify("287082", 150), None);
+        assert_eq!(secret.verify("28708", 59), None);
+        assert_eq!(secret.verify("abcdef", 59), None);
+    }
+
+    #[test]
+    fn test_provisioning_uri() {
+        let secret = rfc_secret();
+        assert_eq!(
+            secret.provisioning_uri("Rusty CMS", "alice"),
+            "otpauth://totp/Rusty%20CMS:alice?secret=GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ\
+             &issuer=Rusty%20CMS&algorithm=SHA1&digits=6&period=30"
+        );
+    }
+
+    #[test]
+    fn test_recovery_codes() {
+        let codes = generate_recovery_codes();
+        assert_eq!(codes.len(), RECOVERY_CODE_COUNT);
+        assert!(codes.iter().all(|code| code.len() == RECOVERY_CODE_LEN + 1));
+        assert_eq!(
+            hash_recovery_code(&codes[0]),
+            hash_recovery_code(&format!(" {} ", codes[0].to_uppercase().replace('-', " ")))
+        );
+    }
+}